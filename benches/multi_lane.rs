@@ -0,0 +1,116 @@
+use std::{
+    hint::black_box,
+    num::NonZeroUsize,
+};
+
+use criterion::{
+    Criterion,
+    criterion_group,
+    criterion_main,
+};
+use rand::{
+    Rng,
+    rng,
+};
+use sliding_features::{
+    MultiView,
+    View,
+    multi_lane::{
+        MultiEcho,
+        MultiEma,
+        MultiSma,
+        MultiSuperSmoother,
+    },
+    pure_functions::Echo,
+    sliding_windows::{
+        Ema,
+        Sma,
+        SuperSmoother,
+    },
+};
+
+const LANES: usize = 8;
+
+/// Compares `LANES` scalar views, `LANES` single-lane views running the same loops as the multi-lane view,
+/// and one multi-lane view, so the gain of the latter over the single-lane views is that of vectorizing the lanes.
+fn bench_lanes<S, M1, M>(
+    c: &mut Criterion,
+    name: &str,
+    vals: &[[f64; LANES]],
+    scalar: impl Fn() -> S,
+    single_lane: impl Fn() -> M1,
+    multi_lane: impl Fn() -> M,
+) where
+    S: View<f64>,
+    M1: MultiView<f64, 1>,
+    M: MultiView<f64, LANES>,
+{
+    let mut group = c.benchmark_group(format!("{name}_8_lanes_100k"));
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            let mut views: [_; LANES] = std::array::from_fn(|_| scalar());
+            for vs in vals.iter() {
+                for (view, v) in views.iter_mut().zip(vs) {
+                    view.update(*v);
+                    let _ = black_box(view.last());
+                }
+            }
+        })
+    });
+    group.bench_function("single_lane", |b| {
+        b.iter(|| {
+            let mut views: [_; LANES] = std::array::from_fn(|_| single_lane());
+            for vs in vals.iter() {
+                for (view, v) in views.iter_mut().zip(vs) {
+                    view.update([*v]);
+                    let _ = black_box(view.last());
+                }
+            }
+        })
+    });
+    group.bench_function("multi_lane", |b| {
+        b.iter(|| {
+            let mut view = multi_lane();
+            for vs in vals.iter() {
+                view.update(*vs);
+                let _ = black_box(view.last());
+            }
+        })
+    });
+    group.finish();
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut rng = rng();
+    const N: usize = 100_000;
+    let vals = Vec::<[f64; LANES]>::from_iter((0..N).map(|_| rng.random()));
+    let len = NonZeroUsize::new(1024).unwrap();
+
+    bench_lanes(
+        c,
+        "ema",
+        &vals,
+        || Ema::<f64, _>::new(Echo::new(), len),
+        || MultiEma::new(MultiEcho::new(), len),
+        || MultiEma::new(MultiEcho::new(), len),
+    );
+    bench_lanes(
+        c,
+        "sma",
+        &vals,
+        || Sma::<f64, _>::new(Echo::new(), len),
+        || MultiSma::new(MultiEcho::new(), len),
+        || MultiSma::new(MultiEcho::new(), len),
+    );
+    bench_lanes(
+        c,
+        "super_smoother",
+        &vals,
+        || SuperSmoother::<f64, _>::new(Echo::new(), len),
+        || MultiSuperSmoother::new(MultiEcho::new(), len),
+        || MultiSuperSmoother::new(MultiEcho::new(), len),
+    );
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+use std::{
+    hint::black_box,
+    num::NonZeroUsize,
+};
+
+use criterion::{
+    BenchmarkId,
+    Criterion,
+    criterion_group,
+    criterion_main,
+};
+use rand::{
+    Rng,
+    rng,
+};
+use sliding_features::{
+    View,
+    pure_functions::Echo,
+    sliding_windows::{
+        Ema,
+        Rsi,
+    },
+    universe::Universe,
+};
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut rng = rng();
+    const N: usize = 10_000;
+
+    let mut group = c.benchmark_group("universe_rsi_ema_10k");
+    for n_symbols in [1, 16, 256, 1024] {
+        let batches = Vec::from_iter(
+            (0..n_symbols).map(|_| Vec::<f64>::from_iter((0..N).map(|_| rng.random()))),
+        );
+        let prototype = Rsi::new(
+            Ema::new(Echo::new(), NonZeroUsize::new(16).unwrap()),
+            NonZeroUsize::new(14).unwrap(),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("sequential", n_symbols),
+            &batches,
+            |b, batches| {
+                b.iter(|| {
+                    for batch in batches.iter() {
+                        let mut view = prototype.clone();
+                        let _ = black_box(view.update_batch(batch));
+                    }
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("parallel", n_symbols),
+            &batches,
+            |b, batches| {
+                b.iter(|| {
+                    let mut universe = Universe::new(prototype.clone(), n_symbols);
+                    let _ = black_box(universe.update_batch(batches));
+                })
+            },
+        );
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
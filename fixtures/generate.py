@@ -0,0 +1,1050 @@
+#!/usr/bin/env python3
+"""Generate the golden-value fixtures used by `src/conformance.rs`.
+
+Every indicator is re-implemented here directly from its published formula,
+independently of the Rust code, using plain loops over the full history.
+Run from the repository root: `python3 fixtures/generate.py`.
+Each output file has one column per input series; an empty cell means `None`.
+"""
+
+import csv
+import math
+import os
+import re
+
+FIXTURE_DIR = os.path.dirname(os.path.abspath(__file__))
+N = 256
+
+
+def load_test_data():
+    path = os.path.join(FIXTURE_DIR, "..", "src", "test_data.rs")
+    with open(path) as f:
+        src = f.read()
+    body = src[src.index("[", src.index("=")) + 1 : src.rindex("]")]
+    vals = [float(v) for v in re.findall(r"-?\d+\.\d+(?:e-?\d+)?", body)]
+    assert len(vals) == N
+    return vals
+
+
+def sine():
+    return [100.0 + 10.0 * math.sin(2.0 * math.pi * i / 20.0) for i in range(N)]
+
+
+def trend():
+    # A rising trend with a short cycle and a flat segment.
+    vals = [100.0 + 0.25 * i + 3.0 * math.sin(2.0 * math.pi * i / 7.0) for i in range(N)]
+    for i in range(100, 110):
+        vals[i] = vals[99]
+    return vals
+
+
+INPUTS = {
+    "test_data": load_test_data(),
+    "sine": sine(),
+    "trend": trend(),
+}
+
+
+def window(xs, i, n):
+    """The last `n` values up to and including index `i`, oldest first."""
+    return xs[max(0, i - n + 1) : i + 1]
+
+
+def sma(xs, n):
+    return [sum(xs[i - n + 1 : i + 1]) / n if i >= n - 1 else None for i in range(len(xs))]
+
+
+def ema(xs, n, alpha=2.0):
+    """EMA seeded with the first value, ready after `n` values."""
+    w = alpha / (n + 1)
+    out = []
+    e = None
+    for i, x in enumerate(xs):
+        e = x if e is None else x * w + e * (1.0 - w)
+        out.append(e if i >= n - 1 else None)
+    return out
+
+
+def dema(xs, n):
+    """Double EMA, where the outer EMA is seeded with the first ready value of the inner one."""
+    e1 = ema(xs, n)
+    e2 = feed(e1, lambda vs: ema(vs, n))
+    return [None if b is None else 2.0 * a - b for a, b in zip(e1, e2)]
+
+
+def tema(xs, n):
+    e1 = ema(xs, n)
+    e2 = feed(e1, lambda vs: ema(vs, n))
+    e3 = feed(e2, lambda vs: ema(vs, n))
+    return [None if c is None else 3.0 * a - 3.0 * b + c for a, b, c in zip(e1, e2, e3)]
+
+
+def t3(xs, n, v=0.7):
+    """Tillson's T3 as published: a weighted sum of the third to the sixth EMA of a cascade."""
+    es = [ema(xs, n)]
+    for _ in range(5):
+        es.append(feed(es[-1], lambda vs: ema(vs, n)))
+    c1 = -(v**3)
+    c2 = 3 * v**2 + 3 * v**3
+    c3 = -6 * v**2 - 3 * v - 3 * v**3
+    c4 = 1 + 3 * v + v**3 + 3 * v**2
+    return [
+        None if e6 is None else c1 * e6 + c2 * e5 + c3 * e4 + c4 * e3
+        for e3, e4, e5, e6 in zip(*es[2:])
+    ]
+
+
+def zlema(xs, n):
+    """Ehlers' zero lag EMA of `2 * x - x[lag]`, starting once `lag` values have been observed."""
+    lag = (n - 1) // 2
+    return [None] * lag + ema([2.0 * xs[i] - xs[i - lag] for i in range(lag, len(xs))], n)
+
+
+def changes(xs, i, n):
+    """The last `n` one-step changes up to index `i`."""
+    return [xs[j] - xs[j - 1] for j in range(i - n + 1, i + 1)]
+
+
+def rsi(xs, n):
+    """Cutler's RSI: simple averages of gains and losses over `n` changes."""
+    out = []
+    for i in range(len(xs)):
+        if i < n:
+            out.append(None)
+            continue
+        ds = changes(xs, i, n)
+        gain = sum(d for d in ds if d > 0) / n
+        loss = sum(-d for d in ds if d < 0) / n
+        out.append(100.0 if loss == 0 else 100.0 - 100.0 / (1.0 + gain / loss))
+    return out
+
+
+def my_rsi(xs, n):
+    """Ehlers MyRSI from "Noise Elimination Technology"."""
+    out = []
+    prev = 0.0
+    for i in range(len(xs)):
+        if i < n:
+            out.append(None)
+            continue
+        ds = changes(xs, i, n)
+        cu = sum(d for d in ds if d > 0)
+        cd = sum(-d for d in ds if d < 0)
+        if cu + cd != 0:
+            prev = (cu - cd) / (cu + cd)
+        out.append(prev)
+    return out
+
+
+def sign(x):
+    return (x > 0) - (x < 0)
+
+
+def net(xs, n):
+    """Ehlers Noise Elimination Technology (kendall correlation) over a partially filled window."""
+    out = []
+    vals = []
+    for x in xs:
+        if x is None:
+            out.append(None)
+            continue
+        vals.append(x)
+        m = min(n, len(vals))
+        if m < 2:
+            out.append(None)
+            continue
+        # X[count] = value[count - 1], newest first.
+        X = [None] + [vals[-c] for c in range(1, m + 1)]
+        num = 0.0
+        for c in range(2, m + 1):
+            for k in range(1, c):
+                num -= sign(X[c] - X[k])
+        out.append(num / (0.5 * m * (m - 1)))
+    return out
+
+
+def roc(xs, n):
+    out = []
+    prev = None
+    for i, x in enumerate(xs):
+        ref = xs[max(0, i - n)]
+        if ref != 0:
+            prev = (x - ref) / ref * 100.0
+        out.append(prev)
+    return out
+
+
+def lag(xs, n):
+    return [xs[i - (n - 1)] if i >= n - 1 else None for i in range(len(xs))]
+
+
+def rolling_max(xs, n):
+    return [max(window(xs, i, n)) for i in range(len(xs))]
+
+
+def rolling_min(xs, n):
+    return [min(window(xs, i, n)) for i in range(len(xs))]
+
+
+def cumulative(xs, n):
+    return [sum(window(xs, i, n)) for i in range(len(xs))]
+
+
+def mean_std(w, ddof):
+    m = sum(w) / len(w)
+    if len(w) <= ddof:
+        return m, 0.0
+    return m, math.sqrt(sum((v - m) ** 2 for v in w) / (len(w) - ddof))
+
+
+def welford_online(xs, n):
+    """Sample standard deviation over the last `n` values."""
+    return [mean_std(xs[i - n + 1 : i + 1], 1)[1] if i >= n - 1 else None for i in range(len(xs))]
+
+
+def welford_rolling(xs):
+    """Population standard deviation over all values so far."""
+    return [mean_std(xs[: i + 1], 0)[1] for i in range(len(xs))]
+
+
+def vst(xs, n):
+    out = []
+    for i, x in enumerate(xs):
+        if i < n - 1:
+            out.append(None)
+            continue
+        _, std = mean_std(xs[i - n + 1 : i + 1], 1)
+        out.append(x if std == 0 else x / std)
+    return out
+
+
+def vsct(xs, n):
+    out = []
+    for i, x in enumerate(xs):
+        if i < n - 1:
+            out.append(None)
+            continue
+        m, std = mean_std(xs[i - n + 1 : i + 1], 1)
+        out.append(0.0 if std == 0 else (x - m) / std)
+    return out
+
+
+def hl_normalizer(xs, n):
+    out = []
+    for i, x in enumerate(xs):
+        w = window(xs, i, n)
+        lo, hi = min(w), max(w)
+        out.append(0.0 if lo == hi else 2.0 * (x - lo) / (hi - lo) - 1.0)
+    return out
+
+
+def center_of_gravity(xs, n):
+    """Ehlers CG Oscillator over a partially filled window."""
+    out = []
+    for i in range(len(xs)):
+        w = window(xs, i, n)[::-1]  # newest first
+        num = sum((1 + k) * p for k, p in enumerate(w))
+        den = sum(w)
+        out.append(-num / den + (len(w) + 1) / 2.0 if den != 0 else 0.0)
+    return out
+
+
+def cyber_cycle(xs, n):
+    """Ehlers Cyber Cycle with `alpha = 2 / (n + 1)`, including the published warm-up for `CurrentBar < 7`."""
+    alpha = 2.0 / (n + 1)
+    smooth = [None] * len(xs)
+    cycle = [0.0] * len(xs)
+    for i, x in enumerate(xs):
+        if i >= 3:
+            smooth[i] = (x + 2 * xs[i - 1] + 2 * xs[i - 2] + xs[i - 3]) / 6.0
+        if i < 2:
+            cycle[i] = 0.0
+        elif i < 6:
+            cycle[i] = (x - 2 * xs[i - 1] + xs[i - 2]) / 4.0
+        else:
+            cycle[i] = (
+                (1 - 0.5 * alpha) ** 2 * (smooth[i] - 2 * smooth[i - 1] + smooth[i - 2])
+                + 2 * (1 - alpha) * cycle[i - 1]
+                - (1 - alpha) ** 2 * cycle[i - 2]
+            )
+    return cycle
+
+
+def super_smoother_coefficients(period):
+    a1 = math.exp(-1.414 * math.pi / period)
+    b1 = 2.0 * a1 * math.cos(1.414 * math.pi / period)
+    c2 = b1
+    c3 = -a1 * a1
+    c1 = 1.0 - c2 - c3
+    return c1, c2, c3
+
+
+def super_smoother_raw(xs, period):
+    """SuperSmoother, seeded with the input for the first two values (`CurrentBar < 3`)."""
+    c1, c2, c3 = super_smoother_coefficients(period)
+    filt = []
+    for i, x in enumerate(xs):
+        if i < 2:
+            filt.append(x)
+        else:
+            filt.append(c1 * (x + xs[i - 1]) / 2.0 + c2 * filt[i - 1] + c3 * filt[i - 2])
+    return filt
+
+
+def super_smoother(xs, n):
+    return [v if i >= n - 1 else None for i, v in enumerate(super_smoother_raw(xs, n))]
+
+
+def roofing_filter(xs, hp_len, ss_len):
+    """Ehlers Roofing Filter: 2-pole high-pass with zero output for the first two values,
+    smoothed by a SuperSmoother once the high-pass warmed up for `hp_len` values."""
+    angle = 0.707 * 2.0 * math.pi / hp_len
+    alpha1 = (math.cos(angle) + math.sin(angle) - 1.0) / math.cos(angle)
+    hp = [0.0] * len(xs)
+    for i in range(2, len(xs)):
+        hp[i] = (
+            (1 - alpha1 / 2) ** 2 * (xs[i] - 2 * xs[i - 1] + xs[i - 2])
+            + 2 * (1 - alpha1) * hp[i - 1]
+            - (1 - alpha1) ** 2 * hp[i - 2]
+        )
+    start = hp_len + 1
+    smoothed = super_smoother(hp[start:], ss_len)
+    return [None] * start + smoothed
+
+
+def laguerre_filter(xs, gamma):
+    out = []
+    l0 = l1 = l2 = l3 = None
+    for x in xs:
+        if l0 is None:
+            l0 = l1 = l2 = l3 = x
+        else:
+            l0_1, l1_1, l2_1 = l0, l1, l2
+            l0 = (1 - gamma) * x + gamma * l0_1
+            l1 = -gamma * l0 + l0_1 + gamma * l1
+            l2 = -gamma * l1 + l1_1 + gamma * l2
+            l3 = -gamma * l2 + l2_1 + gamma * l3
+        out.append((l0 + 2 * l1 + 2 * l2 + l3) / 6.0)
+    return out
+
+
+def laguerre_rsi(xs, n):
+    """Ehlers Laguerre RSI with `gamma = 2 / (n + 1)`, all filter elements starting at zero."""
+    gamma = 2.0 / (n + 1)
+    l0 = l1 = l2 = l3 = 0.0
+    out = []
+    prev = None
+    for x in xs:
+        l0_1, l1_1, l2_1 = l0, l1, l2
+        l0 = (1 - gamma) * x + gamma * l0_1
+        l1 = -gamma * l0 + l0_1 + gamma * l1
+        l2 = -gamma * l1 + l1_1 + gamma * l2
+        l3 = -gamma * l2 + l2_1 + gamma * l3
+        cu = cd = 0.0
+        for a, b in ((l0, l1), (l1, l2), (l2, l3)):
+            if a >= b:
+                cu += a - b
+            else:
+                cd += b - a
+        if cu + cd != 0:
+            prev = cu / (cu + cd)
+        out.append(prev)
+    return out
+
+
+def flex_filter(xs, n):
+    """The SuperSmoother with period `n / 2` used by ReFlex and TrendFlex.
+    The filter history starts at zero and the price before the first one equals the first price."""
+    c1, c2, c3 = super_smoother_coefficients(0.5 * n)
+    filt = []
+    for i, x in enumerate(xs):
+        x1 = xs[i - 1] if i > 0 else x
+        f1 = filt[i - 1] if i > 0 else 0.0
+        f2 = filt[i - 2] if i > 1 else 0.0
+        filt.append(c1 * (x + x1) / 2.0 + c2 * f1 + c3 * f2)
+    return filt
+
+
+def re_flex(xs, n):
+    filt = flex_filter(xs, n)
+    out = []
+    ms = 0.0
+    prev = None
+    for i in range(len(xs)):
+        if i < n:
+            out.append(None)
+            continue
+        slope = (filt[i - n] - filt[i]) / n
+        s = sum((filt[i] + c * slope) - filt[i - c] for c in range(1, n + 1)) / n
+        ms = 0.04 * s * s + 0.96 * ms
+        if ms != 0:
+            prev = s / math.sqrt(ms)
+        out.append(prev)
+    return out
+
+
+def trend_flex(xs, n):
+    filt = flex_filter(xs, n)
+    out = []
+    ms = 0.0
+    for i in range(len(xs)):
+        if i < n:
+            out.append(None)
+            continue
+        s = sum(filt[i] - filt[i - c] for c in range(1, n + 1)) / n
+        ms = 0.04 * s * s + 0.96 * ms
+        out.append(s / math.sqrt(ms) if ms > 0 else 0.0)
+    return out
+
+
+def correlation_trend_indicator(xs, n):
+    out = []
+    for i in range(len(xs)):
+        w = window(xs, i, n)[::-1]  # newest first
+        m = len(w)
+        sx = sy = sxx = sxy = syy = 0.0
+        for c, x in enumerate(w):
+            y = -c
+            sx += x
+            sy += y
+            sxx += x * x
+            sxy += x * y
+            syy += y * y
+        if m * sxx - sx * sx > 0 and m * syy - sy * sy > 0:
+            out.append((m * sxy - sx * sy) / math.sqrt((m * sxx - sx * sx) * (m * syy - sy * sy)))
+        else:
+            out.append(0.0)
+    return out
+
+
+def feed(values, f):
+    """Feed the `Some` values of a series into `f` and align its outputs."""
+    present = [v for v in values if v is not None]
+    outs = iter(f(present))
+    return [next(outs) if v is not None else None for v in values]
+
+
+def polarized_fractal_efficiency(xs, n, ema_len):
+    raw = []
+    for i, x in enumerate(xs):
+        if i < n:
+            raw.append(None)
+            continue
+        num = math.sqrt((x - xs[i - n]) ** 2 + n * n)
+        den = sum(math.sqrt((xs[i - k] - xs[i - k - 1]) ** 2 + 1.0) for k in range(n))
+        p = num / den
+        raw.append(-p if x < xs[i - 1] else p)
+    return feed(raw, lambda vs: ema(vs, ema_len))
+
+
+def ehlers_fisher_transform(xs, n, ema_len):
+    normalized = []
+    for i, x in enumerate(xs):
+        w = window(xs, i, n)
+        lo, hi = min(w), max(w)
+        normalized.append(0.0 if hi == lo else 2.0 * ((x - lo) / (hi - lo) - 0.5))
+    smoothed = ema(normalized, ema_len)
+    out = []
+    fish = 0.0
+    for s in smoothed:
+        # The fisher transform starts at zero, until the moving average is ready.
+        if s is None:
+            out.append(fish)
+            continue
+        if s > 0.99:
+            s = 0.999
+        elif s < -0.99:
+            s = -0.999
+        fish = 0.5 * math.log((1 + s) / (1 - s)) + 0.5 * fish
+        out.append(fish)
+    return out
+
+
+def binary_entropy(xs, n):
+    out = []
+    for i in range(len(xs)):
+        w = window(xs, i, n)
+        pt = sum(1 for v in w if v >= 0) / len(w)
+        pn = 1.0 - pt
+        out.append(-sum(p * math.log2(p) for p in (pt, pn) if p > 0))
+    return out
+
+
+def alma_weights(n, sigma, offset):
+    m = offset * (n - 1)
+    s = n / sigma
+    return [math.exp(-((k - m) ** 2) / (2 * s * s)) for k in range(n)]
+
+
+def alma(xs, n, sigma=6.0, offset=0.85):
+    """Arnaud Legoux MA; while the window fills up the newest values use the newest weights."""
+    weights = alma_weights(n, sigma, offset)
+    out = []
+    for i in range(len(xs)):
+        w = window(xs, i, n)
+        ws = weights[n - len(w) :]
+        out.append(sum(wt * v for wt, v in zip(ws, w)) / sum(ws))
+    return out
+
+
+def weighted_ma(xs, weights):
+    """Weighted MA over full windows, with `weights` from the oldest to the newest value."""
+    n = len(weights)
+    return [
+        sum(wt * v for wt, v in zip(weights, xs[i - n + 1 : i + 1])) / sum(weights)
+        if i >= n - 1
+        else None
+        for i in range(len(xs))
+    ]
+
+
+def wma(xs, n):
+    return weighted_ma(xs, [k + 1 for k in range(n)])
+
+
+def hull_ma(xs, n):
+    """Alan Hull's MA: WMA(2 * WMA(n / 2) - WMA(n), sqrt(n))."""
+    half = wma(xs, max(n // 2, 1))
+    full = wma(xs, n)
+    diff = [None if f is None else 2.0 * h - f for h, f in zip(half, full)]
+    return feed(diff, lambda vs: wma(vs, max(math.isqrt(n), 1)))
+
+
+def trima(xs, n):
+    """Triangular MA as the SMA of an SMA."""
+    return feed(sma(xs, n // 2 + 1), lambda vs: sma(vs, (n + 1) // 2))
+
+
+def sine_ma(xs, n):
+    return weighted_ma(xs, [math.sin(math.pi * (k + 1) / (n + 1)) for k in range(n)])
+
+
+def pascal_ma(xs, n):
+    return weighted_ma(xs, [math.comb(n - 1, k) for k in range(n)])
+
+
+def kama(xs, n, fast=2, slow=30):
+    """Kaufman's adaptive MA, starting from the value before the first efficiency ratio."""
+    fast_sc = 2.0 / (fast + 1)
+    slow_sc = 2.0 / (slow + 1)
+    out = [None] * n
+    k = xs[n - 1]
+    for i in range(n, len(xs)):
+        volatility = sum(abs(xs[j] - xs[j - 1]) for j in range(i - n + 1, i + 1))
+        er = abs(xs[i] - xs[i - n]) / volatility if volatility > 0 else 0.0
+        k += (er * (fast_sc - slow_sc) + slow_sc) ** 2 * (xs[i] - k)
+        out.append(k)
+    return out
+
+
+def cmo(xs, n):
+    """Chande's momentum oscillator as a fraction instead of a percentage."""
+    out = []
+    for i in range(len(xs)):
+        if i < n:
+            out.append(None)
+            continue
+        changes_ = [xs[j] - xs[j - 1] for j in range(i - n + 1, i + 1)]
+        gain = sum(c for c in changes_ if c > 0)
+        loss = -sum(c for c in changes_ if c < 0)
+        out.append((gain - loss) / (gain + loss) if gain + loss > 0 else 0.0)
+    return out
+
+
+def std_dev_ratio(xs, short, long):
+    shorts = welford_online(xs, short)
+    longs = welford_online(xs, long)
+    return [None if lo is None else (0.0 if lo <= 0 else sh / lo) for sh, lo in zip(shorts, longs)]
+
+
+def vidya(xs, n, ks):
+    """Chande's VIDYA driven by the volatility index `ks`, starting from the latest value until it is ready."""
+    alpha = 2.0 / (n + 1)
+    out = []
+    v = None
+    for i, (x, k) in enumerate(zip(xs, ks)):
+        if k is None or v is None:
+            v = x
+        else:
+            a = min(alpha * abs(k), 1.0)
+            v = a * x + (1.0 - a) * v
+        out.append(v if i >= n - 1 and k is not None else None)
+    return out
+
+
+def frama(xs, n):
+    """Ehlers' fractal adaptive MA on closes, keeping the last dimension while a half is flat."""
+    half = (n + 1) // 2
+    n = 2 * half
+    out = [None] * (n - 1)
+    f = xs[n - 2]
+    dim = 1.0
+    for i in range(n - 1, len(xs)):
+        older = xs[i - n + 1 : i - half + 1]
+        newer = xs[i - half + 1 : i + 1]
+        n1 = (max(older) - min(older)) / half
+        n2 = (max(newer) - min(newer)) / half
+        n3 = (max(older + newer) - min(older + newer)) / n
+        if n1 > 0 and n2 > 0 and n3 > 0:
+            dim = (math.log(n1 + n2) - math.log(n3)) / math.log(2.0)
+        a = min(max(math.exp(-4.6 * (dim - 1.0)), 0.01), 1.0)
+        f = a * xs[i] + (1.0 - a) * f
+        out.append(f)
+    return out
+
+
+def mc_ginley_dynamic(xs, n):
+    """McGinley's dynamic, seeded with the first value, with the step capped at the full distance."""
+    out = []
+    md = None
+    for i, x in enumerate(xs):
+        md = x if md is None else md + (x - md) / max(n * (x / md) ** 4, 1.0)
+        out.append(md if i >= n - 1 else None)
+    return out
+
+
+def hilbert_transform(xs):
+    """Ehlers' Hilbert Transform discriminator from the MAMA paper, starting once four values are smoothed,
+    with zero history before. Returns the smoothed period and the phase rate of each bar."""
+    n = len(xs)
+    det, q1, i1, i2, q2, re, im, period, smooth_period, phase, delta_phase = (
+        [0.0] * n for _ in range(11)
+    )
+
+    def at(s, i):
+        return s[i] if i >= 0 else 0.0
+
+    def fir(s, i):
+        return 0.0962 * at(s, i) + 0.5769 * at(s, i - 2) - 0.5769 * at(s, i - 4) - 0.0962 * at(s, i - 6)
+
+    for i in range(3, n):
+        # The FIR coefficients sum to zero, so the deviations of the smoothed values from the newest value
+        # detrend the same, and a flat segment detrends to exactly zero.
+        dev = [
+            (4 * (xs[j] - xs[i]) + 3 * (xs[j - 1] - xs[i]) + 2 * (xs[j - 2] - xs[i]) + (xs[j - 3] - xs[i])) / 10.0
+            if j >= 3
+            else -xs[i]
+            for j in range(i - 6, i + 1)
+        ]
+        prev = at(period, i - 1)
+        gain = 0.075 * prev + 0.54
+        det[i] = fir(dev, 6) * gain
+        q1[i] = fir(det, i) * gain
+        i1[i] = at(det, i - 3)
+        ji = fir(i1, i) * gain
+        jq = fir(q1, i) * gain
+        i2[i] = 0.2 * (i1[i] - jq) + 0.8 * at(i2, i - 1)
+        q2[i] = 0.2 * (q1[i] + ji) + 0.8 * at(q2, i - 1)
+        re[i] = 0.2 * (i2[i] * at(i2, i - 1) + q2[i] * at(q2, i - 1)) + 0.8 * at(re, i - 1)
+        im[i] = 0.2 * (i2[i] * at(q2, i - 1) - q2[i] * at(i2, i - 1)) + 0.8 * at(im, i - 1)
+        p = prev
+        if im[i] != 0.0 and re[i] != 0.0:
+            p = 360.0 / math.degrees(math.atan(im[i] / re[i]))
+        p = min(p, 1.5 * prev)
+        p = max(p, 0.67 * prev)
+        p = max(p, 6.0)
+        p = min(p, 50.0)
+        period[i] = 0.2 * p + 0.8 * prev
+        smooth_period[i] = 0.33 * period[i] + 0.67 * at(smooth_period, i - 1)
+        phase[i] = math.degrees(math.atan(q1[i] / i1[i])) if i1[i] != 0.0 else at(phase, i - 1)
+        delta_phase[i] = max(at(phase, i - 1) - phase[i], 1.0)
+    return smooth_period, delta_phase
+
+
+def hilbert_period(xs):
+    smooth_period, _ = hilbert_transform(xs)
+    return [p if i >= 31 else None for i, p in enumerate(smooth_period)]
+
+
+def mama(xs, fast_limit=0.5, slow_limit=0.05):
+    """Ehlers' MESA adaptive MA and its following average, starting from the latest value until the cascade starts."""
+    _, delta_phase = hilbert_transform(xs)
+    mamas, famas = [], []
+    for i, x in enumerate(xs):
+        if i < 3:
+            m = f = x
+        else:
+            alpha = min(max(fast_limit / delta_phase[i], slow_limit), fast_limit)
+            m = alpha * x + (1 - alpha) * m
+            f = 0.5 * alpha * m + (1 - 0.5 * alpha) * f
+        mamas.append(m if i >= 31 else None)
+        famas.append(f if i >= 31 else None)
+    return mamas, famas
+
+
+def dual_differentiator(xs, min_period=10, max_period=48):
+    """Ehlers' dual differentiator on the roofing filtered values, with the in-phase component
+    at the midpoint of the normalized values and the numerator and denominator of the period smoothed."""
+    out = []
+    re_peak = im_peak = re_1 = i_1 = q_1 = energy = rate = period = 0.0
+    n = 0
+    for filt in roofing_filter(xs, max_period, min_period):
+        if filt is None:
+            out.append(None)
+            continue
+        n += 1
+        re_peak = max(0.991 * re_peak, abs(filt))
+        re = filt / re_peak if re_peak > 0 else 0.0
+        im_peak = max(0.991 * im_peak, abs(re - re_1))
+        q = (re - re_1) / im_peak if im_peak > 0 else 0.0
+        i = (re + re_1) / 2.0
+        energy = 0.1 * (i * i + q * q) + 0.9 * energy
+        rate = 0.1 * (q * (i - i_1) - i * (q - q_1)) + 0.9 * rate
+        re_1, i_1, q_1 = re, i, q
+        if rate != 0:
+            period = 2.0 * math.pi * energy / rate
+        period = min(max(period, min_period), max_period)
+        out.append(period if n >= 3 else None)
+    return out
+
+
+def autocorrelation_periodogram(xs, min_period=10, max_period=48, avg_len=3):
+    """Ehlers' autocorrelation periodogram on the roofing filtered values,
+    with the center of gravity weighted by the power above half of the peak power."""
+    filt = roofing_filter(xs, max_period, min_period)
+    start = next(i for i, f in enumerate(filt) if f is not None)
+    periods = range(min_period, max_period + 1)
+    power = {p: 0.0 for p in periods}
+    peak = 0.0
+    dominant_cycle = 0.0
+    out = []
+    for t in range(len(xs)):
+        if t < start + max_period + avg_len - 1:
+            out.append(None)
+            continue
+        corr = {}
+        for lag in range(3, max_period + 1):
+            x = [filt[t - i] for i in range(avg_len)]
+            y = [filt[t - lag - i] for i in range(avg_len)]
+            mx, my = sum(x) / avg_len, sum(y) / avg_len
+            sxx = sum((a - mx) ** 2 for a in x)
+            syy = sum((b - my) ** 2 for b in y)
+            sxy = sum((a - mx) * (b - my) for a, b in zip(x, y))
+            corr[lag] = sxy / math.sqrt(sxx * syy) if sxx * syy > 0 else 0.0
+        peak *= 0.995
+        for p in periods:
+            cosine_part = sum(corr[n] * math.cos(2.0 * math.pi * n / p) for n in range(3, max_period + 1))
+            sine_part = sum(corr[n] * math.sin(2.0 * math.pi * n / p) for n in range(3, max_period + 1))
+            sq_sum = cosine_part ** 2 + sine_part ** 2
+            power[p] = 0.2 * sq_sum ** 2 + 0.8 * power[p]
+            peak = max(peak, power[p])
+        if peak > 0:
+            weights = {p: power[p] / peak - 0.5 for p in periods}
+            sp = sum(w for w in weights.values() if w > 0)
+            spx = sum(p * w for p, w in weights.items() if w > 0)
+            if sp > 0:
+                dominant_cycle = spx / sp
+        dominant_cycle = min(max(dominant_cycle, min_period), max_period)
+        out.append(dominant_cycle)
+    return out
+
+
+def fractional_window(s, i, period):
+    """The values of `s` over one period back from `i`, newest first, with the value beyond the last whole sample
+    weighted by the fraction of the period."""
+    n_whole = math.floor(period)
+    return [(k, 1.0 if k < n_whole else period - n_whole, s[i - k]) for k in range(n_whole + 1)]
+
+
+def sinewave_lines(phases):
+    """The Sinewave and the Lead Sine, advanced by 45 degrees, of the phases in degrees."""
+    sines = [None if ph is None else math.sin(math.radians(ph)) for ph in phases]
+    leads = [None if ph is None else math.sin(math.radians(ph + 45.0)) for ph in phases]
+    return sines, leads
+
+
+def sinewave(xs):
+    """Ehlers' Sinewave and Lead Sine from "Rocket Science for Traders", correlating the smoothed values
+    over the fractional Hilbert Transform period as deviations from their mean over it,
+    with the phase continuous where the imaginary part vanishes, once 50 smoothed values are available."""
+    period, _ = hilbert_transform(xs)
+    smooth = [0.0] * len(xs)
+    phases = []
+    phase = 0.0
+    for i in range(len(xs)):
+        if i >= 3:
+            smooth[i] = (4 * xs[i] + 3 * xs[i - 1] + 2 * xs[i - 2] + xs[i - 3]) / 10.0
+        if i < 52:
+            phases.append(None)
+            continue
+        p = max(period[i], 1.0)
+        window = fractional_window(smooth, i, p)
+        mean = sum(w * x for _, w, x in window) / p
+        real = sum(w * math.sin(2 * math.pi * k / p) * (x - mean) for k, w, x in window)
+        imag = sum(w * math.cos(2 * math.pi * k / p) * (x - mean) for k, w, x in window)
+        if imag != 0.0:
+            phase = math.degrees(math.atan(real / imag))
+        elif real != 0.0:
+            phase = math.copysign(90.0, real)
+        phase += 90.0 + 360.0 / p
+        if imag < 0.0:
+            phase += 180.0
+        if phase > 315.0:
+            phase -= 360.0
+        phases.append(phase)
+    return sinewave_lines(phases)
+
+
+def sinewave_published(xs):
+    """Ehlers' Sinewave and Lead Sine as published in "Rocket Science for Traders", correlating the smoothed values
+    over the Hilbert Transform period rounded to whole samples, once 50 smoothed values are available."""
+    period, _ = hilbert_transform(xs)
+    smooth = [0.0] * len(xs)
+    phases = []
+    phase = 0.0
+    for i in range(len(xs)):
+        if i >= 3:
+            smooth[i] = (4 * xs[i] + 3 * xs[i - 1] + 2 * xs[i - 2] + xs[i - 3]) / 10.0
+        if i < 52:
+            phases.append(None)
+            continue
+        p = max(period[i], 1.0)
+        dc_period = int(p + 0.5)
+        real = sum(math.sin(2 * math.pi * k / dc_period) * smooth[i - k] for k in range(dc_period))
+        imag = sum(math.cos(2 * math.pi * k / dc_period) * smooth[i - k] for k in range(dc_period))
+        if abs(imag) > 0.0:
+            phase = math.degrees(math.atan(real / imag))
+        if abs(imag) <= 0.001 and real != 0.0:
+            phase += math.copysign(90.0, real)
+        phase += 90.0 + 360.0 / p
+        if imag < 0.0:
+            phase += 180.0
+        if phase > 315.0:
+            phase -= 360.0
+        phases.append(phase)
+    return sinewave_lines(phases)
+
+
+def instantaneous_trendline(xs):
+    """Ehlers' Instantaneous Trendline, the average over the fractional Hilbert Transform period
+    smoothed by a 4-bar WMA, once the values span 50 samples."""
+    period, _ = hilbert_transform(xs)
+    trends, out = [], []
+    for i in range(len(xs)):
+        if i >= 49:
+            p = max(period[i], 1.0)
+            trends.append(sum(w * x for _, w, x in fractional_window(xs, i, p)) / p)
+        if len(trends) < 4:
+            out.append(None)
+        else:
+            out.append((4 * trends[-1] + 3 * trends[-2] + 2 * trends[-3] + trends[-4]) / 10.0)
+    return out
+
+
+def one_pole_high_pass(xs, period):
+    """Ehlers' 1-pole high-pass with zero output for the first value, once warmed up for `period` values."""
+    angle = 2 * math.pi / period
+    alpha = (1 - math.sin(angle)) / math.cos(angle)
+    hp = [0.0]
+    for i in range(1, len(xs)):
+        hp.append(0.5 * (1 + alpha) * (xs[i] - xs[i - 1]) + alpha * hp[-1])
+    return [v if i >= period else None for i, v in enumerate(hp)]
+
+
+def two_pole_high_pass(xs, period):
+    """Ehlers' 2-pole high-pass with zero output for the first two values, once warmed up for `period` values."""
+    angle = 0.707 * 2.0 * math.pi / period
+    alpha = (math.cos(angle) + math.sin(angle) - 1.0) / math.cos(angle)
+    hp = [0.0] * len(xs)
+    for i in range(2, len(xs)):
+        hp[i] = (
+            (1 - alpha / 2) ** 2 * (xs[i] - 2 * xs[i - 1] + xs[i - 2])
+            + 2 * (1 - alpha) * hp[i - 1]
+            - (1 - alpha) ** 2 * hp[i - 2]
+        )
+    return [v if i >= period + 1 else None for i, v in enumerate(hp)]
+
+
+def decycler(xs, period):
+    return [None if hp is None else x - hp for x, hp in zip(xs, one_pole_high_pass(xs, period))]
+
+
+def decycler_oscillator(xs, fast, slow):
+    return [
+        None if f is None or s is None else s - f
+        for f, s in zip(two_pole_high_pass(xs, fast), two_pole_high_pass(xs, slow))
+    ]
+
+
+def even_better_sinewave(xs, hp_len=40, ss_len=10):
+    """Ehlers' Even Better Sinewave: 1-pole high-pass with zero output for the first value,
+    a SuperSmoother and the mean of the last three filtered values normalized by their root mean square."""
+    angle = 2 * math.pi / hp_len
+    alpha = (1 - math.sin(angle)) / math.cos(angle)
+    hp = [0.0]
+    for i in range(1, len(xs)):
+        hp.append(0.5 * (1 + alpha) * (xs[i] - xs[i - 1]) + alpha * hp[-1])
+    filt = super_smoother_raw(hp, ss_len)
+    out = []
+    for i in range(len(xs)):
+        if i < ss_len + 1:
+            out.append(None)
+            continue
+        f = filt[i - 2 : i + 1]
+        power = sum(v * v for v in f) / 3.0
+        out.append(sum(f) / 3.0 / math.sqrt(power) if power > 0 else 0.0)
+    return out
+
+
+def automatic_gain_control(xs, decay=0.991):
+    """Ehlers' automatic gain control, dividing by the peak magnitude decaying per sample,
+    with zero output while the peak is zero."""
+    out = []
+    peak = 0.0
+    for x in xs:
+        if x is None:
+            out.append(None)
+            continue
+        peak = max(decay * peak, abs(x))
+        out.append(x / peak if peak > 0 else 0.0)
+    return out
+
+
+def band_pass(xs, period, bw):
+    """Ehlers' band-pass filter after a 1-pole high-pass, with its signal normalized by the automatic gain control
+    and the trigger, a 1-pole high-pass filter of the signal starting at zero, once warmed up for `period + 1` values."""
+
+    def hp_alpha(cutoff):
+        angle = 2 * math.pi / cutoff
+        return (1 - math.sin(angle)) / math.cos(angle)
+
+    alpha_hp = hp_alpha(period / (0.25 * bw))
+    alpha_trigger = hp_alpha(period / (1.5 * bw))
+    beta = math.cos(2 * math.pi / period)
+    gamma = 1.0 / math.cos(2 * math.pi * bw / period)
+    alpha = gamma - math.sqrt(gamma * gamma - 1.0)
+    hp = [0.0]
+    for i in range(1, len(xs)):
+        hp.append(0.5 * (1 + alpha_hp) * (xs[i] - xs[i - 1]) + alpha_hp * hp[-1])
+    bp = [0.0] * len(xs)
+    for i in range(2, len(xs)):
+        bp[i] = 0.5 * (1 - alpha) * (hp[i] - hp[i - 2]) + beta * (1 + alpha) * bp[i - 1] - alpha * bp[i - 2]
+    start = period + 1
+    signal = automatic_gain_control(bp[start:])
+    trigger = [0.0]
+    for i in range(1, len(signal)):
+        trigger.append(0.5 * (1 + alpha_trigger) * (signal[i] - signal[i - 1]) + alpha_trigger * trigger[-1])
+    pad = [None] * start
+    return pad + bp[start:], pad + signal, pad + trigger
+
+
+def universal_oscillator(xs, band_edge):
+    """Ehlers' Universal Oscillator: a SuperSmoother of the half difference over two samples,
+    normalized by the automatic gain control."""
+    white_noise = [(xs[i] - xs[i - 2]) / 2.0 for i in range(2, len(xs))]
+    return [None] * 2 + automatic_gain_control(super_smoother(white_noise, band_edge))
+
+
+def inverse_fisher_transform_rsi(xs, n):
+    """Ehlers' Inverse Fisher Transform of the RSI: a 9-sample WMA of `0.1 * (rsi - 50)`, then tanh."""
+
+    def transform(vs):
+        return [None if v is None else math.tanh(v) for v in wma([0.1 * (v - 50.0) for v in vs], 9)]
+
+    return feed(rsi(xs, n), transform)
+
+
+def ln_return(xs):
+    return [None] + [math.log(xs[i] / xs[i - 1]) for i in range(1, len(xs))]
+
+
+def drawdown(xs):
+    out = []
+    peak = -math.inf
+    trough = math.inf
+    max_dd = 0.0
+    for x in xs:
+        if x > peak:
+            peak = trough = x
+        trough = min(trough, x)
+        max_dd = max(max_dd, (peak - trough) / peak)
+        out.append(max_dd)
+    return out
+
+
+FIXTURES = {
+    "sma_16": lambda xs: sma(xs, 16),
+    "ema_16": lambda xs: ema(xs, 16),
+    "dema_16": lambda xs: dema(xs, 16),
+    "tema_16": lambda xs: tema(xs, 16),
+    "t3_8_0_7": lambda xs: t3(xs, 8),
+    "zlema_16": lambda xs: zlema(xs, 16),
+    "rsi_16": lambda xs: rsi(xs, 16),
+    "my_rsi_16": lambda xs: my_rsi(xs, 16),
+    "net_my_rsi_16": lambda xs: net(my_rsi(xs, 16), 16),
+    "roc_16": lambda xs: roc(xs, 16),
+    "lag_3": lambda xs: lag(xs, 3),
+    "max_16": lambda xs: rolling_max(xs, 16),
+    "min_16": lambda xs: rolling_min(xs, 16),
+    "cumulative_16": lambda xs: cumulative(xs, 16),
+    "welford_online_16": lambda xs: welford_online(xs, 16),
+    "welford_rolling": welford_rolling,
+    "vst_16": lambda xs: vst(xs, 16),
+    "vsct_16": lambda xs: vsct(xs, 16),
+    "hl_normalizer_16": lambda xs: hl_normalizer(xs, 16),
+    "center_of_gravity_16": lambda xs: center_of_gravity(xs, 16),
+    "cyber_cycle_16": lambda xs: cyber_cycle(xs, 16),
+    "super_smoother_20": lambda xs: super_smoother(xs, 20),
+    "roofing_filter_48_10": lambda xs: roofing_filter(xs, 48, 10),
+    "laguerre_filter_0_8": lambda xs: laguerre_filter(xs, 0.8),
+    "laguerre_rsi_16": lambda xs: laguerre_rsi(xs, 16),
+    "re_flex_16": lambda xs: re_flex(xs, 16),
+    "trend_flex_16": lambda xs: trend_flex(xs, 16),
+    "correlation_trend_indicator_16": lambda xs: correlation_trend_indicator(xs, 16),
+    "polarized_fractal_efficiency_16_ema_16": lambda xs: polarized_fractal_efficiency(xs, 16, 16),
+    "ehlers_fisher_transform_16_ema_5": lambda xs: ehlers_fisher_transform(xs, 16, 5),
+    "binary_entropy_16_centered": lambda xs: binary_entropy([x - 100.0 for x in xs], 16),
+    "alma_16": lambda xs: alma(xs, 16),
+    "wma_16": lambda xs: wma(xs, 16),
+    "hull_ma_16": lambda xs: hull_ma(xs, 16),
+    "trima_16": lambda xs: trima(xs, 16),
+    "sine_ma_16": lambda xs: sine_ma(xs, 16),
+    "pascal_ma_16": lambda xs: pascal_ma(xs, 16),
+    "kama_10": lambda xs: kama(xs, 10),
+    "cmo_14": lambda xs: cmo(xs, 14),
+    "vidya_14_cmo_9": lambda xs: vidya(xs, 14, cmo(xs, 9)),
+    "vidya_14_std_dev_ratio_5_20": lambda xs: vidya(xs, 14, std_dev_ratio(xs, 5, 20)),
+    "frama_16": lambda xs: frama(xs, 16),
+    "mc_ginley_dynamic_14": lambda xs: mc_ginley_dynamic(xs, 14),
+    "hilbert_transform": hilbert_period,
+    "mama_0_5_0_05": lambda xs: mama(xs)[0],
+    "fama_0_5_0_05": lambda xs: mama(xs)[1],
+    "dual_differentiator_10_48": dual_differentiator,
+    "autocorrelation_periodogram_10_48": autocorrelation_periodogram,
+    "sinewave": lambda xs: sinewave(xs)[0],
+    "lead_sine": lambda xs: sinewave(xs)[1],
+    "sinewave_published": lambda xs: sinewave_published(xs)[0],
+    "instantaneous_trendline": instantaneous_trendline,
+    "even_better_sinewave_40_10": even_better_sinewave,
+    "one_pole_high_pass_20": lambda xs: one_pole_high_pass(xs, 20),
+    "two_pole_high_pass_20": lambda xs: two_pole_high_pass(xs, 20),
+    "decycler_20": lambda xs: decycler(xs, 20),
+    "decycler_oscillator_15_30": lambda xs: decycler_oscillator(xs, 15, 30),
+    "automatic_gain_control_re_flex_16": lambda xs: automatic_gain_control(re_flex(xs, 16)),
+    "band_pass_20_0_3": lambda xs: band_pass(xs, 20, 0.3)[0],
+    "band_pass_signal_20_0_3": lambda xs: band_pass(xs, 20, 0.3)[1],
+    "band_pass_trigger_20_0_3": lambda xs: band_pass(xs, 20, 0.3)[2],
+    "universal_oscillator_20": lambda xs: universal_oscillator(xs, 20),
+    "inverse_fisher_transform_rsi_5": lambda xs: inverse_fisher_transform_rsi(xs, 5),
+    "ln_return": ln_return,
+    "drawdown": drawdown,
+}
+
+
+def write_columns(name, columns):
+    path = os.path.join(FIXTURE_DIR, name + ".csv")
+    with open(path, "w", newline="") as f:
+        writer = csv.writer(f, lineterminator="\n")
+        writer.writerow(list(columns.keys()))
+        for row in zip(*columns.values()):
+            writer.writerow(["" if v is None else repr(float(v)) for v in row])
+
+
+def main():
+    write_columns("inputs", INPUTS)
+    for name, f in FIXTURES.items():
+        write_columns(name, {series: f(xs) for series, xs in INPUTS.items()})
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,568 @@
+#!/usr/bin/env python3
+"""Derive the reference values used by the `reference_*` tests in `src/conformance.rs`.
+
+Unlike `fixtures/generate.py`, which re-implements every indicator as a loop over the history,
+the values here are derived independently of any implementation: in closed form from the published
+definitions and transfer functions, e.g. the response of a recursive filter to a geometric series
+or its steady-state response to a sine, or taken from a published worked example.
+No recursion is stepped and no window is iterated pairwise.
+Run from the repository root: `python3 fixtures/reference/derive.py`.
+Each output file starts with `#` lines naming the source and the derivation,
+followed by the columns `input` and `expected`, where an empty expected cell is not checked,
+e.g. while a transient has not decayed yet.
+"""
+
+import cmath
+import csv
+import math
+import os
+from fractions import Fraction
+
+REFERENCE_DIR = os.path.dirname(os.path.abspath(__file__))
+N = 256
+
+
+def write_reference(name, source, derivation, inputs, expected):
+    path = os.path.join(REFERENCE_DIR, name + ".csv")
+    with open(path, "w", newline="") as f:
+        f.write(f"# source: {source}\n")
+        f.write(f"# derivation: {derivation}\n")
+        writer = csv.writer(f, lineterminator="\n")
+        writer.writerow(["input", "expected"])
+        for x, e in zip(inputs, expected):
+            writer.writerow([repr(float(x)), "" if e is None else repr(float(e))])
+
+
+def ramp(slope=1.0):
+    return [100.0 + slope * i for i in range(N)]
+
+
+def zig_zag():
+    """A rising series whose changes alternate between +7 at even and -5 at odd indices."""
+    return [100.0 + i + 3.0 * (-1) ** i for i in range(N)]
+
+
+def sine(period, n=N):
+    return [100.0 + 10.0 * math.sin(2.0 * math.pi * i / period) for i in range(n)]
+
+
+def sine_response(h, period, n):
+    """The steady-state response at index `i` of a filter with frequency response `h`, unity DC gain,
+    to `sine(period)`, i.e. `100 + Re(-10j * h * e^(j w i))`."""
+    w = 2.0 * math.pi / period
+    return lambda i: 100.0 + (-10j * h(w) * cmath.exp(1j * w * i)).real
+
+
+def geometric_ema(alpha, r):
+    """The EMA with weight `alpha` of `r^i`, seeded with the first value:
+    `y_i = b^i + alpha * r * (r^i - b^i) / (r - b)` with `b = 1 - alpha`."""
+    b = 1.0 - alpha
+    return lambda i: b**i + alpha * r * (r**i - b**i) / (r - b)
+
+
+def count_odd(lo, hi):
+    """The number of odd integers within `[lo, hi]`."""
+    return 0 if hi < lo else (hi + 1) // 2 - lo // 2
+
+
+def count_even(lo, hi):
+    return 0 if hi < lo else hi - lo + 1 - count_odd(lo, hi)
+
+
+def sma():
+    n = 16
+    xs = ramp(0.5)
+    expected = [100.0 + 0.5 * (i - (n - 1) / 2.0) if i >= n - 1 else None for i in range(N)]
+    write_reference(
+        "sma_16",
+        "arithmetic mean of an arithmetic progression",
+        "the mean of the last 16 values of 100 + i / 2 is its value at i - 7.5",
+        xs,
+        expected,
+    )
+
+
+def ema():
+    n, r = 16, 1.01
+    xs = [100.0 * r**i for i in range(N)]
+    y = geometric_ema(2.0 / (n + 1), r)
+    expected = [100.0 * y(i) if i >= n - 1 else None for i in range(N)]
+    write_reference(
+        "ema_16",
+        "EMA seeded with the first value, y_0 = x_0 and y_i = a * x_i + (1 - a) * y_(i-1) with a = 2 / (n + 1), "
+        "as pandas ewm(span=n, adjust=False)",
+        "closed form of the recursion for the geometric series 100 * 1.01^i",
+        xs,
+        expected,
+    )
+
+
+def cumulative():
+    n = 16
+    xs = ramp()
+    expected = []
+    for i in range(N):
+        m = min(i + 1, n)
+        expected.append(100.0 * m + m * (2 * i - m + 1) / 2.0)
+    write_reference(
+        "cumulative_16",
+        "sum of an arithmetic progression",
+        "the sum of the last m = min(i + 1, 16) values of 100 + i is 100 m + m (2 i - m + 1) / 2",
+        xs,
+        expected,
+    )
+
+
+def welford_online():
+    n = 8
+    data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+    xs = data + [10.0 + i for i in range(N - len(data))]
+    expected = [None] * N
+    # The sum of squared deviations of the textbook data from its mean 5 is 32.
+    expected[n - 1] = math.sqrt(32.0 / (n - 1))
+    for i in range(2 * len(data) - 1, N):
+        # The sample variance of n consecutive integers is n (n + 1) / 12.
+        expected[i] = math.sqrt(n * (n + 1) / 12.0)
+    write_reference(
+        "welford_online_8",
+        "Wikipedia, Standard deviation: the data 2, 4, 4, 4, 5, 5, 7, 9 have mean 5 "
+        "and squared deviations summing to 32",
+        "sample standard deviation sqrt(32 / 7) over the textbook data, "
+        "then sqrt(n (n + 1) / 12) over 8 consecutive integers",
+        xs,
+        expected,
+    )
+
+
+def rsi():
+    n = 15
+    xs = zig_zag()
+    expected = []
+    for i in range(N):
+        if i < n:
+            expected.append(None)
+            continue
+        gains = 7 * count_even(i - n + 1, i)
+        losses = 5 * count_odd(i - n + 1, i)
+        expected.append(100.0 - 100.0 / (1.0 + gains / losses))
+    write_reference(
+        "rsi_15",
+        "Cutler's RSI, 100 - 100 / (1 + RS) with RS the ratio of the simple averages of gains and losses",
+        "the changes of 100 + i + 3 (-1)^i alternate between +7 at even and -5 at odd i, "
+        "so 15 changes hold 8 gains and 7 losses or the other way around",
+        xs,
+        expected,
+    )
+
+
+def my_rsi():
+    n = 15
+    xs = zig_zag()
+    expected = []
+    for i in range(N):
+        if i < n:
+            expected.append(None)
+            continue
+        cu = 7 * count_even(i - n + 1, i)
+        cd = 5 * count_odd(i - n + 1, i)
+        expected.append((cu - cd) / (cu + cd))
+    write_reference(
+        "my_rsi_15",
+        'Ehlers, "Noise Elimination Technology", MyRSI = (CU - CD) / (CU + CD) over the last n changes',
+        "the changes of 100 + i + 3 (-1)^i alternate between +7 at even and -5 at odd i",
+        xs,
+        expected,
+    )
+
+
+def noise_elimination_technology():
+    n = 16
+    xs = zig_zag()
+    expected = []
+    for i in range(N):
+        m = min(i + 1, n)
+        if m < 2:
+            expected.append(None)
+            continue
+        lo = i - m + 1
+        pairs = m * (m - 1) // 2
+        # A later value j exceeds an earlier one k, unless j is odd and j - k is 1, 3 or 5.
+        discordant = sum(count_odd(lo + d, i) for d in (1, 3, 5))
+        expected.append((pairs - 2 * discordant) / pairs)
+    write_reference(
+        "noise_elimination_technology_16",
+        'Ehlers, "Noise Elimination Technology", the Kendall correlation of the values with their time',
+        "the value of 100 + i + 3 (-1)^i at j exceeds the one at k < j, unless j is odd and j - k is 1, 3 or 5, "
+        "which counts the discordant pairs of each window without comparing them",
+        xs,
+        expected,
+    )
+
+
+def alma():
+    n, sigma, offset = 16, 6.0, 0.85
+    xs = ramp(0.5)
+    m = offset * (n - 1)
+    s = n / sigma
+    weights = [math.exp(-((k - m) ** 2) / (2 * s * s)) for k in range(n)]
+    centroid = sum(k * w for k, w in enumerate(weights)) / sum(weights)
+    expected = [
+        100.0 + 0.5 * (i - (n - 1 - centroid)) if i >= n - 1 else None for i in range(N)
+    ]
+    write_reference(
+        "alma_16",
+        "TradingView, ta.alma: weights exp(-(k - m)^2 / (2 s^2)) with m = offset (n - 1) and s = n / sigma, "
+        "where k = 0 weights the oldest value",
+        "a weighted mean of 100 + i / 2 is its value at the centroid of the weights, "
+        "which lags the newest value by n - 1 - centroid",
+        xs,
+        expected,
+    )
+
+
+def super_smoother_coefficients(period):
+    a1 = math.exp(-1.414 * math.pi / period)
+    b1 = 2.0 * a1 * math.cos(1.414 * math.pi / period)
+    return 1.0 - b1 + a1 * a1, b1, -a1 * a1, a1, 1.414 * math.pi / period
+
+
+def super_smoother():
+    n, r = 20, 1.01
+    xs = [100.0 * r**i for i in range(N)]
+    c1, c2, c3, a1, b = super_smoother_coefficients(n)
+    # The particular solution for r^i is g r^i, with the transfer function at z = r.
+    g = c1 * (1.0 + 1.0 / r) / 2.0 / (1.0 - c2 / r - c3 / (r * r))
+    # The homogeneous solution is 2 Re(C p^i) with the pole p = a1 e^(j b),
+    # fitted to the seeds y_0 = x_0 and y_1 = x_1.
+    p = a1 * cmath.exp(1j * b)
+    h0, h1 = 1.0 - g, r - g * r
+    u = h0 / 2.0
+    v = (u * p.real - h1 / 2.0) / p.imag
+    c = complex(u, v)
+    expected = [
+        100.0 * (g * r**i + 2.0 * (c * p**i).real) if i >= n - 1 else None for i in range(N)
+    ]
+    write_reference(
+        "super_smoother_20",
+        'Ehlers, "Cycle Analytics for Traders" (2013), SuperSmoother '
+        "Filt = c1 (Price + Price[1]) / 2 + c2 Filt[1] + c3 Filt[2], Filt = Price for CurrentBar < 3",
+        "closed form for the geometric series 100 * 1.01^i: the transfer function at z = 1.01 "
+        "plus the homogeneous solution of the complex poles fitted to the two seeds",
+        xs,
+        expected,
+    )
+
+
+def cyber_cycle():
+    n, r = 16, -1.002
+    alpha = 2.0 / (n + 1)
+    b = 1.0 - alpha
+    k = (1.0 - alpha / 2.0) ** 2
+    xs = [100.0 + 10.0 * r**i for i in range(N)]
+    # The constant 100 doesn't contribute, as the cycle is a second difference.
+    # Smooth = s r^i from i = 3 on, so the forcing of the recursion from i = 6 on is k s (1 - 1/r)^2 r^i,
+    # whose particular solution is g r^i with the double pole b.
+    s = (1.0 + 2.0 / r + 2.0 / r**2 + 1.0 / r**3) / 6.0
+    g = k * s * (1.0 - 1.0 / r) ** 2 * r * r / (r - b) ** 2
+
+    def warm_up(i):
+        return r ** (i - 2) * (r - 1.0) ** 2 / 4.0
+
+    # The homogeneous solution (A + B i) b^i, fitted to the published warm-up values at 4 and 5.
+    u = (warm_up(4) - g * r**4) / b**4
+    v = (warm_up(5) - g * r**5) / b**5
+    slope = v - u
+    intercept = u - 4.0 * slope
+    expected = []
+    for i in range(N):
+        if i < 2:
+            expected.append(0.0)
+        elif i < 6:
+            expected.append(10.0 * warm_up(i))
+        else:
+            expected.append(10.0 * (g * r**i + (intercept + slope * i) * b**i))
+    write_reference(
+        "cyber_cycle_16",
+        'Ehlers, "Cybernetic Analysis for Stocks and Futures" (2004), Cyber Cycle '
+        "with Cycle = (Price - 2 Price[1] + Price[2]) / 4 for CurrentBar < 7",
+        "closed form for 100 + 10 (-1.002)^i: the particular solution of the double pole 1 - alpha "
+        "plus (A + B i) (1 - alpha)^i fitted to the published warm-up values",
+        xs,
+        expected,
+    )
+
+
+def two_pole_high_pass_response(period):
+    angle = 0.707 * 2.0 * math.pi / period
+    alpha = (math.cos(angle) + math.sin(angle) - 1.0) / math.cos(angle)
+
+    def h(w):
+        z1 = cmath.exp(-1j * w)
+        return (1 - alpha / 2) ** 2 * (1 - z1) ** 2 / (1 - 2 * (1 - alpha) * z1 + (1 - alpha) ** 2 * z1 * z1)
+
+    return h
+
+
+def super_smoother_response(period):
+    c1, c2, c3, _, _ = super_smoother_coefficients(period)
+
+    def h(w):
+        z1 = cmath.exp(-1j * w)
+        return c1 * (1 + z1) / 2 / (1 - c2 * z1 - c3 * z1 * z1)
+
+    return h
+
+
+def roofing_filter():
+    hp, ss, period, n = 48, 10, 30.0, 512
+    xs = sine(period, n)
+    h_hp = two_pole_high_pass_response(hp)
+    h_ss = super_smoother_response(ss)
+    w = 2.0 * math.pi / period
+    expected = [
+        (-10j * h_hp(w) * h_ss(w) * cmath.exp(1j * w * i)).real if i >= 400 else None
+        for i in range(n)
+    ]
+    write_reference(
+        "roofing_filter_48_10",
+        'Ehlers, "Cycle Analytics for Traders" (2013), Roofing Filter: '
+        "a 2-pole high-pass with alpha1 = (cos(.707 360 / HPPeriod) + sin(.707 360 / HPPeriod) - 1) "
+        "/ cos(.707 360 / HPPeriod) followed by a SuperSmoother",
+        "steady-state response to 100 + 10 sin(2 pi i / 30) from the product of both transfer functions, "
+        "once the transients have decayed",
+        xs,
+        expected,
+    )
+
+
+def laguerre_responses(gamma):
+    """The frequency responses of the four Laguerre elements L0 to L3."""
+
+    def h(k):
+        def response(w):
+            z1 = cmath.exp(-1j * w)
+            return (1 - gamma) / (1 - gamma * z1) * ((-gamma + z1) / (1 - gamma * z1)) ** k
+
+        return response
+
+    return [h(k) for k in range(4)]
+
+
+def laguerre_filter():
+    gamma, period, n = 0.8, 30.0, 512
+    xs = sine(period, n)
+    ls = laguerre_responses(gamma)
+
+    def h(w):
+        return (ls[0](w) + 2 * ls[1](w) + 2 * ls[2](w) + ls[3](w)) / 6
+
+    response = sine_response(h, period, n)
+    expected = [response(i) if i >= 400 else None for i in range(n)]
+    write_reference(
+        "laguerre_filter_0_8",
+        'Ehlers, "Time Warp - Without Space Travel" (2004), Laguerre filter '
+        "(L0 + 2 L1 + 2 L2 + L3) / 6 with L0 = (1 - gamma) Price + gamma L0[1] "
+        "and L(k) = -gamma L(k-1) + L(k-1)[1] + gamma L(k)[1]",
+        "steady-state response to 100 + 10 sin(2 pi i / 30) from the transfer function "
+        "(1 - gamma) / (1 - gamma z^-1) ((-gamma + z^-1) / (1 - gamma z^-1))^k of each element",
+        xs,
+        expected,
+    )
+
+
+def laguerre_rsi():
+    n_len, period, n = 16, 30.0, 512
+    gamma = 2.0 / (n_len + 1)
+    xs = sine(period, n)
+    elements = [sine_response(h, period, n) for h in laguerre_responses(gamma)]
+    expected = []
+    for i in range(n):
+        if i < 200:
+            expected.append(None)
+            continue
+        ls = [element(i) for element in elements]
+        cu = sum(max(a - b, 0.0) for a, b in zip(ls, ls[1:]))
+        cd = sum(max(b - a, 0.0) for a, b in zip(ls, ls[1:]))
+        expected.append(cu / (cu + cd))
+    write_reference(
+        "laguerre_rsi_16",
+        'Ehlers, "Time Warp - Without Space Travel" (2004), Laguerre RSI CU / (CU + CD) '
+        "over the differences of the successive Laguerre elements, with gamma = 2 / (16 + 1)",
+        "steady-state Laguerre elements for 100 + 10 sin(2 pi i / 30) from their transfer functions",
+        xs,
+        expected,
+    )
+
+
+def flex_reference(name, fir, description):
+    """ReFlex and TrendFlex over `window` values: a linear `fir` of the SuperSmoother of half the window,
+    divided by the root of its EMA of squares with weight 0.04, both in the steady state for a sine."""
+    window, period, n = 16, 30.0, 1024
+    xs = sine(period, n)
+    w = 2.0 * math.pi / period
+    h_ss = super_smoother_response(0.5 * window)
+    # The constant 100 passes the SuperSmoother and cancels in the sums.
+    z = -10j * h_ss(w) * fir(w, window)
+    # For sum = Re(z e^(j w i)), sum^2 = |z|^2 / 2 + Re(z^2 e^(2 j w i)) / 2,
+    # whose EMA with weight 0.04 is the DC part plus the response of the EMA at twice the frequency.
+    ema_2w = 0.04 / (1 - 0.96 * cmath.exp(-2j * w))
+    expected = []
+    for i in range(n):
+        if i < 900:
+            expected.append(None)
+            continue
+        s = (z * cmath.exp(1j * w * i)).real
+        ms = abs(z) ** 2 / 2 + (ema_2w * z * z / 2 * cmath.exp(2j * w * i)).real
+        expected.append(s / math.sqrt(ms))
+    write_reference(
+        name,
+        'Ehlers, "Reflex: A New Zero-Lag Indicator", Stocks & Commodities (February 2020), '
+        + description
+        + ", normalized by the root of MS = .04 Sum^2 + .96 MS[1]",
+        "steady-state response to 100 + 10 sin(2 pi i / 30) from the transfer functions "
+        "of the SuperSmoother of period 8 and of the sums over its last 16 values",
+        xs,
+        expected,
+    )
+
+
+def re_flex():
+    def fir(w, n):
+        # Sum of Filt + c Slope - Filt[c] for c = 1..n, with Slope = (Filt[n] - Filt) / n, over n.
+        e = lambda c: cmath.exp(-1j * w * c)
+        return sum(1 + c / n * (e(n) - 1) - e(c) for c in range(1, n + 1)) / n
+
+    flex_reference("re_flex_16", fir, "ReFlex as the mean deviation of Filt from the line to Filt[n]")
+
+
+def trend_flex():
+    def fir(w, n):
+        return sum(1 - cmath.exp(-1j * w * c) for c in range(1, n + 1)) / n
+
+    flex_reference("trend_flex_16", fir, "TrendFlex as the mean of Filt - Filt[c] for c = 1..n")
+
+
+def power_sum(m, p):
+    """The sum of t^p over t = 0..m-1, by Faulhaber's formulas."""
+    m = Fraction(m)
+    return {
+        0: m,
+        1: m * (m - 1) / 2,
+        2: (m - 1) * m * (2 * m - 1) / 6,
+        3: (m * (m - 1) / 2) ** 2,
+        4: (m - 1) * m * (2 * m - 1) * (3 * (m - 1) ** 2 + 3 * (m - 1) - 1) / 30,
+    }[p]
+
+
+def correlation_trend_indicator():
+    n = 16
+    xs = [(i / 10.0) ** 2 for i in range(N)]
+    expected = []
+    for i in range(N):
+        m = min(i + 1, n)
+        if m < 2:
+            expected.append(None)
+            continue
+        # The value t samples back is f(t) = (i - t)^2 / 100, correlated with -t over t = 0..m-1.
+        # Moments of t from the power sums, and of f as the polynomial i^2 - 2 i t + t^2.
+        e = [power_sum(m, p) / m for p in range(5)]
+        var_t = e[2] - e[1] ** 2
+        cov_t_t2 = e[3] - e[1] * e[2]
+        var_t2 = e[4] - e[2] ** 2
+        cov_f_t = -2 * i * var_t + cov_t_t2
+        var_f = 4 * i * i * var_t - 4 * i * cov_t_t2 + var_t2
+        expected.append(-float(cov_f_t) / math.sqrt(float(var_f) * float(var_t)))
+    write_reference(
+        "correlation_trend_indicator_16",
+        'Ehlers, "Correlation As A Trend Indicator", Stocks & Commodities (May 2020), '
+        "the Pearson correlation of the values with a line rising towards the newest one",
+        "the correlation of (i - t)^2 with -t over the last min(i + 1, 16) values, "
+        "from the moments of t given by Faulhaber's formulas",
+        xs,
+        expected,
+    )
+
+
+def polarized_fractal_efficiency():
+    n, ema_len = 16, 16
+    xs = zig_zag()
+    # Over 16 values, the zig-zag rises by 16 through 8 changes of +7 and 8 of -5.
+    p = 16.0 * math.sqrt(2.0) / (8.0 * math.sqrt(50.0) + 8.0 * math.sqrt(26.0))
+    # Its sign alternates with the last change, starting positive at the even index 16.
+    y = geometric_ema(2.0 / (ema_len + 1), -1.0)
+    expected = [p * y(i - n) if i >= n + ema_len - 1 else None for i in range(N)]
+    write_reference(
+        "polarized_fractal_efficiency_16_ema_16",
+        "Hannula, \"Polarized Fractal Efficiency\", Stocks & Commodities (January 1994), "
+        "sqrt((Price - Price[n])^2 + n^2) / sum of sqrt(change^2 + 1), negative when falling, "
+        "smoothed by an EMA seeded with its first value",
+        "over 16 values of 100 + i + 3 (-1)^i the efficiency is 16 sqrt(2) / (8 sqrt(50) + 8 sqrt(26)) "
+        "with alternating sign, whose EMA is in closed form for the geometric series (-1)^i",
+        xs,
+        expected,
+    )
+
+
+def ehlers_fisher_transform():
+    half = N // 2
+    xs = [100.0 + i for i in range(half)] + [100.0 + half - 1 - i for i in range(half)]
+    # Once the smoothed value has been clamped to 0.999 for long, the published recursion
+    # Fish = .5 ln((1 + Value) / (1 - Value)) + .5 Fish[1] reaches its fixed point ln(1999).
+    expected = []
+    for i in range(N):
+        if 100 <= i < half:
+            expected.append(math.log(1999.0))
+        elif i >= half + 100:
+            expected.append(-math.log(1999.0))
+        else:
+            expected.append(None)
+    write_reference(
+        "ehlers_fisher_transform_16_ema_5",
+        'Ehlers, "Using The Fisher Transform", Stocks & Commodities (November 2002), '
+        "Value clamped to +-0.999 beyond +-0.99 and Fish = .5 ln((1 + Value) / (1 - Value)) + .5 Fish[1]",
+        "a rising and then falling series keeps the normalized value at +1 and then -1, "
+        "so the transform reaches the fixed points +-ln(1999)",
+        xs,
+        expected,
+    )
+
+
+def hl_normalizer():
+    n = 20
+    xs = sine(20.0)
+    # Any 20 values span a whole cycle sampled at its peak 110 and trough 90.
+    expected = [math.sin(2.0 * math.pi * i / 20.0) if i >= n - 1 else None for i in range(N)]
+    write_reference(
+        "hl_normalizer_20",
+        "normalization 2 (Price - Lowest) / (Highest - Lowest) - 1 over the window",
+        "over 20 values of 100 + 10 sin(2 pi i / 20), the highest is 110 and the lowest 90, "
+        "so the normalized value is sin(2 pi i / 20)",
+        xs,
+        expected,
+    )
+
+
+def main():
+    sma()
+    ema()
+    cumulative()
+    welford_online()
+    rsi()
+    my_rsi()
+    noise_elimination_technology()
+    alma()
+    super_smoother()
+    cyber_cycle()
+    roofing_filter()
+    laguerre_filter()
+    laguerre_rsi()
+    re_flex()
+    trend_flex()
+    correlation_trend_indicator()
+    polarized_fractal_efficiency()
+    ehlers_fisher_transform()
+    hl_normalizer()
+
+
+if __name__ == "__main__":
+    main()
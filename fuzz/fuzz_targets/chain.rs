@@ -0,0 +1,467 @@
+//! Construct an arbitrary chain of views with arbitrary parameters,
+//! feed it arbitrary values and check that it neither panics nor produces non-finite outputs.
+//!
+//! The first byte is the number of bytes describing the chain,
+//! the remaining bytes are the values as little-endian `f64`.
+//! All finite values are fed to the chain, including zero, subnormal values and those close to `f64::MAX`,
+//! for which views whose outputs or state overflow report `InvalidReason::Overflow` instead.
+//! A chain guarded by `MissingData` is fed the non-finite values as well.
+
+#![no_main]
+
+use std::num::NonZeroUsize;
+
+use arbitrary::{
+    Arbitrary,
+    Unstructured,
+};
+use libfuzzer_sys::fuzz_target;
+use sliding_features::{
+    Status,
+    View,
+    adaptive::Adaptive,
+    missing_data::{
+        MissingData,
+        MissingDataPolicy,
+    },
+    params::Reconfigure,
+    pure_functions::*,
+    rolling::*,
+    sliding_windows::*,
+};
+
+/// Deeper nodes are replaced by `Echo`, as long chains of products overflow.
+const MAX_DEPTH: usize = 6;
+
+#[derive(Debug, Arbitrary)]
+enum Node {
+    Echo,
+    Constant(i16),
+    Add(Box<Node>, Box<Node>),
+    Subtract(Box<Node>, Box<Node>),
+    // Products and quotients of arbitrary chains overflow, so they scale by constants.
+    Multiply(Box<Node>, i16),
+    Divide(Box<Node>, i16),
+    Gte(Box<Node>, i16),
+    Lte(Box<Node>, i16),
+    Tanh(Box<Node>),
+    Drawdown(Box<Node>),
+    LnReturn(Box<Node>),
+    WelfordRolling(Box<Node>),
+    Alma(Box<Node>, u8, f64, f64),
+    BinaryEntropy(Box<Node>, u8),
+    CenterOfGravity(Box<Node>, u8),
+    CorrelationTrendIndicator(Box<Node>, u8),
+    Cumulative(Box<Node>, u8),
+    CyberCycle(Box<Node>, u8),
+    EhlersFisherTransform(Box<Node>, Box<Node>, u8),
+    Ema(Box<Node>, u8, f64),
+    HLNormalizer(Box<Node>, u8),
+    Lag(Box<Node>, u8),
+    LaguerreFilter(Box<Node>, f64),
+    LaguerreRSI(Box<Node>, u8),
+    Max(Box<Node>, u8),
+    Min(Box<Node>, u8),
+    MyRSI(Box<Node>, u8),
+    NoiseEliminationTechnology(Box<Node>, u8),
+    PolarizedFractalEfficiency(Box<Node>, Box<Node>, u8),
+    ReFlex(Box<Node>, u8),
+    Roc(Box<Node>, u8),
+    RoofingFilter(Box<Node>, u8, u8),
+    Rsi(Box<Node>, u8),
+    Sma(Box<Node>, u8),
+    SuperSmoother(Box<Node>, u8),
+    TrendFlex(Box<Node>, u8),
+    Vst(Box<Node>, u8),
+    Vsct(Box<Node>, u8),
+    WelfordOnline(Box<Node>, u8),
+    Wma(Box<Node>, u8),
+    HullMa(Box<Node>, u8),
+    Trima(Box<Node>, u8),
+    SineMa(Box<Node>, u8),
+    PascalMa(Box<Node>, u8),
+    GaussianMa(Box<Node>, u8, f64, f64),
+    Dema(Box<Node>, u8),
+    Tema(Box<Node>, u8),
+    T3(Box<Node>, u8, f64),
+    Zlema(Box<Node>, u8),
+    Cmo(Box<Node>, u8),
+    StdDevRatio(Box<Node>, u8, u8),
+    Kama(Box<Node>, u8, u8, u8),
+    VidyaCmo(Box<Node>, u8, u8),
+    VidyaStdDevRatio(Box<Node>, u8, u8, u8),
+    Frama(Box<Node>, u8),
+    McGinleyDynamic(Box<Node>, u8),
+    HilbertTransform(Box<Node>),
+    Mama(Box<Node>, f64, f64),
+    Fama(Box<Node>, f64, f64),
+    DualDifferentiator(Box<Node>, u8, u8),
+    AutocorrelationPeriodogram(Box<Node>, u8, u8),
+    Sinewave(Box<Node>),
+    LeadSine(Box<Node>),
+    EvenBetterSinewave(Box<Node>, u8, u8),
+    InstantaneousTrendline(Box<Node>),
+    OnePoleHighPass(Box<Node>, u8),
+    TwoPoleHighPass(Box<Node>, u8),
+    Decycler(Box<Node>, u8),
+    DecyclerOscillator(Box<Node>, u8, u8),
+    BandPass(Box<Node>, u8, f64),
+    BandPassTrigger(Box<Node>, u8, f64),
+    UniversalOscillator(Box<Node>, u8),
+    AutomaticGainControl(Box<Node>, f64),
+    InverseFisherTransform(Box<Node>, Box<Node>, f64, f64),
+    Logistic(Box<Node>),
+    Softsign(Box<Node>),
+    Arctan(Box<Node>),
+    FisherTransform(Box<Node>),
+    // The view, selected and adapted by the third field, and the driver of its window length.
+    Adaptive(Box<Node>, Box<Node>, u8, u8),
+    MissingData(Box<Node>, u8),
+}
+
+/// A view that can be cloned behind a `Box`, as `MissingData` clones its chain.
+trait DynView: View<f64> {
+    fn box_clone(&self) -> Box<dyn DynView>;
+}
+
+impl<V: View<f64> + Clone + 'static> DynView for V {
+    fn box_clone(&self) -> Box<dyn DynView> {
+        Box::new(self.clone())
+    }
+}
+
+/// A type-erased chain, so arbitrarily nested views have a common type.
+struct Chain(Box<dyn DynView>);
+
+impl Clone for Chain {
+    fn clone(&self) -> Self {
+        Chain(self.0.box_clone())
+    }
+}
+
+impl View<f64> for Chain {
+    fn update(&mut self, val: f64) {
+        self.0.update(val)
+    }
+
+    fn last(&self) -> Option<f64> {
+        self.0.last()
+    }
+
+    fn status(&self) -> Status<f64> {
+        self.0.status()
+    }
+}
+
+fn window_len(len: u8) -> NonZeroUsize {
+    NonZeroUsize::new(len.max(1) as usize).expect("is non-zero")
+}
+
+/// Drives `param` of `view` with `driver`, or an `Echo` for an invalid `max_len`.
+fn adaptive<V>(view: V, param: &'static str, driver: Chain, max_len: u8) -> Box<dyn DynView>
+where
+    V: View<f64> + Reconfigure + Clone + 'static,
+{
+    match Adaptive::new(view, param, driver, window_len(max_len)) {
+        Ok(view) => Box::new(view),
+        Err(_) => Box::new(Echo::new()),
+    }
+}
+
+/// Constructs the chain with the fallible constructors, where invalid parameters
+/// are rejected up front and the view is replaced by an `Echo`.
+fn build(node: Node, depth: usize) -> Chain {
+    if depth > MAX_DEPTH {
+        return Chain(Box::new(Echo::new()));
+    }
+    let b = |node: Box<Node>| build(*node, depth + 1);
+    Chain(match node {
+        Node::Echo => Box::new(Echo::new()),
+        Node::Constant(val) => Box::new(Constant::new(val as f64)),
+        Node::Add(a, c) => Box::new(Add::new(b(a), b(c))),
+        Node::Subtract(a, c) => Box::new(Subtract::new(b(a), b(c))),
+        Node::Multiply(v, c) => Box::new(Multiply::new(b(v), Constant::new(c as f64))),
+        Node::Divide(v, c) => Box::new(Divide::new(b(v), Constant::new(c as f64))),
+        Node::Gte(v, clip) => Box::new(GTE::new(b(v), clip as f64)),
+        Node::Lte(v, clip) => Box::new(LTE::new(b(v), clip as f64)),
+        Node::Tanh(v) => Box::new(Tanh::new(b(v))),
+        Node::Drawdown(v) => Box::new(Drawdown::new(b(v))),
+        Node::LnReturn(v) => Box::new(LnReturn::new(b(v))),
+        Node::WelfordRolling(v) => Box::new(WelfordRolling::new(b(v))),
+        Node::Alma(v, len, sigma, offset) => {
+            match Alma::try_new_custom(b(v), window_len(len), sigma, offset) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::BinaryEntropy(v, len) => Box::new(BinaryEntropy::new(b(v), window_len(len))),
+        Node::CenterOfGravity(v, len) => Box::new(CenterOfGravity::new(b(v), window_len(len))),
+        Node::CorrelationTrendIndicator(v, len) => {
+            Box::new(CorrelationTrendIndicator::new(b(v), window_len(len)))
+        }
+        Node::Cumulative(v, len) => Box::new(Cumulative::new(b(v), window_len(len))),
+        Node::CyberCycle(v, len) => Box::new(CyberCycle::new(b(v), window_len(len))),
+        Node::EhlersFisherTransform(v, ma, len) => {
+            Box::new(EhlersFisherTransform::new(b(v), b(ma), window_len(len)))
+        }
+        Node::Ema(v, len, alpha) => match Ema::try_with_alpha(b(v), window_len(len), alpha) {
+            Ok(view) => Box::new(view),
+            Err(_) => Box::new(Echo::new()),
+        },
+        Node::HLNormalizer(v, len) => Box::new(HLNormalizer::new(b(v), window_len(len))),
+        Node::Lag(v, len) => Box::new(Lag::new(b(v), window_len(len))),
+        Node::LaguerreFilter(v, gamma) => match LaguerreFilter::try_new(b(v), gamma) {
+            Ok(view) => Box::new(view),
+            Err(_) => Box::new(Echo::new()),
+        },
+        Node::LaguerreRSI(v, len) => Box::new(LaguerreRSI::new(b(v), window_len(len))),
+        Node::Max(v, len) => Box::new(Max::new(b(v), window_len(len))),
+        Node::Min(v, len) => Box::new(Min::new(b(v), window_len(len))),
+        Node::MyRSI(v, len) => Box::new(MyRSI::new(b(v), window_len(len))),
+        Node::NoiseEliminationTechnology(v, len) => {
+            Box::new(NoiseEliminationTechnology::new(b(v), window_len(len)))
+        }
+        Node::PolarizedFractalEfficiency(v, ma, len) => Box::new(PolarizedFractalEfficiency::new(
+            b(v),
+            b(ma),
+            window_len(len),
+        )),
+        Node::ReFlex(v, len) => Box::new(ReFlex::new(b(v), window_len(len))),
+        Node::Roc(v, len) => Box::new(Roc::new(b(v), window_len(len))),
+        Node::RoofingFilter(v, low_pass, high_pass) => {
+            match RoofingFilter::try_new(b(v), window_len(low_pass), window_len(high_pass)) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::Rsi(v, len) => Box::new(Rsi::new(b(v), window_len(len))),
+        Node::Sma(v, len) => Box::new(Sma::new(b(v), window_len(len))),
+        Node::SuperSmoother(v, len) => Box::new(SuperSmoother::new(b(v), window_len(len))),
+        Node::TrendFlex(v, len) => Box::new(TrendFlex::new(b(v), window_len(len))),
+        Node::Vst(v, len) => Box::new(Vst::new(b(v), window_len(len))),
+        Node::Vsct(v, len) => Box::new(Vsct::new(b(v), window_len(len))),
+        Node::WelfordOnline(v, len) => Box::new(WelfordOnline::new(b(v), window_len(len))),
+        Node::Wma(v, len) => Box::new(Wma::new(b(v), window_len(len))),
+        Node::HullMa(v, len) => Box::new(HullMa::new(b(v), window_len(len))),
+        Node::Trima(v, len) => Box::new(Trima::new(b(v), window_len(len))),
+        Node::SineMa(v, len) => Box::new(SineMa::new(b(v), window_len(len))),
+        Node::PascalMa(v, len) => Box::new(PascalMa::new(b(v), window_len(len))),
+        Node::GaussianMa(v, len, sigma, offset) => match GaussianKernel::new(sigma, offset) {
+            Ok(kernel) => Box::new(KernelMa::with_kernel(b(v), window_len(len), kernel)),
+            Err(_) => Box::new(Echo::new()),
+        },
+        Node::Dema(v, len) => Box::new(Dema::new(b(v), window_len(len))),
+        Node::Tema(v, len) => Box::new(Tema::new(b(v), window_len(len))),
+        Node::T3(v, len, volume_factor) => {
+            match T3::try_with_volume_factor(b(v), window_len(len), volume_factor) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::Zlema(v, len) => Box::new(Zlema::new(b(v), window_len(len))),
+        Node::Cmo(v, len) => Box::new(Cmo::new(b(v), window_len(len))),
+        Node::StdDevRatio(v, short, long) => {
+            Box::new(StdDevRatio::new(b(v), window_len(short), window_len(long)))
+        }
+        Node::Kama(v, len, fast, slow) => Box::new(Kama::new_custom(
+            b(v),
+            window_len(len),
+            window_len(fast),
+            window_len(slow),
+        )),
+        Node::VidyaCmo(v, len, cmo_len) => {
+            Box::new(Vidya::with_cmo(b(v), window_len(len), window_len(cmo_len)))
+        }
+        Node::VidyaStdDevRatio(v, len, short, long) => Box::new(Vidya::with_std_dev_ratio(
+            b(v),
+            window_len(len),
+            window_len(short),
+            window_len(long),
+        )),
+        Node::Frama(v, len) => Box::new(Frama::new(b(v), window_len(len))),
+        Node::McGinleyDynamic(v, len) => Box::new(McGinleyDynamic::new(b(v), window_len(len))),
+        Node::HilbertTransform(v) => Box::new(HilbertTransform::new(b(v))),
+        Node::Mama(v, fast_limit, slow_limit) => {
+            match Mama::try_with_limits(b(v), fast_limit, slow_limit) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::Fama(v, fast_limit, slow_limit) => {
+            match Fama::try_with_limits(b(v), fast_limit, slow_limit) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::DualDifferentiator(v, min_period, max_period) => {
+            match DualDifferentiator::try_with_periods(
+                b(v),
+                window_len(min_period),
+                window_len(max_period),
+            ) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::AutocorrelationPeriodogram(v, min_period, max_period) => {
+            match AutocorrelationPeriodogram::try_with_periods(
+                b(v),
+                window_len(min_period),
+                window_len(max_period),
+            ) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::Sinewave(v) => Box::new(Sinewave::new(b(v))),
+        Node::LeadSine(v) => Box::new(LeadSine::new(b(v))),
+        Node::EvenBetterSinewave(v, high_pass_len, super_smoother_len) => {
+            match EvenBetterSinewave::try_with_lens(
+                b(v),
+                window_len(high_pass_len),
+                window_len(super_smoother_len),
+            ) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::InstantaneousTrendline(v) => Box::new(InstantaneousTrendline::new(b(v))),
+        Node::OnePoleHighPass(v, cutoff_period) => {
+            match OnePoleHighPass::try_new(b(v), window_len(cutoff_period)) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::TwoPoleHighPass(v, cutoff_period) => {
+            match TwoPoleHighPass::try_new(b(v), window_len(cutoff_period)) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::Decycler(v, cutoff_period) => {
+            match Decycler::try_new(b(v), window_len(cutoff_period)) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::DecyclerOscillator(v, fast_period, slow_period) => {
+            match DecyclerOscillator::try_new(
+                b(v),
+                window_len(fast_period),
+                window_len(slow_period),
+            ) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::BandPass(v, period, bandwidth) => {
+            match BandPass::try_new(b(v), window_len(period), bandwidth) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::BandPassTrigger(v, period, bandwidth) => {
+            match BandPassTrigger::try_new(b(v), window_len(period), bandwidth) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::UniversalOscillator(v, band_edge) => {
+            Box::new(UniversalOscillator::new(b(v), window_len(band_edge)))
+        }
+        Node::AutomaticGainControl(v, decay) => {
+            match AutomaticGainControl::try_with_decay(b(v), decay) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::InverseFisherTransform(v, ma, center, scale) => {
+            match InverseFisherTransform::try_new(b(v), b(ma), center, scale) {
+                Ok(view) => Box::new(view),
+                Err(_) => Box::new(Echo::new()),
+            }
+        }
+        Node::Logistic(v) => Box::new(Logistic::new(b(v))),
+        Node::Softsign(v) => Box::new(Softsign::new(b(v))),
+        Node::Arctan(v) => Box::new(Arctan::new(b(v))),
+        Node::FisherTransform(v) => Box::new(FisherTransform::new(b(v))),
+        Node::Adaptive(v, driver, kind, max_len) => {
+            let (v, driver) = (b(v), b(driver));
+            match kind % 7 {
+                0 => adaptive(Rsi::new(v, window_len(14)), "window_len", driver, max_len),
+                1 => adaptive(
+                    SuperSmoother::new(v, window_len(14)),
+                    "window_len",
+                    driver,
+                    max_len,
+                ),
+                2 => adaptive(Wma::new(v, window_len(14)), "window_len", driver, max_len),
+                3 => adaptive(DualDifferentiator::new(v), "min_period", driver, max_len),
+                4 => adaptive(DualDifferentiator::new(v), "max_period", driver, max_len),
+                5 => adaptive(
+                    AutocorrelationPeriodogram::new(v),
+                    "min_period",
+                    driver,
+                    max_len,
+                ),
+                _ => adaptive(
+                    AutocorrelationPeriodogram::new(v),
+                    "max_period",
+                    driver,
+                    max_len,
+                ),
+            }
+        }
+        Node::MissingData(v, policy) => {
+            let policy = match policy % 4 {
+                0 => MissingDataPolicy::Skip,
+                1 => MissingDataPolicy::ForwardFill,
+                2 => MissingDataPolicy::Reset,
+                _ => MissingDataPolicy::EmitNone,
+            };
+            Box::new(MissingData::new(b(v), policy))
+        }
+    })
+}
+
+/// The chain and the values to feed it.
+#[derive(Debug)]
+struct Input {
+    chain: Node,
+    vals: Vec<f64>,
+}
+
+impl<'a> Arbitrary<'a> for Input {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::arbitrary_take_rest(Unstructured::new(u.bytes(u.len())?))
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> arbitrary::Result<Self> {
+        let data = u.take_rest();
+        let (spec_len, data) = data.split_first().ok_or(arbitrary::Error::NotEnoughData)?;
+        let (spec, vals) = data.split_at((*spec_len as usize).min(data.len()));
+        let chain = Node::arbitrary_take_rest(Unstructured::new(spec))?;
+        let vals = vals
+            .chunks_exact(8)
+            .map(|bytes| f64::from_le_bytes(bytes.try_into().expect("has 8 bytes")))
+            .collect();
+        Ok(Self { chain, vals })
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let guarded = matches!(input.chain, Node::MissingData(..));
+    let mut chain = build(input.chain, 0);
+    for val in input.vals {
+        if !(val.is_finite() || guarded) {
+            continue;
+        }
+        chain.update(val);
+        if let Some(out) = chain.last() {
+            assert!(out.is_finite(), "non-finite output {out} of {val}");
+        }
+        if let Status::Ready(out) = chain.status() {
+            assert!(out.is_finite(), "non-finite status {out} of {val}");
+        }
+    }
+});
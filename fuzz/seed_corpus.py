@@ -0,0 +1,33 @@
+#!/usr/bin/env python3
+"""Seed the corpus of the `chain` fuzz target with `TEST_DATA`, fed to one chain per view.
+Run from the `fuzz` directory: `python3 seed_corpus.py`.
+"""
+
+import os
+import re
+import struct
+
+FUZZ_DIR = os.path.dirname(os.path.abspath(__file__))
+N_VARIANTS = 40
+
+
+def load_test_data():
+    with open(os.path.join(FUZZ_DIR, "..", "src", "test_data.rs")) as f:
+        return [float(v) for v in re.findall(r"-?\d+\.\d+(?:e-?\d+)?", f.read())]
+
+
+def main():
+    vals = b"".join(struct.pack("<d", v) for v in load_test_data())
+    out_dir = os.path.join(FUZZ_DIR, "corpus", "chain")
+    os.makedirs(out_dir, exist_ok=True)
+    for variant in range(N_VARIANTS):
+        # The enum tag is scaled from a `u32` to the number of variants,
+        # followed by an `Echo` as the inner view and a window length of 16.
+        tag = (variant << 32) // N_VARIANTS + 1
+        spec = struct.pack("<I", tag) + struct.pack("<I", 0) + bytes([16, 16])
+        with open(os.path.join(out_dir, f"seed_{variant:02}"), "wb") as f:
+            f.write(bytes([len(spec)]) + spec + vals)
+
+
+if __name__ == "__main__":
+    main()
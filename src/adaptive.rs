@@ -0,0 +1,465 @@
+//! Cycle-adaptive lookbacks, where the window length of a view is driven by the output of another view.
+//! E.g. an `Rsi` over half of the dominant cycle measured by a `HilbertTransform`
+//! retunes its lookback each tick as the market cycle shortens or lengthens.
+//! The length is set through `Reconfigure`, so the adapted view resizes its buffers and recomputes its coefficients
+//! as it would on any other runtime reconfiguration.
+
+use std::{
+    marker::PhantomData,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamKind,
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
+
+/// Drives a window length parameter of a view with the output of another view, the driver.
+/// Both observe the same values, so the driver usually starts with its own `Echo`,
+/// e.g. `HilbertTransform::new(Echo::new()) * 0.5` for half of the dominant cycle.
+/// Each update, the driver observes the value first and its rounded output,
+/// limited to `[min_len, max_len]`, becomes the window length of the view before it observes the value.
+/// Until the driver is ready, the view keeps the window length it was constructed with,
+/// and it keeps its current window length when it rejects a length conflicting with its other parameters.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct Adaptive<T, V, D> {
+    view: V,
+    driver: D,
+    /// The name of the adapted parameter.
+    #[getset(get_copy = "pub")]
+    param: &'static str,
+    /// The shortest window length, which is the lower bound of the adapted parameter.
+    #[getset(get_copy = "pub")]
+    min_len: NonZeroUsize,
+    /// The longest window length, which bounds the memory of the view for any output of the driver.
+    #[getset(get_copy = "pub")]
+    max_len: NonZeroUsize,
+    /// The current window length of the view.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    _marker: PhantomData<T>,
+}
+
+impl<T, V, D> Adaptive<T, V, D>
+where
+    V: View<T> + Reconfigure,
+    D: View<T>,
+    T: Float,
+{
+    /// Create a view whose window length parameter `param` is driven by `driver`, up to `max_len`.
+    /// `param` must be a window length parameter of `view`, and `max_len` at least its lower bound.
+    pub fn new(
+        view: V,
+        param: &'static str,
+        driver: D,
+        max_len: NonZeroUsize,
+    ) -> Result<Self, Error> {
+        let spec = view
+            .param_specs()
+            .into_iter()
+            .find(|spec| spec.name == param)
+            .ok_or(Error::UnknownParameter)?;
+        if spec.kind != ParamKind::Int {
+            return Err(Error::ParameterType(param));
+        }
+        let window_len = window_len_param(param, spec.value)?;
+        let min_len = match spec.min {
+            Bound::Inclusive(min) => min as usize,
+            Bound::Exclusive(min) => min as usize + 1,
+            Bound::Unbounded => 1,
+        };
+        let min_len = NonZeroUsize::new(min_len.max(1)).expect("is non-zero");
+        check_max_len(min_len, max_len)?;
+        Ok(Self {
+            view,
+            driver,
+            param,
+            min_len,
+            max_len,
+            window_len,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Set the longest window length, which must be at least `min_len`.
+    /// It takes effect with the next update.
+    pub fn set_max_len(&mut self, max_len: NonZeroUsize) -> Result<(), Error> {
+        check_max_len(self.min_len, max_len)?;
+        self.max_len = max_len;
+        Ok(())
+    }
+
+    /// The adapted view.
+    #[inline(always)]
+    pub fn view(&self) -> &V {
+        &self.view
+    }
+
+    /// The driver of the window length.
+    #[inline(always)]
+    pub fn driver(&self) -> &D {
+        &self.driver
+    }
+}
+
+/// The longest window length of an `Adaptive` view, which must be at least the shortest.
+fn check_max_len(min_len: NonZeroUsize, max_len: NonZeroUsize) -> Result<(), Error> {
+    if max_len < min_len {
+        return Err(Error::AdaptiveMaxLen(max_len.get(), min_len.get()));
+    }
+    Ok(())
+}
+
+impl<T, V, D> View<T> for Adaptive<T, V, D>
+where
+    V: View<T> + Reconfigure,
+    D: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.driver.update(val);
+        if let Some(len) = self.driver.last() {
+            debug_assert!(len.is_finite(), "value from `View` must be finite");
+            // Limiting before the conversion keeps arbitrarily large outputs convertible.
+            let len = len
+                .round()
+                .max(T::from(self.min_len.get()).expect("can convert"))
+                .min(T::from(self.max_len.get()).expect("can convert"))
+                .to_usize()
+                .expect("can convert");
+            let len = NonZeroUsize::new(len).expect("is at least `min_len`");
+            // A length the view rejects, e.g. a `min_period` beyond its `max_period`, keeps the current one.
+            if len != self.window_len
+                && self
+                    .view
+                    .set_param(self.param, ParamValue::Int(len.get()))
+                    .is_ok()
+            {
+                self.window_len = len;
+            }
+        }
+        self.view.update(val);
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<T> {
+        self.view.last()
+    }
+
+    #[inline(always)]
+    fn status(&self) -> Status<T> {
+        self.view.status()
+    }
+}
+
+impl<T, V, D> Introspect for Adaptive<T, V, D>
+where
+    V: Introspect,
+    D: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Adaptive")
+            .param("param", self.param)
+            .param("max_len", self.max_len())
+            .child(self.view.node())
+            .child(self.driver.node())
+    }
+}
+
+impl<T, V, D> Reconfigure for Adaptive<T, V, D>
+where
+    V: View<T> + Reconfigure,
+    D: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "max_len",
+            self.max_len.get(),
+            self.min_len.get(),
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "max_len" => self.set_max_len(window_len_param("max_len", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generators,
+        pure_functions::{
+            Constant,
+            Echo,
+        },
+        sliding_windows::{
+            AutocorrelationPeriodogram,
+            CenterOfGravity,
+            CyberCycle,
+            DualDifferentiator,
+            HLNormalizer,
+            HilbertTransform,
+            LaguerreFilter,
+            Rsi,
+            SuperSmoother,
+        },
+        test_data::TEST_DATA,
+    };
+
+    fn len(window_len: usize) -> NonZeroUsize {
+        NonZeroUsize::new(window_len).unwrap()
+    }
+
+    /// A driver which outputs a fixed schedule of window lengths.
+    #[derive(Debug, Clone)]
+    struct Schedule {
+        lens: Vec<f64>,
+        n_observed: usize,
+    }
+
+    impl View<f64> for Schedule {
+        fn update(&mut self, _val: f64) {
+            self.n_observed += 1;
+        }
+
+        fn last(&self) -> Option<f64> {
+            Some(self.lens[self.n_observed - 1])
+        }
+    }
+
+    /// Shrinks and grows the window length, ending with 256 values of the length 30.
+    fn schedule() -> Schedule {
+        let lens = [10.0, 25.0, 8.0, 30.0]
+            .into_iter()
+            .flat_map(|len| std::iter::repeat_n(len, TEST_DATA.len()))
+            .collect();
+        Schedule {
+            lens,
+            n_observed: 0,
+        }
+    }
+
+    /// After the last change of the window length, the adapted view converges to a fresh view of that length.
+    /// The windows agree once they have refilled, while the recursive filters forget their state geometrically,
+    /// so the outputs are compared `settle_len` values after the change, within the relative `tolerance`.
+    fn check_adapts<V: View<f64> + Reconfigure>(
+        view: V,
+        fresh: V,
+        settle_len: usize,
+        tolerance: f64,
+    ) {
+        let vals: Vec<f64> = TEST_DATA
+            .iter()
+            .cycle()
+            .take(4 * TEST_DATA.len())
+            .copied()
+            .collect();
+        let mut adaptive = Adaptive::new(view, "window_len", schedule(), len(64)).unwrap();
+        let outs = adaptive.update_batch(&vals);
+        assert_eq!(adaptive.window_len(), len(30));
+        assert!(outs.iter().flatten().all(|out| out.is_finite()));
+        let mut fresh = fresh;
+        let expected = fresh.update_batch(&vals);
+        let settled = 3 * TEST_DATA.len() + settle_len;
+        for (out, expected) in outs[settled..].iter().zip(&expected[settled..]) {
+            let (out, expected) = (out.unwrap(), expected.unwrap());
+            assert!(
+                (out - expected).abs() <= tolerance * expected.abs().max(1.0),
+                "output {out} differs from {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn adaptive_rsi() {
+        check_adapts(
+            Rsi::new(Echo::new(), len(14)),
+            Rsi::new(Echo::new(), len(30)),
+            64,
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn adaptive_cyber_cycle() {
+        check_adapts(
+            CyberCycle::new(Echo::new(), len(14)),
+            CyberCycle::new(Echo::new(), len(30)),
+            224,
+            1e-4,
+        );
+    }
+
+    #[test]
+    fn adaptive_center_of_gravity() {
+        check_adapts(
+            CenterOfGravity::new(Echo::new(), len(14)),
+            CenterOfGravity::new(Echo::new(), len(30)),
+            64,
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn adaptive_super_smoother() {
+        check_adapts(
+            SuperSmoother::new(Echo::new(), len(14)),
+            SuperSmoother::new(Echo::new(), len(30)),
+            224,
+            1e-4,
+        );
+    }
+
+    #[test]
+    fn adaptive_hl_normalizer() {
+        check_adapts(
+            HLNormalizer::new(Echo::new(), len(14)),
+            HLNormalizer::new(Echo::new(), len(30)),
+            64,
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn adaptive_constant_driver() {
+        let mut adaptive = Adaptive::new(
+            Rsi::new(Echo::new(), len(14)),
+            "window_len",
+            Constant::new(20.4),
+            len(64),
+        )
+        .unwrap();
+        let mut fresh = Rsi::new(Echo::new(), len(20));
+        assert_eq!(
+            adaptive.update_batch(&TEST_DATA),
+            fresh.update_batch(&TEST_DATA)
+        );
+        assert_eq!(adaptive.window_len(), len(20));
+    }
+
+    #[test]
+    fn adaptive_limits() {
+        let rsi = || Rsi::new(Echo::<f64>::new(), len(14));
+        let mut adaptive =
+            Adaptive::new(rsi(), "window_len", Constant::new(1e30), len(64)).unwrap();
+        adaptive.update(100.0);
+        assert_eq!(adaptive.window_len(), len(64));
+        let mut adaptive =
+            Adaptive::new(rsi(), "window_len", Constant::new(-3.0), len(64)).unwrap();
+        adaptive.update(100.0);
+        assert_eq!(adaptive.window_len(), len(1));
+
+        assert_eq!(
+            Adaptive::new(rsi(), "alpha", Constant::new(10.0), len(64)).unwrap_err(),
+            Error::UnknownParameter
+        );
+        let laguerre = LaguerreFilter::new(Echo::<f64>::new(), 0.5);
+        assert_eq!(
+            Adaptive::new(laguerre, "gamma", Constant::new(10.0), len(64)).unwrap_err(),
+            Error::ParameterType("gamma")
+        );
+        let roofing =
+            crate::sliding_windows::RoofingFilter::new(Echo::<f64>::new(), len(48), len(10));
+        assert_eq!(
+            Adaptive::new(roofing, "window_len_low_pass", Constant::new(10.0), len(2)).unwrap_err(),
+            Error::AdaptiveMaxLen(2, 3)
+        );
+    }
+
+    #[test]
+    fn adaptive_rejected_len() {
+        // The lengths conflict with the other period, so the views keep their periods of 10 to 48.
+        for (param, driver) in [("min_period", 60.0), ("max_period", 8.0)] {
+            let mut adaptive = Adaptive::new(
+                DualDifferentiator::new(Echo::new()),
+                param,
+                Constant::new(driver),
+                len(64),
+            )
+            .unwrap();
+            let mut fresh = DualDifferentiator::new(Echo::new());
+            assert_eq!(
+                adaptive.update_batch(&TEST_DATA),
+                fresh.update_batch(&TEST_DATA)
+            );
+            assert_eq!(adaptive.view().min_period(), len(10));
+            assert_eq!(adaptive.view().max_period(), len(48));
+
+            let mut adaptive = Adaptive::new(
+                AutocorrelationPeriodogram::new(Echo::new()),
+                param,
+                Constant::new(driver),
+                len(64),
+            )
+            .unwrap();
+            let mut fresh = AutocorrelationPeriodogram::new(Echo::new());
+            assert_eq!(
+                adaptive.update_batch(&TEST_DATA),
+                fresh.update_batch(&TEST_DATA)
+            );
+            assert_eq!(
+                adaptive.window_len(),
+                len(if param == "min_period" { 10 } else { 48 })
+            );
+        }
+    }
+
+    #[test]
+    fn adaptive_half_dominant_cycle() {
+        let driver = || HilbertTransform::new(Echo::new()) * 0.5;
+        for period in [16.0, 30.0] {
+            let vals = generators::sine(400, period, 1.0, 100.0);
+            let mut adaptive = Adaptive::new(
+                CyberCycle::new(Echo::new(), len(14)),
+                "window_len",
+                driver(),
+                len(50),
+            )
+            .unwrap();
+            adaptive.update_batch(&vals);
+            assert_eq!(
+                adaptive.window_len().get() as f64,
+                (period / 2.0_f64).round()
+            );
+            assert_eq!(adaptive.view().window_len(), adaptive.window_len());
+        }
+    }
+
+    #[test]
+    fn adaptive_node() {
+        let adaptive = Adaptive::new(
+            Rsi::new(Echo::<f64>::new(), len(14)),
+            "window_len",
+            Constant::new(20.0),
+            len(64),
+        )
+        .unwrap();
+        let node = adaptive.node();
+        assert_eq!(node.children.len(), 2);
+        assert_eq!(adaptive.param_specs()[0].value, ParamValue::Int(64));
+    }
+}
@@ -0,0 +1,683 @@
+//! Golden-value conformance tests against the fixtures in `fixtures/`,
+//! which are generated from the published formulas by `fixtures/generate.py`,
+//! and against the references in `fixtures/reference/`, which `fixtures/reference/derive.py` derives
+//! independently of any implementation, e.g. in closed form, and whose headers name their sources.
+
+use std::{
+    fmt::Write,
+    num::NonZeroUsize,
+};
+
+use crate::{
+    View,
+    pure_functions::{
+        Add,
+        Constant,
+        Echo,
+    },
+    rolling::{
+        Drawdown,
+        LnReturn,
+        WelfordRolling,
+    },
+    sliding_windows::*,
+};
+
+/// Relative tolerance for comparing against the reference values.
+const TOLERANCE: f64 = 1e-9;
+
+/// Print at most this many mismatching indices per series.
+const MAX_REPORTED_MISMATCHES: usize = 20;
+
+struct Columns {
+    names: Vec<String>,
+    values: Vec<Vec<Option<f64>>>,
+}
+
+fn read_fixture(name: &str) -> Columns {
+    let path = format!("{}/fixtures/{name}.csv", env!("CARGO_MANIFEST_DIR"));
+    let content = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Can read fixture {path}: {err}"));
+    // Lines starting with `#` describe the source of the values.
+    let mut lines = content.lines().filter(|line| !line.starts_with('#'));
+    let names = Vec::from_iter(
+        lines
+            .next()
+            .expect("fixture has a header")
+            .split(',')
+            .map(String::from),
+    );
+    let mut values = vec![Vec::new(); names.len()];
+    for line in lines {
+        for (column, cell) in values.iter_mut().zip(line.split(',')) {
+            column.push(match cell {
+                "" => None,
+                cell => Some(cell.parse().expect("fixture cell is a float")),
+            });
+        }
+    }
+    Columns { names, values }
+}
+
+fn matches(expected: Option<f64>, actual: Option<f64>) -> bool {
+    match (expected, actual) {
+        (None, None) => true,
+        (Some(e), Some(a)) => (e - a).abs() <= TOLERANCE * e.abs().max(1.0),
+        (None, Some(_)) | (Some(_), None) => false,
+    }
+}
+
+/// Run `view` over the `inputs` and append the indices whose outputs don't match the `expected` ones to `report`,
+/// as a per-index diff under the `label`.
+fn report_mismatches<V: View<f64>>(
+    report: &mut String,
+    label: &str,
+    mut view: V,
+    inputs: &[Option<f64>],
+    expected: &[Option<f64>],
+    matches: impl Fn(Option<f64>, Option<f64>) -> bool,
+) {
+    assert_eq!(inputs.len(), expected.len(), "{label}: length");
+    let mut n_mismatches = 0;
+    for (i, (input, expected)) in inputs.iter().zip(expected).enumerate() {
+        view.update(input.expect("inputs are always present"));
+        let actual = view.last();
+        if matches(*expected, actual) {
+            continue;
+        }
+        if n_mismatches == 0 {
+            writeln!(
+                report,
+                "{label}:\n{:>6} {:>24} {:>24} {:>24} {:>12}",
+                "index", "input", "expected", "actual", "diff"
+            )
+            .unwrap();
+        }
+        n_mismatches += 1;
+        if n_mismatches <= MAX_REPORTED_MISMATCHES {
+            let diff = match (expected, actual) {
+                (Some(e), Some(a)) => format!("{:.3e}", a - e),
+                _ => String::from("-"),
+            };
+            writeln!(
+                report,
+                "{i:>6} {:>24} {:>24} {:>24} {diff:>12}",
+                format!("{:?}", input.unwrap()),
+                format!("{expected:?}"),
+                format!("{actual:?}"),
+            )
+            .unwrap();
+        }
+    }
+    if n_mismatches > MAX_REPORTED_MISMATCHES {
+        writeln!(
+            report,
+            "... and {} more mismatches",
+            n_mismatches - MAX_REPORTED_MISMATCHES
+        )
+        .unwrap();
+    }
+}
+
+/// Run a fresh view from `new_view` over every fixture input series
+/// and compare its outputs index by index against the `fixture`.
+fn assert_conforms<V, F>(fixture: &str, new_view: F)
+where
+    V: View<f64>,
+    F: Fn() -> V,
+{
+    let inputs = read_fixture("inputs");
+    let expected = read_fixture(fixture);
+    assert_eq!(inputs.names, expected.names, "{fixture}: series mismatch");
+
+    let mut report = String::new();
+    for ((series, inputs), expected) in inputs
+        .names
+        .iter()
+        .zip(&inputs.values)
+        .zip(&expected.values)
+    {
+        report_mismatches(
+            &mut report,
+            &format!("{fixture}/{series}"),
+            new_view(),
+            inputs,
+            expected,
+            matches,
+        );
+    }
+    assert!(report.is_empty(), "\n{report}");
+}
+
+/// Run `view` over the input of the independently derived `reference` in `fixtures/reference/`
+/// and compare its outputs against the expected ones within the relative `tolerance`,
+/// skipping the indices without an expected value.
+fn assert_matches_reference<V: View<f64>>(reference: &str, tolerance: f64, view: V) {
+    let columns = read_fixture(&format!("reference/{reference}"));
+    assert_eq!(columns.names, ["input", "expected"], "{reference}: columns");
+
+    let mut report = String::new();
+    report_mismatches(
+        &mut report,
+        reference,
+        view,
+        &columns.values[0],
+        &columns.values[1],
+        |expected, actual| match (expected, actual) {
+            (None, _) => true,
+            (Some(e), Some(a)) => (e - a).abs() <= tolerance * e.abs().max(1.0),
+            (Some(_), None) => false,
+        },
+    );
+    assert!(report.is_empty(), "\n{report}");
+}
+
+/// Run a fresh view from `new_view` over every fixture input series, which must become ready with the `fixture`,
+/// and return the largest absolute difference of its outputs from the `fixture` per series,
+/// e.g. to measure how far a view deviates from a published formula it deliberately departs from.
+fn max_deviations<V, F>(fixture: &str, new_view: F) -> Vec<(String, f64)>
+where
+    V: View<f64>,
+    F: Fn() -> V,
+{
+    let inputs = read_fixture("inputs");
+    let expected = read_fixture(fixture);
+    assert_eq!(inputs.names, expected.names, "{fixture}: series mismatch");
+
+    let mut deviations = Vec::new();
+    for ((series, inputs), expected) in inputs
+        .names
+        .iter()
+        .zip(&inputs.values)
+        .zip(&expected.values)
+    {
+        let mut view = new_view();
+        let mut max_deviation: f64 = 0.0;
+        for (i, (input, expected)) in inputs.iter().zip(expected).enumerate() {
+            view.update(input.expect("inputs are always present"));
+            match (expected, view.last()) {
+                (Some(e), Some(a)) => max_deviation = max_deviation.max((a - e).abs()),
+                (None, None) => {}
+                (expected, actual) => {
+                    panic!(
+                        "{fixture}/{series}: readiness differs at index {i}: {expected:?} vs {actual:?}"
+                    )
+                }
+            }
+        }
+        deviations.push((series.clone(), max_deviation));
+    }
+    deviations
+}
+
+fn window_len(n: usize) -> NonZeroUsize {
+    NonZeroUsize::new(n).unwrap()
+}
+
+#[test]
+fn sma_conformance() {
+    assert_conforms("sma_16", || Sma::new(Echo::new(), window_len(16)));
+    assert_matches_reference("sma_16", TOLERANCE, Sma::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn ema_conformance() {
+    assert_conforms("ema_16", || Ema::new(Echo::new(), window_len(16)));
+    assert_matches_reference("ema_16", TOLERANCE, Ema::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn dema_conformance() {
+    assert_conforms("dema_16", || Dema::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn tema_conformance() {
+    assert_conforms("tema_16", || Tema::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn t3_conformance() {
+    assert_conforms("t3_8_0_7", || T3::new(Echo::new(), window_len(8)));
+}
+
+#[test]
+fn zlema_conformance() {
+    assert_conforms("zlema_16", || Zlema::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn alma_conformance() {
+    assert_conforms("alma_16", || Alma::new(Echo::new(), window_len(16)));
+    assert_matches_reference("alma_16", TOLERANCE, Alma::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn rsi_conformance() {
+    assert_conforms("rsi_16", || Rsi::new(Echo::new(), window_len(16)));
+    assert_matches_reference("rsi_15", TOLERANCE, Rsi::new(Echo::new(), window_len(15)));
+}
+
+#[test]
+fn my_rsi_conformance() {
+    assert_conforms("my_rsi_16", || MyRSI::new(Echo::new(), window_len(16)));
+    assert_matches_reference(
+        "my_rsi_15",
+        TOLERANCE,
+        MyRSI::new(Echo::new(), window_len(15)),
+    );
+}
+
+#[test]
+fn noise_elimination_technology_conformance() {
+    assert_conforms("net_my_rsi_16", || {
+        NoiseEliminationTechnology::new(MyRSI::new(Echo::new(), window_len(16)), window_len(16))
+    });
+    assert_matches_reference(
+        "noise_elimination_technology_16",
+        TOLERANCE,
+        NoiseEliminationTechnology::new(Echo::new(), window_len(16)),
+    );
+}
+
+#[test]
+fn roc_conformance() {
+    assert_conforms("roc_16", || Roc::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn lag_conformance() {
+    assert_conforms("lag_3", || Lag::new(Echo::new(), window_len(3)));
+}
+
+#[test]
+fn max_conformance() {
+    assert_conforms("max_16", || Max::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn min_conformance() {
+    assert_conforms("min_16", || Min::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn cumulative_conformance() {
+    assert_conforms("cumulative_16", || {
+        Cumulative::new(Echo::new(), window_len(16))
+    });
+    assert_matches_reference(
+        "cumulative_16",
+        TOLERANCE,
+        Cumulative::new(Echo::new(), window_len(16)),
+    );
+}
+
+#[test]
+fn welford_online_conformance() {
+    assert_conforms("welford_online_16", || {
+        WelfordOnline::new(Echo::new(), window_len(16))
+    });
+    assert_matches_reference(
+        "welford_online_8",
+        TOLERANCE,
+        WelfordOnline::new(Echo::new(), window_len(8)),
+    );
+}
+
+#[test]
+fn welford_rolling_conformance() {
+    assert_conforms("welford_rolling", WelfordRolling::default);
+}
+
+#[test]
+fn vst_conformance() {
+    assert_conforms("vst_16", || Vst::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn vsct_conformance() {
+    assert_conforms("vsct_16", || Vsct::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn hl_normalizer_conformance() {
+    assert_conforms("hl_normalizer_16", || {
+        HLNormalizer::new(Echo::new(), window_len(16))
+    });
+    assert_matches_reference(
+        "hl_normalizer_20",
+        TOLERANCE,
+        HLNormalizer::new(Echo::new(), window_len(20)),
+    );
+}
+
+#[test]
+fn center_of_gravity_conformance() {
+    assert_conforms("center_of_gravity_16", || {
+        CenterOfGravity::new(Echo::new(), window_len(16))
+    });
+}
+
+#[test]
+fn cyber_cycle_conformance() {
+    assert_conforms("cyber_cycle_16", || {
+        CyberCycle::new(Echo::new(), window_len(16))
+    });
+    assert_matches_reference(
+        "cyber_cycle_16",
+        TOLERANCE,
+        CyberCycle::new(Echo::new(), window_len(16)),
+    );
+}
+
+#[test]
+fn super_smoother_conformance() {
+    assert_conforms("super_smoother_20", || {
+        SuperSmoother::new(Echo::new(), window_len(20))
+    });
+    assert_matches_reference(
+        "super_smoother_20",
+        TOLERANCE,
+        SuperSmoother::new(Echo::new(), window_len(20)),
+    );
+}
+
+#[test]
+fn roofing_filter_conformance() {
+    assert_conforms("roofing_filter_48_10", || {
+        RoofingFilter::new(Echo::new(), window_len(48), window_len(10))
+    });
+    assert_matches_reference(
+        "roofing_filter_48_10",
+        TOLERANCE,
+        RoofingFilter::new(Echo::new(), window_len(48), window_len(10)),
+    );
+}
+
+#[test]
+fn laguerre_filter_conformance() {
+    assert_conforms("laguerre_filter_0_8", || {
+        LaguerreFilter::new(Echo::new(), 0.8)
+    });
+    assert_matches_reference(
+        "laguerre_filter_0_8",
+        TOLERANCE,
+        LaguerreFilter::new(Echo::new(), 0.8),
+    );
+}
+
+#[test]
+fn laguerre_rsi_conformance() {
+    assert_conforms("laguerre_rsi_16", || {
+        LaguerreRSI::new(Echo::new(), window_len(16))
+    });
+    assert_matches_reference(
+        "laguerre_rsi_16",
+        TOLERANCE,
+        LaguerreRSI::new(Echo::new(), window_len(16)),
+    );
+}
+
+#[test]
+fn re_flex_conformance() {
+    assert_conforms("re_flex_16", || ReFlex::new(Echo::new(), window_len(16)));
+    assert_matches_reference(
+        "re_flex_16",
+        TOLERANCE,
+        ReFlex::new(Echo::new(), window_len(16)),
+    );
+}
+
+#[test]
+fn trend_flex_conformance() {
+    assert_conforms("trend_flex_16", || {
+        TrendFlex::new(Echo::new(), window_len(16))
+    });
+    assert_matches_reference(
+        "trend_flex_16",
+        TOLERANCE,
+        TrendFlex::new(Echo::new(), window_len(16)),
+    );
+}
+
+#[test]
+fn correlation_trend_indicator_conformance() {
+    assert_conforms("correlation_trend_indicator_16", || {
+        CorrelationTrendIndicator::new(Echo::new(), window_len(16))
+    });
+    assert_matches_reference(
+        "correlation_trend_indicator_16",
+        TOLERANCE,
+        CorrelationTrendIndicator::new(Echo::new(), window_len(16)),
+    );
+}
+
+#[test]
+fn polarized_fractal_efficiency_conformance() {
+    assert_conforms("polarized_fractal_efficiency_16_ema_16", || {
+        PolarizedFractalEfficiency::new(
+            Echo::new(),
+            Ema::new(Echo::new(), window_len(16)),
+            window_len(16),
+        )
+    });
+    assert_matches_reference(
+        "polarized_fractal_efficiency_16_ema_16",
+        TOLERANCE,
+        PolarizedFractalEfficiency::new(
+            Echo::new(),
+            Ema::new(Echo::new(), window_len(16)),
+            window_len(16),
+        ),
+    );
+}
+
+#[test]
+fn ehlers_fisher_transform_conformance() {
+    assert_conforms("ehlers_fisher_transform_16_ema_5", || {
+        EhlersFisherTransform::new(
+            Echo::new(),
+            Ema::new(Echo::new(), window_len(5)),
+            window_len(16),
+        )
+    });
+    assert_matches_reference(
+        "ehlers_fisher_transform_16_ema_5",
+        TOLERANCE,
+        EhlersFisherTransform::new(
+            Echo::new(),
+            Ema::new(Echo::new(), window_len(5)),
+            window_len(16),
+        ),
+    );
+}
+
+#[test]
+fn binary_entropy_conformance() {
+    assert_conforms("binary_entropy_16_centered", || {
+        BinaryEntropy::new(Add::new(Echo::new(), Constant::new(-100.0)), window_len(16))
+    });
+}
+
+#[test]
+fn wma_conformance() {
+    assert_conforms("wma_16", || Wma::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn hull_ma_conformance() {
+    assert_conforms("hull_ma_16", || HullMa::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn trima_conformance() {
+    assert_conforms("trima_16", || Trima::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn sine_ma_conformance() {
+    assert_conforms("sine_ma_16", || SineMa::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn pascal_ma_conformance() {
+    assert_conforms("pascal_ma_16", || {
+        PascalMa::new(Echo::new(), window_len(16))
+    });
+}
+
+#[test]
+fn kama_conformance() {
+    assert_conforms("kama_10", || Kama::new(Echo::new(), window_len(10)));
+}
+
+#[test]
+fn cmo_conformance() {
+    assert_conforms("cmo_14", || Cmo::new(Echo::new(), window_len(14)));
+}
+
+#[test]
+fn vidya_conformance() {
+    assert_conforms("vidya_14_cmo_9", || {
+        Vidya::with_cmo(Echo::new(), window_len(14), window_len(9))
+    });
+    assert_conforms("vidya_14_std_dev_ratio_5_20", || {
+        Vidya::with_std_dev_ratio(Echo::new(), window_len(14), window_len(5), window_len(20))
+    });
+}
+
+#[test]
+fn frama_conformance() {
+    assert_conforms("frama_16", || Frama::new(Echo::new(), window_len(16)));
+}
+
+#[test]
+fn mc_ginley_dynamic_conformance() {
+    assert_conforms("mc_ginley_dynamic_14", || {
+        McGinleyDynamic::new(Echo::new(), window_len(14))
+    });
+}
+
+#[test]
+fn hilbert_transform_conformance() {
+    assert_conforms("hilbert_transform", || HilbertTransform::new(Echo::new()));
+}
+
+#[test]
+fn mama_conformance() {
+    assert_conforms("mama_0_5_0_05", || Mama::new(Echo::new()));
+    assert_conforms("fama_0_5_0_05", || Fama::new(Echo::new()));
+}
+
+#[test]
+fn dual_differentiator_conformance() {
+    assert_conforms("dual_differentiator_10_48", || {
+        DualDifferentiator::new(Echo::new())
+    });
+}
+
+#[test]
+fn autocorrelation_periodogram_conformance() {
+    assert_conforms("autocorrelation_periodogram_10_48", || {
+        AutocorrelationPeriodogram::new(Echo::new())
+    });
+}
+
+#[test]
+fn sinewave_conformance() {
+    assert_conforms("sinewave", || Sinewave::new(Echo::new()));
+    assert_conforms("lead_sine", || LeadSine::new(Echo::new()));
+}
+
+#[test]
+fn sinewave_published_deviation() {
+    // The published correlation over the period rounded to whole samples jumps as the rounded period changes,
+    // e.g. by up to 0.62 on the noisy test data, while it agrees closely for a clean cycle or trend.
+    let deviations = max_deviations("sinewave_published", || Sinewave::new(Echo::new()));
+    assert_eq!(
+        deviations
+            .iter()
+            .map(|(series, _)| series.as_str())
+            .collect::<Vec<_>>(),
+        ["test_data", "sine", "trend"]
+    );
+    assert!(deviations[0].1 < 0.7, "{deviations:?}");
+    assert!(deviations[1].1 < 0.02, "{deviations:?}");
+    assert!(deviations[2].1 < 0.1, "{deviations:?}");
+}
+
+#[test]
+fn instantaneous_trendline_conformance() {
+    assert_conforms("instantaneous_trendline", || {
+        InstantaneousTrendline::new(Echo::new())
+    });
+}
+
+#[test]
+fn even_better_sinewave_conformance() {
+    assert_conforms("even_better_sinewave_40_10", || {
+        EvenBetterSinewave::new(Echo::new())
+    });
+}
+
+#[test]
+fn high_pass_conformance() {
+    assert_conforms("one_pole_high_pass_20", || {
+        OnePoleHighPass::new(Echo::new(), window_len(20))
+    });
+    assert_conforms("two_pole_high_pass_20", || {
+        TwoPoleHighPass::new(Echo::new(), window_len(20))
+    });
+}
+
+#[test]
+fn decycler_conformance() {
+    assert_conforms("decycler_20", || Decycler::new(Echo::new(), window_len(20)));
+    assert_conforms("decycler_oscillator_15_30", || {
+        DecyclerOscillator::new(Echo::new(), window_len(15), window_len(30))
+    });
+}
+
+#[test]
+fn automatic_gain_control_conformance() {
+    assert_conforms("automatic_gain_control_re_flex_16", || {
+        AutomaticGainControl::new(ReFlex::new(Echo::new(), window_len(16)))
+    });
+}
+
+#[test]
+fn band_pass_conformance() {
+    assert_conforms("band_pass_20_0_3", || {
+        BandPass::new(Echo::new(), window_len(20), 0.3)
+    });
+    assert_conforms("band_pass_signal_20_0_3", || {
+        AutomaticGainControl::new(BandPass::new(Echo::new(), window_len(20), 0.3))
+    });
+    assert_conforms("band_pass_trigger_20_0_3", || {
+        BandPassTrigger::new(Echo::new(), window_len(20), 0.3)
+    });
+}
+
+#[test]
+fn universal_oscillator_conformance() {
+    assert_conforms("universal_oscillator_20", || {
+        UniversalOscillator::new(Echo::new(), window_len(20))
+    });
+}
+
+#[test]
+fn inverse_fisher_transform_conformance() {
+    assert_conforms("inverse_fisher_transform_rsi_5", || {
+        InverseFisherTransform::with_rsi_scaling(Rsi::new(Echo::new(), window_len(5)))
+    });
+}
+
+#[test]
+fn ln_return_conformance() {
+    assert_conforms("ln_return", LnReturn::default);
+}
+
+#[test]
+fn drawdown_conformance() {
+    assert_conforms("drawdown", Drawdown::default);
+}
@@ -0,0 +1,130 @@
+//! The crate-wide error type, returned by the fallible constructors and setters.
+
+use std::fmt::{
+    Display,
+    Formatter,
+};
+
+/// A parameter outside of its valid domain, which would otherwise produce non-finite outputs.
+/// Each variant documents the valid range and carries the rejected value.
+/// New views add variants, so matching on it requires a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The `gamma` of a `LaguerreFilter` must be within `[0, 1)`.
+    LaguerreFilterGamma(f64),
+    /// The `sigma` of an `Alma` must be finite and positive.
+    AlmaSigma(f64),
+    /// The `offset` of an `Alma` must be within `[0, 1]`.
+    AlmaOffset(f64),
+    /// The `alpha` of an `Ema` must be within `(0, 2]`.
+    EmaAlpha(f64),
+    /// The volume factor of a `T3` must be within `[0, 1]`.
+    T3VolumeFactor(f64),
+    /// The fast limit of a `Mama` must be within `(0, 1]`.
+    MamaFastLimit(f64),
+    /// The slow limit of a `Mama` must be within `(0, fast_limit]`.
+    MamaSlowLimit(f64),
+    /// The periods of a dominant cycle estimator must satisfy `3 <= min_period <= max_period`.
+    CyclePeriods(usize, usize),
+    /// The low-pass window length of a `RoofingFilter` must be at least 3.
+    RoofingFilterLowPassLen(usize),
+    /// The cutoff period of a 1-pole high-pass filter must be at least 5.
+    OnePoleHighPassLen(usize),
+    /// The cutoff period of a 2-pole high-pass filter must be at least 3.
+    TwoPoleHighPassLen(usize),
+    /// The decay of an `AutomaticGainControl` must be within `(0, 1)`.
+    AutomaticGainControlDecay(f64),
+    /// The center period of a `BandPass` must be at least 7.
+    BandPassPeriod(usize),
+    /// The relative bandwidth of a `BandPass` must be within `(0, 1]`.
+    BandPassBandwidth(f64),
+    /// The center of an `InverseFisherTransform` must be finite.
+    InverseFisherTransformCenter(f64),
+    /// The scale of an `InverseFisherTransform` must be positive and finite.
+    InverseFisherTransformScale(f64),
+    /// The maximum window length of an `Adaptive` view must be at least the lower bound of its parameter,
+    /// carrying the maximum and the lower bound.
+    AdaptiveMaxLen(usize, usize),
+    /// The clipping point of a `GTE` or `LTE` must be finite.
+    ClippingPoint(f64),
+    /// The view has no parameter of the given name.
+    UnknownParameter,
+    /// The parameter of the given name has another type.
+    ParameterType(&'static str),
+    /// The window length parameter of the given name must be at least 1.
+    ZeroWindowLen(&'static str),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::LaguerreFilterGamma(gamma) => {
+                write!(f, "LaguerreFilter gamma {gamma} is not within [0, 1)")
+            }
+            Error::AlmaSigma(sigma) => write!(f, "Alma sigma {sigma} is not positive"),
+            Error::AlmaOffset(offset) => write!(f, "Alma offset {offset} is not within [0, 1]"),
+            Error::EmaAlpha(alpha) => write!(f, "Ema alpha {alpha} is not within (0, 2]"),
+            Error::T3VolumeFactor(volume_factor) => {
+                write!(f, "T3 volume factor {volume_factor} is not within [0, 1]")
+            }
+            Error::MamaFastLimit(fast_limit) => {
+                write!(f, "Mama fast limit {fast_limit} is not within (0, 1]")
+            }
+            Error::MamaSlowLimit(slow_limit) => {
+                write!(
+                    f,
+                    "Mama slow limit {slow_limit} is not within (0, fast_limit]"
+                )
+            }
+            Error::CyclePeriods(min_period, max_period) => {
+                write!(
+                    f,
+                    "cycle periods [{min_period}, {max_period}] are not within [3, max_period]"
+                )
+            }
+            Error::RoofingFilterLowPassLen(len) => {
+                write!(
+                    f,
+                    "RoofingFilter low-pass window length {len} is less than 3"
+                )
+            }
+            Error::AdaptiveMaxLen(max_len, min_len) => {
+                write!(
+                    f,
+                    "Adaptive maximum window length {max_len} is less than {min_len}"
+                )
+            }
+            Error::OnePoleHighPassLen(len) => {
+                write!(f, "1-pole high-pass cutoff period {len} is less than 5")
+            }
+            Error::TwoPoleHighPassLen(len) => {
+                write!(f, "2-pole high-pass cutoff period {len} is less than 3")
+            }
+            Error::AutomaticGainControlDecay(decay) => {
+                write!(f, "AutomaticGainControl decay {decay} is not within (0, 1)")
+            }
+            Error::BandPassPeriod(period) => {
+                write!(f, "BandPass period {period} is less than 7")
+            }
+            Error::BandPassBandwidth(bandwidth) => {
+                write!(f, "BandPass bandwidth {bandwidth} is not within (0, 1]")
+            }
+            Error::InverseFisherTransformCenter(center) => {
+                write!(f, "InverseFisherTransform center {center} is not finite")
+            }
+            Error::InverseFisherTransformScale(scale) => {
+                write!(
+                    f,
+                    "InverseFisherTransform scale {scale} is not positive and finite"
+                )
+            }
+            Error::ClippingPoint(point) => write!(f, "clipping point {point} is not finite"),
+            Error::UnknownParameter => write!(f, "unknown parameter"),
+            Error::ParameterType(name) => write!(f, "parameter {name} has another type"),
+            Error::ZeroWindowLen(name) => write!(f, "window length {name} is zero"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
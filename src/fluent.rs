@@ -0,0 +1,1066 @@
+//! Fluent composition of chains, as an alternative to nesting constructors.
+//! `ViewExt` wraps any view in the next one, e.g. `Echo::new().ema(10).roc(15).hl_normalize(20)`,
+//! taking window lengths as plain `usize`.
+//! The arithmetic operators combine views into `Add`, `Subtract`, `Multiply` and `Divide` nodes,
+//! e.g. `ema - sma`, with `f32` or `f64` scalars as `Constant` operands, e.g. `2.0 * ema`.
+
+use std::{
+    num::NonZeroUsize,
+    ops,
+};
+
+use num::Float;
+
+use crate::{
+    View,
+    pure_functions::{
+        Add,
+        Arctan,
+        Constant,
+        Divide,
+        Echo,
+        FisherTransform,
+        GTE,
+        LTE,
+        Logistic,
+        Multiply,
+        Softsign,
+        Subtract,
+        Tanh,
+    },
+    rolling::{
+        Drawdown,
+        LnReturn,
+        WelfordRolling,
+    },
+    sliding_windows::{
+        Alma,
+        AutocorrelationPeriodogram,
+        AutomaticGainControl,
+        BandPass,
+        BandPassTrigger,
+        BinaryEntropy,
+        CenterOfGravity,
+        Cmo,
+        CorrelationTrendIndicator,
+        Cumulative,
+        CyberCycle,
+        Decycler,
+        DecyclerOscillator,
+        Dema,
+        DualDifferentiator,
+        EhlersFisherTransform,
+        Ema,
+        EvenBetterSinewave,
+        Fama,
+        Frama,
+        HLNormalizer,
+        HilbertTransform,
+        HullMa,
+        InstantaneousTrendline,
+        InverseFisherTransform,
+        Kama,
+        Kernel,
+        KernelMa,
+        Lag,
+        LaguerreFilter,
+        LaguerreRSI,
+        LeadSine,
+        Mama,
+        Max,
+        McGinleyDynamic,
+        Min,
+        MyRSI,
+        NoiseEliminationTechnology,
+        OnePoleHighPass,
+        PascalMa,
+        PolarizedFractalEfficiency,
+        ReFlex,
+        Roc,
+        RoofingFilter,
+        Rsi,
+        SineMa,
+        Sinewave,
+        Sma,
+        StdDevRatio,
+        SuperSmoother,
+        T3,
+        Tema,
+        TrendFlex,
+        Trima,
+        TwoPoleHighPass,
+        UniversalOscillator,
+        Vidya,
+        Vsct,
+        Vst,
+        WelfordOnline,
+        Wma,
+        Zlema,
+    },
+};
+
+/// Fluent constructors, wrapping a view in the next view of the chain.
+/// The window lengths must be positive, otherwise the constructors panic.
+pub trait ViewExt<T: Float>: View<T> + Sized {
+    /// Wrap in an `Alma` with the default sigma and offset.
+    fn alma(self, window_len: usize) -> Alma<T, Self> {
+        Alma::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in an `Alma` with a custom sigma and offset.
+    fn alma_custom(self, window_len: usize, sigma: T, offset: T) -> Alma<T, Self> {
+        Alma::new_custom(self, non_zero(window_len), sigma, offset)
+    }
+
+    /// Wrap in an `AutocorrelationPeriodogram` with the default periods of 10 to 48 samples.
+    fn autocorrelation_periodogram(self) -> AutocorrelationPeriodogram<T, Self> {
+        AutocorrelationPeriodogram::new(self)
+    }
+
+    /// Wrap in an `AutocorrelationPeriodogram` with a custom range of periods.
+    fn autocorrelation_periodogram_with_periods(
+        self,
+        min_period: usize,
+        max_period: usize,
+    ) -> AutocorrelationPeriodogram<T, Self> {
+        AutocorrelationPeriodogram::with_periods(self, non_zero(min_period), non_zero(max_period))
+    }
+
+    /// Wrap in an `AutomaticGainControl` with the default decay of 0.991.
+    fn automatic_gain_control(self) -> AutomaticGainControl<T, Self> {
+        AutomaticGainControl::new(self)
+    }
+
+    /// Wrap in an `AutomaticGainControl` with a custom decay of the peak.
+    fn automatic_gain_control_with_decay(self, decay: T) -> AutomaticGainControl<T, Self> {
+        AutomaticGainControl::with_decay(self, decay)
+    }
+
+    /// Wrap in a `BandPass`.
+    fn band_pass(self, period: usize, bandwidth: T) -> BandPass<T, Self> {
+        BandPass::new(self, non_zero(period), bandwidth)
+    }
+
+    /// Wrap in a `BandPassTrigger`.
+    fn band_pass_trigger(self, period: usize, bandwidth: T) -> BandPassTrigger<T, Self> {
+        BandPassTrigger::new(self, non_zero(period), bandwidth)
+    }
+
+    /// Wrap in a `BinaryEntropy`.
+    fn binary_entropy(self, window_len: usize) -> BinaryEntropy<T, Self> {
+        BinaryEntropy::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `CenterOfGravity`.
+    fn center_of_gravity(self, window_len: usize) -> CenterOfGravity<T, Self> {
+        CenterOfGravity::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Cmo`.
+    fn cmo(self, window_len: usize) -> Cmo<T, Self> {
+        Cmo::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `CorrelationTrendIndicator`.
+    fn correlation_trend_indicator(self, window_len: usize) -> CorrelationTrendIndicator<T, Self> {
+        CorrelationTrendIndicator::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Cumulative`.
+    fn cumulative(self, window_len: usize) -> Cumulative<T, Self> {
+        Cumulative::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `CyberCycle`.
+    fn cyber_cycle(self, window_len: usize) -> CyberCycle<T, Self> {
+        CyberCycle::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Decycler` with a 1-pole high-pass filter.
+    fn decycler(self, cutoff_period: usize) -> Decycler<T, Self> {
+        Decycler::new(self, non_zero(cutoff_period))
+    }
+
+    /// Wrap in a `Decycler` with any `high_pass` filter chained on an `Echo`.
+    fn decycler_with_high_pass<H: View<T>>(self, high_pass: H) -> Decycler<T, Self, H> {
+        Decycler::with_high_pass(self, high_pass)
+    }
+
+    /// Wrap in a `DecyclerOscillator`.
+    fn decycler_oscillator(
+        self,
+        fast_period: usize,
+        slow_period: usize,
+    ) -> DecyclerOscillator<T, Self> {
+        DecyclerOscillator::new(self, non_zero(fast_period), non_zero(slow_period))
+    }
+
+    /// Wrap in a `Dema`.
+    fn dema(self, window_len: usize) -> Dema<T, Self> {
+        Dema::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `DualDifferentiator` with the default periods of 10 to 48 samples.
+    fn dual_differentiator(self) -> DualDifferentiator<T, Self> {
+        DualDifferentiator::new(self)
+    }
+
+    /// Wrap in a `DualDifferentiator` with a custom range of periods.
+    fn dual_differentiator_with_periods(
+        self,
+        min_period: usize,
+        max_period: usize,
+    ) -> DualDifferentiator<T, Self> {
+        DualDifferentiator::with_periods(self, non_zero(min_period), non_zero(max_period))
+    }
+
+    /// Wrap in an `EhlersFisherTransform`, smoothed by the moving average `ma`.
+    fn ehlers_fisher_transform<M: View<T>>(
+        self,
+        ma: M,
+        window_len: usize,
+    ) -> EhlersFisherTransform<T, Self, M> {
+        EhlersFisherTransform::new(self, ma, non_zero(window_len))
+    }
+
+    /// Wrap in an `Ema` with the default alpha.
+    fn ema(self, window_len: usize) -> Ema<T, Self> {
+        Ema::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in an `Ema` with a custom alpha.
+    fn ema_with_alpha(self, window_len: usize, alpha: T) -> Ema<T, Self> {
+        Ema::with_alpha(self, non_zero(window_len), alpha)
+    }
+
+    /// Wrap in an `EvenBetterSinewave` with the default high-pass and `SuperSmoother` lengths.
+    fn even_better_sinewave(self) -> EvenBetterSinewave<T, Self> {
+        EvenBetterSinewave::new(self)
+    }
+
+    /// Wrap in an `EvenBetterSinewave` with custom high-pass and `SuperSmoother` lengths.
+    fn even_better_sinewave_with_lens(
+        self,
+        high_pass_len: usize,
+        super_smoother_len: usize,
+    ) -> EvenBetterSinewave<T, Self> {
+        EvenBetterSinewave::with_lens(self, non_zero(high_pass_len), non_zero(super_smoother_len))
+    }
+
+    /// Wrap in a `Fama` with the default limits of the smoothing constant.
+    fn fama(self) -> Fama<T, Self> {
+        Fama::new(self)
+    }
+
+    /// Wrap in a `Fama` with custom limits of the smoothing constant.
+    fn fama_with_limits(self, fast_limit: T, slow_limit: T) -> Fama<T, Self> {
+        Fama::with_limits(self, fast_limit, slow_limit)
+    }
+
+    /// Wrap in a `Frama`, rounding the window length up to an even one.
+    fn frama(self, window_len: usize) -> Frama<T, Self> {
+        Frama::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `HilbertTransform`, measuring the dominant cycle period.
+    fn hilbert_transform(self) -> HilbertTransform<T, Self> {
+        HilbertTransform::new(self)
+    }
+
+    /// Wrap in an `HLNormalizer`.
+    fn hl_normalize(self, window_len: usize) -> HLNormalizer<T, Self> {
+        HLNormalizer::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `HullMa`.
+    fn hull_ma(self, window_len: usize) -> HullMa<T, Self> {
+        HullMa::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in an `InstantaneousTrendline`.
+    fn instantaneous_trendline(self) -> InstantaneousTrendline<T, Self> {
+        InstantaneousTrendline::new(self)
+    }
+
+    /// Wrap in an `InverseFisherTransform`, centering and scaling the values before smoothing them with `ma`.
+    fn inverse_fisher_transform<M: View<T>>(
+        self,
+        ma: M,
+        center: T,
+        scale: T,
+    ) -> InverseFisherTransform<T, Self, M> {
+        InverseFisherTransform::new(self, ma, center, scale)
+    }
+
+    /// Wrap in an `InverseFisherTransform` with Ehlers' conventions for an `Rsi`.
+    fn inverse_fisher_transform_rsi(self) -> InverseFisherTransform<T, Self, Wma<T, Echo<T>>> {
+        InverseFisherTransform::with_rsi_scaling(self)
+    }
+
+    /// Wrap in a `Kama` with the default fast and slow EMA lengths.
+    fn kama(self, window_len: usize) -> Kama<T, Self> {
+        Kama::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Kama` with custom fast and slow EMA lengths.
+    fn kama_custom(self, window_len: usize, fast_len: usize, slow_len: usize) -> Kama<T, Self> {
+        Kama::new_custom(
+            self,
+            non_zero(window_len),
+            non_zero(fast_len),
+            non_zero(slow_len),
+        )
+    }
+
+    /// Wrap in a `KernelMa` with a custom kernel.
+    fn kernel_ma<K: Kernel<T>>(self, window_len: usize, kernel: K) -> KernelMa<T, Self, K> {
+        KernelMa::with_kernel(self, non_zero(window_len), kernel)
+    }
+
+    /// Wrap in a `Lag`.
+    fn lag(self, window_len: usize) -> Lag<T, Self> {
+        Lag::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `LaguerreFilter`.
+    fn laguerre_filter(self, gamma: T) -> LaguerreFilter<T, Self> {
+        LaguerreFilter::new(self, gamma)
+    }
+
+    /// Wrap in a `LaguerreRSI`.
+    fn laguerre_rsi(self, window_len: usize) -> LaguerreRSI<T, Self> {
+        LaguerreRSI::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `LeadSine`, the lead sine of a `Sinewave`.
+    fn lead_sine(self) -> LeadSine<T, Self> {
+        LeadSine::new(self)
+    }
+
+    /// Wrap in a `Mama` with the default limits of the smoothing constant.
+    fn mama(self) -> Mama<T, Self> {
+        Mama::new(self)
+    }
+
+    /// Wrap in a `Mama` with custom limits of the smoothing constant.
+    fn mama_with_limits(self, fast_limit: T, slow_limit: T) -> Mama<T, Self> {
+        Mama::with_limits(self, fast_limit, slow_limit)
+    }
+
+    /// Wrap in a sliding `Max`.
+    fn max(self, window_len: usize) -> Max<T, Self> {
+        Max::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `McGinleyDynamic`.
+    fn mc_ginley_dynamic(self, window_len: usize) -> McGinleyDynamic<T, Self> {
+        McGinleyDynamic::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a sliding `Min`.
+    fn min(self, window_len: usize) -> Min<T, Self> {
+        Min::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `MyRSI`.
+    fn my_rsi(self, window_len: usize) -> MyRSI<T, Self> {
+        MyRSI::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `NoiseEliminationTechnology`.
+    fn noise_elimination_technology(
+        self,
+        window_len: usize,
+    ) -> NoiseEliminationTechnology<T, Self> {
+        NoiseEliminationTechnology::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `OnePoleHighPass`.
+    fn one_pole_high_pass(self, cutoff_period: usize) -> OnePoleHighPass<T, Self> {
+        OnePoleHighPass::new(self, non_zero(cutoff_period))
+    }
+
+    /// Wrap in a `PascalMa`.
+    fn pascal_ma(self, window_len: usize) -> PascalMa<T, Self> {
+        PascalMa::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `PolarizedFractalEfficiency`, smoothed by the `moving_average`.
+    fn polarized_fractal_efficiency<M: View<T>>(
+        self,
+        moving_average: M,
+        window_len: usize,
+    ) -> PolarizedFractalEfficiency<T, Self, M> {
+        PolarizedFractalEfficiency::new(self, moving_average, non_zero(window_len))
+    }
+
+    /// Wrap in a `ReFlex`.
+    fn re_flex(self, window_len: usize) -> ReFlex<T, Self> {
+        ReFlex::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Roc`.
+    fn roc(self, window_len: usize) -> Roc<T, Self> {
+        Roc::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `RoofingFilter`.
+    fn roofing_filter(
+        self,
+        window_len_low_pass: usize,
+        super_smoother_len_high_pass: usize,
+    ) -> RoofingFilter<T, Self> {
+        RoofingFilter::new(
+            self,
+            non_zero(window_len_low_pass),
+            non_zero(super_smoother_len_high_pass),
+        )
+    }
+
+    /// Wrap in a `RoofingFilter` with any `high_pass` filter and `smoother`, both chained on an `Echo`.
+    fn roofing_filter_with_filters<H: View<T>, S: View<T>>(
+        self,
+        high_pass: H,
+        smoother: S,
+    ) -> RoofingFilter<T, Self, H, S> {
+        RoofingFilter::with_filters(self, high_pass, smoother)
+    }
+
+    /// Wrap in an `Rsi`.
+    fn rsi(self, window_len: usize) -> Rsi<T, Self> {
+        Rsi::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `SineMa`.
+    fn sine_ma(self, window_len: usize) -> SineMa<T, Self> {
+        SineMa::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Sinewave`.
+    fn sinewave(self) -> Sinewave<T, Self> {
+        Sinewave::new(self)
+    }
+
+    /// Wrap in an `Sma`.
+    fn sma(self, window_len: usize) -> Sma<T, Self> {
+        Sma::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `StdDevRatio`.
+    fn std_dev_ratio(self, short_len: usize, long_len: usize) -> StdDevRatio<T, Self> {
+        StdDevRatio::new(self, non_zero(short_len), non_zero(long_len))
+    }
+
+    /// Wrap in a `SuperSmoother`.
+    fn super_smoother(self, window_len: usize) -> SuperSmoother<T, Self> {
+        SuperSmoother::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `TrendFlex`.
+    fn trend_flex(self, window_len: usize) -> TrendFlex<T, Self> {
+        TrendFlex::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `T3` with the default volume factor.
+    fn t3(self, window_len: usize) -> T3<T, Self> {
+        T3::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `T3` with a custom volume factor.
+    fn t3_with_volume_factor(self, window_len: usize, volume_factor: T) -> T3<T, Self> {
+        T3::with_volume_factor(self, non_zero(window_len), volume_factor)
+    }
+
+    /// Wrap in a `Tema`.
+    fn tema(self, window_len: usize) -> Tema<T, Self> {
+        Tema::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Trima`.
+    fn trima(self, window_len: usize) -> Trima<T, Self> {
+        Trima::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `TwoPoleHighPass`.
+    fn two_pole_high_pass(self, cutoff_period: usize) -> TwoPoleHighPass<T, Self> {
+        TwoPoleHighPass::new(self, non_zero(cutoff_period))
+    }
+
+    /// Wrap in a `UniversalOscillator`.
+    fn universal_oscillator(self, band_edge: usize) -> UniversalOscillator<T, Self> {
+        UniversalOscillator::new(self, non_zero(band_edge))
+    }
+
+    /// Wrap in a `Vidya` with a custom volatility index, which must be chained to an `Echo`.
+    fn vidya<K: View<T>>(self, window_len: usize, volatility_index: K) -> Vidya<T, Self, K> {
+        Vidya::new(self, non_zero(window_len), volatility_index)
+    }
+
+    /// Wrap in a `Vidya` with the absolute `Cmo` as volatility index.
+    fn vidya_cmo(self, window_len: usize, cmo_len: usize) -> Vidya<T, Self, Cmo<T, Echo<T>>> {
+        Vidya::with_cmo(self, non_zero(window_len), non_zero(cmo_len))
+    }
+
+    /// Wrap in a `Vidya` with a `StdDevRatio` as volatility index.
+    fn vidya_std_dev_ratio(
+        self,
+        window_len: usize,
+        short_len: usize,
+        long_len: usize,
+    ) -> Vidya<T, Self, StdDevRatio<T, Echo<T>>> {
+        Vidya::with_std_dev_ratio(
+            self,
+            non_zero(window_len),
+            non_zero(short_len),
+            non_zero(long_len),
+        )
+    }
+
+    /// Wrap in a `Vsct`.
+    fn vsct(self, window_len: usize) -> Vsct<T, Self> {
+        Vsct::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Vst`.
+    fn vst(self, window_len: usize) -> Vst<T, Self> {
+        Vst::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `WelfordOnline`.
+    fn welford_online(self, window_len: usize) -> WelfordOnline<T, Self> {
+        WelfordOnline::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Wma`.
+    fn wma(self, window_len: usize) -> Wma<T, Self> {
+        Wma::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Zlema`.
+    fn zlema(self, window_len: usize) -> Zlema<T, Self> {
+        Zlema::new(self, non_zero(window_len))
+    }
+
+    /// Wrap in a `Drawdown`.
+    fn drawdown(self) -> Drawdown<T, Self> {
+        Drawdown::new(self)
+    }
+
+    /// Wrap in an `LnReturn`.
+    fn ln_return(self) -> LnReturn<T, Self> {
+        LnReturn::new(self)
+    }
+
+    /// Wrap in a `WelfordRolling`.
+    fn welford_rolling(self) -> WelfordRolling<T, Self> {
+        WelfordRolling::new(self)
+    }
+
+    /// Wrap in an `Arctan`, mapping into `(-1, 1)`.
+    fn arctan(self) -> Arctan<T, Self> {
+        Arctan::new(self)
+    }
+
+    /// Wrap in a `FisherTransform`, without normalizing the values.
+    fn fisher_transform(self) -> FisherTransform<T, Self> {
+        FisherTransform::new(self)
+    }
+
+    /// Wrap in a `GTE`, clipping lower values to `clipping_point`.
+    fn gte(self, clipping_point: T) -> GTE<T, Self> {
+        GTE::new(self, clipping_point)
+    }
+
+    /// Wrap in a `Logistic`, mapping into `(-1, 1)`.
+    fn logistic(self) -> Logistic<T, Self> {
+        Logistic::new(self)
+    }
+
+    /// Wrap in an `LTE`, clipping higher values to `clipping_value`.
+    fn lte(self, clipping_value: T) -> LTE<T, Self> {
+        LTE::new(self, clipping_value)
+    }
+
+    /// Wrap in a `Softsign`, mapping into `(-1, 1)`.
+    fn softsign(self) -> Softsign<T, Self> {
+        Softsign::new(self)
+    }
+
+    /// Wrap in a `Tanh`.
+    fn tanh(self) -> Tanh<T, Self> {
+        Tanh::new(self)
+    }
+}
+
+impl<T: Float, V: View<T>> ViewExt<T> for V {}
+
+fn non_zero(window_len: usize) -> NonZeroUsize {
+    NonZeroUsize::new(window_len).expect("window length must be positive")
+}
+
+/// Implement the arithmetic operators for a view type, whose type parameters other than `T` are views,
+/// except for an optional trailing parameter after a `;` with a bound generic over `T`, e.g. a `Kernel`.
+macro_rules! impl_ops {
+    ($($view:ident<T $(, $param:ident)* $(; $extra:ident: $bound:ident)?>),* $(,)?) => {
+        $(
+            impl_ops!(@binary $view<T $(, $param)* $(; $extra: $bound)?>, Add, add, Add);
+            impl_ops!(@binary $view<T $(, $param)* $(; $extra: $bound)?>, Sub, sub, Subtract);
+            impl_ops!(@binary $view<T $(, $param)* $(; $extra: $bound)?>, Mul, mul, Multiply);
+            impl_ops!(@binary $view<T $(, $param)* $(; $extra: $bound)?>, Div, div, Divide);
+
+            impl<T: Float, $($param: View<T>,)* $($extra: $bound<T>)?> ops::Neg
+                for $view<T $(, $param)* $(, $extra)?>
+            {
+                type Output = Multiply<T, Self, Constant<T>>;
+
+                fn neg(self) -> Self::Output {
+                    Multiply::new(self, Constant::new(-T::one()))
+                }
+            }
+        )*
+    };
+    (@binary $view:ident<T $(, $param:ident)* $(; $extra:ident: $bound:ident)?>, $op:ident, $method:ident, $node:ident) => {
+        impl<T: Float, $($param: View<T>,)* $($extra: $bound<T>,)? R: View<T>> ops::$op<R>
+            for $view<T $(, $param)* $(, $extra)?>
+        {
+            type Output = $node<T, Self, R>;
+
+            fn $method(self, rhs: R) -> Self::Output {
+                $node::new(self, rhs)
+            }
+        }
+
+        impl_ops!(@scalar $view<T $(, $param)* $(; $extra: $bound)?>, $op, $method, $node, f32);
+        impl_ops!(@scalar $view<T $(, $param)* $(; $extra: $bound)?>, $op, $method, $node, f64);
+    };
+    (@scalar $view:ident<T $(, $param:ident)* $(; $extra:ident: $bound:ident)?>, $op:ident, $method:ident, $node:ident, $float:ty) => {
+        impl<$($param: View<$float>,)* $($extra: $bound<$float>)?> ops::$op<$float>
+            for $view<$float $(, $param)* $(, $extra)?>
+        {
+            type Output = $node<$float, Self, Constant<$float>>;
+
+            fn $method(self, rhs: $float) -> Self::Output {
+                $node::new(self, Constant::new(rhs))
+            }
+        }
+
+        impl<$($param: View<$float>,)* $($extra: $bound<$float>)?> ops::$op<$view<$float $(, $param)* $(, $extra)?>>
+            for $float
+        {
+            type Output = $node<$float, Constant<$float>, $view<$float $(, $param)* $(, $extra)?>>;
+
+            fn $method(self, rhs: $view<$float $(, $param)* $(, $extra)?>) -> Self::Output {
+                $node::new(Constant::new(self), rhs)
+            }
+        }
+    };
+}
+
+impl_ops!(
+    Add<T, A, B>,
+    Arctan<T, V>,
+    Constant<T>,
+    Divide<T, A, B>,
+    Echo<T>,
+    FisherTransform<T, V>,
+    GTE<T, V>,
+    Logistic<T, V>,
+    LTE<T, V>,
+    Multiply<T, A, B>,
+    Softsign<T, V>,
+    Subtract<T, A, B>,
+    Tanh<T, V>,
+    Drawdown<T, V>,
+    LnReturn<T, V>,
+    WelfordRolling<T, V>,
+    Alma<T, V>,
+    AutocorrelationPeriodogram<T, V>,
+    AutomaticGainControl<T, V>,
+    BandPass<T, V>,
+    BandPassTrigger<T, V>,
+    BinaryEntropy<T, V>,
+    CenterOfGravity<T, V>,
+    Cmo<T, V>,
+    CorrelationTrendIndicator<T, V>,
+    Cumulative<T, V>,
+    CyberCycle<T, V>,
+    Decycler<T, V, H>,
+    DecyclerOscillator<T, V>,
+    Dema<T, V>,
+    DualDifferentiator<T, V>,
+    EhlersFisherTransform<T, V, M>,
+    Ema<T, V>,
+    EvenBetterSinewave<T, V>,
+    Fama<T, V>,
+    Frama<T, V>,
+    HilbertTransform<T, V>,
+    HLNormalizer<T, V>,
+    HullMa<T, V>,
+    InstantaneousTrendline<T, V>,
+    InverseFisherTransform<T, V, M>,
+    Kama<T, V>,
+    KernelMa<T, V; K: Kernel>,
+    Lag<T, V>,
+    LaguerreFilter<T, V>,
+    LaguerreRSI<T, V>,
+    LeadSine<T, V>,
+    Mama<T, V>,
+    Max<T, V>,
+    McGinleyDynamic<T, V>,
+    Min<T, V>,
+    MyRSI<T, V>,
+    NoiseEliminationTechnology<T, V>,
+    OnePoleHighPass<T, V>,
+    PolarizedFractalEfficiency<T, V, M>,
+    ReFlex<T, V>,
+    Roc<T, V>,
+    RoofingFilter<T, V, H, S>,
+    Rsi<T, V>,
+    Sinewave<T, V>,
+    Sma<T, V>,
+    StdDevRatio<T, V>,
+    SuperSmoother<T, V>,
+    T3<T, V>,
+    Tema<T, V>,
+    TrendFlex<T, V>,
+    TwoPoleHighPass<T, V>,
+    UniversalOscillator<T, V>,
+    Vidya<T, V, K>,
+    Vsct<T, V>,
+    Vst<T, V>,
+    WelfordOnline<T, V>,
+    Wma<T, V>,
+    Zlema<T, V>,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        introspect::Introspect,
+        sliding_windows::GaussianKernel,
+        test_data::TEST_DATA,
+    };
+
+    fn len(window_len: usize) -> NonZeroUsize {
+        NonZeroUsize::new(window_len).unwrap()
+    }
+
+    #[test]
+    fn fluent_chain() {
+        let mut fluent = Echo::new().ema(10).roc(15).hl_normalize(20);
+        let mut nested =
+            HLNormalizer::new(Roc::new(Ema::new(Echo::new(), len(10)), len(15)), len(20));
+        assert_eq!(fluent.node(), nested.node());
+        assert_eq!(
+            fluent.update_batch(&TEST_DATA),
+            nested.update_batch(&TEST_DATA)
+        );
+    }
+
+    #[test]
+    fn fluent_covers_all_views() {
+        let echo = Echo::<f64>::new;
+        let pairs = [
+            (echo().alma(8).node(), Alma::new(echo(), len(8)).node()),
+            (
+                echo().alma_custom(8, 3.0, 0.5).node(),
+                Alma::new_custom(echo(), len(8), 3.0, 0.5).node(),
+            ),
+            (
+                echo().autocorrelation_periodogram().node(),
+                AutocorrelationPeriodogram::new(echo()).node(),
+            ),
+            (
+                echo()
+                    .autocorrelation_periodogram_with_periods(8, 32)
+                    .node(),
+                AutocorrelationPeriodogram::with_periods(echo(), len(8), len(32)).node(),
+            ),
+            (
+                echo().automatic_gain_control().node(),
+                AutomaticGainControl::new(echo()).node(),
+            ),
+            (
+                echo().automatic_gain_control_with_decay(0.9).node(),
+                AutomaticGainControl::with_decay(echo(), 0.9).node(),
+            ),
+            (
+                echo().band_pass(20, 0.3).node(),
+                BandPass::new(echo(), len(20), 0.3).node(),
+            ),
+            (
+                echo().band_pass_trigger(20, 0.3).node(),
+                BandPassTrigger::new(echo(), len(20), 0.3).node(),
+            ),
+            (
+                echo().binary_entropy(8).node(),
+                BinaryEntropy::new(echo(), len(8)).node(),
+            ),
+            (
+                echo().center_of_gravity(8).node(),
+                CenterOfGravity::new(echo(), len(8)).node(),
+            ),
+            (echo().cmo(8).node(), Cmo::new(echo(), len(8)).node()),
+            (
+                echo().correlation_trend_indicator(8).node(),
+                CorrelationTrendIndicator::new(echo(), len(8)).node(),
+            ),
+            (
+                echo().cumulative(8).node(),
+                Cumulative::new(echo(), len(8)).node(),
+            ),
+            (
+                echo().cyber_cycle(8).node(),
+                CyberCycle::new(echo(), len(8)).node(),
+            ),
+            (
+                echo().decycler(8).node(),
+                Decycler::new(echo(), len(8)).node(),
+            ),
+            (
+                echo()
+                    .decycler_with_high_pass(echo().two_pole_high_pass(8))
+                    .node(),
+                Decycler::with_high_pass(echo(), TwoPoleHighPass::new(echo(), len(8))).node(),
+            ),
+            (
+                echo().decycler_oscillator(8, 16).node(),
+                DecyclerOscillator::new(echo(), len(8), len(16)).node(),
+            ),
+            (echo().dema(8).node(), Dema::new(echo(), len(8)).node()),
+            (
+                echo().dual_differentiator().node(),
+                DualDifferentiator::new(echo()).node(),
+            ),
+            (
+                echo().dual_differentiator_with_periods(8, 32).node(),
+                DualDifferentiator::with_periods(echo(), len(8), len(32)).node(),
+            ),
+            (
+                echo().ehlers_fisher_transform(echo().ema(3), 8).node(),
+                EhlersFisherTransform::new(echo(), Ema::new(echo(), len(3)), len(8)).node(),
+            ),
+            (echo().ema(8).node(), Ema::new(echo(), len(8)).node()),
+            (
+                echo().ema_with_alpha(8, 1.0).node(),
+                Ema::with_alpha(echo(), len(8), 1.0).node(),
+            ),
+            (
+                echo().even_better_sinewave().node(),
+                EvenBetterSinewave::new(echo()).node(),
+            ),
+            (
+                echo().even_better_sinewave_with_lens(20, 8).node(),
+                EvenBetterSinewave::with_lens(echo(), len(20), len(8)).node(),
+            ),
+            (echo().fama().node(), Fama::new(echo()).node()),
+            (
+                echo().fama_with_limits(0.4, 0.1).node(),
+                Fama::with_limits(echo(), 0.4, 0.1).node(),
+            ),
+            (echo().frama(8).node(), Frama::new(echo(), len(8)).node()),
+            (
+                echo().hilbert_transform().node(),
+                HilbertTransform::new(echo()).node(),
+            ),
+            (
+                echo().hl_normalize(8).node(),
+                HLNormalizer::new(echo(), len(8)).node(),
+            ),
+            (echo().hull_ma(8).node(), HullMa::new(echo(), len(8)).node()),
+            (
+                echo().instantaneous_trendline().node(),
+                InstantaneousTrendline::new(echo()).node(),
+            ),
+            (
+                echo()
+                    .inverse_fisher_transform(echo().wma(4), 0.0, 2.0)
+                    .node(),
+                InverseFisherTransform::new(echo(), Wma::new(echo(), len(4)), 0.0, 2.0).node(),
+            ),
+            (
+                echo().inverse_fisher_transform_rsi().node(),
+                InverseFisherTransform::with_rsi_scaling(echo()).node(),
+            ),
+            (echo().kama(8).node(), Kama::new(echo(), len(8)).node()),
+            (
+                echo().kama_custom(8, 3, 20).node(),
+                Kama::new_custom(echo(), len(8), len(3), len(20)).node(),
+            ),
+            (
+                echo()
+                    .kernel_ma(8, GaussianKernel::new(3.0, 0.5).unwrap())
+                    .node(),
+                KernelMa::with_kernel(echo(), len(8), GaussianKernel::new(3.0, 0.5).unwrap())
+                    .node(),
+            ),
+            (echo().lag(8).node(), Lag::new(echo(), len(8)).node()),
+            (
+                echo().laguerre_filter(0.8).node(),
+                LaguerreFilter::new(echo(), 0.8).node(),
+            ),
+            (
+                echo().laguerre_rsi(8).node(),
+                LaguerreRSI::new(echo(), len(8)).node(),
+            ),
+            (echo().lead_sine().node(), LeadSine::new(echo()).node()),
+            (echo().mama().node(), Mama::new(echo()).node()),
+            (
+                echo().mama_with_limits(0.4, 0.1).node(),
+                Mama::with_limits(echo(), 0.4, 0.1).node(),
+            ),
+            (echo().max(8).node(), Max::new(echo(), len(8)).node()),
+            (
+                echo().mc_ginley_dynamic(8).node(),
+                McGinleyDynamic::new(echo(), len(8)).node(),
+            ),
+            (echo().min(8).node(), Min::new(echo(), len(8)).node()),
+            (echo().my_rsi(8).node(), MyRSI::new(echo(), len(8)).node()),
+            (
+                echo().noise_elimination_technology(8).node(),
+                NoiseEliminationTechnology::new(echo(), len(8)).node(),
+            ),
+            (
+                echo().one_pole_high_pass(8).node(),
+                OnePoleHighPass::new(echo(), len(8)).node(),
+            ),
+            (
+                echo().pascal_ma(8).node(),
+                PascalMa::new(echo(), len(8)).node(),
+            ),
+            (
+                echo().polarized_fractal_efficiency(echo().ema(3), 8).node(),
+                PolarizedFractalEfficiency::new(echo(), Ema::new(echo(), len(3)), len(8)).node(),
+            ),
+            (echo().re_flex(8).node(), ReFlex::new(echo(), len(8)).node()),
+            (echo().roc(8).node(), Roc::new(echo(), len(8)).node()),
+            (
+                echo().roofing_filter(48, 10).node(),
+                RoofingFilter::new(echo(), len(48), len(10)).node(),
+            ),
+            (
+                echo()
+                    .roofing_filter_with_filters(echo().one_pole_high_pass(20), echo().ema(5))
+                    .node(),
+                RoofingFilter::with_filters(
+                    echo(),
+                    OnePoleHighPass::new(echo(), len(20)),
+                    Ema::new(echo(), len(5)),
+                )
+                .node(),
+            ),
+            (echo().rsi(8).node(), Rsi::new(echo(), len(8)).node()),
+            (echo().sine_ma(8).node(), SineMa::new(echo(), len(8)).node()),
+            (echo().sinewave().node(), Sinewave::new(echo()).node()),
+            (echo().sma(8).node(), Sma::new(echo(), len(8)).node()),
+            (
+                echo().std_dev_ratio(4, 8).node(),
+                StdDevRatio::new(echo(), len(4), len(8)).node(),
+            ),
+            (
+                echo().super_smoother(8).node(),
+                SuperSmoother::new(echo(), len(8)).node(),
+            ),
+            (
+                echo().trend_flex(8).node(),
+                TrendFlex::new(echo(), len(8)).node(),
+            ),
+            (echo().t3(8).node(), T3::new(echo(), len(8)).node()),
+            (
+                echo().t3_with_volume_factor(8, 0.5).node(),
+                T3::with_volume_factor(echo(), len(8), 0.5).node(),
+            ),
+            (echo().tema(8).node(), Tema::new(echo(), len(8)).node()),
+            (echo().trima(8).node(), Trima::new(echo(), len(8)).node()),
+            (
+                echo().two_pole_high_pass(8).node(),
+                TwoPoleHighPass::new(echo(), len(8)).node(),
+            ),
+            (
+                echo().universal_oscillator(8).node(),
+                UniversalOscillator::new(echo(), len(8)).node(),
+            ),
+            (
+                echo().vidya(8, echo().cmo(4)).node(),
+                Vidya::new(echo(), len(8), Cmo::new(echo(), len(4))).node(),
+            ),
+            (
+                echo().vidya_cmo(8, 4).node(),
+                Vidya::with_cmo(echo(), len(8), len(4)).node(),
+            ),
+            (
+                echo().vidya_std_dev_ratio(8, 4, 16).node(),
+                Vidya::with_std_dev_ratio(echo(), len(8), len(4), len(16)).node(),
+            ),
+            (echo().vsct(8).node(), Vsct::new(echo(), len(8)).node()),
+            (echo().vst(8).node(), Vst::new(echo(), len(8)).node()),
+            (
+                echo().welford_online(8).node(),
+                WelfordOnline::new(echo(), len(8)).node(),
+            ),
+            (echo().wma(8).node(), Wma::new(echo(), len(8)).node()),
+            (echo().zlema(8).node(), Zlema::new(echo(), len(8)).node()),
+            (echo().drawdown().node(), Drawdown::new(echo()).node()),
+            (echo().ln_return().node(), LnReturn::new(echo()).node()),
+            (
+                echo().welford_rolling().node(),
+                WelfordRolling::new(echo()).node(),
+            ),
+            (echo().arctan().node(), Arctan::new(echo()).node()),
+            (
+                echo().fisher_transform().node(),
+                FisherTransform::new(echo()).node(),
+            ),
+            (echo().gte(1.0).node(), GTE::new(echo(), 1.0).node()),
+            (echo().logistic().node(), Logistic::new(echo()).node()),
+            (echo().lte(1.0).node(), LTE::new(echo(), 1.0).node()),
+            (echo().softsign().node(), Softsign::new(echo()).node()),
+            (echo().tanh().node(), Tanh::new(echo()).node()),
+        ];
+        for (fluent, nested) in pairs {
+            assert_eq!(fluent, nested);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "window length must be positive")]
+    fn fluent_zero_window_len() {
+        let _ = Echo::<f64>::new().sma(0);
+    }
+
+    #[test]
+    fn ops_between_views() {
+        let ema = Echo::new().ema(10);
+        let sma = Echo::new().sma(20);
+        let mut diff = ema.clone() - sma.clone();
+        let mut nested = Subtract::new(ema.clone(), sma.clone());
+        assert_eq!(diff.node(), nested.node());
+        assert_eq!(
+            diff.update_batch(&TEST_DATA),
+            nested.update_batch(&TEST_DATA)
+        );
+
+        let ratio = (ema.clone() + sma.clone()) / (ema.clone() * sma.clone());
+        assert_eq!(ratio.node().name, "Divide");
+        assert_eq!(ratio.node().children[0].name, "Add");
+        assert_eq!(ratio.node().children[1].name, "Multiply");
+    }
+
+    #[test]
+    fn ops_with_scalars() {
+        let mut scaled = 2.0 * Echo::<f64>::new().sma(4) - 1.0;
+        let mut neg = -Echo::<f64>::new().sma(4);
+        let mut ratio = Echo::<f32>::new() / 4.0_f32;
+        for val in TEST_DATA {
+            scaled.update(val);
+            neg.update(val);
+            ratio.update(val as f32);
+        }
+        let sma = TEST_DATA[TEST_DATA.len() - 4..].iter().sum::<f64>() / 4.0;
+        assert!((scaled.last().unwrap() - (2.0 * sma - 1.0)).abs() < 1e-9);
+        assert!((neg.last().unwrap() + sma).abs() < 1e-9);
+        assert_eq!(ratio.last(), Some(*TEST_DATA.last().unwrap() as f32 / 4.0));
+        assert_eq!(scaled.node().children[1].label(), "Constant(val=1)");
+    }
+}
@@ -0,0 +1,175 @@
+//! Reproducible synthetic series with known ground truth, for validating views.
+//! The stochastic generators draw from a caller-provided `Rng`, so seeding it reproduces the series.
+//! Requires the `generators` feature.
+
+use std::f64::consts::PI;
+
+use rand::Rng;
+
+/// A sine wave with a known `period` in samples, oscillating by `amplitude` around `offset`.
+pub fn sine(len: usize, period: f64, amplitude: f64, offset: f64) -> Vec<f64> {
+    (0..len)
+        .map(|i| offset + amplitude * (2.0 * PI * i as f64 / period).sin())
+        .collect()
+}
+
+/// A geometric brownian motion starting at `start`,
+/// with `drift` and `volatility` per sample.
+pub fn gbm<R: Rng>(rng: &mut R, len: usize, start: f64, drift: f64, volatility: f64) -> Vec<f64> {
+    let mut price = start;
+    (0..len)
+        .map(|i| {
+            if i > 0 {
+                let log_return =
+                    drift - 0.5 * volatility.powi(2) + volatility * standard_normal(rng);
+                price *= log_return.exp();
+            }
+            price
+        })
+        .collect()
+}
+
+/// An Ornstein-Uhlenbeck process starting at `start`, which reverts to `mean`
+/// at the rate `reversion` per sample, with `volatility` per sample.
+pub fn ornstein_uhlenbeck<R: Rng>(
+    rng: &mut R,
+    len: usize,
+    start: f64,
+    mean: f64,
+    reversion: f64,
+    volatility: f64,
+) -> Vec<f64> {
+    let mut val = start;
+    (0..len)
+        .map(|i| {
+            if i > 0 {
+                val += reversion * (mean - val) + volatility * standard_normal(rng);
+            }
+            val
+        })
+        .collect()
+}
+
+/// A regime of a `regime_switches` series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Regime {
+    /// The number of samples in the regime.
+    pub len: usize,
+    /// The drift of the log price per sample.
+    pub drift: f64,
+    /// The volatility of the log price per sample.
+    pub volatility: f64,
+}
+
+/// A geometric brownian motion starting at `start`, which switches between the given regimes in order.
+/// The `i`-th regime starts at the sum of the lengths of the previous ones.
+pub fn regime_switches<R: Rng>(rng: &mut R, start: f64, regimes: &[Regime]) -> Vec<f64> {
+    let mut out = Vec::with_capacity(regimes.iter().map(|regime| regime.len).sum());
+    let mut price = start;
+    for regime in regimes {
+        // Each regime continues from the last price of the previous one.
+        let segment = gbm(rng, regime.len + 1, price, regime.drift, regime.volatility);
+        out.extend_from_slice(&segment[1..]);
+        price = *segment.last().expect("is not empty");
+    }
+    out
+}
+
+/// Add jumps to a series, where each `(index, size)` shifts the series from `index` onwards by `size`.
+pub fn with_jumps(mut vals: Vec<f64>, jumps: &[(usize, f64)]) -> Vec<f64> {
+    for &(index, size) in jumps {
+        for val in vals.iter_mut().skip(index) {
+            *val += size;
+        }
+    }
+    vals
+}
+
+/// Hold a series flat over segments, where each `(start, len)` repeats the value at `start`
+/// for `len` samples. The series continues unchanged afterwards, so it may jump at the segment end.
+pub fn with_flat_segments(mut vals: Vec<f64>, segments: &[(usize, usize)]) -> Vec<f64> {
+    for &(start, len) in segments {
+        let Some(&level) = vals.get(start) else {
+            continue;
+        };
+        for val in vals.iter_mut().skip(start).take(len) {
+            *val = level;
+        }
+    }
+    vals
+}
+
+/// A standard normal sample using the Box-Muller transform.
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    // Sampling from `(0, 1]` keeps the logarithm finite.
+    let u1 = 1.0 - rng.random::<f64>();
+    let u2 = rng.random::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        SeedableRng,
+        rngs::SmallRng,
+    };
+
+    use super::*;
+    use crate::properties::zero_crossing_period;
+
+    #[test]
+    fn sine_period() {
+        let vals = sine(1000, 25.0, 1.0, 0.0);
+        let period = zero_crossing_period(&vals).unwrap();
+        assert!((period - 25.0).abs() < 0.1, "period {period}");
+    }
+
+    #[test]
+    fn gbm_reproducible() {
+        let vals = gbm(&mut SmallRng::seed_from_u64(0), 256, 100.0, 0.0, 0.01);
+        assert_eq!(vals[0], 100.0);
+        assert!(vals.iter().all(|val| *val > 0.0));
+        assert_eq!(
+            vals,
+            gbm(&mut SmallRng::seed_from_u64(0), 256, 100.0, 0.0, 0.01)
+        );
+    }
+
+    #[test]
+    fn ornstein_uhlenbeck_reverts() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let vals = ornstein_uhlenbeck(&mut rng, 10_000, 200.0, 100.0, 0.1, 1.0);
+        let mean = vals[1000..].iter().sum::<f64>() / (vals.len() - 1000) as f64;
+        assert!((mean - 100.0).abs() < 1.0, "mean {mean}");
+    }
+
+    #[test]
+    fn regime_switches_len() {
+        let calm = Regime {
+            len: 100,
+            drift: 0.0,
+            volatility: 0.001,
+        };
+        let wild = Regime {
+            len: 50,
+            volatility: 0.05,
+            ..calm
+        };
+        let vals = regime_switches(&mut SmallRng::seed_from_u64(0), 100.0, &[calm, wild]);
+        assert_eq!(vals.len(), 150);
+        let max_step = |vals: &[f64]| {
+            vals.windows(2)
+                .map(|w| (w[1] / w[0]).ln().abs())
+                .fold(0.0, f64::max)
+        };
+        assert!(max_step(&vals[..100]) < max_step(&vals[100..]));
+    }
+
+    #[test]
+    fn jumps_and_flat_segments() {
+        let vals = with_jumps(vec![1.0; 4], &[(2, 10.0)]);
+        assert_eq!(vals, vec![1.0, 1.0, 11.0, 11.0]);
+        let vals = with_flat_segments(vec![1.0, 2.0, 3.0, 4.0], &[(1, 2), (3, 10)]);
+        assert_eq!(vals, vec![1.0, 2.0, 2.0, 4.0]);
+    }
+}
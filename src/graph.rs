@@ -0,0 +1,355 @@
+//! Sharing computed views between several consumers, turning the tree of a chain into a DAG.
+//! If both branches of an `Add` start with the same `Ema`, a `Shared` handle updates it once per tick
+//! and lets both branches read it. For larger feature sets, a `Graph` owns the nodes
+//! and updates each of them exactly once per tick in topological order.
+
+use std::{
+    cell::{
+        Cell,
+        RefCell,
+    },
+    fmt::{
+        Debug,
+        Formatter,
+    },
+    marker::PhantomData,
+    rc::Rc,
+};
+
+use num::Float;
+
+use crate::{
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
+
+#[derive(Debug)]
+struct SharedNode<V> {
+    view: V,
+    n_updates: usize,
+}
+
+/// A handle to a view which is observed by several consumers, e.g. both branches of an `Add`.
+/// Each further consumer gets its own handle from `consumer`,
+/// so the view is updated by whichever consumer is updated first in a tick, and only read by the others.
+/// Every consumer must be updated with the same values, which holds within a chain.
+///
+/// Cloning the handle copies the view, so a cloned chain, e.g. in a `Sweep`
+/// or the pristine copy of `MissingDataPolicy::Reset`, evaluates independently of the original.
+/// The clone no longer shares the view with the other consumers, so each clone computes it again.
+/// The handles share the view through an `Rc`, so a chain containing them isn't `Send`
+/// and can't be driven by a `Universe`.
+#[derive(Debug)]
+pub struct Shared<T, V> {
+    node: Rc<RefCell<SharedNode<V>>>,
+    /// The number of updates of this consumer.
+    n_updates: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T, V> Shared<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new handle to a chained View, from which each further consumer gets a handle with `consumer`.
+    pub fn new(view: V) -> Self {
+        Self {
+            node: Rc::new(RefCell::new(SharedNode { view, n_updates: 0 })),
+            n_updates: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// A further handle to the same view, for another consumer.
+    pub fn consumer(&self) -> Self {
+        Self {
+            node: Rc::clone(&self.node),
+            n_updates: self.n_updates,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, V: Clone> Clone for Shared<T, V> {
+    fn clone(&self) -> Self {
+        let node = self.node.borrow();
+        Self {
+            node: Rc::new(RefCell::new(SharedNode {
+                view: node.view.clone(),
+                n_updates: node.n_updates,
+            })),
+            n_updates: self.n_updates,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, V> View<T> for Shared<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.n_updates += 1;
+        let mut node = self.node.borrow_mut();
+        // Another consumer has already updated the view in this tick.
+        if node.n_updates >= self.n_updates {
+            return;
+        }
+        node.view.update(val);
+        node.n_updates = self.n_updates;
+    }
+
+    fn last(&self) -> Option<T> {
+        self.node.borrow().view.last()
+    }
+
+    fn status(&self) -> Status<T> {
+        self.node.borrow().view.status()
+    }
+}
+
+impl<T, V> Introspect for Shared<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Shared").child(self.node.borrow().view.node())
+    }
+}
+
+/// The id of a node in a `Graph`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// A view reading the output of a node of a `Graph`, from which the views of later nodes are built.
+/// Its updates are driven by the graph, so it ignores the values it is updated with.
+#[derive(Clone)]
+pub struct NodeOutput<T> {
+    id: NodeId,
+    slot: Rc<Cell<Status<T>>>,
+}
+
+impl<T: Float + Debug> Debug for NodeOutput<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeOutput")
+            .field("id", &self.id)
+            .field("slot", &self.slot.get())
+            .finish()
+    }
+}
+
+impl<T: Float> View<T> for NodeOutput<T> {
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+    }
+
+    fn last(&self) -> Option<T> {
+        self.slot.get().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        self.slot.get()
+    }
+}
+
+impl<T: Float> Introspect for NodeOutput<T> {
+    fn node(&self) -> Node {
+        Node::new("NodeOutput").param("node", self.id.0)
+    }
+}
+
+/// Executes a DAG of views, whose nodes read the outputs of earlier nodes through `NodeOutput`s.
+/// A node can only read nodes added before it, so the insertion order is a topological order,
+/// in which each node is updated exactly once per tick.
+pub struct Graph<T> {
+    nodes: Vec<Box<dyn View<T>>>,
+    slots: Vec<Rc<Cell<Status<T>>>>,
+}
+
+impl<T: Float + 'static> Graph<T> {
+    /// Create a new graph without nodes.
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![],
+            slots: vec![],
+        }
+    }
+
+    /// Add a node, whose view is built on `Echo` for the input values
+    /// and on the `output` of earlier nodes.
+    pub fn add<V: View<T> + 'static>(&mut self, view: V) -> NodeId {
+        self.nodes.push(Box::new(view));
+        self.slots.push(Rc::new(Cell::new(Status::WarmingUp)));
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// A view reading the output of the node `id`, for building later nodes.
+    ///
+    /// # Panics
+    /// If `id` belongs to another graph with more nodes.
+    pub fn output(&self, id: NodeId) -> NodeOutput<T> {
+        NodeOutput {
+            id,
+            slot: Rc::clone(&self.slots[id.0]),
+        }
+    }
+
+    /// The number of nodes.
+    #[inline(always)]
+    pub fn n_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Update each node exactly once with a new value, in topological order.
+    pub fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        for (node, slot) in self.nodes.iter_mut().zip(&self.slots) {
+            node.update(val);
+            slot.set(node.status());
+        }
+    }
+
+    /// The last value of the node `id`.
+    pub fn last(&self, id: NodeId) -> Option<T> {
+        self.slots[id.0].get().value()
+    }
+
+    /// The status of the last value of the node `id`.
+    pub fn status(&self, id: NodeId) -> Status<T> {
+        self.slots[id.0].get()
+    }
+}
+
+impl<T: Float + 'static> Default for Graph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::{
+        missing_data::{
+            MissingData,
+            MissingDataPolicy,
+        },
+        pure_functions::{
+            Add,
+            Echo,
+        },
+        sliding_windows::{
+            Ema,
+            Roc,
+            Rsi,
+        },
+        test_data::TEST_DATA,
+    };
+
+    /// Counts its updates, to check that shared views are updated once per tick.
+    #[derive(Debug, Clone)]
+    struct Counter {
+        n_updates: Rc<Cell<usize>>,
+        out: Option<f64>,
+    }
+
+    impl View<f64> for Counter {
+        fn update(&mut self, val: f64) {
+            self.n_updates.set(self.n_updates.get() + 1);
+            self.out = Some(val);
+        }
+
+        fn last(&self) -> Option<f64> {
+            self.out
+        }
+    }
+
+    #[test]
+    fn shared_updates_once() {
+        let len = NonZeroUsize::new(10).unwrap();
+        let n_updates = Rc::new(Cell::new(0));
+        let counter = Counter {
+            n_updates: Rc::clone(&n_updates),
+            out: None,
+        };
+        let ema = Shared::new(Ema::new(counter, len));
+        let mut chain = Add::new(Roc::new(ema.consumer(), len), Rsi::new(ema, len));
+        let mut tree = Add::new(
+            Roc::new(Ema::new(Echo::new(), len), len),
+            Rsi::new(Ema::new(Echo::new(), len), len),
+        );
+        for val in TEST_DATA {
+            chain.update(val);
+            tree.update(val);
+            assert_eq!(chain.last(), tree.last());
+        }
+        assert_eq!(n_updates.get(), TEST_DATA.len());
+    }
+
+    #[test]
+    fn shared_missing_data_reset() {
+        // The pristine copy of the chain must not share its view with the live chain.
+        let len = NonZeroUsize::new(4).unwrap();
+        let ema = Shared::new(Ema::new(Echo::new(), len));
+        let mut chain = MissingData::new(
+            Add::new(Roc::new(ema.consumer(), len), Rsi::new(ema, len)),
+            MissingDataPolicy::Reset,
+        );
+        let mut tree = MissingData::new(
+            Add::new(
+                Roc::new(Ema::new(Echo::new(), len), len),
+                Rsi::new(Ema::new(Echo::new(), len), len),
+            ),
+            MissingDataPolicy::Reset,
+        );
+        let mut vals = TEST_DATA.to_vec();
+        vals[20] = f64::NAN;
+        vals[40] = f64::NAN;
+        for val in vals {
+            chain.update(val);
+            tree.update(val);
+            assert_eq!(chain.last(), tree.last());
+            assert_eq!(chain.status(), tree.status());
+        }
+
+        // A clone evaluates independently of the original.
+        let mut clone = chain.clone();
+        clone.update(50.0);
+        chain.update(100.0);
+        tree.update(100.0);
+        assert_eq!(chain.last(), tree.last());
+    }
+
+    #[test]
+    fn graph_topological_order() {
+        let len = NonZeroUsize::new(10).unwrap();
+        let mut graph = Graph::new();
+        let ema = graph.add(Ema::new(Echo::new(), len));
+        let roc = graph.add(Roc::new(graph.output(ema), len));
+        let rsi = graph.add(Rsi::new(graph.output(ema), len));
+        let sum = graph.add(Add::new(graph.output(roc), graph.output(rsi)));
+        assert_eq!(graph.n_nodes(), 4);
+
+        let mut tree = Add::new(
+            Roc::new(Ema::new(Echo::new(), len), len),
+            Rsi::new(Ema::new(Echo::new(), len), len),
+        );
+        for val in TEST_DATA {
+            graph.update(val);
+            tree.update(val);
+            assert_eq!(graph.last(sum), tree.last());
+            assert_eq!(graph.status(sum), tree.status());
+        }
+        assert_eq!(graph.output(rsi).node().label(), "NodeOutput(node=2)");
+    }
+}
@@ -0,0 +1,242 @@
+//! Introspection of the structure of a chain, e.g. to log the exact feature definitions of a model.
+//! Every view reports its name, parameters and the views it observes as a `Node`,
+//! which exports to Mermaid, Graphviz DOT or a readable tree with `Display`.
+
+use std::{
+    fmt::{
+        Display,
+        Formatter,
+        Write,
+    },
+    num::NonZeroUsize,
+};
+
+/// Implemented by views which report their structure.
+pub trait Introspect {
+    /// The node of this view, with the nodes of the views it observes as children.
+    fn node(&self) -> Node;
+}
+
+impl<I: Introspect + ?Sized> Introspect for Box<I> {
+    fn node(&self) -> Node {
+        self.as_ref().node()
+    }
+}
+
+/// The value of a view parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamValue {
+    /// An integer parameter, e.g. a window length.
+    Int(usize),
+    /// A floating point parameter, e.g. a smoothing coefficient.
+    Float(f64),
+    /// A named choice, e.g. a policy.
+    Text(&'static str),
+}
+
+impl From<usize> for ParamValue {
+    fn from(val: usize) -> Self {
+        ParamValue::Int(val)
+    }
+}
+
+impl From<NonZeroUsize> for ParamValue {
+    fn from(val: NonZeroUsize) -> Self {
+        ParamValue::Int(val.get())
+    }
+}
+
+impl From<f64> for ParamValue {
+    fn from(val: f64) -> Self {
+        ParamValue::Float(val)
+    }
+}
+
+impl From<&'static str> for ParamValue {
+    fn from(val: &'static str) -> Self {
+        ParamValue::Text(val)
+    }
+}
+
+impl Display for ParamValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamValue::Int(val) => write!(f, "{val}"),
+            ParamValue::Float(val) => write!(f, "{val}"),
+            ParamValue::Text(val) => write!(f, "{val}"),
+        }
+    }
+}
+
+/// The structure of a view: its name, parameters and the views it observes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    /// The name of the view type.
+    pub name: &'static str,
+    /// The parameters in constructor order.
+    pub params: Vec<(&'static str, ParamValue)>,
+    /// The nodes of the observed views, e.g. two for the binary combinators.
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    /// Create a new node without parameters and children.
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            params: vec![],
+            children: vec![],
+        }
+    }
+
+    /// Add a parameter.
+    pub fn param(mut self, name: &'static str, val: impl Into<ParamValue>) -> Self {
+        self.params.push((name, val.into()));
+        self
+    }
+
+    /// Add the node of an observed view.
+    pub fn child(mut self, child: Node) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// The name with the parameters, e.g. `Ema(window_len=16, alpha=2)`.
+    pub fn label(&self) -> String {
+        if self.params.is_empty() {
+            return self.name.to_string();
+        }
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|(name, val)| format!("{name}={val}"))
+            .collect();
+        format!("{}({})", self.name, params.join(", "))
+    }
+
+    /// Export as a Mermaid flowchart, where values flow from the inputs to the outermost view.
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart TD\n");
+        self.visit(None, &mut 0, &mut |id, label, parent| {
+            writeln!(out, "    n{id}[\"{label}\"]").expect("can write to string");
+            if let Some(parent) = parent {
+                writeln!(out, "    n{id} --> n{parent}").expect("can write to string");
+            }
+        });
+        out
+    }
+
+    /// Export as a Graphviz DOT digraph, where values flow from the inputs to the outermost view.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph chain {\n");
+        self.visit(None, &mut 0, &mut |id, label, parent| {
+            writeln!(out, "    n{id} [label=\"{label}\"];").expect("can write to string");
+            if let Some(parent) = parent {
+                writeln!(out, "    n{id} -> n{parent};").expect("can write to string");
+            }
+        });
+        out.push_str("}\n");
+        out
+    }
+
+    /// Visit the nodes depth-first with unique ids, the escaped label and the id of the parent.
+    fn visit(
+        &self,
+        parent: Option<usize>,
+        next_id: &mut usize,
+        visit: &mut impl FnMut(usize, String, Option<usize>),
+    ) {
+        let id = *next_id;
+        *next_id += 1;
+        visit(id, self.label().replace('"', "'"), parent);
+        for child in &self.children {
+            child.visit(Some(id), next_id, visit);
+        }
+    }
+
+    fn fmt_tree(&self, f: &mut Formatter<'_>, prefix: &str) -> std::fmt::Result {
+        writeln!(f, "{}", self.label())?;
+        for (i, child) in self.children.iter().enumerate() {
+            let last = i + 1 == self.children.len();
+            write!(f, "{prefix}{}", if last { "└── " } else { "├── " })?;
+            child.fmt_tree(
+                f,
+                &format!("{prefix}{}", if last { "    " } else { "│   " }),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Node {
+    /// A tree with the outermost view at the top and one line per view.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt_tree(f, "")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::{
+        pure_functions::{
+            Add,
+            Echo,
+        },
+        sliding_windows::{
+            Ema,
+            HLNormalizer,
+            Roc,
+            Rsi,
+            Sma,
+        },
+    };
+
+    /// The chain of the flowchart in the README.
+    fn readme_chain() -> impl Introspect {
+        let len = NonZeroUsize::new(16).unwrap();
+        HLNormalizer::new(
+            Add::new(
+                Roc::new(Ema::new(Echo::<f64>::new(), len), len),
+                Rsi::new(Sma::new(Echo::new(), len), len),
+            ),
+            len,
+        )
+    }
+
+    #[test]
+    fn introspect_display() {
+        assert_eq!(
+            readme_chain().node().to_string(),
+            "\
+HLNormalizer(window_len=16)
+└── Add
+    ├── Roc(window_len=16)
+    │   └── Ema(window_len=16, alpha=2)
+    │       └── Echo
+    └── Rsi(window_len=16)
+        └── Sma(window_len=16)
+            └── Echo
+"
+        );
+    }
+
+    #[test]
+    fn introspect_mermaid() {
+        let mermaid = readme_chain().node().to_mermaid();
+        assert!(mermaid.starts_with("flowchart TD\n    n0[\"HLNormalizer(window_len=16)\"]\n"));
+        assert!(mermaid.contains("    n3[\"Ema(window_len=16, alpha=2)\"]\n"));
+        assert!(mermaid.contains("    n4 --> n3\n"));
+        assert_eq!(mermaid.matches("-->").count(), 7);
+    }
+
+    #[test]
+    fn introspect_dot() {
+        let dot = readme_chain().node().to_dot();
+        assert!(dot.starts_with("digraph chain {\n"));
+        assert!(dot.contains("    n1 [label=\"Add\"];\n    n1 -> n0;\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
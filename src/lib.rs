@@ -9,12 +9,35 @@
 //! The sliding_features crate provides modular, chainable sliding windows
 //! for various signal processing function and technical indicators
 
+pub mod adaptive;
+mod error;
+pub mod fluent;
+#[cfg(any(test, feature = "generators"))]
+pub mod generators;
+pub mod graph;
+pub mod introspect;
+pub mod missing_data;
+pub mod multi_lane;
+pub mod params;
+#[cfg(any(test, feature = "plot"))]
+pub mod plot;
 pub mod pure_functions;
 pub mod rolling;
 pub mod sliding_windows;
+mod status;
+pub mod sweep;
+pub mod universe;
 
+pub use error::Error;
+pub use status::{
+    InvalidReason,
+    Status,
+};
+
+#[cfg(test)]
+mod conformance;
 #[cfg(test)]
-mod plot;
+mod properties;
 #[cfg(test)]
 mod test_data;
 
@@ -25,6 +48,35 @@ pub trait View<T: num::Float> {
 
     /// Return the last value, if `Some`, then its ready.
     fn last(&self) -> Option<T>;
+
+    /// Return the status of the last value, which tells apart a view that is still warming up
+    /// from one whose output is undefined for the current window.
+    /// `last` remains the `Option` compatible accessor, which is `None` in both cases,
+    /// except for views that fall back to a neutral output for degenerate windows, e.g. `HLNormalizer`.
+    fn status(&self) -> Status<T> {
+        self.last().into()
+    }
+
+    /// Update the state with a batch of values, returning the output observed after each value.
+    fn update_batch(&mut self, vals: &[T]) -> Vec<Option<T>> {
+        vals.iter()
+            .map(|val| {
+                self.update(*val);
+                self.last()
+            })
+            .collect()
+    }
+}
+
+/// The lane-parallel counterpart of `View`, which advances `LANES` independent series per update.
+/// The values are laid out as structure-of-arrays and processed by plain loops over the lanes,
+/// without explicit SIMD, which the compiler may auto-vectorize depending on the target and the view.
+pub trait MultiView<T: num::Float, const LANES: usize> {
+    /// Update the state with a new value for each lane
+    fn update(&mut self, vals: [T; LANES]);
+
+    /// Return the last value of each lane, if `Some`, then all lanes are ready.
+    fn last(&self) -> Option<[T; LANES]>;
 }
 
 #[cfg(test)]
@@ -0,0 +1,249 @@
+//! Handling of NaN, infinite and missing inputs.
+//! The views themselves only `debug_assert!` finite values, so in release builds a single NaN
+//! would poison their internal state permanently.
+//! Wrapping a chain in `MissingData` applies a `MissingDataPolicy` before any of its views observe the value.
+//! It only guards the inputs of the chain: the views don't produce non-finite values from finite ones,
+//! as those whose outputs overflow report `InvalidReason::Overflow` instead, which their chained views skip.
+//! Should a view still output a non-finite value, `MissingData` reports it as an overflow
+//! rather than passing it on, although the views chained on it have already observed it.
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::{
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
+
+/// What to do with a NaN, infinite or missing input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingDataPolicy {
+    /// Ignore the sample, keeping the last output of the chain.
+    Skip,
+    /// Feed the last finite value to the chain again.
+    /// Samples are skipped until a finite value has been observed.
+    ForwardFill,
+    /// Reset the chain to the state it was constructed with, so it warms up again.
+    Reset,
+    /// Ignore the sample and output `None` until the next finite value.
+    EmitNone,
+}
+
+/// Guards a chain against NaN, infinite and missing inputs, according to a `MissingDataPolicy`.
+/// As it is the outermost view of the chain, all views of the chain,
+/// including both sides of the binary combinators, observe the same cleaned values.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct MissingData<T, V> {
+    view: V,
+    /// The policy for NaN, infinite and missing inputs.
+    #[getset(get_copy = "pub")]
+    policy: MissingDataPolicy,
+    /// The chain as it was constructed, only kept for `MissingDataPolicy::Reset`.
+    pristine: Option<V>,
+    last_valid: Option<T>,
+    missing: bool,
+}
+
+impl<T, V> MissingData<T, V>
+where
+    V: View<T> + Clone,
+    T: Float,
+{
+    /// Create a new guard around a chained View, with a policy for NaN, infinite and missing inputs.
+    pub fn new(view: V, policy: MissingDataPolicy) -> Self {
+        let pristine = (policy == MissingDataPolicy::Reset).then(|| view.clone());
+        Self {
+            view,
+            policy,
+            pristine,
+            last_valid: None,
+            missing: false,
+        }
+    }
+
+    /// Update the chain with a value, where `None` marks a missing sample, e.g. a gap in a feed.
+    pub fn update_option(&mut self, val: Option<T>) {
+        match val {
+            Some(val) if val.is_finite() => {
+                self.missing = false;
+                self.last_valid = Some(val);
+                self.view.update(val);
+            }
+            _ => self.on_missing(),
+        }
+    }
+
+    fn on_missing(&mut self) {
+        match self.policy {
+            MissingDataPolicy::Skip => {}
+            MissingDataPolicy::ForwardFill => {
+                if let Some(val) = self.last_valid {
+                    self.view.update(val);
+                }
+            }
+            MissingDataPolicy::Reset => {
+                if let Some(pristine) = &self.pristine {
+                    self.view = pristine.clone();
+                }
+                self.last_valid = None;
+            }
+            MissingDataPolicy::EmitNone => self.missing = true,
+        }
+    }
+}
+
+impl<T, V> View<T> for MissingData<T, V>
+where
+    V: View<T> + Clone,
+    T: Float,
+{
+    /// Non-finite values are handled according to the policy.
+    fn update(&mut self, val: T) {
+        self.update_option(Some(val));
+    }
+
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        if self.missing {
+            return Status::Invalid(InvalidReason::MissingInput);
+        }
+        match self.view.status() {
+            Status::Ready(out) if !out.is_finite() => Status::Invalid(InvalidReason::Overflow),
+            status => status,
+        }
+    }
+}
+
+impl<T, V> Introspect for MissingData<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        let policy = match self.policy {
+            MissingDataPolicy::Skip => "Skip",
+            MissingDataPolicy::ForwardFill => "ForwardFill",
+            MissingDataPolicy::Reset => "Reset",
+            MissingDataPolicy::EmitNone => "EmitNone",
+        };
+        Node::new("MissingData")
+            .param("policy", policy)
+            .child(self.view.node())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::{
+        pure_functions::{
+            Add,
+            Echo,
+        },
+        sliding_windows::{
+            Ema,
+            Sma,
+        },
+    };
+
+    const VALS: [f64; 6] = [1.0, 2.0, f64::NAN, 3.0, f64::INFINITY, 4.0];
+
+    fn outputs(policy: MissingDataPolicy) -> Vec<Option<f64>> {
+        let sma = Sma::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        MissingData::new(sma, policy).update_batch(&VALS)
+    }
+
+    #[test]
+    fn missing_data_skip() {
+        assert_eq!(
+            outputs(MissingDataPolicy::Skip),
+            vec![None, Some(1.5), Some(1.5), Some(2.5), Some(2.5), Some(3.5)]
+        );
+    }
+
+    #[test]
+    fn missing_data_forward_fill() {
+        assert_eq!(
+            outputs(MissingDataPolicy::ForwardFill),
+            vec![None, Some(1.5), Some(2.0), Some(2.5), Some(3.0), Some(3.5)]
+        );
+    }
+
+    #[test]
+    fn missing_data_reset() {
+        assert_eq!(
+            outputs(MissingDataPolicy::Reset),
+            vec![None, Some(1.5), None, None, None, None]
+        );
+    }
+
+    #[test]
+    fn missing_data_emit_none() {
+        assert_eq!(
+            outputs(MissingDataPolicy::EmitNone),
+            vec![None, Some(1.5), None, Some(2.5), None, Some(3.5)]
+        );
+        let sma = Sma::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        let mut guard = MissingData::new(sma, MissingDataPolicy::EmitNone);
+        guard.update(f64::NAN);
+        assert_eq!(guard.status(), Status::Invalid(InvalidReason::MissingInput));
+    }
+
+    #[test]
+    fn missing_data_update_option() {
+        let sma = Sma::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        let mut guard = MissingData::new(sma, MissingDataPolicy::ForwardFill);
+        guard.update_option(Some(1.0));
+        guard.update_option(None);
+        assert_eq!(guard.last(), Some(1.0));
+    }
+
+    /// A view that outputs NaN regardless of its inputs.
+    #[derive(Clone)]
+    struct Poisoned;
+
+    impl View<f64> for Poisoned {
+        fn update(&mut self, _val: f64) {}
+
+        fn last(&self) -> Option<f64> {
+            Some(f64::NAN)
+        }
+    }
+
+    #[test]
+    fn missing_data_non_finite_output() {
+        let mut guard = MissingData::new(Poisoned, MissingDataPolicy::Skip);
+        guard.update(1.0);
+        assert_eq!(guard.last(), None);
+        assert_eq!(guard.status(), Status::Invalid(InvalidReason::Overflow));
+    }
+
+    #[test]
+    fn missing_data_combinator() {
+        let window_len = NonZeroUsize::new(3).unwrap();
+        let chain = Add::new(
+            Sma::new(Echo::new(), window_len),
+            Ema::new(Echo::new(), window_len),
+        );
+        let mut guard = MissingData::new(chain.clone(), MissingDataPolicy::Skip);
+        let mut reference = chain;
+        for val in [1.0, f64::NAN, 2.0, f64::NEG_INFINITY, 3.0, 4.0] {
+            guard.update(val);
+            if val.is_finite() {
+                reference.update(val);
+            }
+            assert_eq!(guard.last(), reference.last());
+        }
+        assert!(guard.last().unwrap().is_finite());
+    }
+}
@@ -0,0 +1,37 @@
+//! Lane-parallel Echo, which always returns the last values just like an echo
+
+use num::Float;
+
+use crate::MultiView;
+
+/// Lane-parallel Echo, which always returns the last values just like an echo
+#[derive(Clone, Debug)]
+pub struct MultiEcho<T, const LANES: usize> {
+    out: Option<[T; LANES]>,
+}
+
+impl<T, const LANES: usize> Default for MultiEcho<T, LANES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const LANES: usize> MultiEcho<T, LANES> {
+    /// Create a new lane-parallel Echo
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self { out: None }
+    }
+}
+
+impl<T: Float, const LANES: usize> MultiView<T, LANES> for MultiEcho<T, LANES> {
+    fn update(&mut self, vals: [T; LANES]) {
+        debug_assert!(vals.iter().all(|v| v.is_finite()), "values must be finite");
+        self.out = Some(vals);
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<[T; LANES]> {
+        self.out
+    }
+}
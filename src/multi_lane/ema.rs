@@ -0,0 +1,108 @@
+//! Lane-parallel EMA - Exponential Moving Average
+
+use std::num::NonZeroUsize;
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::MultiView;
+
+/// Lane-parallel EMA - Exponential Moving Average.
+/// Each lane yields the same values as `sliding_windows::Ema`.
+#[derive(Clone, Debug, CopyGetters)]
+pub struct MultiEma<T, V, const LANES: usize> {
+    view: V,
+    /// The non-zero window length.
+    #[getset(get_copy = "pub")]
+    window_len: usize,
+    alpha: T,
+    last_ema: [T; LANES],
+    out: [T; LANES],
+    n_observed_values: usize,
+}
+
+impl<T, V, const LANES: usize> MultiEma<T, V, LANES>
+where
+    V: MultiView<T, LANES>,
+    T: Float,
+{
+    /// Create a new lane-parallel EMA with a chained view and a given window length
+    /// and a default alpha value of 2.0
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self::with_alpha(view, window_len, T::from(2.0).expect("can convert"))
+    }
+
+    /// Create a new lane-parallel EMA with a custom alpha as well
+    pub fn with_alpha(view: V, window_len: NonZeroUsize, alpha: T) -> Self {
+        Self {
+            view,
+            window_len: window_len.get(),
+            alpha,
+            last_ema: [T::zero(); LANES],
+            out: [T::zero(); LANES],
+            n_observed_values: 0,
+        }
+    }
+}
+
+impl<T, V, const LANES: usize> MultiView<T, LANES> for MultiEma<T, V, LANES>
+where
+    V: MultiView<T, LANES>,
+    T: Float,
+{
+    fn update(&mut self, vals: [T; LANES]) {
+        debug_assert!(vals.iter().all(|v| v.is_finite()), "values must be finite");
+        self.view.update(vals);
+        let Some(vals) = self.view.last() else { return };
+        debug_assert!(vals.iter().all(|v| v.is_finite()), "values must be finite");
+
+        self.n_observed_values += 1;
+        let weight = self.alpha / (T::one() + T::from(self.window_len).expect("can convert"));
+
+        if self.n_observed_values == 1 {
+            self.out = vals;
+        } else {
+            // Branch-free over the lanes, which the compiler vectorizes.
+            let keep = T::one() - weight;
+            self.out = std::array::from_fn(|lane| vals[lane] * weight + self.last_ema[lane] * keep);
+        }
+        self.last_ema = self.out;
+    }
+
+    fn last(&self) -> Option<[T; LANES]> {
+        if self.n_observed_values < self.window_len {
+            return None;
+        }
+        debug_assert!(
+            self.out.iter().all(|v| v.is_finite()),
+            "values must be finite"
+        );
+        Some(self.out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        multi_lane::{
+            MultiEcho,
+            assert_lanes_match_scalar,
+        },
+        pure_functions::Echo,
+        sliding_windows::Ema,
+    };
+
+    #[test]
+    fn multi_ema() {
+        let window_len = NonZeroUsize::new(16).unwrap();
+        assert_lanes_match_scalar::<_, _, 8>(
+            MultiEma::new(MultiEcho::new(), window_len),
+            std::array::from_fn(|_| Ema::new(Echo::new(), window_len)),
+        );
+        assert_lanes_match_scalar::<_, _, 16>(
+            MultiEma::with_alpha(MultiEcho::new(), window_len, 1.5),
+            std::array::from_fn(|_| Ema::with_alpha(Echo::new(), window_len, 1.5)),
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! This module contains `MultiView` implementations that evaluate the same chain over many series at once.
+//! Each of them produces exactly the same output per lane as its scalar `View` counterpart.
+//! The lanes are processed by plain loops over fixed-size arrays, relying on auto-vectorization
+//! rather than explicit SIMD lanes. The `multi_lane` benchmark compares them with as many scalar views.
+
+mod echo;
+mod ema;
+mod sma;
+mod super_smoother;
+
+pub use echo::MultiEcho;
+pub use ema::MultiEma;
+pub use sma::MultiSma;
+pub use super_smoother::MultiSuperSmoother;
+
+/// Feed each lane a differently rotated version of `TEST_DATA`
+/// and assert that every lane matches its scalar counterpart exactly.
+#[cfg(test)]
+fn assert_lanes_match_scalar<M, S, const LANES: usize>(mut multi: M, mut scalars: [S; LANES])
+where
+    M: crate::MultiView<f64, LANES>,
+    S: crate::View<f64>,
+{
+    use crate::test_data::TEST_DATA;
+
+    for i in 0..TEST_DATA.len() {
+        let vals: [f64; LANES] =
+            std::array::from_fn(|lane| TEST_DATA[(i + lane * 7) % TEST_DATA.len()]);
+        multi.update(vals);
+        for (scalar, val) in scalars.iter_mut().zip(vals) {
+            scalar.update(val);
+        }
+        match multi.last() {
+            Some(outs) => {
+                for (scalar, out) in scalars.iter().zip(outs) {
+                    assert_eq!(scalar.last(), Some(out));
+                }
+            }
+            None => assert!(scalars.iter().all(|scalar| scalar.last().is_none())),
+        }
+    }
+}
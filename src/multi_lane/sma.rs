@@ -0,0 +1,137 @@
+//! Lane-parallel SMA - Simple Moving Average
+
+use std::{
+    collections::VecDeque,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::MultiView;
+
+/// Lane-parallel SMA - Simple Moving Average.
+/// Each lane yields the same values as `sliding_windows::Sma`.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct MultiSma<T, V, const LANES: usize> {
+    view: V,
+    /// The non-zero window length.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    q_vals: VecDeque<[T; LANES]>,
+    sum: [T; LANES],
+    /// The number of updates until the sums are re-computed from the window.
+    n_until_recompute: usize,
+}
+
+impl<T, V, const LANES: usize> MultiSma<T, V, LANES>
+where
+    V: MultiView<T, LANES>,
+    T: Float,
+{
+    /// Create a new lane-parallel simple moving average with a chained `MultiView`
+    /// and a given sliding window length
+    #[inline]
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self {
+            view,
+            window_len,
+            q_vals: VecDeque::new(),
+            sum: [T::zero(); LANES],
+            n_until_recompute: window_len.get(),
+        }
+    }
+
+    /// Re-compute the sum of a lane from the window, like `Sma` does.
+    fn recompute_sum(&mut self, lane: usize) {
+        self.sum[lane] = self
+            .q_vals
+            .iter()
+            .fold(T::zero(), |sum, vals| sum + vals[lane]);
+    }
+}
+
+impl<T, V, const LANES: usize> MultiView<T, LANES> for MultiSma<T, V, LANES>
+where
+    V: MultiView<T, LANES>,
+    T: Float,
+{
+    fn update(&mut self, vals: [T; LANES]) {
+        debug_assert!(vals.iter().all(|v| v.is_finite()), "values must be finite");
+        self.view.update(vals);
+        let Some(vals) = self.view.last() else { return };
+        debug_assert!(vals.iter().all(|v| v.is_finite()), "values must be finite");
+
+        if self.q_vals.len() >= self.window_len.get() {
+            let old_vals = self.q_vals.pop_front().unwrap();
+            for (sum, old_val) in self.sum.iter_mut().zip(old_vals) {
+                *sum = *sum - old_val;
+            }
+        }
+        self.q_vals.push_back(vals);
+
+        for (sum, val) in self.sum.iter_mut().zip(vals) {
+            *sum = *sum + val;
+        }
+
+        self.n_until_recompute -= 1;
+        let recompute = self.n_until_recompute == 0;
+        if recompute {
+            self.n_until_recompute = self.window_len.get();
+        }
+        for lane in 0..LANES {
+            // An overflowed sum can't subtract the evicted values, so it's re-computed until it fits again.
+            if recompute || !self.sum[lane].is_finite() {
+                self.recompute_sum(lane);
+            }
+        }
+    }
+
+    fn last(&self) -> Option<[T; LANES]> {
+        if self.q_vals.len() < self.window_len.get() {
+            return None;
+        }
+        let n = T::from(self.q_vals.len()).expect("can convert");
+        let sma = std::array::from_fn(|lane| {
+            if self.sum[lane].is_finite() {
+                self.sum[lane] / n
+            } else {
+                let (sma, min, max) = self.q_vals.iter().fold(
+                    (T::zero(), T::infinity(), T::neg_infinity()),
+                    |(sma, min, max), vals| {
+                        (
+                            sma + vals[lane] / n,
+                            min.min(vals[lane]),
+                            max.max(vals[lane]),
+                        )
+                    },
+                );
+                sma.max(min).min(max)
+            }
+        });
+        debug_assert!(sma.iter().all(|v| v.is_finite()), "values must be finite");
+        Some(sma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        multi_lane::{
+            MultiEcho,
+            assert_lanes_match_scalar,
+        },
+        pure_functions::Echo,
+        sliding_windows::Sma,
+    };
+
+    #[test]
+    fn multi_sma() {
+        let window_len = NonZeroUsize::new(16).unwrap();
+        assert_lanes_match_scalar::<_, _, 8>(
+            MultiSma::new(MultiEcho::new(), window_len),
+            std::array::from_fn(|_| Sma::new(Echo::new(), window_len)),
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Lane-parallel John Ehlers SuperSmoother filter
+
+use std::{
+    f64::consts::PI,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::MultiView;
+
+/// Lane-parallel John Ehlers SuperSmoother filter.
+/// Each lane yields the same values as `sliding_windows::SuperSmoother`.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct MultiSuperSmoother<T, V, const LANES: usize> {
+    view: V,
+    /// The sliding window length.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    i: usize,
+    c1: T,
+    c2: T,
+    c3: T,
+    /// filter values at current step
+    filt: [T; LANES],
+    // filter one step ago
+    filt_1: [T; LANES],
+    // filter two steps ago
+    filt_2: [T; LANES],
+    last_vals: [T; LANES],
+}
+
+impl<T, V, const LANES: usize> MultiSuperSmoother<T, V, LANES>
+where
+    V: MultiView<T, LANES>,
+    T: Float,
+{
+    /// Create a new instance of the lane-parallel SuperSmoother with a chained `MultiView`
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        let wl = T::from(window_len.get()).expect("can convert");
+        // NOTE: 1.414 * PI radians are the 1.414 * 180 degrees of the original formula.
+        let angle = T::from(1.414 * PI).expect("can convert") / wl;
+        let a1 = (-angle).exp();
+        let b1 = T::from(2.0).expect("can convert") * a1 * angle.cos();
+        let c2 = b1;
+        let c3 = -a1 * a1;
+
+        Self {
+            view,
+            window_len,
+            i: 0,
+            c1: T::one() - c2 - c3,
+            c2,
+            c3,
+            filt: [T::zero(); LANES],
+            filt_1: [T::zero(); LANES],
+            filt_2: [T::zero(); LANES],
+            last_vals: [T::zero(); LANES],
+        }
+    }
+}
+
+impl<T, V, const LANES: usize> MultiView<T, LANES> for MultiSuperSmoother<T, V, LANES>
+where
+    V: MultiView<T, LANES>,
+    T: Float,
+{
+    fn update(&mut self, vals: [T; LANES]) {
+        debug_assert!(vals.iter().all(|v| v.is_finite()), "values must be finite");
+        self.view.update(vals);
+        let Some(vals) = self.view.last() else { return };
+        debug_assert!(vals.iter().all(|v| v.is_finite()), "values must be finite");
+
+        if self.i < 2 {
+            // Seed the filter with the first values, so it does not start from zero.
+            self.filt = vals;
+        } else {
+            // Branch-free over the lanes, which the compiler vectorizes.
+            let two = T::from(2.0).expect("can convert");
+            self.filt = std::array::from_fn(|lane| {
+                self.c1 * (vals[lane] + self.last_vals[lane]) / two
+                    + (self.c2 * self.filt_1[lane])
+                    + (self.c3 * self.filt_2[lane])
+            });
+        }
+        self.filt_2 = self.filt_1;
+        self.filt_1 = self.filt;
+        self.last_vals = vals;
+        self.i += 1;
+    }
+
+    #[inline]
+    fn last(&self) -> Option<[T; LANES]> {
+        // NOTE: filter only kicks in after warmup steps are done
+        if self.i < self.window_len.get() {
+            None
+        } else {
+            debug_assert!(
+                self.filt.iter().all(|v| v.is_finite()),
+                "values must be finite"
+            );
+            Some(self.filt)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        multi_lane::{
+            MultiEcho,
+            assert_lanes_match_scalar,
+        },
+        pure_functions::Echo,
+        sliding_windows::SuperSmoother,
+    };
+
+    #[test]
+    fn multi_super_smoother() {
+        let window_len = NonZeroUsize::new(20).unwrap();
+        assert_lanes_match_scalar::<_, _, 16>(
+            MultiSuperSmoother::new(MultiEcho::new(), window_len),
+            std::array::from_fn(|_| SuperSmoother::new(Echo::new(), window_len)),
+        );
+    }
+}
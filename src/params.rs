@@ -0,0 +1,147 @@
+//! Runtime reconfiguration of view parameters, e.g. to adapt lookbacks to volatility regimes
+//! without rebuilding and re-warming a chain.
+//! Every view with parameters implements `Reconfigure`, listing them with their types and valid ranges.
+//!
+//! Changing a parameter keeps the observed values where possible and takes effect with the next update:
+//! shrinking a window evicts the oldest values, while growing it waits for more data,
+//! so views which only output full windows warm up again. Derived coefficients are recomputed.
+
+use std::num::NonZeroUsize;
+
+use num::Float;
+
+use crate::{
+    Error,
+    introspect::ParamValue,
+};
+
+/// The type of a parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// An integer, e.g. a window length.
+    Int,
+    /// A floating point number, e.g. a smoothing coefficient.
+    Float,
+}
+
+/// A bound of the valid range of a parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+    /// The range includes the bound.
+    Inclusive(f64),
+    /// The range excludes the bound.
+    Exclusive(f64),
+    /// The range is unbounded, but values must be finite.
+    Unbounded,
+}
+
+/// The schema of a parameter, with its current value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamSpec {
+    /// The name, as used by `Reconfigure::set_param`.
+    pub name: &'static str,
+    /// The type.
+    pub kind: ParamKind,
+    /// The lower bound of the valid range.
+    pub min: Bound,
+    /// The upper bound of the valid range.
+    pub max: Bound,
+    /// The current value.
+    pub value: ParamValue,
+}
+
+impl ParamSpec {
+    /// A window length of at least `min`.
+    pub(crate) fn window_len(name: &'static str, value: usize, min: usize) -> Self {
+        Self {
+            name,
+            kind: ParamKind::Int,
+            min: Bound::Inclusive(min as f64),
+            max: Bound::Unbounded,
+            value: ParamValue::Int(value),
+        }
+    }
+
+    /// A floating point parameter within the given bounds.
+    pub(crate) fn float<T: Float>(name: &'static str, value: T, min: Bound, max: Bound) -> Self {
+        Self {
+            name,
+            kind: ParamKind::Float,
+            min,
+            max,
+            value: ParamValue::Float(value.to_f64().expect("can convert")),
+        }
+    }
+
+    /// Whether `val` is a finite value within the valid range.
+    pub fn contains(&self, val: f64) -> bool {
+        let above_min = match self.min {
+            Bound::Inclusive(min) => val >= min,
+            Bound::Exclusive(min) => val > min,
+            Bound::Unbounded => true,
+        };
+        let below_max = match self.max {
+            Bound::Inclusive(max) => val <= max,
+            Bound::Exclusive(max) => val < max,
+            Bound::Unbounded => true,
+        };
+        val.is_finite() && above_min && below_max
+    }
+}
+
+/// Implemented by views whose parameters can be listed and changed at runtime.
+/// Only the parameters of the view itself are covered, not those of the views it observes.
+pub trait Reconfigure {
+    /// The schema of the parameters, with their current values.
+    fn param_specs(&self) -> Vec<ParamSpec>;
+
+    /// Set the parameter `name` to `val`, which is rejected if it is of another type or out of range,
+    /// leaving the view unchanged.
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error>;
+}
+
+/// The value of a window length parameter.
+pub(crate) fn window_len_param(name: &'static str, val: ParamValue) -> Result<NonZeroUsize, Error> {
+    let ParamValue::Int(len) = val else {
+        return Err(Error::ParameterType(name));
+    };
+    NonZeroUsize::new(len).ok_or(Error::ZeroWindowLen(name))
+}
+
+/// The value of a floating point parameter, which may also be given as an integer.
+pub(crate) fn float_param<T: Float>(name: &'static str, val: ParamValue) -> Result<T, Error> {
+    match val {
+        ParamValue::Float(val) => Ok(T::from(val).expect("can convert")),
+        ParamValue::Int(val) => Ok(T::from(val).expect("can convert")),
+        ParamValue::Text(_) => Err(Error::ParameterType(name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn param_spec_contains() {
+        let spec = ParamSpec::float("gamma", 0.5, Bound::Inclusive(0.0), Bound::Exclusive(1.0));
+        assert!(spec.contains(0.0));
+        assert!(!spec.contains(1.0));
+        assert!(!spec.contains(f64::NAN));
+        let spec = ParamSpec::window_len("window_len", 16, 1);
+        assert!(spec.contains(1.0));
+        assert!(!spec.contains(0.0));
+        assert!(!spec.contains(f64::INFINITY));
+    }
+
+    #[test]
+    fn window_len_param_type() {
+        assert_eq!(
+            window_len_param("window_len", ParamValue::Float(2.0)),
+            Err(Error::ParameterType("window_len"))
+        );
+        assert_eq!(
+            window_len_param("window_len", ParamValue::Int(0)),
+            Err(Error::ZeroWindowLen("window_len"))
+        );
+    }
+}
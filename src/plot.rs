@@ -1,6 +1,226 @@
-use plotters::prelude::*;
+//! Plotting of `View` outputs, e.g. to visually inspect each stage of a chain.
+//! A `Figure` stacks `Panel`s vertically, each of which overlays any number of `Series`,
+//! optionally on a secondary y axis. It is rendered to SVG or PNG, depending on the file extension.
+//! Requires the `plot` feature.
+
+use std::{
+    error::Error,
+    ops::Range,
+    path::Path,
+};
+
+use plotters::{
+    coord::Shift,
+    prelude::*,
+};
+
+use crate::View;
+
+/// A labelled series of values, where `None` leaves a gap, e.g. while a view is warming up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    /// The label shown in the legend.
+    pub label: String,
+    /// The values, indexed by sample.
+    pub vals: Vec<Option<f64>>,
+}
+
+impl Series {
+    /// Create a new labelled series from values.
+    pub fn new(label: impl Into<String>, vals: &[f64]) -> Self {
+        Self {
+            label: label.into(),
+            vals: vals.iter().copied().map(Some).collect(),
+        }
+    }
+
+    /// Create a new labelled series from the outputs of a `view` observed after each of the values.
+    pub fn from_view<V: View<f64>>(label: impl Into<String>, mut view: V, vals: &[f64]) -> Self {
+        Self {
+            label: label.into(),
+            vals: view.update_batch(vals),
+        }
+    }
+
+    /// The contiguous segments of ready values as `(index, value)` points.
+    fn segments(&self) -> Vec<Vec<(f64, f64)>> {
+        let mut segments = vec![];
+        let mut segment = vec![];
+        for (i, val) in self.vals.iter().enumerate() {
+            match val {
+                Some(val) => segment.push((i as f64, *val)),
+                None if !segment.is_empty() => segments.push(std::mem::take(&mut segment)),
+                None => {}
+            }
+        }
+        if !segment.is_empty() {
+            segments.push(segment);
+        }
+        segments
+    }
+}
+
+/// A subplot, overlaying series on a primary and an optional secondary y axis.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Panel {
+    /// The series on the left y axis.
+    pub series: Vec<Series>,
+    /// The series on the right y axis, e.g. an indicator over the price.
+    pub secondary: Vec<Series>,
+}
+
+impl Panel {
+    /// Create a new panel with a single series.
+    pub fn new(series: Series) -> Self {
+        Self {
+            series: vec![series],
+            secondary: vec![],
+        }
+    }
+
+    /// Overlay a series on the primary y axis.
+    pub fn overlay(mut self, series: Series) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Overlay a series on the secondary y axis.
+    pub fn secondary(mut self, series: Series) -> Self {
+        self.secondary.push(series);
+        self
+    }
+}
+
+/// A titled figure of vertically stacked panels, sharing the x axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Figure {
+    /// The caption above the panels.
+    pub title: String,
+    /// The panels from top to bottom.
+    pub panels: Vec<Panel>,
+    /// The width in pixels.
+    pub width: u32,
+    /// The height in pixels, or `None` for 480 pixels per panel.
+    pub height: Option<u32>,
+}
+
+impl Figure {
+    /// Create a new figure without panels, with a default size of 640 by 480 pixels per panel.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            panels: vec![],
+            width: 640,
+            height: None,
+        }
+    }
+
+    /// Add a panel below the existing ones.
+    pub fn panel(mut self, panel: Panel) -> Self {
+        self.panels.push(panel);
+        self
+    }
+
+    /// Render the figure to `path`, as SVG if its extension is `svg` and as PNG otherwise.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        let path = path.as_ref();
+        let height = self.height.unwrap_or(480 * self.panels.len().max(1) as u32);
+        let size = (self.width, height);
+        if path.extension().is_some_and(|ext| ext == "svg") {
+            self.draw(SVGBackend::new(path, size).into_drawing_area())
+        } else {
+            self.draw(BitMapBackend::new(path, size).into_drawing_area())
+        }
+    }
+
+    fn draw<DB>(&self, root: DrawingArea<DB, Shift>) -> Result<(), Box<dyn Error>>
+    where
+        DB: DrawingBackend,
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+        let root = root.titled(&self.title, ("sans-serif", 30))?;
+        let areas = root.split_evenly((self.panels.len().max(1), 1));
+        let x_range = 0.0..self
+            .panels
+            .iter()
+            .flat_map(|panel| panel.series.iter().chain(&panel.secondary))
+            .map(|series| series.vals.len())
+            .max()
+            .unwrap_or(1) as f64;
+
+        let mut color = 0;
+        let mut next_color = || {
+            color += 1;
+            Palette99::pick(color - 1).to_rgba()
+        };
+        for (panel, area) in self.panels.iter().zip(&areas) {
+            let mut chart = ChartBuilder::on(area)
+                .margin(5)
+                .x_label_area_size(30)
+                .y_label_area_size(50)
+                .right_y_label_area_size(if panel.secondary.is_empty() { 0 } else { 50 })
+                .build_cartesian_2d(x_range.clone(), y_range(&panel.series))?
+                .set_secondary_coord(x_range.clone(), y_range(&panel.secondary));
+            chart.configure_mesh().draw()?;
+            if !panel.secondary.is_empty() {
+                chart.configure_secondary_axes().draw()?;
+            }
+
+            for series in &panel.series {
+                let color = next_color();
+                for (i, segment) in series.segments().into_iter().enumerate() {
+                    let drawn = chart.draw_series(LineSeries::new(segment, color))?;
+                    if i == 0 {
+                        drawn.label(&series.label).legend(move |(x, y)| {
+                            PathElement::new(vec![(x, y), (x + 20, y)], color)
+                        });
+                    }
+                }
+            }
+            for series in &panel.secondary {
+                let color = next_color();
+                for (i, segment) in series.segments().into_iter().enumerate() {
+                    let drawn = chart.draw_secondary_series(LineSeries::new(segment, color))?;
+                    if i == 0 {
+                        drawn.label(&series.label).legend(move |(x, y)| {
+                            PathElement::new(vec![(x, y), (x + 20, y)], color)
+                        });
+                    }
+                }
+            }
+
+            chart
+                .configure_series_labels()
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()?;
+        }
+        root.present()?;
+
+        Ok(())
+    }
+}
+
+/// The range of the ready values, widened if it is empty or flat.
+fn y_range(series: &[Series]) -> Range<f64> {
+    let (min, max) = series
+        .iter()
+        .flat_map(|series| series.vals.iter().flatten())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), val| {
+            (min.min(*val), max.max(*val))
+        });
+    if min > max {
+        return 0.0..1.0;
+    }
+    if min == max {
+        return min - 1.0..max + 1.0;
+    }
+    min..max
+}
 
 /// prepare_vec returns a 2d vector suitable for plotting and also min, max values of input vector
+#[cfg(test)]
 fn prepare_vec(vals: Vec<f64>) -> (Vec<(f64, f64)>, f64, f64) {
     assert!(!vals.is_empty());
     let mut out = vec![(0.0, 0.0); vals.len()];
@@ -15,16 +235,14 @@ fn prepare_vec(vals: Vec<f64>) -> (Vec<(f64, f64)>, f64, f64) {
             min = vals[i]
         }
     }
-    return (out, min, max);
+    (out, min, max)
 }
 
 /// Plots the given values in a single plot to filename
 /// returns an Error if there has been an error
 /// Used for graphing the timeseries
-pub(crate) fn plot_values(
-    vals: Vec<f64>,
-    filename: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
+#[cfg(test)]
+pub(crate) fn plot_values(vals: Vec<f64>, filename: &str) -> Result<(), Box<dyn Error>> {
     let (vec2d, min, max) = prepare_vec(vals);
 
     let root = BitMapBackend::new(filename, (640, 480)).into_drawing_area();
@@ -44,9 +262,54 @@ pub(crate) fn plot_values(
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::{
+        pure_functions::Echo,
+        sliding_windows::{
+            Alma,
+            Vsct,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn series_segments() {
+        let series = Series {
+            label: "gaps".to_string(),
+            vals: vec![None, Some(1.0), Some(2.0), None, Some(3.0)],
+        };
+        assert_eq!(
+            series.segments(),
+            vec![vec![(1.0, 1.0), (2.0, 2.0)], vec![(4.0, 3.0)]]
+        );
+    }
+
+    #[test]
+    fn figure_chain_stages() {
+        let window_len = NonZeroUsize::new(16).unwrap();
+        let price = Series::new("price", &TEST_DATA);
+        let vsct = Series::from_view("Vsct(Echo)", Vsct::new(Echo::new(), window_len), &TEST_DATA);
+        let alma = Series::from_view(
+            "Alma(Vsct(Echo))",
+            Alma::new(Vsct::new(Echo::new(), window_len), window_len),
+            &TEST_DATA,
+        );
+        let figure = Figure::new("Alma(Vsct(Echo))")
+            .panel(Panel::new(price).secondary(alma.clone()))
+            .panel(Panel::new(vsct).overlay(alma));
+        figure.save("img/alma_vsct_chain.svg").unwrap();
+        let svg = std::fs::read_to_string("img/alma_vsct_chain.svg").unwrap();
+        assert!(svg.contains("Alma(Vsct(Echo))"));
+    }
+}
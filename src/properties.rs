@@ -0,0 +1,224 @@
+//! A generic property-testing harness, checking invariants of `View`s over random finite series.
+//! Any view can opt in to the common properties with `view_properties!` from its test module,
+//! and check its output domain with `check_bounded`.
+//! Views of finite memory additionally opt in to `reconfigure_properties!`.
+
+use std::num::NonZeroUsize;
+
+use proptest::{
+    prelude::*,
+    test_runner::TestCaseError,
+};
+
+use crate::{
+    View,
+    introspect::ParamValue,
+    params::Reconfigure,
+};
+
+/// The relative tolerance with which `f32` outputs need to agree with `f64` outputs.
+const F32_TOLERANCE: f64 = 1e-2;
+
+/// Random positive price series, following a random walk with flat segments.
+/// The values are exactly representable as `f32`, so both precisions observe the same inputs.
+pub(crate) fn series() -> impl Strategy<Value = Vec<f64>> {
+    let step = prop_oneof![1 => Just(0.0), 3 => -1.0..1.0_f64];
+    proptest::collection::vec(step, 1..256).prop_map(|steps| {
+        steps
+            .iter()
+            .scan(0.0_f64, |log_price, step| {
+                *log_price += 0.01 * step;
+                Some((100.0 * log_price.exp()) as f32 as f64)
+            })
+            .collect()
+    })
+}
+
+/// Random window lengths, small enough to exercise the warm-up as well as the steady state.
+pub(crate) fn window_len() -> impl Strategy<Value = NonZeroUsize> {
+    (1..32_usize).prop_map(|len| NonZeroUsize::new(len).expect("is non-zero"))
+}
+
+/// Every output of the `view` lies within `[min, max]`.
+pub(crate) fn check_bounded<V: View<f64>>(
+    mut view: V,
+    vals: &[f64],
+    min: f64,
+    max: f64,
+) -> Result<(), TestCaseError> {
+    for (i, val) in vals.iter().enumerate() {
+        view.update(*val);
+        if let Some(out) = view.last() {
+            prop_assert!(
+                (min..=max).contains(&out),
+                "output {out} at index {i} is out of [{min}, {max}]"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// A clone taken after `split` values produces the same outputs as the original from then on.
+pub(crate) fn check_clone_determinism<V: View<f64> + Clone>(
+    mut view: V,
+    vals: &[f64],
+    split: usize,
+) -> Result<(), TestCaseError> {
+    let split = split.min(vals.len());
+    for val in &vals[..split] {
+        view.update(*val);
+    }
+    let mut clone = view.clone();
+    prop_assert_eq!(view.last(), clone.last());
+    for (i, val) in vals.iter().enumerate().skip(split) {
+        view.update(*val);
+        clone.update(*val);
+        prop_assert_eq!(view.last(), clone.last(), "diverged at index {}", i);
+    }
+    Ok(())
+}
+
+/// The `f32` and `f64` instances of a view become ready at the same time and agree within tolerance.
+pub(crate) fn check_f32_f64_agree<V32: View<f32>, V64: View<f64>>(
+    mut view_f32: V32,
+    mut view_f64: V64,
+    vals: &[f64],
+) -> Result<(), TestCaseError> {
+    for (i, val) in vals.iter().enumerate() {
+        view_f32.update(*val as f32);
+        view_f64.update(*val);
+        match (view_f32.last(), view_f64.last()) {
+            (Some(out_f32), Some(out_f64)) => {
+                let diff = (out_f32 as f64 - out_f64).abs();
+                prop_assert!(
+                    diff <= F32_TOLERANCE * out_f64.abs().max(1.0),
+                    "f32 output {out_f32} differs from f64 output {out_f64} at index {i}"
+                );
+            }
+            (None, None) => {}
+            (out_f32, out_f64) => {
+                return Err(TestCaseError::fail(format!(
+                    "readiness differs at index {i}: f32 {out_f32:?}, f64 {out_f64:?}"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Setting the window length of `view` after `split` values keeps its outputs finite,
+/// and once `refill_len` further values have refilled its windows, they agree with `fresh`, which was constructed with it.
+pub(crate) fn check_reconfigure<V: View<f64> + Reconfigure>(
+    mut view: V,
+    mut fresh: V,
+    vals: &[f64],
+    split: usize,
+    window_len: NonZeroUsize,
+    refill_len: usize,
+) -> Result<(), TestCaseError> {
+    let split = split.min(vals.len());
+    for (i, val) in vals.iter().enumerate() {
+        if i == split {
+            view.set_param("window_len", ParamValue::Int(window_len.get()))
+                .map_err(|err| TestCaseError::fail(err.to_string()))?;
+        }
+        view.update(*val);
+        fresh.update(*val);
+        if let Some(out) = view.last() {
+            prop_assert!(out.is_finite(), "output {out} at index {i} is not finite");
+        }
+        if i > split + refill_len {
+            match (view.last(), fresh.last()) {
+                (Some(out), Some(expected)) => prop_assert!(
+                    (out - expected).abs() <= 1e-6 * expected.abs().max(1.0),
+                    "output {out} differs from {expected} at index {i}"
+                ),
+                (out, expected) => {
+                    prop_assert_eq!(out, expected, "readiness differs at index {}", i)
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The mean period between upward zero crossings, or `None` with fewer than two crossings.
+pub(crate) fn zero_crossing_period(vals: &[f64]) -> Option<f64> {
+    let crossings: Vec<f64> = vals
+        .windows(2)
+        .enumerate()
+        .filter(|(_, w)| w[0] < 0.0 && w[1] >= 0.0)
+        // Interpolate linearly between the samples.
+        .map(|(i, w)| i as f64 + w[0] / (w[0] - w[1]))
+        .collect();
+    if crossings.len() < 2 {
+        return None;
+    }
+    Some((crossings[crossings.len() - 1] - crossings[0]) / (crossings.len() - 1) as f64)
+}
+
+/// Opt a view in to the common properties: cloning mid-stream yields identical futures,
+/// and the `f32` and `f64` instances agree.
+/// The view is constructed from the given expression, in which `window_len` is random.
+macro_rules! view_properties {
+    (|$window_len:ident| $new_view:expr) => {
+        proptest::proptest! {
+            #[test]
+            fn clone_determinism(
+                vals in crate::properties::series(),
+                $window_len in crate::properties::window_len(),
+                split in 0..256_usize,
+            ) {
+                crate::properties::check_clone_determinism($new_view, &vals, split)?;
+            }
+
+            #[test]
+            fn f32_f64_agree(
+                vals in crate::properties::series(),
+                $window_len in crate::properties::window_len(),
+            ) {
+                crate::properties::check_f32_f64_agree($new_view, $new_view, &vals)?;
+            }
+        }
+    };
+}
+
+pub(crate) use view_properties;
+
+/// Opt a view of finite memory in to reconfiguring its window length mid-stream,
+/// which must agree with a view constructed with the new window length once the window has refilled.
+/// The view is constructed from the given expression, in which `window_len` is random.
+/// Views composed of several windows pass the number of values after which they have all refilled as `refill`,
+/// which defaults to the window length.
+macro_rules! reconfigure_properties {
+    (|$window_len:ident| $new_view:expr) => {
+        crate::properties::reconfigure_properties!(|$window_len| $new_view, refill = |window_len| window_len.get());
+    };
+    (|$window_len:ident| $new_view:expr, refill = |$refill_len:ident| $refill:expr) => {
+        proptest::proptest! {
+            #[test]
+            fn reconfigure_window_len(
+                vals in crate::properties::series(),
+                window_len in crate::properties::window_len(),
+                new_window_len in crate::properties::window_len(),
+                split in 0..256_usize,
+            ) {
+                let view = {
+                    let $window_len = window_len;
+                    $new_view
+                };
+                let fresh = {
+                    let $window_len = new_window_len;
+                    $new_view
+                };
+                let refill_len = {
+                    let $refill_len = new_window_len;
+                    $refill
+                };
+                crate::properties::check_reconfigure(view, fresh, &vals, split, new_window_len, refill_len)?;
+            }
+        }
+    };
+}
+
+pub(crate) use reconfigure_properties;
@@ -1,9 +1,17 @@
 use num::Float;
 
-use crate::View;
+use crate::{
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
 
 /// Add View a to b
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Add<T, A, B> {
     a: A,
     b: B,
@@ -42,13 +50,45 @@ where
 
     #[inline]
     fn last(&self) -> Option<T> {
-        match (self.a.last(), self.b.last()) {
-            (Some(a), Some(b)) => {
-                debug_assert!(a.is_finite(), "value must be finite");
-                debug_assert!(b.is_finite(), "value must be finite");
-                Some(a + b)
+        self.status().value()
+    }
+
+    #[inline]
+    fn status(&self) -> Status<T> {
+        self.a.status().zip_with(self.b.status(), |a, b| {
+            debug_assert!(a.is_finite(), "value must be finite");
+            debug_assert!(b.is_finite(), "value must be finite");
+            let out = a + b;
+            // The sum of large values overflows.
+            if !out.is_finite() {
+                return Status::Invalid(InvalidReason::Overflow);
             }
-            (None, None) | (None, Some(_)) | (Some(_), None) => None,
-        }
+            Status::Ready(out)
+        })
+    }
+}
+
+impl<T, A, B> Introspect for Add<T, A, B>
+where
+    A: Introspect,
+    B: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Add").child(self.a.node()).child(self.b.node())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pure_functions::Constant;
+
+    #[test]
+    fn add_overflow() {
+        let mut add = Add::new(Constant::new(f64::MAX), Constant::new(f64::MAX));
+        add.update(1.0);
+        assert_eq!(add.last(), None);
+        assert_eq!(add.status(), Status::Invalid(InvalidReason::Overflow));
     }
 }
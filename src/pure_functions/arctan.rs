@@ -0,0 +1,85 @@
+use std::f64::consts::PI;
+
+use num::Float;
+
+use crate::{
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
+
+/// Applies the arctangent, rescaled from `(-pi / 2, pi / 2)` to `(-1, 1)`, i.e. `2 / pi * atan(x)`,
+/// to the output of its View component.
+/// It approaches its bounds like `1 / x`, between the `Tanh` and the `Softsign`.
+#[derive(Debug, Clone)]
+pub struct Arctan<T, V> {
+    view: V,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T, V> Arctan<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new instance with a chained View
+    pub fn new(view: V) -> Self {
+        Self {
+            view,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, V> View<T> for Arctan<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+    }
+
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        self.view.status().map(|v| {
+            debug_assert!(v.is_finite(), "value must be finite");
+            T::from(2.0 / PI).expect("can convert") * v.atan()
+        })
+    }
+}
+
+impl<T, V> Introspect for Arctan<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Arctan").child(self.view.node())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pure_functions::Echo;
+
+    #[test]
+    fn arctan() {
+        let mut view = Arctan::new(Echo::new());
+        for (val, expected) in [(0.0, 0.0), (1.0, 0.5), (-1.0, -0.5)] {
+            view.update(val);
+            assert!((view.last().unwrap() - expected).abs() < 1e-12);
+        }
+        view.update(1e9);
+        assert!(view.last().unwrap() < 1.0);
+        assert!(view.last().unwrap() > 0.999_999);
+    }
+}
@@ -1,4 +1,10 @@
-use crate::View;
+use crate::{
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
 
 /// Provides a float value to other views
 #[derive(Default, Debug, Clone)]
@@ -20,3 +26,9 @@ impl<T: num::Float> View<T> for Constant<T> {
         Some(self.val)
     }
 }
+
+impl<T: num::Float> Introspect for Constant<T> {
+    fn node(&self) -> Node {
+        Node::new("Constant").param("val", self.val.to_f64().expect("can convert"))
+    }
+}
@@ -1,8 +1,17 @@
 use num::Float;
 
-use crate::View;
+use crate::{
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
 
 /// Divide View a by b
+/// Returns `None` while `b` is zero or the quotient overflows.
 #[derive(Debug, Clone)]
 pub struct Divide<T, A, B> {
     a: A,
@@ -30,7 +39,7 @@ impl<T, A, B> View<T> for Divide<T, A, B>
 where
     A: View<T>,
     B: View<T>,
-    T: Float + std::fmt::Debug,
+    T: Float,
 {
     fn update(&mut self, val: T) {
         debug_assert!(val.is_finite(), "value must be finite");
@@ -39,14 +48,55 @@ where
     }
 
     fn last(&self) -> Option<T> {
-        match (self.a.last(), self.b.last()) {
-            (Some(a), Some(b)) => {
-                debug_assert!(a.is_finite(), "value must be finite");
-                debug_assert!(b.is_finite(), "value must be finite");
-                debug_assert_ne!(b, T::zero(), "cannot divide by zero");
-                Some(a / b)
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        self.a.status().zip_with(self.b.status(), |a, b| {
+            debug_assert!(a.is_finite(), "value must be finite");
+            debug_assert!(b.is_finite(), "value must be finite");
+            // The quotient is undefined for a zero divisor, and it overflows for tiny ones.
+            if b == T::zero() {
+                return Status::Invalid(InvalidReason::ZeroDenominator);
             }
-            (None, None) | (None, Some(_)) | (Some(_), None) => None,
-        }
+            let out = a / b;
+            if !out.is_finite() {
+                return Status::Invalid(InvalidReason::Overflow);
+            }
+            Status::Ready(out)
+        })
+    }
+}
+
+impl<T, A, B> Introspect for Divide<T, A, B>
+where
+    A: Introspect,
+    B: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Divide")
+            .child(self.a.node())
+            .child(self.b.node())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pure_functions::{
+        Echo,
+        Subtract,
+    };
+
+    #[test]
+    fn divide_by_zero() {
+        let mut divide = Divide::new(Echo::new(), Subtract::new(Echo::new(), Echo::new()));
+        divide.update(1.0);
+        assert_eq!(divide.last(), None);
+        assert_eq!(
+            divide.status(),
+            Status::Invalid(InvalidReason::ZeroDenominator)
+        );
     }
 }
@@ -1,6 +1,12 @@
 //! Echo always return the last value just like an echo
 
-use crate::View;
+use crate::{
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
 
 #[derive(Default, Clone, Debug)]
 /// Echo always return the last value just like an echo
@@ -27,6 +33,12 @@ impl<T: num::Float> View<T> for Echo<T> {
     }
 }
 
+impl<T: num::Float> Introspect for Echo<T> {
+    fn node(&self) -> Node {
+        Node::new("Echo")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,90 @@
+use num::Float;
+
+use crate::{
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
+
+/// Applies the Fisher transform `0.5 * ln((1 + x) / (1 - x))`, the inverse of the `Tanh`,
+/// to the output of its View component, which must already be within `[-1, 1]`, e.g. a `MyRSI`.
+/// Unlike the `EhlersFisherTransform`, it neither normalizes nor smoothes the values.
+/// As Ehlers does, it clips them to `[-0.999, 0.999]`, so the output is finite, within about `[-3.8, 3.8]`.
+#[derive(Debug, Clone)]
+pub struct FisherTransform<T, V> {
+    view: V,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T, V> FisherTransform<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new instance with a chained View
+    pub fn new(view: V) -> Self {
+        Self {
+            view,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, V> View<T> for FisherTransform<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+    }
+
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        self.view.status().map(|v| {
+            debug_assert!(v.is_finite(), "value must be finite");
+            let limit = T::from(0.999).expect("can convert");
+            let v = v.max(-limit).min(limit);
+            let half = T::from(0.5).expect("can convert");
+            half * ((T::one() + v) / (T::one() - v)).ln()
+        })
+    }
+}
+
+impl<T, V> Introspect for FisherTransform<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("FisherTransform").child(self.view.node())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pure_functions::Echo;
+
+    #[test]
+    fn fisher_transform() {
+        let mut view = FisherTransform::new(Echo::new());
+        for val in [-0.9, -0.5, 0.0, 0.3, 0.99] {
+            view.update(val);
+            assert!((view.last().unwrap() - f64::atanh(val)).abs() < 1e-12);
+        }
+        // Values beyond the bounds are clipped.
+        view.update(1.0);
+        let limit = f64::atanh(0.999);
+        assert!((view.last().unwrap() - limit).abs() < 1e-12);
+        view.update(-5.0);
+        assert!((view.last().unwrap() + limit).abs() < 1e-12);
+    }
+}
@@ -1,6 +1,21 @@
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamSpec,
+        Reconfigure,
+        float_param,
+    },
+};
 
 /// Greater Than or Equal
 /// Will allow values >= clipping_point through and clip other values to the clipping point
@@ -25,6 +40,19 @@ where
             out: None,
         }
     }
+
+    /// Create a new instance with a chained View and a given clipping point, which must be finite.
+    pub fn try_new(view: V, clipping_point: T) -> Result<Self, Error> {
+        check_clipping_point(clipping_point)?;
+        Ok(Self::new(view, clipping_point))
+    }
+
+    /// Set the clipping point, which must be finite.
+    pub fn set_clipping_point(&mut self, clipping_point: T) -> Result<(), Error> {
+        check_clipping_point(clipping_point)?;
+        self.clipping_point = clipping_point;
+        Ok(())
+    }
 }
 
 impl<T, V> View<T> for GTE<T, V>
@@ -48,6 +76,56 @@ where
     fn last(&self) -> Option<T> {
         self.out
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for GTE<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("GTE")
+            .param(
+                "clipping_point",
+                self.clipping_point.to_f64().expect("can convert"),
+            )
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for GTE<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::float(
+            "clipping_point",
+            self.clipping_point,
+            Bound::Unbounded,
+            Bound::Unbounded,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "clipping_point" => self.set_clipping_point(float_param("clipping_point", val)?),
+            _ => Err(Error::UnknownParameter),
+        }
+    }
+}
+
+fn check_clipping_point<T: Float>(clipping_point: T) -> Result<(), Error> {
+    if !clipping_point.is_finite() {
+        return Err(Error::ClippingPoint(
+            clipping_point.to_f64().expect("can convert"),
+        ));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -63,4 +141,26 @@ mod tests {
         gte.update(0.5);
         assert_eq!(gte.last().unwrap(), 1.0);
     }
+
+    #[test]
+    fn gte_try_new() {
+        assert!(GTE::try_new(Echo::new(), 1.0).is_ok());
+        assert_eq!(
+            GTE::try_new(Echo::new(), f64::INFINITY).unwrap_err(),
+            Error::ClippingPoint(f64::INFINITY)
+        );
+    }
+
+    #[test]
+    fn gte_set_param() {
+        let mut view = GTE::new(Echo::<f64>::new(), 0.0);
+        assert_eq!(
+            view.set_param("clipping_point", ParamValue::Float(f64::INFINITY)),
+            Err(Error::ClippingPoint(f64::INFINITY))
+        );
+        view.set_param("clipping_point", ParamValue::Float(1.0))
+            .unwrap();
+        view.update(0.5);
+        assert_eq!(view.last(), Some(1.0));
+    }
 }
@@ -1,6 +1,21 @@
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamSpec,
+        Reconfigure,
+        float_param,
+    },
+};
 
 /// Lower Than or Equal filter,
 /// which only allows values lower than the specified clipping point through
@@ -25,6 +40,19 @@ where
             out: None,
         }
     }
+
+    /// Create a new instance with a chained View and a given clipping point, which must be finite.
+    pub fn try_new(view: V, clipping_value: T) -> Result<Self, Error> {
+        check_clipping_point(clipping_value)?;
+        Ok(Self::new(view, clipping_value))
+    }
+
+    /// Set the clipping point, which must be finite.
+    pub fn set_clipping_value(&mut self, clipping_value: T) -> Result<(), Error> {
+        check_clipping_point(clipping_value)?;
+        self.clipping_value = clipping_value;
+        Ok(())
+    }
 }
 
 impl<T, V> View<T> for LTE<T, V>
@@ -48,6 +76,56 @@ where
     fn last(&self) -> Option<T> {
         self.out
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for LTE<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("LTE")
+            .param(
+                "clipping_value",
+                self.clipping_value.to_f64().expect("can convert"),
+            )
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for LTE<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::float(
+            "clipping_value",
+            self.clipping_value,
+            Bound::Unbounded,
+            Bound::Unbounded,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "clipping_value" => self.set_clipping_value(float_param("clipping_value", val)?),
+            _ => Err(Error::UnknownParameter),
+        }
+    }
+}
+
+fn check_clipping_point<T: Float>(clipping_value: T) -> Result<(), Error> {
+    if !clipping_value.is_finite() {
+        return Err(Error::ClippingPoint(
+            clipping_value.to_f64().expect("can convert"),
+        ));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -63,4 +141,26 @@ mod tests {
         lte.update(1.5);
         assert_eq!(lte.last().unwrap(), 1.0);
     }
+
+    #[test]
+    fn lte_try_new() {
+        assert!(LTE::try_new(Echo::new(), 1.0).is_ok());
+        assert_eq!(
+            LTE::try_new(Echo::new(), f64::NEG_INFINITY).unwrap_err(),
+            Error::ClippingPoint(f64::NEG_INFINITY)
+        );
+    }
+
+    #[test]
+    fn lte_set_param() {
+        let mut view = LTE::new(Echo::<f64>::new(), 0.0);
+        assert_eq!(
+            view.set_param("clipping_value", ParamValue::Float(f64::INFINITY)),
+            Err(Error::ClippingPoint(f64::INFINITY))
+        );
+        view.set_param("clipping_value", ParamValue::Float(1.0))
+            .unwrap();
+        view.update(1.5);
+        assert_eq!(view.last(), Some(1.0));
+    }
 }
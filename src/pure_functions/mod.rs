@@ -2,21 +2,29 @@
 //! They don't really rely on an internal state, but rather process value from other `View`s
 
 mod add;
+mod arctan;
 mod constant;
 mod divide;
 mod echo;
+mod fisher_transform;
 mod gte;
+mod logistic;
 mod lte;
 mod multiply;
+mod softsign;
 mod subtract;
 mod tanh;
 
 pub use add::Add;
+pub use arctan::Arctan;
 pub use constant::Constant;
 pub use divide::Divide;
 pub use echo::Echo;
+pub use fisher_transform::FisherTransform;
 pub use gte::GTE;
+pub use logistic::Logistic;
 pub use lte::LTE;
 pub use multiply::Multiply;
+pub use softsign::Softsign;
 pub use subtract::Subtract;
 pub use tanh::Tanh;
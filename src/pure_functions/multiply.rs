@@ -1,6 +1,14 @@
 use num::Float;
 
-use crate::View;
+use crate::{
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
 
 /// Multiply View a by b
 #[derive(Debug, Clone)]
@@ -39,13 +47,33 @@ where
     }
 
     fn last(&self) -> Option<T> {
-        match (self.a.last(), self.b.last()) {
-            (Some(a), Some(b)) => {
-                debug_assert!(a.is_finite(), "value must be finite");
-                debug_assert!(b.is_finite(), "value must be finite");
-                Some(a * b)
+        self.status().value()
+    }
+
+    #[inline]
+    fn status(&self) -> Status<T> {
+        self.a.status().zip_with(self.b.status(), |a, b| {
+            debug_assert!(a.is_finite(), "value must be finite");
+            debug_assert!(b.is_finite(), "value must be finite");
+            let out = a * b;
+            // The product of large values overflows.
+            if !out.is_finite() {
+                return Status::Invalid(InvalidReason::Overflow);
             }
-            (None, None) | (None, Some(_)) | (Some(_), None) => None,
-        }
+            Status::Ready(out)
+        })
+    }
+}
+
+impl<T, A, B> Introspect for Multiply<T, A, B>
+where
+    A: Introspect,
+    B: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Multiply")
+            .child(self.a.node())
+            .child(self.b.node())
     }
 }
@@ -0,0 +1,80 @@
+use num::Float;
+
+use crate::{
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
+
+/// Applies the softsign function `x / (1 + |x|)` to the output of its View component,
+/// which maps into `(-1, 1)` and approaches its bounds polynomially rather than exponentially,
+/// so it keeps distinguishing large values.
+#[derive(Debug, Clone)]
+pub struct Softsign<T, V> {
+    view: V,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T, V> Softsign<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new instance with a chained View
+    pub fn new(view: V) -> Self {
+        Self {
+            view,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, V> View<T> for Softsign<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+    }
+
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        self.view.status().map(|v| {
+            debug_assert!(v.is_finite(), "value must be finite");
+            v / (T::one() + v.abs())
+        })
+    }
+}
+
+impl<T, V> Introspect for Softsign<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Softsign").child(self.view.node())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pure_functions::Echo;
+
+    #[test]
+    fn softsign() {
+        let mut view = Softsign::new(Echo::new());
+        for (val, expected) in [(0.0, 0.0), (1.0, 0.5), (-3.0, -0.75), (99.0, 0.99)] {
+            view.update(val);
+            assert_eq!(view.last(), Some(expected));
+        }
+    }
+}
@@ -1,6 +1,13 @@
 use num::Float;
 
-use crate::View;
+use crate::{
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
 
 /// Applies the Tanh function to the output of its View component
 #[derive(Debug, Clone)]
@@ -34,9 +41,23 @@ where
     }
 
     fn last(&self) -> Option<T> {
-        self.view.last().map(|v| {
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        self.view.status().map(|v| {
             debug_assert!(v.is_finite(), "value must be finite");
             v.tanh()
         })
     }
 }
+
+impl<T, V> Introspect for Tanh<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Tanh").child(self.view.node())
+    }
+}
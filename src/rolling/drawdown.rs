@@ -1,10 +1,11 @@
-use num::{
-    Float,
-    Zero,
-};
+use num::Float;
 
 use crate::{
     View,
+    introspect::{
+        Introspect,
+        Node,
+    },
     pure_functions::Echo,
 };
 
@@ -59,9 +60,13 @@ where
         if val < self.min_after_peak {
             self.min_after_peak = val;
         }
-        debug_assert!(self.peak != Zero::zero());
+        // The drawdown is relative to the peak, which is only meaningful for positive peaks.
+        if self.peak <= T::zero() {
+            return;
+        }
         let dd = (self.peak - self.min_after_peak) / self.peak;
-        if dd > self.max_drawdown {
+        // Tiny peaks can overflow the relative drawdown.
+        if dd.is_finite() && dd > self.max_drawdown {
             self.max_drawdown = dd;
         }
     }
@@ -73,9 +78,26 @@ where
     }
 }
 
+impl<T, V> Introspect for Drawdown<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Drawdown").child(self.view.node())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::properties::{
+        self,
+        check_bounded,
+        view_properties,
+    };
 
     #[test]
     fn drawdown() {
@@ -91,4 +113,25 @@ mod tests {
         dd.update(87.0);
         assert_eq!(dd.last().unwrap(), 0.20909090909090908);
     }
+
+    #[test]
+    fn drawdown_non_positive_peak() {
+        let mut dd = Drawdown::default();
+        dd.update(0.0);
+        assert_eq!(dd.last().unwrap(), 0.0);
+        dd.update(-1.0);
+        assert_eq!(dd.last().unwrap(), 0.0);
+        dd.update(2.0);
+        dd.update(1.0);
+        assert_eq!(dd.last().unwrap(), 0.5);
+    }
+
+    view_properties!(|_window_len| Drawdown::new(Echo::new()));
+
+    proptest! {
+        #[test]
+        fn drawdown_bounded(vals in properties::series()) {
+            check_bounded(Drawdown::new(Echo::new()), &vals, 0.0, 1.0)?;
+        }
+    }
 }
@@ -1,7 +1,13 @@
 use num::Float;
 
 use crate::{
+    InvalidReason,
+    Status,
     View,
+    introspect::{
+        Introspect,
+        Node,
+    },
     pure_functions::Echo,
 };
 
@@ -10,8 +16,8 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct LnReturn<T, V> {
     view: V,
-    last_val: T,
-    current_val: T,
+    last_val: Option<T>,
+    current_val: Option<T>,
 }
 
 impl<T: Float> Default for LnReturn<T, Echo<T>> {
@@ -29,8 +35,8 @@ where
     pub fn new(view: V) -> Self {
         Self {
             view,
-            last_val: T::zero(),
-            current_val: T::zero(),
+            last_val: None,
+            current_val: None,
         }
     }
 }
@@ -47,30 +53,72 @@ where
         debug_assert!(val.is_finite(), "value must be finite");
 
         self.last_val = self.current_val;
-        self.current_val = val;
+        self.current_val = Some(val);
     }
 
     fn last(&self) -> Option<T> {
-        if self.last_val == T::zero() {
-            return None;
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        let (Some(last_val), Some(current_val)) = (self.last_val, self.current_val) else {
+            return Status::WarmingUp.chained(self.view.status());
+        };
+        // The log return is only defined for consecutive non-zero values of the same sign.
+        if last_val == T::zero() {
+            return Status::Invalid(InvalidReason::ZeroDenominator);
+        }
+        let ratio = current_val / last_val;
+        if ratio <= T::zero() {
+            return Status::Invalid(InvalidReason::NonPositiveRatio);
+        }
+        if !ratio.is_finite() {
+            return Status::Invalid(InvalidReason::Overflow);
         }
 
-        let out = (self.current_val / self.last_val).ln();
+        let out = ratio.ln();
         debug_assert!(out.is_finite(), "value must be finite");
-        Some(out)
+        Status::Ready(out)
+    }
+}
+
+impl<T, V> Introspect for LnReturn<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("LnReturn").child(self.view.node())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::properties::view_properties;
 
     #[test]
     fn ln_return() {
         let mut ln_return = LnReturn::new(Echo::new());
         ln_return.update(100.0);
         assert!(ln_return.last().is_none());
+        assert_eq!(ln_return.status(), Status::WarmingUp);
         ln_return.update(110.0);
         assert_eq!(ln_return.last().unwrap(), 0.09531017980432493);
+        ln_return.update(-110.0);
+        assert!(ln_return.last().is_none());
+        assert_eq!(
+            ln_return.status(),
+            Status::Invalid(InvalidReason::NonPositiveRatio)
+        );
+        ln_return.update(0.0);
+        assert!(ln_return.last().is_none());
+        ln_return.update(1.0);
+        assert_eq!(
+            ln_return.status(),
+            Status::Invalid(InvalidReason::ZeroDenominator)
+        );
     }
+
+    view_properties!(|_window_len| LnReturn::new(Echo::new()));
 }
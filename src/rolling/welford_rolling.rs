@@ -5,7 +5,13 @@ use getset::CopyGetters;
 use num::Float;
 
 use crate::{
+    InvalidReason,
+    Status,
     View,
+    introspect::{
+        Introspect,
+        Node,
+    },
     pure_functions::Echo,
 };
 
@@ -70,12 +76,30 @@ where
     }
 
     fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    /// The squared deviations of values beyond about `1e154` overflow,
+    /// and as all observed values are kept in the statistics, the status stays `InvalidReason::Overflow`.
+    fn status(&self) -> Status<T> {
         if self.n == 0 {
-            return None;
+            return Status::WarmingUp.chained(self.view.status());
         }
         let out = self.variance().sqrt();
-        debug_assert!(out.is_finite(), "value must be finite");
-        Some(out)
+        if !out.is_finite() {
+            return Status::Invalid(InvalidReason::Overflow);
+        }
+        Status::Ready(out)
+    }
+}
+
+impl<T, V> Introspect for WelfordRolling<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("WelfordRolling").child(self.view.node())
     }
 }
 
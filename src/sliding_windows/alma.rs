@@ -9,23 +9,46 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use super::kernel_ma::{
+    GaussianKernel,
+    Kernel,
+    check_offset,
+    check_sigma,
+    normalized_weights,
+    weighted_mean,
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        float_param,
+        window_len_param,
+    },
+};
 
 /// ALMA - Arnaud Legoux Moving Average
 /// reference: <https://forex-station.com/download/file.php?id=3326661&sid=d6b440bfbba5e1905b4c75188c2797ce>
+/// Weights the values by a `GaussianKernel`, and agrees with a `KernelMa` of it once the window is full.
+/// Unlike the `KernelMa`, it outputs while the window fills up, weighting the values like the newest ones of a full window.
 #[derive(Clone, Debug, CopyGetters)]
 pub struct Alma<T, V> {
     view: V,
     /// The configured window length.
     #[getset(get_copy = "pub")]
     window_len: NonZeroUsize,
-    wtd_sum: T,
-    cum_wt: T,
-    m: T,
-    s: T,
+    kernel: GaussianKernel<T>,
+    /// The normalized gaussian weights, from the oldest to the newest value in the window.
+    weights: Vec<T>,
     q_vals: VecDeque<T>,
-    q_wtd: VecDeque<T>,
-    q_out: VecDeque<T>,
+    out: Option<T>,
 }
 
 impl<T, V> Alma<T, V>
@@ -44,23 +67,59 @@ where
         )
     }
 
-    /// Create a Arnaud Legoux Moving Average with custom parameters
+    /// Create a Arnaud Legoux Moving Average with custom parameters,
+    /// where an `offset` outside of `[0, 1]` moves the peak of the weights beyond the window,
+    /// see `try_new_custom` to reject it.
     pub fn new_custom(view: V, window_len: NonZeroUsize, sigma: T, offset: T) -> Self {
-        let wl = T::from(window_len.get()).expect("can convert");
-        let m = offset * (wl + T::one());
-        let s = wl / sigma;
+        let kernel = GaussianKernel::new_custom(sigma, offset);
         Alma {
             view,
             window_len,
-            m,
-            s,
-            wtd_sum: T::zero(),
-            cum_wt: T::zero(),
+            weights: normalized_weights(&kernel, window_len),
+            kernel,
             q_vals: VecDeque::with_capacity(window_len.get()),
-            q_wtd: VecDeque::with_capacity(window_len.get()),
-            q_out: VecDeque::with_capacity(window_len.get()),
+            out: None,
+        }
+    }
+
+    /// Create a Arnaud Legoux Moving Average with custom parameters,
+    /// where `sigma` must be positive and `offset` must be within `[0, 1]`.
+    pub fn try_new_custom(
+        view: V,
+        window_len: NonZeroUsize,
+        sigma: T,
+        offset: T,
+    ) -> Result<Self, Error> {
+        check_sigma(sigma)?;
+        check_offset(offset)?;
+        Ok(Self::new_custom(view, window_len, sigma, offset))
+    }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    /// The weights are recomputed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        self.weights = normalized_weights(&self.kernel, window_len);
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
         }
     }
+
+    /// Set the sigma, which must be positive. The weights are recomputed.
+    pub fn set_sigma(&mut self, sigma: T) -> Result<(), Error> {
+        check_sigma(sigma)?;
+        self.kernel.sigma = sigma;
+        self.weights = normalized_weights(&self.kernel, self.window_len);
+        Ok(())
+    }
+
+    /// Set the offset, which must be within `[0, 1]`. The weights are recomputed.
+    pub fn set_offset(&mut self, offset: T) -> Result<(), Error> {
+        check_offset(offset)?;
+        self.kernel.offset = offset;
+        self.weights = normalized_weights(&self.kernel, self.window_len);
+        Ok(())
+    }
 }
 
 impl<T, V> View<T> for Alma<T, V>
@@ -76,32 +135,63 @@ where
         debug_assert!(val.is_finite(), "value must be finite");
 
         if self.q_vals.len() >= self.window_len.get() {
-            let old_val = self.q_vals.front().unwrap();
-            let old_wtd = self.q_wtd.front().unwrap();
-            self.wtd_sum = self.wtd_sum - *old_wtd * *old_val;
-            self.cum_wt = self.cum_wt - *old_wtd;
-
             self.q_vals.pop_front();
-            self.q_wtd.pop_front();
-            self.q_out.pop_front();
         }
-        let count = T::from(self.q_vals.len()).expect("can convert");
-        let wtd = (-(count - self.m).powi(2)
-            / (T::from(2.0).expect("can convert") * self.s * self.s))
-            .exp();
-        self.wtd_sum = self.wtd_sum + wtd * val;
-        self.cum_wt = self.cum_wt + wtd;
-
         self.q_vals.push_back(val);
-        self.q_wtd.push_back(wtd);
 
-        let ala = self.wtd_sum / self.cum_wt;
+        let ala = weighted_mean(&self.q_vals, &self.weights);
         debug_assert!(ala.is_finite(), "value must be finite");
-        self.q_out.push_back(ala);
+        self.out = Some(ala);
     }
 
     fn last(&self) -> Option<T> {
-        self.q_out.back().copied()
+        self.out
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Alma<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Kernel::<T>::params(&self.kernel)
+            .into_iter()
+            .fold(
+                Node::new("Alma").param("window_len", self.window_len()),
+                |node, (name, val)| node.param(name, val),
+            )
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Alma<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        let mut specs = vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )];
+        specs.extend(Kernel::<T>::param_specs(&self.kernel));
+        specs
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            "sigma" => self.set_sigma(float_param("sigma", val)?)?,
+            "offset" => self.set_offset(float_param("offset", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
     }
 }
 
@@ -115,6 +205,10 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
@@ -133,6 +227,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn alma_huge_values() {
+        let mut alma = Alma::new(Echo::new(), NonZeroUsize::new(3).unwrap());
+        alma.update_batch(&[f64::MAX; 3]);
+        assert!((alma.last().unwrap() / f64::MAX - 1.0).abs() < 1e-12);
+    }
+
     #[test]
     fn alma_plot() {
         let mut alma = Alma::new(Echo::new(), NonZeroUsize::new(16).unwrap());
@@ -144,4 +245,52 @@ mod tests {
         let filename = "img/alma.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| Alma::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Alma::new(Echo::new(), window_len));
+
+    #[test]
+    fn alma_try_new_custom() {
+        let window_len = NonZeroUsize::new(16).unwrap();
+        assert!(Alma::try_new_custom(Echo::new(), window_len, 6.0, 0.85).is_ok());
+        assert_eq!(
+            Alma::try_new_custom(Echo::new(), window_len, 0.0, 0.85).unwrap_err(),
+            Error::AlmaSigma(0.0)
+        );
+        assert_eq!(
+            Alma::try_new_custom(Echo::new(), window_len, 6.0, 1.5).unwrap_err(),
+            Error::AlmaOffset(1.5)
+        );
+    }
+
+    #[test]
+    fn alma_offset_beyond_window() {
+        // The weights underflow, leaving the value nearest to the peak beyond either end of the window.
+        let len = NonZeroUsize::new(3).unwrap();
+        let mut alma = Alma::new_custom(Echo::new(), len, 1000.0, -10.0);
+        assert_eq!(
+            alma.update_batch(&[1.0, 2.0, 3.0, 4.0]),
+            [Some(1.0), Some(1.0), Some(1.0), Some(2.0)]
+        );
+        let mut alma = Alma::new_custom(Echo::new(), len, 1000.0, 10.0);
+        assert_eq!(
+            alma.update_batch(&[1.0, 2.0, 3.0, 4.0]),
+            [Some(1.0), Some(2.0), Some(3.0), Some(4.0)]
+        );
+    }
+
+    #[test]
+    fn alma_set_param() {
+        let len = NonZeroUsize::new(9).unwrap();
+        let mut alma = Alma::new(Echo::<f64>::new(), len);
+        assert_eq!(
+            alma.set_param("offset", ParamValue::Float(1.5)),
+            Err(Error::AlmaOffset(1.5))
+        );
+        alma.set_param("sigma", ParamValue::Float(3.0)).unwrap();
+        alma.set_param("offset", ParamValue::Float(0.5)).unwrap();
+        let fresh = Alma::new_custom(Echo::<f64>::new(), len, 3.0, 0.5);
+        assert_eq!((&alma.weights, alma.kernel), (&fresh.weights, fresh.kernel));
+    }
 }
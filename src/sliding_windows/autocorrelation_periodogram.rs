@@ -0,0 +1,349 @@
+//! John Ehlers Autocorrelation Periodogram
+//! from: "Cycle Analytics for Traders" by John Ehlers, 2013
+
+use std::{
+    collections::VecDeque,
+    f64::consts::PI,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::{
+    RoofingFilter,
+    dual_differentiator::check_periods,
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// The number of value pairs of each correlation.
+const AVG_LEN: usize = 3;
+
+/// The shortest lag of the correlations transformed into the spectrum.
+const MIN_LAG: usize = 3;
+
+/// The decay of the peak power of the automatic gain control per sample.
+const PEAK_DECAY: f64 = 0.995;
+
+/// John Ehlers Autocorrelation Periodogram
+/// from: "Cycle Analytics for Traders" by John Ehlers, 2013
+/// Correlates the last three roofing filtered values with their lagged values for each lag up to `max_period`,
+/// and transforms the correlations into the power of each period within `[min_period, max_period]`.
+/// The power is smoothed and normalized by its decaying peak, an automatic gain control,
+/// so the dominant cycle is the center of gravity of the periods weighted by their power above half of the peak power.
+/// Unlike the original cutoff at half of the peak power, the weights vanish continuously,
+/// so a period crossing the cutoff does not make the dominant cycle jump.
+/// Ready once `max_period + 3` roofing filtered values have been observed.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct AutocorrelationPeriodogram<T, V> {
+    view: V,
+    roofing: RoofingFilter<T, Echo<T>>,
+    /// The shortest period, which is also the cutoff period of the smoothing of the roofing filter.
+    #[getset(get_copy = "pub")]
+    min_period: NonZeroUsize,
+    /// The longest period, which is also the cutoff period of the high-pass of the roofing filter.
+    #[getset(get_copy = "pub")]
+    max_period: NonZeroUsize,
+    // The roofing filtered values, newest first.
+    q_vals: VecDeque<T>,
+    // The cosine and sine of each lag from `MIN_LAG` for each period, starting at `min_period`.
+    trig: Vec<Vec<(T, T)>>,
+    // The smoothed power of each period, starting at `min_period`.
+    power: Vec<T>,
+    peak_power: T,
+    dominant_cycle: T,
+}
+
+impl<T, V> AutocorrelationPeriodogram<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Autocorrelation Periodogram with a chained View
+    /// and the default periods of 10 to 48 samples
+    pub fn new(view: V) -> Self {
+        Self::with_periods(
+            view,
+            NonZeroUsize::new(10).expect("is non-zero"),
+            NonZeroUsize::new(48).expect("is non-zero"),
+        )
+    }
+
+    /// Create a new Autocorrelation Periodogram with a custom range of periods
+    pub fn with_periods(view: V, min_period: NonZeroUsize, max_period: NonZeroUsize) -> Self {
+        Self {
+            view,
+            roofing: RoofingFilter::new(Echo::new(), max_period, min_period),
+            min_period,
+            max_period,
+            q_vals: VecDeque::with_capacity(max_period.get() + AVG_LEN),
+            trig: trig_table(min_period, max_period),
+            power: vec![T::zero(); n_periods(min_period, max_period)],
+            peak_power: T::zero(),
+            dominant_cycle: T::zero(),
+        }
+    }
+
+    /// Create a new Autocorrelation Periodogram with a custom range of periods,
+    /// where `3 <= min_period <= max_period`.
+    pub fn try_with_periods(
+        view: V,
+        min_period: NonZeroUsize,
+        max_period: NonZeroUsize,
+    ) -> Result<Self, Error> {
+        check_periods(min_period, max_period)?;
+        Ok(Self::with_periods(view, min_period, max_period))
+    }
+
+    /// Set the shortest period, which must be within `[3, max_period]`, recomputing the roofing filter.
+    /// The smoothed spectrum restarts from zero.
+    pub fn set_min_period(&mut self, min_period: NonZeroUsize) -> Result<(), Error> {
+        check_periods(min_period, self.max_period)?;
+        self.min_period = min_period;
+        self.roofing.set_super_smoother_len_high_pass(min_period);
+        self.trig = trig_table(min_period, self.max_period);
+        self.reset_spectrum();
+        Ok(())
+    }
+
+    /// Set the longest period, which must be at least `min_period`, recomputing the roofing filter.
+    /// Shrinking it evicts the oldest values, and the smoothed spectrum restarts from zero.
+    pub fn set_max_period(&mut self, max_period: NonZeroUsize) -> Result<(), Error> {
+        check_periods(self.min_period, max_period)?;
+        self.max_period = max_period;
+        self.roofing.set_window_len_low_pass(max_period)?;
+        self.q_vals.truncate(max_period.get() + AVG_LEN);
+        self.trig = trig_table(self.min_period, max_period);
+        self.reset_spectrum();
+        Ok(())
+    }
+
+    fn reset_spectrum(&mut self) {
+        self.power = vec![T::zero(); n_periods(self.min_period, self.max_period)];
+        self.peak_power = T::zero();
+    }
+
+    /// The Pearson correlation of the newest values with the values `lag` samples before.
+    fn correlation(&self, lag: usize) -> T {
+        let xs = self.q_vals.range(..AVG_LEN);
+        let ys = self.q_vals.range(lag..lag + AVG_LEN);
+        let m = T::from(AVG_LEN).expect("can convert");
+        let mean_x = xs.clone().fold(T::zero(), |sum, x| sum + *x) / m;
+        let mean_y = ys.clone().fold(T::zero(), |sum, y| sum + *y) / m;
+        // Summing the deviations from the means avoids the cancellation of nearby values.
+        let (sxx, syy, sxy) = xs.zip(ys).fold(
+            (T::zero(), T::zero(), T::zero()),
+            |(sxx, syy, sxy), (x, y)| {
+                let (dx, dy) = (*x - mean_x, *y - mean_y);
+                (sxx + dx * dx, syy + dy * dy, sxy + dx * dy)
+            },
+        );
+        let denominator = sxx * syy;
+        if denominator > T::zero() {
+            sxy / denominator.sqrt()
+        } else {
+            T::zero()
+        }
+    }
+}
+
+/// The number of periods within `[min_period, max_period]`.
+fn n_periods(min_period: NonZeroUsize, max_period: NonZeroUsize) -> usize {
+    (max_period.get() + 1).saturating_sub(min_period.get())
+}
+
+/// The cosine and sine of the phase of each lag within `[MIN_LAG, max_period]`
+/// for each period within `[min_period, max_period]`.
+fn trig_table<T: Float>(min_period: NonZeroUsize, max_period: NonZeroUsize) -> Vec<Vec<(T, T)>> {
+    (min_period.get()..=max_period.get())
+        .map(|period| {
+            (MIN_LAG..=max_period.get())
+                .map(|lag| {
+                    let angle = 2.0 * PI * lag as f64 / period as f64;
+                    (
+                        T::from(angle.cos()).expect("can convert"),
+                        T::from(angle.sin()).expect("can convert"),
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl<T, V> View<T> for AutocorrelationPeriodogram<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.roofing.update(val);
+        let Some(filt) = self.roofing.last() else {
+            return;
+        };
+        if self.q_vals.len() == self.max_period.get() + AVG_LEN {
+            self.q_vals.pop_back();
+        }
+        self.q_vals.push_front(filt);
+        if self.q_vals.len() < self.max_period.get() + AVG_LEN {
+            return;
+        }
+
+        let correlations: Vec<T> = (MIN_LAG..=self.max_period.get())
+            .map(|lag| self.correlation(lag))
+            .collect();
+        let alpha = T::from(0.2).expect("can convert");
+        let decay = T::from(PEAK_DECAY).expect("can convert");
+        self.peak_power = decay * self.peak_power;
+        for (power, trig) in self.power.iter_mut().zip(&self.trig) {
+            let (cosine_part, sine_part) = correlations.iter().zip(trig).fold(
+                (T::zero(), T::zero()),
+                |(cosine_part, sine_part), (corr, (cos, sin))| {
+                    (cosine_part + *corr * *cos, sine_part + *corr * *sin)
+                },
+            );
+            let sq_sum = cosine_part * cosine_part + sine_part * sine_part;
+            *power = alpha * sq_sum * sq_sum + (T::one() - alpha) * *power;
+            self.peak_power = self.peak_power.max(*power);
+        }
+
+        // The center of gravity of the periods weighted by their power above half of the peak power.
+        if self.peak_power > T::zero() {
+            let half = T::from(0.5).expect("can convert");
+            let (spx, sp) = self.power.iter().enumerate().fold(
+                (T::zero(), T::zero()),
+                |(spx, sp), (i, power)| {
+                    let power = *power / self.peak_power - half;
+                    if power <= T::zero() {
+                        return (spx, sp);
+                    }
+                    let period = T::from(self.min_period.get() + i).expect("can convert");
+                    (spx + period * power, sp + power)
+                },
+            );
+            if sp > T::zero() {
+                self.dominant_cycle = spx / sp;
+            }
+        }
+        self.dominant_cycle = self
+            .dominant_cycle
+            .max(T::from(self.min_period.get()).expect("can convert"))
+            .min(T::from(self.max_period.get()).expect("can convert"));
+        debug_assert!(self.dominant_cycle.is_finite(), "value must be finite");
+    }
+
+    fn last(&self) -> Option<T> {
+        if self.q_vals.len() < self.max_period.get() + AVG_LEN {
+            return None;
+        }
+        Some(self.dominant_cycle)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for AutocorrelationPeriodogram<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("AutocorrelationPeriodogram")
+            .param("min_period", self.min_period())
+            .param("max_period", self.max_period())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for AutocorrelationPeriodogram<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::window_len("min_period", self.min_period.get(), 3),
+            ParamSpec::window_len("max_period", self.max_period.get(), 3),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "min_period" => self.set_min_period(window_len_param("min_period", val)?)?,
+            "max_period" => self.set_max_period(window_len_param("max_period", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::view_properties,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn autocorrelation_periodogram_sine_period() {
+        for period in [15.0, 20.0, 30.0] {
+            let vals = generators::sine(500, period, 1.0, 100.0);
+            let measured = AutocorrelationPeriodogram::new(Echo::new())
+                .update_batch(&vals)
+                .last()
+                .copied()
+                .flatten()
+                .unwrap();
+            assert!(
+                (measured - period).abs() < 0.05 * period,
+                "measured period {measured} of {period}"
+            );
+        }
+    }
+
+    #[test]
+    fn autocorrelation_periodogram_warm_up() {
+        let mut view = AutocorrelationPeriodogram::new(Echo::new());
+        let outs = view.update_batch(&generators::sine(200, 20.0, 1.0, 100.0));
+        // The roofing filter is ready after 59 values.
+        assert_eq!(outs.iter().position(Option::is_some), Some(58 + 48 + 2));
+    }
+
+    #[test]
+    fn autocorrelation_periodogram_plot() {
+        let mut view = AutocorrelationPeriodogram::new(Echo::new());
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/autocorrelation_periodogram.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|_window_len| AutocorrelationPeriodogram::new(Echo::new()));
+}
@@ -0,0 +1,271 @@
+//! Automatic gain control by John Ehlers
+//! from: "Cycle Analytics for Traders" by John Ehlers, 2013
+
+use num::Float;
+
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamSpec,
+        Reconfigure,
+        float_param,
+    },
+};
+
+/// The default decay of the peak per sample.
+const PEAK_DECAY: f64 = 0.991;
+
+/// Automatic gain control by John Ehlers
+/// from: "Cycle Analytics for Traders" by John Ehlers, 2013
+/// Divides the values by their peak magnitude, which decays by `decay` per sample,
+/// so an oscillator, e.g. a `ReFlex` or `TrendFlex`, swings within `[-1, 1]` whatever its amplitude,
+/// while the gain recovers within about `1 / (1 - decay)` samples after a spike.
+/// Ready once the chained view is.
+#[derive(Debug, Clone)]
+pub struct AutomaticGainControl<T, V> {
+    view: V,
+    decay: T,
+    peak: T,
+    val: Option<T>,
+}
+
+impl<T, V> AutomaticGainControl<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new automatic gain control with a chained View and the default decay of 0.991
+    pub fn new(view: V) -> Self {
+        Self::with_decay(view, T::from(PEAK_DECAY).expect("can convert"))
+    }
+
+    /// Create a new automatic gain control with a chained View and a custom decay of the peak
+    pub fn with_decay(view: V, decay: T) -> Self {
+        Self {
+            view,
+            decay,
+            peak: T::zero(),
+            val: None,
+        }
+    }
+
+    /// Create a new automatic gain control with a chained View and a custom decay of the peak,
+    /// which must be within `(0, 1)`.
+    pub fn try_with_decay(view: V, decay: T) -> Result<Self, Error> {
+        check_decay(decay)?;
+        Ok(Self::with_decay(view, decay))
+    }
+
+    /// The decay of the peak per sample.
+    #[inline(always)]
+    pub fn decay(&self) -> T {
+        self.decay
+    }
+
+    /// The current peak magnitude, which the values are divided by.
+    #[inline(always)]
+    pub fn peak(&self) -> T {
+        self.peak
+    }
+
+    /// Set the decay of the peak, which must be within `(0, 1)`.
+    pub fn set_decay(&mut self, decay: T) -> Result<(), Error> {
+        check_decay(decay)?;
+        self.decay = decay;
+        Ok(())
+    }
+}
+
+impl<T, V> View<T> for AutomaticGainControl<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.peak = (self.decay * self.peak).max(val.abs());
+        self.val = Some(val);
+    }
+
+    fn last(&self) -> Option<T> {
+        match self.status() {
+            Status::Invalid(InvalidReason::FlatRange) => Some(T::zero()),
+            status => status.value(),
+        }
+    }
+
+    /// Without any peak, i.e. while all values are zero, the output is zero,
+    /// while its status is `InvalidReason::FlatRange`.
+    fn status(&self) -> Status<T> {
+        let Some(val) = self.val else {
+            return Status::WarmingUp.chained(self.view.status());
+        };
+        if self.peak == T::zero() {
+            return Status::Invalid(InvalidReason::FlatRange);
+        }
+        let out = val / self.peak;
+        debug_assert!(out.is_finite(), "value must be finite");
+        Status::Ready(out)
+    }
+}
+
+impl<T, V> Introspect for AutomaticGainControl<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("AutomaticGainControl")
+            .param("decay", self.decay.to_f64().expect("can convert"))
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for AutomaticGainControl<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::float(
+            "decay",
+            self.decay,
+            Bound::Exclusive(0.0),
+            Bound::Exclusive(1.0),
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "decay" => self.set_decay(float_param("decay", val)?),
+            _ => Err(Error::UnknownParameter),
+        }
+    }
+}
+
+fn check_decay<T: Float>(decay: T) -> Result<(), Error> {
+    if !(decay > T::zero() && decay < T::one()) {
+        return Err(Error::AutomaticGainControlDecay(
+            decay.to_f64().expect("can convert"),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::{
+            self,
+            check_bounded,
+            view_properties,
+        },
+        pure_functions::Echo,
+        sliding_windows::{
+            ReFlex,
+            TrendFlex,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn automatic_gain_control_amplitude() {
+        // The output swings within the bounds, whatever the amplitude of the cycle.
+        for amplitude in [0.01, 1.0, 100.0] {
+            let vals = generators::sine(200, 20.0, amplitude, 0.0);
+            let outs: Vec<f64> = AutomaticGainControl::new(Echo::new())
+                .update_batch(&vals)
+                .into_iter()
+                .flatten()
+                .collect();
+            let max = outs[100..].iter().fold(0.0_f64, |max, out| max.max(*out));
+            let min = outs[100..].iter().fold(0.0_f64, |min, out| min.min(*out));
+            assert!(max > 0.99 && min < -0.99, "{min} {max} of {amplitude}");
+        }
+    }
+
+    #[test]
+    fn automatic_gain_control_decay() {
+        let mut agc = AutomaticGainControl::new(Echo::new());
+        agc.update(10.0);
+        assert_eq!(agc.last(), Some(1.0));
+        // After a spike, the gain recovers as the peak decays.
+        for i in 1..300 {
+            agc.update(1.0);
+            let peak = (10.0 * PEAK_DECAY.powi(i)).max(1.0);
+            assert!((agc.peak() - peak).abs() < 1e-9);
+            assert!((agc.last().unwrap() - 1.0 / peak).abs() < 1e-9);
+        }
+        assert_eq!(agc.last(), Some(1.0));
+
+        let mut agc = AutomaticGainControl::new(Echo::new());
+        assert_eq!(agc.status(), Status::WarmingUp);
+        agc.update_batch(&[0.0; 10]);
+        assert_eq!(agc.status(), Status::Invalid(InvalidReason::FlatRange));
+        assert_eq!(agc.last(), Some(0.0));
+    }
+
+    #[test]
+    fn automatic_gain_control_set_param() {
+        assert_eq!(
+            AutomaticGainControl::<f64, _>::try_with_decay(Echo::new(), 1.0).unwrap_err(),
+            Error::AutomaticGainControlDecay(1.0)
+        );
+        let mut agc = AutomaticGainControl::<f64, _>::new(Echo::new());
+        assert_eq!(
+            agc.set_param("decay", ParamValue::Float(0.0)),
+            Err(Error::AutomaticGainControlDecay(0.0))
+        );
+        agc.set_param("decay", ParamValue::Float(0.9)).unwrap();
+        assert_eq!(agc.decay(), 0.9);
+    }
+
+    #[test]
+    fn automatic_gain_control_plot() {
+        let window_len = NonZeroUsize::new(20).unwrap();
+        let mut view = AutomaticGainControl::new(ReFlex::new(Echo::new(), window_len));
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/automatic_gain_control.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| AutomaticGainControl::new(TrendFlex::new(
+        Echo::new(),
+        window_len
+    )));
+
+    proptest! {
+        #[test]
+        fn automatic_gain_control_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            check_bounded(AutomaticGainControl::new(ReFlex::new(Echo::new(), window_len)), &vals, -1.0, 1.0)?;
+            check_bounded(AutomaticGainControl::new(TrendFlex::new(Echo::new(), window_len)), &vals, -1.0, 1.0)?;
+        }
+    }
+}
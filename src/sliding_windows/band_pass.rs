@@ -0,0 +1,544 @@
+//! Band-pass filter by John Ehlers
+//! from: "Cycle Analytics for Traders" by John Ehlers, 2013,
+//! and "The Bandpass Indicator" by John Ehlers, Stocks & Commodities, 2016
+
+use std::{
+    f64::consts::PI,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::{
+    AutomaticGainControl,
+    OnePoleHighPass,
+};
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamSpec,
+        Reconfigure,
+        float_param,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// Band-pass filter by John Ehlers
+/// from: "Cycle Analytics for Traders" by John Ehlers, 2013,
+/// and "The Bandpass Indicator" by John Ehlers, Stocks & Commodities, 2016
+/// Passes the cycles around the center `period` within the relative `bandwidth`, e.g. 0.3 for 30% of the period,
+/// after a `OnePoleHighPass` whitens the values, i.e. removes the trend.
+/// Besides the filtered values, it computes the amplitude normalized `signal`,
+/// whose peak is tracked by an `AutomaticGainControl`, and its `trigger`, a `OnePoleHighPass` of the signal
+/// leading it, so their crossings anticipate the turning points of the cycle.
+/// The trigger is also available as the `BandPassTrigger` view.
+/// Both high-pass filters have the gain `1 - alpha / 2` of the `OnePoleHighPass`,
+/// where the published code has `1 + alpha / 2`.
+/// Ready after `period + 2` values, i.e. once it has warmed up over one period past the two previous values it needs.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct BandPass<T, V> {
+    view: V,
+    /// The center period of the passed cycles.
+    #[getset(get_copy = "pub")]
+    period: NonZeroUsize,
+    bandwidth: T,
+    // The high-pass filter of the values, whose output is band-pass filtered from the first value on.
+    high_pass: OnePoleHighPass<T, Echo<T>>,
+    // The coefficients of the band-pass filter.
+    beta: T,
+    alpha: T,
+    n_observed: usize,
+    // The last three high-pass filtered values, newest first.
+    hps: [T; 3],
+    // The last two band-pass filtered values, newest first.
+    bps: [T; 2],
+    agc: AutomaticGainControl<T, Echo<T>>,
+    // The high-pass filter of the signal, which starts at zero.
+    trigger: OnePoleHighPass<T, Echo<T>>,
+}
+
+impl<T, V> BandPass<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new band-pass filter with a chained View, the center period and the relative bandwidth
+    pub fn new(view: V, period: NonZeroUsize, bandwidth: T) -> Self {
+        let (hp_cutoff_period, trigger_cutoff_period) = cutoff_periods(period, bandwidth);
+        let (beta, alpha) = coefficients(period, bandwidth);
+        Self {
+            view,
+            period,
+            bandwidth,
+            high_pass: OnePoleHighPass::with_fractional_cutoff_period(
+                Echo::new(),
+                hp_cutoff_period,
+            ),
+            beta,
+            alpha,
+            n_observed: 0,
+            hps: [T::zero(); 3],
+            bps: [T::zero(); 2],
+            agc: AutomaticGainControl::new(Echo::new()),
+            trigger: OnePoleHighPass::with_fractional_cutoff_period(
+                Echo::new(),
+                trigger_cutoff_period,
+            ),
+        }
+    }
+
+    /// Create a new band-pass filter with a chained View,
+    /// where the period must be at least 7 and the bandwidth within `(0, 1]` for the filters to be stable.
+    pub fn try_new(view: V, period: NonZeroUsize, bandwidth: T) -> Result<Self, Error> {
+        check_period(period)?;
+        check_bandwidth(bandwidth)?;
+        Ok(Self::new(view, period, bandwidth))
+    }
+
+    /// Set the center period, which must be at least 7, recomputing the filter coefficients.
+    /// The filter state is kept, while growing it waits for the additional values to be observed.
+    pub fn set_period(&mut self, period: NonZeroUsize) -> Result<(), Error> {
+        check_period(period)?;
+        self.period = period;
+        self.set_coefficients();
+        Ok(())
+    }
+
+    /// Set the relative bandwidth, which must be within `(0, 1]`, recomputing the filter coefficients.
+    pub fn set_bandwidth(&mut self, bandwidth: T) -> Result<(), Error> {
+        check_bandwidth(bandwidth)?;
+        self.bandwidth = bandwidth;
+        self.set_coefficients();
+        Ok(())
+    }
+
+    fn set_coefficients(&mut self) {
+        let (hp_cutoff_period, trigger_cutoff_period) = cutoff_periods(self.period, self.bandwidth);
+        self.high_pass
+            .set_fractional_cutoff_period(hp_cutoff_period);
+        self.trigger
+            .set_fractional_cutoff_period(trigger_cutoff_period);
+        (self.beta, self.alpha) = coefficients(self.period, self.bandwidth);
+    }
+
+    /// The bandwidth relative to the center period.
+    #[inline(always)]
+    pub fn bandwidth(&self) -> T {
+        self.bandwidth
+    }
+
+    /// The latest filtered value normalized by its decaying peak, within `[-1, 1]`, once ready.
+    #[inline(always)]
+    pub fn signal(&self) -> Option<T> {
+        self.last().and(self.agc.last())
+    }
+
+    /// The latest trigger, the high-pass filtered signal, once ready.
+    pub fn trigger(&self) -> Option<T> {
+        self.last().map(|_| self.trigger.hp())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.n_observed > self.period.get() + 1
+    }
+}
+
+/// The center period of a band-pass filter, which must be at least 7,
+/// so the high-pass filter of the signal is stable for any bandwidth.
+fn check_period(period: NonZeroUsize) -> Result<(), Error> {
+    if period.get() < 7 {
+        return Err(Error::BandPassPeriod(period.get()));
+    }
+    Ok(())
+}
+
+fn check_bandwidth<T: Float>(bandwidth: T) -> Result<(), Error> {
+    if !(bandwidth > T::zero() && bandwidth <= T::one()) {
+        return Err(Error::BandPassBandwidth(
+            bandwidth.to_f64().expect("can convert"),
+        ));
+    }
+    Ok(())
+}
+
+/// The cutoff periods of the high-pass filters of the values and the signal,
+/// which are `4 / bandwidth` and `2 / 3 / bandwidth` center periods.
+fn cutoff_periods<T: Float>(period: NonZeroUsize, bandwidth: T) -> (T, T) {
+    let period = T::from(period.get()).expect("can convert");
+    (
+        period / (T::from(0.25).expect("can convert") * bandwidth),
+        period / (T::from(1.5).expect("can convert") * bandwidth),
+    )
+}
+
+/// The coefficients of the band-pass filter.
+fn coefficients<T: Float>(period: NonZeroUsize, bandwidth: T) -> (T, T) {
+    let period = T::from(period.get()).expect("can convert");
+    let angle = T::from(2.0 * PI).expect("can convert") / period;
+    let beta = angle.cos();
+    let gamma = (angle * bandwidth).cos().recip();
+    let alpha = gamma - (gamma * gamma - T::one()).sqrt();
+    (beta, alpha)
+}
+
+impl<T, V> View<T> for BandPass<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        let half = T::from(0.5).expect("can convert");
+        self.high_pass.update(val);
+        let hp = self.high_pass.hp();
+        let [hp_1, hp_2, _] = self.hps;
+        self.hps = [hp, hp_1, hp_2];
+
+        // The band-pass filter needs two previous values.
+        let [bp_1, bp_2] = self.bps;
+        let bp = if self.n_observed < 2 {
+            T::zero()
+        } else {
+            half * (T::one() - self.alpha) * (hp - hp_2)
+                + self.beta * (T::one() + self.alpha) * bp_1
+                - self.alpha * bp_2
+        };
+        self.bps = [bp, bp_1];
+        self.n_observed += 1;
+        if !self.is_ready() || !bp.is_finite() {
+            return;
+        }
+
+        self.agc.update(bp);
+        let signal = self.agc.last().expect("is ready after an update");
+        self.trigger.update(signal);
+    }
+
+    #[inline]
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    /// The filters overshoot their input, so their states overflow for values close to the largest float,
+    /// after which the status stays `InvalidReason::Overflow`.
+    fn status(&self) -> Status<T> {
+        if !self.is_ready() {
+            return Status::WarmingUp.chained(self.view.status());
+        }
+        let [bp, _] = self.bps;
+        if !bp.is_finite() {
+            return Status::Invalid(InvalidReason::Overflow);
+        }
+        Status::Ready(bp)
+    }
+}
+
+impl<T, V> Introspect for BandPass<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("BandPass")
+            .param("period", self.period())
+            .param("bandwidth", self.bandwidth.to_f64().expect("can convert"))
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for BandPass<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::window_len("period", self.period.get(), 7),
+            ParamSpec::float(
+                "bandwidth",
+                self.bandwidth,
+                Bound::Exclusive(0.0),
+                Bound::Inclusive(1.0),
+            ),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "period" => self.set_period(window_len_param("period", val)?),
+            "bandwidth" => self.set_bandwidth(float_param("bandwidth", val)?),
+            _ => Err(Error::UnknownParameter),
+        }
+    }
+}
+
+/// Trigger of the band-pass filter by John Ehlers
+/// from: "The Bandpass Indicator" by John Ehlers, Stocks & Commodities, 2016
+/// The high-pass filtered, amplitude normalized signal of a `BandPass` as a view of its own,
+/// e.g. to compute its difference to the signal, which crosses zero ahead of the turning points of the cycle.
+/// Ready once the `BandPass` is.
+#[derive(Debug, Clone)]
+pub struct BandPassTrigger<T, V> {
+    band_pass: BandPass<T, V>,
+}
+
+impl<T, V> BandPassTrigger<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new band-pass trigger with a chained View, the center period and the relative bandwidth
+    pub fn new(view: V, period: NonZeroUsize, bandwidth: T) -> Self {
+        Self {
+            band_pass: BandPass::new(view, period, bandwidth),
+        }
+    }
+
+    /// Create a new band-pass trigger with a chained View,
+    /// where the period must be at least 7 and the bandwidth within `(0, 1]` for the filters to be stable.
+    pub fn try_new(view: V, period: NonZeroUsize, bandwidth: T) -> Result<Self, Error> {
+        Ok(Self {
+            band_pass: BandPass::try_new(view, period, bandwidth)?,
+        })
+    }
+
+    /// The `BandPass` computing the signal and its trigger.
+    #[inline(always)]
+    pub fn band_pass(&self) -> &BandPass<T, V> {
+        &self.band_pass
+    }
+}
+
+impl<T, V> View<T> for BandPassTrigger<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        self.band_pass.update(val);
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<T> {
+        self.band_pass.trigger()
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.band_pass.status())
+    }
+}
+
+impl<T, V> Introspect for BandPassTrigger<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("BandPassTrigger")
+            .param("period", self.band_pass.period())
+            .param(
+                "bandwidth",
+                self.band_pass.bandwidth.to_f64().expect("can convert"),
+            )
+            .child(self.band_pass.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for BandPassTrigger<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        self.band_pass.param_specs()
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        self.band_pass.set_param(name, val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::{
+            self,
+            check_bounded,
+            view_properties,
+            zero_crossing_period,
+        },
+        test_data::TEST_DATA,
+    };
+
+    fn len(len: usize) -> NonZeroUsize {
+        NonZeroUsize::new(len).unwrap()
+    }
+
+    /// The amplitude of the band-pass filtered sine of `period`, once the filter has settled.
+    fn amplitude(period: f64) -> f64 {
+        let vals = generators::sine(800, period, 1.0, 100.0);
+        BandPass::new(Echo::new(), len(20), 0.3).update_batch(&vals)[400..]
+            .iter()
+            .flatten()
+            .fold(0.0, |max, out| out.abs().max(max))
+    }
+
+    #[test]
+    fn band_pass_passes_center_period() {
+        // The center period passes, while periods outside of the band are attenuated.
+        let center = amplitude(20.0);
+        assert!((center - 1.0).abs() < 0.1, "{center}");
+        for period in [8.0, 60.0] {
+            let out = amplitude(period);
+            assert!(out < 0.35 * center, "{out} at {period}");
+        }
+    }
+
+    #[test]
+    fn band_pass_signal_and_trigger() {
+        let vals = generators::sine(500, 20.0, 5.0, 100.0);
+        let mut band_pass = BandPass::new(Echo::new(), len(20), 0.3);
+        let mut trigger = BandPassTrigger::new(Echo::new(), len(20), 0.3);
+        let (mut signals, mut triggers) = (Vec::new(), Vec::new());
+        for (i, val) in vals.iter().enumerate() {
+            band_pass.update(*val);
+            trigger.update(*val);
+            assert_eq!(trigger.last(), band_pass.trigger());
+            assert_eq!(band_pass.last().is_some(), i >= 21);
+            assert_eq!(band_pass.signal().is_some(), i >= 21);
+            if let (Some(signal), Some(trigger)) = (band_pass.signal(), band_pass.trigger()) {
+                signals.push(signal);
+                triggers.push(trigger);
+            }
+        }
+        // The signal is normalized and swings with the period of the cycle.
+        let signals = &signals[200..];
+        assert!(signals.iter().any(|s| *s > 0.99) && signals.iter().any(|s| *s < -0.99));
+        let measured = zero_crossing_period(signals).unwrap();
+        assert!((measured - 20.0).abs() < 1.0, "{measured}");
+        // The trigger leads the signal by up to a quarter cycle.
+        let distance = |shift: usize| {
+            signals
+                .iter()
+                .zip(&triggers[200 - shift..])
+                .map(|(signal, trigger)| (signal - trigger).abs())
+                .sum::<f64>()
+        };
+        let closest = (0..=10)
+            .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+            .unwrap();
+        assert!(
+            (1..=5).contains(&closest),
+            "closest at a shift of {closest}"
+        );
+    }
+
+    #[test]
+    fn band_pass_set_param() {
+        assert_eq!(
+            BandPass::<f64, _>::try_new(Echo::new(), len(6), 0.3).unwrap_err(),
+            Error::BandPassPeriod(6)
+        );
+        assert_eq!(
+            BandPass::<f64, _>::try_new(Echo::new(), len(20), 1.5).unwrap_err(),
+            Error::BandPassBandwidth(1.5)
+        );
+        let mut view = BandPassTrigger::<f64, _>::new(Echo::new(), len(10), 0.5);
+        assert_eq!(
+            view.set_param("bandwidth", ParamValue::Float(0.0)),
+            Err(Error::BandPassBandwidth(0.0))
+        );
+        view.set_param("period", ParamValue::Int(20)).unwrap();
+        view.set_param("bandwidth", ParamValue::Float(0.3)).unwrap();
+        let mut fresh = BandPassTrigger::<f64, _>::new(Echo::new(), len(20), 0.3);
+        assert_eq!(view.param_specs(), fresh.param_specs());
+        assert_eq!(
+            view.update_batch(&TEST_DATA),
+            fresh.update_batch(&TEST_DATA)
+        );
+
+        // Growing the period waits for the additional values.
+        let mut view = BandPass::<f64, _>::new(Echo::new(), len(10), 0.5);
+        view.update_batch(&TEST_DATA[..12]);
+        assert!(view.last().is_some());
+        view.set_param("period", ParamValue::Int(20)).unwrap();
+        assert_eq!(view.status(), Status::WarmingUp);
+        view.update_batch(&TEST_DATA[12..22]);
+        assert!(view.last().is_some());
+    }
+
+    #[test]
+    fn band_pass_overflow() {
+        let mut view = BandPass::new(Echo::new(), len(7), 1.0);
+        view.update_batch(&[f64::MAX, -f64::MAX].repeat(8));
+        assert_eq!(view.status(), Status::Invalid(InvalidReason::Overflow));
+        assert_eq!((view.signal(), view.trigger()), (None, None));
+        let mut trigger = BandPassTrigger::new(Echo::new(), len(7), 1.0);
+        trigger.update_batch(&[f64::MAX, -f64::MAX].repeat(8));
+        assert_eq!(trigger.status(), Status::Invalid(InvalidReason::Overflow));
+    }
+
+    #[test]
+    fn band_pass_plot() {
+        let mut view = BandPass::new(Echo::new(), len(20), 0.3);
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/band_pass.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    // The filters are only stable for periods of at least 7.
+    mod band_pass {
+        use super::*;
+
+        view_properties!(|window_len| BandPass::new(
+            Echo::new(),
+            window_len.saturating_add(6),
+            0.3
+        ));
+    }
+
+    mod band_pass_trigger {
+        use super::*;
+
+        view_properties!(|window_len| BandPassTrigger::new(
+            Echo::new(),
+            window_len.saturating_add(6),
+            0.3
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn band_pass_signal_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            let view = AutomaticGainControl::new(BandPass::new(Echo::new(), window_len.saturating_add(6), 0.3));
+            check_bounded(view, &vals, -1.0, 1.0)?;
+        }
+    }
+}
@@ -9,7 +9,21 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// Shannon entropy sliding window over values,
 /// where a positive / negative values are interpreted as true / false
@@ -38,6 +52,18 @@ where
             p: 0,
         }
     }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        // The newest value is at the front.
+        while self.q_vals.len() > window_len.get() {
+            let old_val = self.q_vals.pop_back().expect("is not empty");
+            if old_val >= T::zero() {
+                self.p -= 1;
+            }
+        }
+    }
 }
 
 impl<T, V> View<T> for BinaryEntropy<T, V>
@@ -79,12 +105,56 @@ where
         }
         Some(-value)
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for BinaryEntropy<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("BinaryEntropy")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for BinaryEntropy<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::pure_functions::Echo;
+    use crate::{
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
+        pure_functions::Echo,
+    };
 
     #[test]
     fn binary_entropy() {
@@ -114,4 +184,8 @@ mod tests {
         let last = e.last().unwrap();
         assert_eq!(last, 1.0);
     }
+
+    view_properties!(|window_len| BinaryEntropy::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| BinaryEntropy::new(Echo::new(), window_len));
 }
@@ -9,7 +9,22 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// John Ehlers Center of Gravity Indicator
 /// from: <https://mesasoftware.com/papers/TheCGOscillator.pdf>
@@ -20,7 +35,7 @@ pub struct CenterOfGravity<T, V> {
     #[getset(get_copy = "pub")]
     window_len: NonZeroUsize,
     q_vals: VecDeque<T>,
-    out: Option<T>,
+    out: Status<T>,
 }
 
 impl<T, V> CenterOfGravity<T, V>
@@ -35,7 +50,15 @@ where
             view,
             window_len,
             q_vals: VecDeque::with_capacity(window_len.get()),
-            out: None,
+            out: Status::WarmingUp,
+        }
+    }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
         }
     }
 }
@@ -70,16 +93,59 @@ where
                 + (T::from(q_len).expect("can convert") + T::one())
                     / T::from(2.0).expect("can convert");
 
-            debug_assert!(out.is_finite(), "value must be finite");
-            self.out = Some(out);
+            // The weighted sum of large values overflows, as does the quotient for tiny sums.
+            self.out = if out.is_finite() {
+                Status::Ready(out)
+            } else {
+                Status::Invalid(InvalidReason::Overflow)
+            };
         } else {
-            self.out = Some(T::zero());
+            self.out = Status::Ready(T::zero());
         }
     }
 
     #[inline(always)]
     fn last(&self) -> Option<T> {
-        self.out
+        self.out.value()
+    }
+
+    #[inline(always)]
+    fn status(&self) -> Status<T> {
+        self.out.chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for CenterOfGravity<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("CenterOfGravity")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for CenterOfGravity<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
     }
 }
 
@@ -88,6 +154,10 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
@@ -103,4 +173,8 @@ mod tests {
         let filename = "img/center_of_gravity.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| CenterOfGravity::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| CenterOfGravity::new(Echo::new(), window_len));
 }
@@ -0,0 +1,223 @@
+//! CMO - Chande Momentum Oscillator
+//! from: "The New Technical Trader" by Tushar Chande and Stanley Kroll, 1994
+
+use std::{
+    collections::VecDeque,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
+
+/// CMO - Chande Momentum Oscillator
+/// from: "The New Technical Trader" by Tushar Chande and Stanley Kroll, 1994
+/// The difference of the summed gains and losses over the window relative to their total,
+/// within `[-1, 1]` instead of Chande's percentage, so its absolute value can drive a `Vidya` directly.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct Cmo<T, V> {
+    view: V,
+    /// The number of changes in the window.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    q_vals: VecDeque<T>,
+    out: Option<T>,
+}
+
+impl<T, V> Cmo<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Chande Momentum Oscillator with a chained View
+    /// and a given sliding window length
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self {
+            view,
+            window_len,
+            q_vals: VecDeque::with_capacity(window_len.get() + 1),
+            out: None,
+        }
+    }
+
+    /// Set the window length, where shrinking evicts the oldest changes.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() + 1 {
+            self.q_vals.pop_front();
+        }
+    }
+}
+
+impl<T, V> View<T> for Cmo<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        if self.q_vals.len() > self.window_len.get() {
+            self.q_vals.pop_front();
+        }
+        self.q_vals.push_back(val);
+
+        // `window_len` changes require one more value.
+        if self.q_vals.len() <= self.window_len.get() {
+            return;
+        }
+
+        // Summing the changes over the window, instead of keeping running sums,
+        // prevents rounding errors from accumulating in flat markets.
+        // The changes are of the values divided by `scale`, which doesn't change the ratio.
+        let sums = |scale: T| {
+            self.q_vals.iter().zip(self.q_vals.iter().skip(1)).fold(
+                (T::zero(), T::zero()),
+                |(gain, loss), (prev, val)| {
+                    let change = *val / scale - *prev / scale;
+                    if change > T::zero() {
+                        (gain + change, loss)
+                    } else {
+                        (gain, loss - change)
+                    }
+                },
+            )
+        };
+        let (mut gain, mut loss) = sums(T::one());
+        if !(gain + loss).is_finite() {
+            // The changes of values close to the largest float overflow, as do their sums,
+            // unlike for the values divided by twice the number of changes.
+            let n = T::from(self.window_len.get()).expect("can convert");
+            (gain, loss) = sums(T::from(2.0).expect("can convert") * n);
+        }
+
+        // A flat window has no momentum.
+        let cmo = if gain + loss > T::zero() {
+            (gain - loss) / (gain + loss)
+        } else {
+            T::zero()
+        };
+        debug_assert!(cmo.is_finite(), "value must be finite");
+        self.out = Some(cmo);
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<T> {
+        self.out
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Cmo<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Cmo")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Cmo<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        properties::{
+            self,
+            check_bounded,
+            reconfigure_properties,
+            view_properties,
+        },
+        pure_functions::Echo,
+        sliding_windows::Rsi,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn cmo_rsi() {
+        // The CMO is a rescaled RSI.
+        let window_len = NonZeroUsize::new(14).unwrap();
+        let mut cmo = Cmo::new(Echo::new(), window_len);
+        let mut rsi = Rsi::new(Echo::new(), window_len);
+        for (cmo, rsi) in cmo
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .zip(rsi.update_batch(&TEST_DATA))
+        {
+            match (cmo, rsi) {
+                (Some(cmo), Some(rsi)) => assert!((cmo - (rsi / 50.0 - 1.0)).abs() < 1e-9),
+                (cmo, rsi) => assert_eq!(cmo, rsi),
+            }
+        }
+    }
+
+    #[test]
+    fn cmo_huge_values() {
+        let mut cmo = Cmo::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        cmo.update_batch(&[-f64::MAX, f64::MAX, f64::MAX]);
+        assert_eq!(cmo.last(), Some(1.0));
+        cmo.update(-f64::MAX);
+        assert_eq!(cmo.last(), Some(-1.0));
+    }
+
+    view_properties!(|window_len| Cmo::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Cmo::new(Echo::new(), window_len));
+
+    proptest! {
+        #[test]
+        fn cmo_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            check_bounded(Cmo::new(Echo::new(), window_len), &vals, -1.0, 1.0)?;
+        }
+    }
+}
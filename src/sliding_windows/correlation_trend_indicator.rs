@@ -9,7 +9,21 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// John Ehlers Correlation Trend Indicator
 /// from: <https://financial-hacker.com/petra-on-programming-a-unique-trend-indicator/>
@@ -37,6 +51,14 @@ where
             q_vals: VecDeque::with_capacity(window_len.get()),
         }
     }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
+        }
+    }
 }
 
 impl<T, V> View<T> for CorrelationTrendIndicator<T, V>
@@ -63,30 +85,92 @@ where
         let mut sxy = T::zero();
         let mut syy = T::zero();
 
+        // The correlation does not depend on the level and scale of the values,
+        // so shifting them by the oldest value avoids cancellation in the sums of squares,
+        // and scaling them by their largest deviation keeps the sums of large and tiny values finite and normal.
+        // Halving the values first keeps their deviations finite.
+        let two = T::from(2.0).expect("can convert");
+        let shift = *self.q_vals.front()? / two;
+        let scale = self
+            .q_vals
+            .iter()
+            .fold(T::zero(), |max, v| max.max((*v / two - shift).abs()));
+        if scale == T::zero() {
+            return Some(T::zero());
+        }
         for (i, v) in self.q_vals.iter().enumerate() {
             let count = T::from(i).expect("can convert");
-            sx = sx + *v;
+            let v = (*v / two - shift) / scale;
+            sx = sx + v;
             sy = sy + count;
             sxx = sxx + v.powi(2);
-            sxy = sxy + *v * count;
+            sxy = sxy + v * count;
             syy = syy + count.powi(2);
         }
-        let window_len = T::from(self.window_len.get()).expect("Can convert");
-        if window_len * sxx - sx.powi(2) > T::zero() && window_len * syy - sy.powi(2) > T::zero() {
-            let out = (window_len * sxy - sx * sy)
-                / ((window_len * sxx - sx.powi(2)) * (window_len * syy - sy.powi(2))).sqrt();
+        // Use the number of observed values, as the window may not be filled yet.
+        let n = T::from(self.q_vals.len()).expect("Can convert");
+        if n * sxx - sx.powi(2) > T::zero() && n * syy - sy.powi(2) > T::zero() {
+            let out =
+                (n * sxy - sx * sy) / ((n * sxx - sx.powi(2)) * (n * syy - sy.powi(2))).sqrt();
             debug_assert!(out.is_finite(), "value must be finite");
-            return Some(out);
+            // Guard against rounding errors of perfectly correlated values.
+            return Some(out.clamp(-T::one(), T::one()));
         }
         Some(T::zero())
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for CorrelationTrendIndicator<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("CorrelationTrendIndicator")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for CorrelationTrendIndicator<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            self,
+            check_bounded,
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
@@ -103,6 +187,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn correlation_trend_indicator_extreme_values() {
+        let window_len = NonZeroUsize::new(3).unwrap();
+        let mut cti = CorrelationTrendIndicator::new(Echo::new(), window_len);
+        cti.update_batch(&[-f64::MAX, 0.0, f64::MAX]);
+        assert!((cti.last().unwrap() - 1.0).abs() < 1e-12);
+        let tiny = f64::from_bits(1);
+        cti.update_batch(&[3.0 * tiny, 2.0 * tiny, tiny]);
+        assert!((cti.last().unwrap() + 1.0).abs() < 1e-12);
+    }
+
     #[test]
     fn correlation_trend_indicator_plot() {
         let mut cti = CorrelationTrendIndicator::new(Echo::new(), NonZeroUsize::new(16).unwrap());
@@ -118,4 +213,18 @@ mod tests {
         let filename = "./img/correlation_trend_indicator.png";
         plot_values(outs, filename).unwrap();
     }
+
+    view_properties!(|window_len| CorrelationTrendIndicator::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| CorrelationTrendIndicator::new(Echo::new(), window_len));
+
+    proptest! {
+        #[test]
+        fn correlation_trend_indicator_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            check_bounded(CorrelationTrendIndicator::new(Echo::new(), window_len), &vals, -1.0, 1.0)?;
+        }
+    }
 }
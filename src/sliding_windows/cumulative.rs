@@ -8,7 +8,22 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// Cumulative Sliding Window with a chained view
 #[derive(Debug, Clone, CopyGetters)]
@@ -19,6 +34,8 @@ pub struct Cumulative<T, V> {
     window_len: NonZeroUsize,
     q_vals: VecDeque<T>,
     out: Option<T>,
+    /// The number of updates until the sum is re-computed from the window.
+    n_until_recompute: usize,
 }
 
 impl<T, V> Cumulative<T, V>
@@ -33,6 +50,25 @@ where
             window_len,
             q_vals: VecDeque::with_capacity(window_len.get()),
             out: None,
+            n_until_recompute: window_len.get(),
+        }
+    }
+
+    /// Removing values accumulates rounding errors, which are as large as the largest value in the window,
+    /// so re-compute the sum from the window once every `window_len` updates, keeping the cost amortized constant.
+    fn recompute_sum(&mut self) {
+        self.out = Some(self.q_vals.iter().fold(T::zero(), |sum, val| sum + *val));
+    }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
+        }
+        self.n_until_recompute = window_len.get();
+        if self.out.is_some() {
+            self.recompute_sum();
         }
     }
 }
@@ -49,7 +85,7 @@ where
         debug_assert!(val.is_finite(), "value must be finite");
 
         if self.out.is_none() {
-            self.out = Some(val);
+            self.out = Some(T::zero());
         }
 
         if self.q_vals.len() >= self.window_len.get() {
@@ -60,12 +96,61 @@ where
         self.q_vals.push_back(val);
         let out = self.out.as_mut().expect("Is some at this point");
         *out = *out + val;
-        debug_assert!(out.is_finite(), "value must be finite");
+
+        // An overflowed sum can't subtract the evicted values, so it's re-computed until it fits again.
+        let overflowed = !out.is_finite();
+        self.n_until_recompute -= 1;
+        if self.n_until_recompute == 0 || overflowed {
+            self.n_until_recompute = self.window_len.get();
+            self.recompute_sum();
+        }
     }
 
     #[inline(always)]
     fn last(&self) -> Option<T> {
-        self.out
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        match self.out {
+            None => Status::WarmingUp.chained(self.view.status()),
+            Some(out) if !out.is_finite() => Status::Invalid(InvalidReason::Overflow),
+            Some(out) => Status::Ready(out),
+        }
+    }
+}
+
+impl<T, V> Introspect for Cumulative<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Cumulative")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Cumulative<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
     }
 }
 
@@ -74,10 +159,26 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
 
+    #[test]
+    fn cumulative_overflow() {
+        let mut cum = Cumulative::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        cum.update_batch(&[f64::MAX, f64::MAX]);
+        assert_eq!(cum.status(), Status::Invalid(InvalidReason::Overflow));
+        cum.update(1.0);
+        assert_eq!(cum.status(), Status::Ready(f64::MAX));
+        // The rounding error of evicting the largest float is gone after the next re-computation.
+        cum.update_batch(&[2.0, 3.0]);
+        assert_eq!(cum.status(), Status::Ready(5.0));
+    }
+
     #[test]
     fn cumulative_plot() {
         let mut cum = Cumulative::new(Echo::new(), NonZeroUsize::new(TEST_DATA.len()).unwrap());
@@ -89,4 +190,8 @@ mod tests {
         let filename = "img/cumulative.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| Cumulative::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Cumulative::new(Echo::new(), window_len));
 }
@@ -1,18 +1,32 @@
 //! John Ehlers Cyber Cycle Indicator
 //! from: <https://www.mesasoftware.com/papers/TheInverseFisherTransform.pdf>
 
-use std::{
-    collections::VecDeque,
-    num::NonZeroUsize,
-};
+use std::num::NonZeroUsize;
 
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// John Ehlers Cyber Cycle Indicator
 /// from: <https://www.mesasoftware.com/papers/TheInverseFisherTransform.pdf>
+/// The window length determines the smoothing factor `alpha = 2 / (window_len + 1)`.
+/// The paper sharpens its turning points with the `InverseFisherTransform`.
 #[derive(Clone, Debug, CopyGetters)]
 pub struct CyberCycle<T, V> {
     view: V,
@@ -20,10 +34,13 @@ pub struct CyberCycle<T, V> {
     #[getset(get_copy = "pub")]
     window_len: NonZeroUsize,
     alpha: T,
-    vals: VecDeque<T>,
-    out: VecDeque<T>,
-    // avoid allocation in `update` step by re-using this buffer.
-    smooth: Vec<T>,
+    n_observed: usize,
+    // The last four values, newest first.
+    vals: [T; 4],
+    // The last three smoothed values, newest first.
+    smooth: [T; 3],
+    // The last two cycle values, newest first.
+    cycles: [T; 2],
 }
 
 impl<T, V> CyberCycle<T, V>
@@ -38,13 +55,19 @@ where
         CyberCycle {
             view,
             window_len,
-            alpha: T::from(2.0).expect("can convert")
-                / (T::from(window_len.get()).expect("can convert") + T::one()),
-            vals: VecDeque::with_capacity(window_len.get()),
-            out: VecDeque::with_capacity(window_len.get()),
-            smooth: vec![T::zero(); window_len.get()],
+            alpha: smoothing_factor(window_len),
+            n_observed: 0,
+            vals: [T::zero(); 4],
+            smooth: [T::zero(); 3],
+            cycles: [T::zero(); 2],
         }
     }
+
+    /// Set the window length, recomputing the smoothing factor `alpha`.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        self.alpha = smoothing_factor(window_len);
+    }
 }
 
 impl<T, V> View<T> for CyberCycle<T, V>
@@ -58,54 +81,106 @@ where
         let Some(val) = self.view.last() else { return };
         debug_assert!(val.is_finite(), "value must be finite");
 
-        if self.vals.len() >= self.window_len.get() {
-            self.vals.pop_front();
-            self.out.pop_front();
-        }
-        self.vals.push_back(val);
+        self.n_observed += 1;
+        let [v1, v2, v3, _] = self.vals;
+        self.vals = [val, v1, v2, v3];
 
-        if self.vals.len() < self.window_len.get() {
-            self.out.push_back(T::zero());
-            return;
-        }
-        let last = self.vals.len() - 1;
         let two = T::from(2.0).expect("can convert");
-        for (i, v) in self
-            .smooth
-            .iter_mut()
-            .enumerate()
-            .take(self.vals.len())
-            .skip(3)
-        {
-            *v = (val
-                + two * *self.vals.get(i - 1).unwrap()
-                + two * *self.vals.get(i - 2).unwrap()
-                + *self.vals.get(i - 3).unwrap())
-                / T::from(6.0).expect("can convert")
-        }
-        let cc = (T::one() - T::from(0.5).expect("can convert") * self.alpha).powi(2)
-            * (self.smooth[last] - two * self.smooth[last - 1] + self.smooth[last - 2])
-            + two * (T::one() - self.alpha) * *self.out.get(last - 1).unwrap()
-            - (T::one() - self.alpha).powi(2) * *self.out.get(last - 2).unwrap();
-        debug_assert!(cc.is_finite(), "value must be finite");
-        self.out.push_back(cc);
+        let smooth = (val + two * v1 + two * v2 + v3) / T::from(6.0).expect("can convert");
+        let [smooth_1, smooth_2, _] = self.smooth;
+        self.smooth = [smooth, smooth_1, smooth_2];
+
+        let [cycle_1, cycle_2] = self.cycles;
+        let cycle = if self.n_observed < 3 {
+            T::zero()
+        } else if self.n_observed < 7 {
+            // The warm-up of the original formula, until there are enough smoothed values.
+            (val - two * v1 + v2) / T::from(4.0).expect("can convert")
+        } else {
+            (T::one() - T::from(0.5).expect("can convert") * self.alpha).powi(2)
+                * (smooth - two * smooth_1 + smooth_2)
+                + two * (T::one() - self.alpha) * cycle_1
+                - (T::one() - self.alpha).powi(2) * cycle_2
+        };
+        self.cycles = [cycle, cycle_1];
     }
 
     #[inline(always)]
     fn last(&self) -> Option<T> {
-        self.out.back().copied()
+        self.status().value()
+    }
+
+    /// The filter overshoots its input, so its state overflows for values close to the largest float,
+    /// after which the status stays `InvalidReason::Overflow`.
+    fn status(&self) -> Status<T> {
+        if self.n_observed == 0 {
+            return Status::WarmingUp.chained(self.view.status());
+        }
+        if !self.cycles[0].is_finite() {
+            return Status::Invalid(InvalidReason::Overflow);
+        }
+        Status::Ready(self.cycles[0])
+    }
+}
+
+impl<T, V> Introspect for CyberCycle<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("CyberCycle")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for CyberCycle<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
     }
 }
 
+/// The smoothing factor `2 / (window_len + 1)`.
+fn smoothing_factor<T: Float>(window_len: NonZeroUsize) -> T {
+    T::from(2.0).expect("can convert")
+        / (T::from(window_len.get()).expect("can convert") + T::one())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
+        generators,
         plot::plot_values,
+        properties::view_properties,
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
 
+    #[test]
+    fn cyber_cycle_overflow() {
+        let mut view = CyberCycle::new(Echo::new(), NonZeroUsize::new(1).unwrap());
+        view.update_batch(&[f64::MAX, -f64::MAX, f64::MAX]);
+        assert_eq!(view.status(), Status::Invalid(InvalidReason::Overflow));
+    }
+
     #[test]
     fn cyber_cycle_plot() {
         let mut cc = CyberCycle::new(Echo::new(), NonZeroUsize::new(16).unwrap());
@@ -117,4 +192,51 @@ mod tests {
         let filename = "img/cyber_cycle.png";
         plot_values(out, filename).unwrap();
     }
+
+    #[test]
+    fn cyber_cycle_response() {
+        let amplitude = |vals: &[f64]| {
+            let outs =
+                CyberCycle::new(Echo::new(), NonZeroUsize::new(16).unwrap()).update_batch(vals);
+            // Ready from the first value.
+            assert!(outs[0].is_some());
+            outs[500..]
+                .iter()
+                .flatten()
+                .fold(0.0_f64, |max, out| out.abs().max(max))
+        };
+        // Passes cycles around the window length and attenuates longer ones.
+        let passed = amplitude(&generators::sine(1000, 20.0, 1.0, 100.0));
+        let attenuated = amplitude(&generators::sine(1000, 80.0, 1.0, 100.0));
+        assert!(passed > 0.7, "amplitude {passed} at period 20");
+        assert!(
+            attenuated < 0.5 * passed,
+            "amplitude {attenuated} at period 80"
+        );
+        // Removes the offset and a linear trend.
+        let trending: Vec<f64> = generators::sine(1000, 20.0, 1.0, 100.0)
+            .iter()
+            .enumerate()
+            .map(|(i, val)| val + 0.05 * i as f64)
+            .collect();
+        let outs: Vec<f64> = CyberCycle::new(Echo::new(), NonZeroUsize::new(16).unwrap())
+            .update_batch(&trending)
+            .into_iter()
+            .skip(500)
+            .flatten()
+            .collect();
+        let mean = outs.iter().sum::<f64>() / outs.len() as f64;
+        assert!(mean.abs() < 1e-6, "mean {mean}");
+    }
+
+    view_properties!(|window_len| CyberCycle::new(Echo::new(), window_len));
+
+    #[test]
+    fn cyber_cycle_set_window_len() {
+        let mut view = CyberCycle::new(Echo::<f64>::new(), NonZeroUsize::new(16).unwrap());
+        view.set_param("window_len", ParamValue::Int(8)).unwrap();
+        let fresh = CyberCycle::new(Echo::<f64>::new(), NonZeroUsize::new(8).unwrap());
+        assert_eq!((view.alpha), (fresh.alpha));
+        assert_eq!(view.param_specs()[0].value, ParamValue::Int(8));
+    }
 }
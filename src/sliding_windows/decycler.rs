@@ -0,0 +1,455 @@
+//! Decycler and Decycler Oscillator by John Ehlers
+//! from: "Cycle Analytics for Traders" by John Ehlers, 2013
+
+use std::num::NonZeroUsize;
+
+use num::Float;
+
+use super::{
+    OnePoleHighPass,
+    TwoPoleHighPass,
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// Decycler by John Ehlers
+/// from: "Cycle Analytics for Traders" by John Ehlers, 2013
+/// The values minus their high-pass filtered values, which leaves the trend with little lag,
+/// as the cycles shorter than the cutoff period are removed.
+/// By default, the high-pass filter is a `OnePoleHighPass`,
+/// while `with_high_pass` takes any high-pass filter chained on an `Echo`,
+/// e.g. a `TwoPoleHighPass`, whose decycler follows a linear trend without lag.
+/// Ready once the high-pass filter is.
+#[derive(Debug, Clone)]
+pub struct Decycler<T, V, H = OnePoleHighPass<T, Echo<T>>> {
+    view: V,
+    high_pass: H,
+    val: T,
+}
+
+impl<T, V> Decycler<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Decycler with a chained View and the cutoff period of its 1-pole high-pass filter
+    pub fn new(view: V, cutoff_period: NonZeroUsize) -> Self {
+        Self::with_high_pass(view, OnePoleHighPass::new(Echo::new(), cutoff_period))
+    }
+
+    /// Create a new Decycler with a chained View,
+    /// where the cutoff period must be at least 5 for the high-pass filter to be stable.
+    pub fn try_new(view: V, cutoff_period: NonZeroUsize) -> Result<Self, Error> {
+        Ok(Self::with_high_pass(
+            view,
+            OnePoleHighPass::try_new(Echo::new(), cutoff_period)?,
+        ))
+    }
+
+    /// The cutoff period of the high-pass filter.
+    #[inline(always)]
+    pub fn cutoff_period(&self) -> NonZeroUsize {
+        self.high_pass.cutoff_period()
+    }
+
+    /// Set the cutoff period of the high-pass filter, which must be at least 5.
+    pub fn set_cutoff_period(&mut self, cutoff_period: NonZeroUsize) -> Result<(), Error> {
+        self.high_pass.set_cutoff_period(cutoff_period)
+    }
+}
+
+impl<T, V, H> Decycler<T, V, H>
+where
+    V: View<T>,
+    H: View<T>,
+    T: Float,
+{
+    /// Create a new Decycler with a chained View from any high-pass filter,
+    /// which is expected to be chained on an `Echo`, as it observes the values passed to it.
+    pub fn with_high_pass(view: V, high_pass: H) -> Self {
+        Self {
+            view,
+            high_pass,
+            val: T::zero(),
+        }
+    }
+
+    /// The high-pass filter, whose output is subtracted from the values.
+    #[inline(always)]
+    pub fn high_pass(&self) -> &H {
+        &self.high_pass
+    }
+}
+
+impl<T, V, H> View<T> for Decycler<T, V, H>
+where
+    V: View<T>,
+    H: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.high_pass.update(val);
+        self.val = val;
+    }
+
+    #[inline]
+    fn last(&self) -> Option<T> {
+        let hp = self.high_pass.last()?;
+        let out = self.val - hp;
+        debug_assert!(out.is_finite(), "value must be finite");
+        Some(out)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V, H> Introspect for Decycler<T, V, H>
+where
+    V: Introspect,
+    H: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Decycler")
+            .child(self.view.node())
+            .child(self.high_pass.node())
+    }
+}
+
+impl<T, V> Reconfigure for Decycler<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "cutoff_period",
+            self.cutoff_period().get(),
+            5,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "cutoff_period" => self.set_cutoff_period(window_len_param("cutoff_period", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+/// Decycler Oscillator by John Ehlers
+/// from: "Cycle Analytics for Traders" by John Ehlers, 2013
+/// The difference of two decyclers, with the 2-pole high-pass filters of `fast_period` and `slow_period`,
+/// i.e. the slow minus the fast high-pass filtered values.
+/// This passes the cycles between both cutoff periods, so it is positive while the trend rises
+/// and crosses zero as the trend reverses, while a linear trend is removed completely.
+/// Ready once both high-pass filters are.
+#[derive(Debug, Clone)]
+pub struct DecyclerOscillator<T, V> {
+    view: V,
+    fast: TwoPoleHighPass<T, Echo<T>>,
+    slow: TwoPoleHighPass<T, Echo<T>>,
+}
+
+impl<T, V> DecyclerOscillator<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Decycler Oscillator with a chained View and the cutoff periods of its decyclers,
+    /// e.g. 30 and 60
+    pub fn new(view: V, fast_period: NonZeroUsize, slow_period: NonZeroUsize) -> Self {
+        Self {
+            view,
+            fast: TwoPoleHighPass::new(Echo::new(), fast_period),
+            slow: TwoPoleHighPass::new(Echo::new(), slow_period),
+        }
+    }
+
+    /// Create a new Decycler Oscillator with a chained View,
+    /// where the cutoff periods must be at least 3 for the high-pass filters to be stable.
+    pub fn try_new(
+        view: V,
+        fast_period: NonZeroUsize,
+        slow_period: NonZeroUsize,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            view,
+            fast: TwoPoleHighPass::try_new(Echo::new(), fast_period)?,
+            slow: TwoPoleHighPass::try_new(Echo::new(), slow_period)?,
+        })
+    }
+
+    /// The cutoff period of the fast decycler.
+    #[inline(always)]
+    pub fn fast_period(&self) -> NonZeroUsize {
+        self.fast.cutoff_period()
+    }
+
+    /// The cutoff period of the slow decycler.
+    #[inline(always)]
+    pub fn slow_period(&self) -> NonZeroUsize {
+        self.slow.cutoff_period()
+    }
+
+    /// Set the cutoff period of the fast decycler, which must be at least 3.
+    pub fn set_fast_period(&mut self, fast_period: NonZeroUsize) -> Result<(), Error> {
+        self.fast.set_cutoff_period(fast_period)
+    }
+
+    /// Set the cutoff period of the slow decycler, which must be at least 3.
+    pub fn set_slow_period(&mut self, slow_period: NonZeroUsize) -> Result<(), Error> {
+        self.slow.set_cutoff_period(slow_period)
+    }
+}
+
+impl<T, V> View<T> for DecyclerOscillator<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.fast.update(val);
+        self.slow.update(val);
+    }
+
+    #[inline]
+    fn last(&self) -> Option<T> {
+        let out = self.slow.last()? - self.fast.last()?;
+        debug_assert!(out.is_finite(), "value must be finite");
+        Some(out)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for DecyclerOscillator<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("DecyclerOscillator")
+            .param("fast_period", self.fast.cutoff_period())
+            .param("slow_period", self.slow.cutoff_period())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for DecyclerOscillator<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::window_len("fast_period", self.fast_period().get(), 3),
+            ParamSpec::window_len("slow_period", self.slow_period().get(), 3),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "fast_period" => self.set_fast_period(window_len_param("fast_period", val)?)?,
+            "slow_period" => self.set_slow_period(window_len_param("slow_period", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::view_properties,
+        test_data::TEST_DATA,
+    };
+
+    fn len(len: usize) -> NonZeroUsize {
+        NonZeroUsize::new(len).unwrap()
+    }
+
+    /// The largest deviation of the output from `center`, once the view has settled.
+    fn deviation<V: View<f64>>(mut view: V, vals: &[f64], center: f64) -> f64 {
+        view.update_batch(vals)[300..]
+            .iter()
+            .flatten()
+            .fold(0.0, |max, out| (out - center).abs().max(max))
+    }
+
+    #[test]
+    fn decycler_removes_cycles() {
+        // Cycles well below the cutoff period are removed, leaving their offset.
+        let vals = generators::sine(600, 10.0, 1.0, 100.0);
+        let outs = Decycler::new(Echo::new(), len(60)).update_batch(&vals);
+        assert_eq!(outs.iter().position(Option::is_some), Some(60));
+        let dev = deviation(Decycler::new(Echo::new(), len(60)), &vals, 100.0);
+        assert!(dev < 0.2, "{dev}");
+        // while cycles well above it pass.
+        let vals = generators::sine(600, 200.0, 1.0, 100.0);
+        let dev = deviation(Decycler::new(Echo::new(), len(60)), &vals, 100.0);
+        assert!(dev > 0.9, "{dev}");
+    }
+
+    #[test]
+    fn decycler_trend() {
+        let trend: Vec<f64> = (0..300).map(|i| 100.0 + 0.5 * i as f64).collect();
+        // The 1-pole decycler lags the trend by a constant,
+        let outs = Decycler::new(Echo::new(), len(20)).update_batch(&trend);
+        let lags: Vec<f64> = trend[200..]
+            .iter()
+            .zip(&outs[200..])
+            .map(|(val, out)| val - out.unwrap())
+            .collect();
+        assert!(
+            lags.iter()
+                .all(|lag| *lag > 0.0 && (lag - lags[0]).abs() < 1e-9)
+        );
+        // while the 2-pole decycler follows it without lag.
+        let outs =
+            Decycler::with_high_pass(Echo::new(), TwoPoleHighPass::new(Echo::new(), len(20)))
+                .update_batch(&trend);
+        for (val, out) in trend[200..].iter().zip(&outs[200..]) {
+            assert!((val - out.unwrap()).abs() < 1e-6, "{val} {out:?}");
+        }
+    }
+
+    #[test]
+    fn decycler_oscillator_band() {
+        let oscillator = || DecyclerOscillator::new(Echo::new(), len(30), len(60));
+        // The cycles between both cutoff periods pass,
+        let mid = generators::sine(600, 45.0, 1.0, 100.0);
+        assert_eq!(
+            oscillator()
+                .update_batch(&mid)
+                .iter()
+                .position(Option::is_some),
+            Some(61)
+        );
+        let mid = deviation(oscillator(), &mid, 0.0);
+        // while shorter cycles pass both high-pass filters and mostly cancel,
+        // and longer cycles are attenuated by both.
+        let short = deviation(oscillator(), &generators::sine(600, 10.0, 1.0, 100.0), 0.0);
+        let long = deviation(oscillator(), &generators::sine(600, 200.0, 1.0, 100.0), 0.0);
+        assert!(
+            mid > 2.0 * short && mid > 2.0 * long,
+            "{short} {mid} {long}"
+        );
+        // and a linear trend is removed by both.
+        let trend: Vec<f64> = (0..600).map(|i| 100.0 + 0.5 * i as f64).collect();
+        let dev = deviation(
+            DecyclerOscillator::new(Echo::new(), len(30), len(60)),
+            &trend,
+            0.0,
+        );
+        assert!(dev < 1e-6, "{dev}");
+    }
+
+    #[test]
+    fn decycler_set_param() {
+        let mut decycler = Decycler::<f64, _>::new(Echo::new(), len(20));
+        assert_eq!(
+            decycler.set_param("cutoff_period", ParamValue::Int(4)),
+            Err(Error::OnePoleHighPassLen(4))
+        );
+        decycler
+            .set_param("cutoff_period", ParamValue::Int(40))
+            .unwrap();
+        assert_eq!(decycler.cutoff_period(), len(40));
+
+        assert_eq!(
+            DecyclerOscillator::<f64, _>::try_new(Echo::new(), len(2), len(60)).unwrap_err(),
+            Error::TwoPoleHighPassLen(2)
+        );
+        let mut oscillator = DecyclerOscillator::<f64, _>::new(Echo::new(), len(30), len(60));
+        assert_eq!(
+            oscillator.set_param("slow_period", ParamValue::Int(2)),
+            Err(Error::TwoPoleHighPassLen(2))
+        );
+        oscillator
+            .set_param("fast_period", ParamValue::Int(20))
+            .unwrap();
+        oscillator
+            .set_param("slow_period", ParamValue::Int(40))
+            .unwrap();
+        let mut fresh = DecyclerOscillator::<f64, _>::new(Echo::new(), len(20), len(40));
+        assert_eq!(oscillator.param_specs(), fresh.param_specs());
+        assert_eq!(
+            oscillator.update_batch(&TEST_DATA),
+            fresh.update_batch(&TEST_DATA)
+        );
+    }
+
+    #[test]
+    fn decycler_plot() {
+        let mut view = Decycler::new(Echo::new(), len(20));
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/decycler.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    #[test]
+    fn decycler_oscillator_plot() {
+        let mut view = DecyclerOscillator::new(Echo::new(), len(15), len(30));
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/decycler_oscillator.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    // The high-pass filters are only stable for cutoff periods of at least 5 and 3.
+    mod decycler {
+        use super::*;
+
+        view_properties!(|window_len| Decycler::new(Echo::new(), window_len.saturating_add(4)));
+    }
+
+    mod decycler_oscillator {
+        use super::*;
+
+        view_properties!(|window_len| DecyclerOscillator::new(
+            Echo::new(),
+            window_len.saturating_add(2),
+            window_len.saturating_add(12)
+        ));
+    }
+}
@@ -0,0 +1,185 @@
+//! DEMA - Double Exponential Moving Average by Patrick Mulloy
+
+use std::num::NonZeroUsize;
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::{
+    Ema,
+    ema::update_cascade,
+    extrapolation::extrapolate,
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// DEMA - Double Exponential Moving Average by Patrick Mulloy,
+/// which reduces the lag of an EMA as `2 * EMA - EMA(EMA)`.
+/// Ready once the EMA of the EMA is, i.e. after `2 * window_len - 1` values.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct Dema<T, V> {
+    view: V,
+    /// The window length of each EMA.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    emas: [Ema<T, Echo<T>>; 2],
+}
+
+impl<T, V> Dema<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Double Exponential Moving Average with a chained View
+    /// and a given window length
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self {
+            view,
+            window_len,
+            emas: std::array::from_fn(|_| Ema::new(Echo::new(), window_len)),
+        }
+    }
+
+    /// Set the window length of each EMA, where growing it waits for the additional values to be observed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        for ema in &mut self.emas {
+            ema.set_window_len(window_len);
+        }
+    }
+}
+
+impl<T, V> View<T> for Dema<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        update_cascade(&mut self.emas, val);
+    }
+
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.emas[1].last())
+            .and_then(|ema_2| {
+                let ema_1 = self.emas[0].last().expect("is ready");
+                extrapolate(
+                    &[T::from(2.0).expect("can convert"), -T::one()],
+                    [ema_1, ema_2],
+                )
+            })
+            .chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Dema<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Dema")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Dema<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        InvalidReason,
+        plot::plot_values,
+        properties::view_properties,
+        pure_functions::{
+            Constant,
+            Multiply,
+            Subtract,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn dema_hand_wired() {
+        let window_len = NonZeroUsize::new(8).unwrap();
+        let mut dema = Dema::new(Echo::new(), window_len);
+        let mut wired = Subtract::new(
+            Multiply::new(Ema::new(Echo::new(), window_len), Constant::new(2.0)),
+            Ema::new(Ema::new(Echo::new(), window_len), window_len),
+        );
+        let outs = dema.update_batch(&TEST_DATA);
+        assert_eq!(outs.iter().position(Option::is_some), Some(14));
+        for (dema, wired) in outs.into_iter().zip(wired.update_batch(&TEST_DATA)) {
+            match (dema, wired) {
+                (Some(dema), Some(wired)) => assert!((dema - wired).abs() < 1e-9),
+                (dema, wired) => assert_eq!(dema, wired),
+            }
+        }
+    }
+
+    #[test]
+    fn dema_overflow() {
+        let mut dema = Dema::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        dema.update_batch(&[-f64::MAX, -f64::MAX, -f64::MAX, f64::MAX, f64::MAX]);
+        assert_eq!(dema.status(), Status::Invalid(InvalidReason::Overflow));
+        assert_eq!(dema.last(), None);
+    }
+
+    #[test]
+    fn dema_plot() {
+        let mut dema = Dema::new(Echo::new(), NonZeroUsize::new(16).unwrap());
+        let out: Vec<f64> = dema
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/dema.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| Dema::new(Echo::new(), window_len));
+}
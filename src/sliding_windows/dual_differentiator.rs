@@ -0,0 +1,288 @@
+//! John Ehlers Dual Differentiator dominant cycle estimator
+//! from: "Cycle Analytics for Traders" by John Ehlers, 2013
+
+use std::{
+    f64::consts::PI,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::{
+    AutomaticGainControl,
+    RoofingFilter,
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// The smoothing constant of the numerator and denominator of the period.
+const SMOOTHING: f64 = 0.1;
+
+/// John Ehlers Dual Differentiator dominant cycle estimator
+/// from: "Cycle Analytics for Traders" by John Ehlers, 2013
+/// Normalizes the roofing filtered values with an automatic gain control, whose normalized difference
+/// is the quadrature component, half a sample behind the values, so the in-phase component is their midpoint.
+/// As both components rotate at the angular frequency,
+/// differentiating them again measures the period `2 * pi * (i^2 + q^2) / (q * i' - i * q')`.
+/// Its numerator and denominator are smoothed, which averages out the ripple of the automatic gain control,
+/// and the period is limited to `[min_period, max_period]`.
+/// Ready once three roofing filtered values have been observed.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct DualDifferentiator<T, V> {
+    view: V,
+    roofing: RoofingFilter<T, Echo<T>>,
+    /// The shortest period, which is also the cutoff period of the smoothing of the roofing filter.
+    #[getset(get_copy = "pub")]
+    min_period: NonZeroUsize,
+    /// The longest period, which is also the cutoff period of the high-pass of the roofing filter.
+    #[getset(get_copy = "pub")]
+    max_period: NonZeroUsize,
+    // The automatic gain controls of both components.
+    re_agc: AutomaticGainControl<T, Echo<T>>,
+    im_agc: AutomaticGainControl<T, Echo<T>>,
+    re: T,
+    in_phase: T,
+    quadrature: T,
+    // The smoothed numerator and denominator of the period.
+    energy: T,
+    rate: T,
+    period: T,
+    n_observed: usize,
+}
+
+impl<T, V> DualDifferentiator<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Dual Differentiator with a chained View
+    /// and the default periods of 10 to 48 samples
+    pub fn new(view: V) -> Self {
+        Self::with_periods(
+            view,
+            NonZeroUsize::new(10).expect("is non-zero"),
+            NonZeroUsize::new(48).expect("is non-zero"),
+        )
+    }
+
+    /// Create a new Dual Differentiator with a custom range of periods
+    pub fn with_periods(view: V, min_period: NonZeroUsize, max_period: NonZeroUsize) -> Self {
+        Self {
+            view,
+            roofing: RoofingFilter::new(Echo::new(), max_period, min_period),
+            min_period,
+            max_period,
+            re_agc: AutomaticGainControl::new(Echo::new()),
+            im_agc: AutomaticGainControl::new(Echo::new()),
+            re: T::zero(),
+            in_phase: T::zero(),
+            quadrature: T::zero(),
+            energy: T::zero(),
+            rate: T::zero(),
+            period: T::zero(),
+            n_observed: 0,
+        }
+    }
+
+    /// Create a new Dual Differentiator with a custom range of periods,
+    /// where `3 <= min_period <= max_period`.
+    pub fn try_with_periods(
+        view: V,
+        min_period: NonZeroUsize,
+        max_period: NonZeroUsize,
+    ) -> Result<Self, Error> {
+        check_periods(min_period, max_period)?;
+        Ok(Self::with_periods(view, min_period, max_period))
+    }
+
+    /// Set the shortest period, which must be within `[3, max_period]`, recomputing the roofing filter.
+    pub fn set_min_period(&mut self, min_period: NonZeroUsize) -> Result<(), Error> {
+        check_periods(min_period, self.max_period)?;
+        self.min_period = min_period;
+        self.roofing.set_super_smoother_len_high_pass(min_period);
+        Ok(())
+    }
+
+    /// Set the longest period, which must be at least `min_period`, recomputing the roofing filter.
+    pub fn set_max_period(&mut self, max_period: NonZeroUsize) -> Result<(), Error> {
+        check_periods(self.min_period, max_period)?;
+        self.max_period = max_period;
+        self.roofing.set_window_len_low_pass(max_period)
+    }
+}
+
+/// The range of periods of a dominant cycle estimator, which must satisfy `3 <= min_period <= max_period`.
+pub(super) fn check_periods(
+    min_period: NonZeroUsize,
+    max_period: NonZeroUsize,
+) -> Result<(), Error> {
+    if min_period.get() < 3 || min_period > max_period {
+        return Err(Error::CyclePeriods(min_period.get(), max_period.get()));
+    }
+    Ok(())
+}
+
+impl<T, V> View<T> for DualDifferentiator<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.roofing.update(val);
+        let Some(filt) = self.roofing.last() else {
+            return;
+        };
+        self.n_observed += 1;
+
+        // The automatic gain controls normalize both components by their decaying peaks.
+        self.re_agc.update(filt);
+        let re = self.re_agc.last().expect("is ready after an update");
+        let re_dot = re - self.re;
+        self.im_agc.update(re_dot);
+        let quadrature = self.im_agc.last().expect("is ready after an update");
+        let in_phase = (re + self.re) / T::from(2.0).expect("can convert");
+        let in_phase_dot = in_phase - self.in_phase;
+        let quadrature_dot = quadrature - self.quadrature;
+        (self.re, self.in_phase, self.quadrature) = (re, in_phase, quadrature);
+
+        let alpha = T::from(SMOOTHING).expect("can convert");
+        self.energy = alpha * (in_phase * in_phase + quadrature * quadrature)
+            + (T::one() - alpha) * self.energy;
+        self.rate = alpha * (quadrature * in_phase_dot - in_phase * quadrature_dot)
+            + (T::one() - alpha) * self.rate;
+        if self.rate != T::zero() {
+            self.period = T::from(2.0 * PI).expect("can convert") * self.energy / self.rate;
+        }
+        self.period = self
+            .period
+            .max(T::from(self.min_period.get()).expect("can convert"))
+            .min(T::from(self.max_period.get()).expect("can convert"));
+        debug_assert!(self.period.is_finite(), "value must be finite");
+    }
+
+    fn last(&self) -> Option<T> {
+        if self.n_observed < 3 {
+            return None;
+        }
+        Some(self.period)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for DualDifferentiator<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("DualDifferentiator")
+            .param("min_period", self.min_period())
+            .param("max_period", self.max_period())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for DualDifferentiator<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::window_len("min_period", self.min_period.get(), 3),
+            ParamSpec::window_len("max_period", self.max_period.get(), 3),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "min_period" => self.set_min_period(window_len_param("min_period", val)?)?,
+            "max_period" => self.set_max_period(window_len_param("max_period", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::view_properties,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn dual_differentiator_sine_period() {
+        for period in [15.0, 20.0, 30.0] {
+            let vals = generators::sine(500, period, 1.0, 100.0);
+            let outs: Vec<f64> = DualDifferentiator::new(Echo::new())
+                .update_batch(&vals)
+                .into_iter()
+                .flatten()
+                .collect();
+            // The measurement ripples around the period, so it is averaged over several cycles.
+            let measured = outs[outs.len() - 120..].iter().sum::<f64>() / 120.0;
+            assert!(
+                (measured - period).abs() < 0.05 * period,
+                "measured period {measured} of {period}"
+            );
+        }
+    }
+
+    #[test]
+    fn dual_differentiator_periods() {
+        let len = |len| NonZeroUsize::new(len).unwrap();
+        assert_eq!(
+            DualDifferentiator::<f64, _>::try_with_periods(Echo::new(), len(2), len(48))
+                .unwrap_err(),
+            Error::CyclePeriods(2, 48)
+        );
+        let mut view = DualDifferentiator::<f64, _>::new(Echo::new());
+        assert_eq!(
+            view.set_param("max_period", ParamValue::Int(8)),
+            Err(Error::CyclePeriods(10, 8))
+        );
+        view.set_param("min_period", ParamValue::Int(8)).unwrap();
+        assert_eq!(view.min_period().get(), 8);
+    }
+
+    #[test]
+    fn dual_differentiator_plot() {
+        let mut view = DualDifferentiator::new(Echo::new());
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/dual_differentiator.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|_window_len| DualDifferentiator::new(Echo::new()));
+}
@@ -10,7 +10,21 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// John Ehlers Fisher Transform Indicator
 /// from: <http://www.mesasoftware.com/papers/UsingTheFisherTransform.pdf>
@@ -24,7 +38,7 @@ pub struct EhlersFisherTransform<T, V, M> {
     q_vals: VecDeque<T>,
     high: T,
     low: T,
-    q_out: VecDeque<T>,
+    out: Option<T>,
 }
 
 impl<T, V, M> EhlersFisherTransform<T, V, M>
@@ -43,7 +57,21 @@ where
             q_vals: VecDeque::with_capacity(window_len.get()),
             high: T::zero(),
             low: T::zero(),
-            q_out: VecDeque::with_capacity(window_len.get()),
+            out: None,
+        }
+    }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
+        }
+        if let (Some(high), Some(low)) = (
+            self.q_vals.iter().copied().reduce(T::max),
+            self.q_vals.iter().copied().reduce(T::min),
+        ) {
+            (self.high, self.low) = (high, low);
         }
     }
 }
@@ -60,12 +88,16 @@ where
         let Some(val) = self.view.last() else { return };
         debug_assert!(val.is_finite(), "value must be finite");
 
-        if self.q_vals.is_empty() {
+        let mut removed = None;
+        if self.q_vals.len() >= self.window_len.get() {
+            removed = self.q_vals.pop_front();
+        }
+        self.q_vals.push_back(val);
+        if self.q_vals.len() == 1 {
             self.high = val;
             self.low = val;
         }
-        if self.q_vals.len() >= self.window_len.get() {
-            let old_val = self.q_vals.pop_front().unwrap();
+        if let Some(old_val) = removed {
             // update high and low values if needed
             if old_val >= self.high {
                 // re-compute high
@@ -84,44 +116,87 @@ where
                     .unwrap();
             }
         }
-        self.q_vals.push_back(val);
         if val > self.high {
             self.high = val;
         } else if val < self.low {
             self.low = val;
         }
 
-        if self.high == self.low {
-            self.q_out.push_back(T::zero());
-            return;
-        }
         let half = T::from(0.5).expect("can convert");
-        let val =
-            T::from(2.0).expect("can convert") * ((val - self.low) / (self.high - self.low) - half);
+        // A flat window puts the value right in the middle of the range.
+        let two = T::from(2.0).expect("can convert");
+        let normalized = if self.high == self.low {
+            T::zero()
+        } else if (self.high - self.low).is_finite() {
+            two * ((val - self.low) / (self.high - self.low) - half)
+        } else {
+            // The range of values beyond half the largest float overflows, unlike the range of their halves.
+            two * ((val / two - self.low / two) / (self.high / two - self.low / two) - half)
+        };
+        // The fisher transform starts at zero, until the moving average is ready.
+        let fish_1 = *self.out.get_or_insert(T::zero());
+
         // smooth with moving average
-        self.moving_average.update(val);
+        self.moving_average.update(normalized);
         let Some(mut smoothed) = self.moving_average.last() else {
             return;
         };
-        smoothed = smoothed.clamp(
-            T::from(-0.99).expect("can convert"),
-            T::from(0.99).expect("can convert"),
-        );
-
-        if self.q_out.is_empty() {
-            // do not insert values when there are not enough values yet
-            self.q_out.push_back(T::zero());
-            return;
+        let limit = T::from(0.99).expect("can convert");
+        if smoothed > limit {
+            smoothed = T::from(0.999).expect("can convert");
+        } else if smoothed < -limit {
+            smoothed = T::from(-0.999).expect("can convert");
         }
-        let fish = half * ((T::one() + smoothed) / (T::one() - smoothed)).ln()
-            + half * *self.q_out.back().unwrap();
+
+        let fish = half * ((T::one() + smoothed) / (T::one() - smoothed)).ln() + half * fish_1;
         debug_assert!(fish.is_finite(), "value must be finite");
-        self.q_out.push_back(fish);
+        self.out = Some(fish);
     }
 
     #[inline(always)]
     fn last(&self) -> Option<T> {
-        self.q_out.back().copied()
+        self.out
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V, M> Introspect for EhlersFisherTransform<T, V, M>
+where
+    V: Introspect,
+    M: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("EhlersFisherTransform")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+            .child(self.moving_average.node())
+    }
+}
+
+impl<T, V, M> Reconfigure for EhlersFisherTransform<T, V, M>
+where
+    V: View<T>,
+    M: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
     }
 }
 
@@ -130,11 +205,20 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::view_properties,
         pure_functions::Echo,
         sliding_windows::Ema,
         test_data::TEST_DATA,
     };
 
+    #[test]
+    fn ehlers_fisher_transform_huge_range() {
+        let mut view =
+            EhlersFisherTransform::new(Echo::new(), Echo::new(), NonZeroUsize::new(2).unwrap());
+        let outs = view.update_batch(&[f64::MAX, -f64::MAX, f64::MAX]);
+        assert!(outs.iter().flatten().all(|out| out.is_finite()));
+    }
+
     #[test]
     fn ehlers_fisher_transform_plot() {
         let mut eft = EhlersFisherTransform::new(
@@ -151,4 +235,10 @@ mod tests {
         let filename = "img/ehlers_fisher_transform.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| EhlersFisherTransform::new(
+        Echo::new(),
+        Ema::new(Echo::new(), window_len),
+        window_len
+    ));
 }
@@ -5,7 +5,24 @@ use std::num::NonZeroUsize;
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamSpec,
+        Reconfigure,
+        float_param,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
 
 #[derive(Clone, Debug, CopyGetters)]
 /// EMA - Exponential Moving Average
@@ -42,6 +59,24 @@ where
             n_observed_values: 0,
         }
     }
+
+    /// Create a new EMA with a custom alpha as well, which must be within `(0, 2]`.
+    pub fn try_with_alpha(view: V, window_len: NonZeroUsize, alpha: T) -> Result<Self, Error> {
+        check_alpha(alpha)?;
+        Ok(Self::with_alpha(view, window_len, alpha))
+    }
+
+    /// Set the window length, where growing it waits for the additional values to be observed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len.get();
+    }
+
+    /// Set the alpha, which must be within `(0, 2]`.
+    pub fn set_alpha(&mut self, alpha: T) -> Result<(), Error> {
+        check_alpha(alpha)?;
+        self.alpha = alpha;
+        Ok(())
+    }
 }
 
 impl<T, V> View<T> for Ema<T, V>
@@ -58,7 +93,7 @@ where
         self.n_observed_values += 1;
         let weight = self.alpha / (T::one() + T::from(self.window_len).expect("can convert"));
 
-        if self.last_ema == T::zero() {
+        if self.n_observed_values == 1 {
             self.out = val;
             self.last_ema = val;
             return;
@@ -75,6 +110,68 @@ where
         debug_assert!(self.out.is_finite(), "value must be finite");
         Some(self.out)
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Ema<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Ema")
+            .param("window_len", self.window_len())
+            .param("alpha", self.alpha.to_f64().expect("can convert"))
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Ema<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::window_len("window_len", self.window_len, 1),
+            ParamSpec::float(
+                "alpha",
+                self.alpha,
+                Bound::Exclusive(0.0),
+                Bound::Inclusive(2.0),
+            ),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            "alpha" => self.set_alpha(float_param("alpha", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+/// Update a cascade of EMAs with a new value, where each EMA smooths the output of the previous one
+/// once it is ready, so the warm-up of the last EMA spans the window lengths of all of them.
+pub(super) fn update_cascade<T: Float>(emas: &mut [Ema<T, Echo<T>>], val: T) {
+    let mut val = Some(val);
+    for ema in emas {
+        let Some(v) = val else { return };
+        ema.update(v);
+        val = ema.last();
+    }
+}
+
+fn check_alpha<T: Float>(alpha: T) -> Result<(), Error> {
+    if !(alpha > T::zero() && alpha <= T::from(2.0).expect("can convert")) {
+        return Err(Error::EmaAlpha(alpha.to_f64().expect("can convert")));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -82,7 +179,7 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
-        pure_functions::Echo,
+        properties::view_properties,
         test_data::TEST_DATA,
     };
 
@@ -99,4 +196,45 @@ mod tests {
         let filename = "img/ema.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| Ema::new(Echo::new(), window_len));
+
+    #[test]
+    fn ema_try_with_alpha() {
+        let window_len = NonZeroUsize::new(16).unwrap();
+        assert!(Ema::try_with_alpha(Echo::new(), window_len, 2.0).is_ok());
+        for alpha in [0.0, 2.5, f64::NAN] {
+            assert!(matches!(
+                Ema::try_with_alpha(Echo::new(), window_len, alpha),
+                Err(Error::EmaAlpha(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn ema_set_param() {
+        let mut ema = Ema::new(Echo::<f64>::new(), NonZeroUsize::new(4).unwrap());
+        assert_eq!(
+            ema.set_param("alpha", ParamValue::Float(3.0)),
+            Err(Error::EmaAlpha(3.0))
+        );
+        assert_eq!(
+            ema.set_param("alpha", ParamValue::Text("fast")),
+            Err(Error::ParameterType("alpha"))
+        );
+        assert_eq!(
+            ema.set_param("beta", ParamValue::Float(1.0)),
+            Err(Error::UnknownParameter)
+        );
+        ema.set_param("alpha", ParamValue::Int(1)).unwrap();
+        assert_eq!(ema.param_specs()[1].value, ParamValue::Float(1.0));
+
+        // Growing the window waits for the additional values.
+        ema.update_batch(&[1.0, 2.0, 3.0, 4.0]);
+        assert!(ema.last().is_some());
+        ema.set_window_len(NonZeroUsize::new(6).unwrap());
+        assert_eq!(ema.last(), None);
+        ema.update_batch(&[5.0, 6.0]);
+        assert!(ema.last().is_some());
+    }
 }
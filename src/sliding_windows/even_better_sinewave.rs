@@ -0,0 +1,349 @@
+//! Even Better Sinewave Indicator by John Ehlers
+//! from: "Cycle Analytics for Traders" by John Ehlers, 2013
+
+use std::num::NonZeroUsize;
+
+use num::Float;
+
+use super::{
+    OnePoleHighPass,
+    SuperSmoother,
+};
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// Even Better Sinewave Indicator by John Ehlers
+/// from: "Cycle Analytics for Traders" by John Ehlers, 2013
+/// Removes the trend with a `OnePoleHighPass` of cutoff period `high_pass_len`
+/// and the noise with a `SuperSmoother` of `super_smoother_len`,
+/// which smooths the high-pass filtered values from the first value on, before the high-pass filter is ready.
+/// The average of the last three filtered values is normalized by their root mean square, an automatic gain control,
+/// so the output swings between -1 and 1 in cycle mode, while it sticks to either bound in trend mode.
+/// Ready once the `SuperSmoother` has output three values.
+#[derive(Debug, Clone)]
+pub struct EvenBetterSinewave<T, V> {
+    view: V,
+    high_pass: OnePoleHighPass<T, Echo<T>>,
+    super_smoother: SuperSmoother<T, Echo<T>>,
+    // The last three filtered values, newest first.
+    filts: [T; 3],
+    n_filts: usize,
+}
+
+impl<T, V> EvenBetterSinewave<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Even Better Sinewave Indicator with a chained View
+    /// and the default high-pass and `SuperSmoother` lengths of 40 and 10
+    pub fn new(view: V) -> Self {
+        Self::with_lens(
+            view,
+            NonZeroUsize::new(40).expect("is non-zero"),
+            NonZeroUsize::new(10).expect("is non-zero"),
+        )
+    }
+
+    /// Create a new Even Better Sinewave Indicator with custom high-pass and `SuperSmoother` lengths
+    pub fn with_lens(
+        view: V,
+        high_pass_len: NonZeroUsize,
+        super_smoother_len: NonZeroUsize,
+    ) -> Self {
+        Self {
+            view,
+            high_pass: OnePoleHighPass::new(Echo::new(), high_pass_len),
+            super_smoother: SuperSmoother::new(Echo::new(), super_smoother_len),
+            filts: [T::zero(); 3],
+            n_filts: 0,
+        }
+    }
+
+    /// Create a new Even Better Sinewave Indicator with custom high-pass and `SuperSmoother` lengths,
+    /// where the high-pass length must be at least 5 for the filter to be stable.
+    pub fn try_with_lens(
+        view: V,
+        high_pass_len: NonZeroUsize,
+        super_smoother_len: NonZeroUsize,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            view,
+            high_pass: OnePoleHighPass::try_new(Echo::new(), high_pass_len)?,
+            super_smoother: SuperSmoother::new(Echo::new(), super_smoother_len),
+            filts: [T::zero(); 3],
+            n_filts: 0,
+        })
+    }
+
+    /// The cutoff period of the high-pass filter.
+    #[inline(always)]
+    pub fn high_pass_len(&self) -> NonZeroUsize {
+        self.high_pass.cutoff_period()
+    }
+
+    /// The window length of the `SuperSmoother`.
+    #[inline(always)]
+    pub fn super_smoother_len(&self) -> NonZeroUsize {
+        self.super_smoother.window_len()
+    }
+
+    /// Set the cutoff period of the high-pass filter, which must be at least 5, recomputing its coefficient.
+    pub fn set_high_pass_len(&mut self, high_pass_len: NonZeroUsize) -> Result<(), Error> {
+        self.high_pass.set_cutoff_period(high_pass_len)
+    }
+
+    /// Set the window length of the `SuperSmoother`, recomputing its coefficients.
+    pub fn set_super_smoother_len(&mut self, super_smoother_len: NonZeroUsize) {
+        self.super_smoother.set_window_len(super_smoother_len);
+    }
+}
+
+impl<T, V> View<T> for EvenBetterSinewave<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.high_pass.update(val);
+        let hp = self.high_pass.hp();
+        if !hp.is_finite() {
+            return;
+        }
+        self.super_smoother.update(hp);
+        let Some(filt) = self.super_smoother.last() else {
+            return;
+        };
+        let [f1, f2, _] = self.filts;
+        self.filts = [filt, f1, f2];
+        self.n_filts += 1;
+    }
+
+    fn last(&self) -> Option<T> {
+        match self.status() {
+            Status::Invalid(InvalidReason::FlatRange) => Some(T::zero()),
+            status => status.value(),
+        }
+    }
+
+    /// Without any filtered power, e.g. for constant values, the output is zero,
+    /// while its status is `InvalidReason::FlatRange`.
+    /// The differences of values spanning more than the largest float overflow the high-pass filter,
+    /// as the overshoot of values close to the largest float overflows the `SuperSmoother`,
+    /// after which the status stays `InvalidReason::Overflow`.
+    fn status(&self) -> Status<T> {
+        if !self.high_pass.hp().is_finite() {
+            return Status::Invalid(InvalidReason::Overflow);
+        }
+        if let Status::Invalid(reason) = self.super_smoother.status() {
+            return Status::Invalid(reason);
+        }
+        if self.n_filts < self.filts.len() {
+            return Status::WarmingUp.chained(self.view.status());
+        }
+        let three = T::from(3.0).expect("can convert");
+        let sq_sum = |[f0, f1, f2]: [T; 3]| f0 * f0 + f1 * f1 + f2 * f2;
+        // The power of values close to the largest float overflows,
+        // unlike that of the values divided by the largest one, which doesn't change the wave.
+        let scale = if sq_sum(self.filts).is_finite() {
+            T::one()
+        } else {
+            self.filts.iter().fold(T::zero(), |max, f| max.max(f.abs()))
+        };
+        let filts = self.filts.map(|f| f / scale);
+        let power = sq_sum(filts) / three;
+        if power == T::zero() {
+            return Status::Invalid(InvalidReason::FlatRange);
+        }
+        let [f0, f1, f2] = filts;
+        let wave = (f0 + f1 + f2) / three / power.sqrt();
+        debug_assert!(wave.is_finite(), "value must be finite");
+        Status::Ready(wave)
+    }
+}
+
+impl<T, V> Introspect for EvenBetterSinewave<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("EvenBetterSinewave")
+            .param("high_pass_len", self.high_pass.cutoff_period())
+            .param("super_smoother_len", self.super_smoother.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for EvenBetterSinewave<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::window_len("high_pass_len", self.high_pass_len().get(), 5),
+            ParamSpec::window_len("super_smoother_len", self.super_smoother_len().get(), 1),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "high_pass_len" => self.set_high_pass_len(window_len_param("high_pass_len", val)?)?,
+            "super_smoother_len" => {
+                self.set_super_smoother_len(window_len_param("super_smoother_len", val)?)
+            }
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::{
+            self,
+            check_bounded,
+            view_properties,
+            zero_crossing_period,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn even_better_sinewave_cycle_mode() {
+        for period in [15.0, 20.0, 30.0] {
+            let vals = generators::sine(500, period, 1.0, 100.0);
+            let outs: Vec<f64> = EvenBetterSinewave::new(Echo::new())
+                .update_batch(&vals)
+                .into_iter()
+                .flatten()
+                .collect();
+            let outs = &outs[200..];
+            let measured = zero_crossing_period(outs).unwrap();
+            assert!(
+                (measured - period).abs() < 0.05 * period,
+                "measured period {measured} of {period}"
+            );
+            assert!(outs.iter().any(|out| *out > 0.9) && outs.iter().any(|out| *out < -0.9));
+        }
+    }
+
+    #[test]
+    fn even_better_sinewave_trend_mode() {
+        let trend: Vec<f64> = (0..300).map(|i| 100.0 + 0.5 * i as f64).collect();
+        let outs = EvenBetterSinewave::new(Echo::new()).update_batch(&trend);
+        assert_eq!(outs.iter().position(Option::is_some), Some(11));
+        // The filtered trend is positive, so the wave sticks to the upper bound.
+        for out in outs[20..].iter().flatten() {
+            assert!(*out > 0.99, "wave {out}");
+        }
+
+        let mut view = EvenBetterSinewave::new(Echo::new());
+        view.update_batch(&[100.0; 20]);
+        assert_eq!(view.status(), Status::Invalid(InvalidReason::FlatRange));
+        assert_eq!(view.last(), Some(0.0));
+    }
+
+    #[test]
+    fn even_better_sinewave_overflow() {
+        let mut view = EvenBetterSinewave::new(Echo::new());
+        view.update_batch(&[-f64::MAX, f64::MAX]);
+        assert_eq!(view.status(), Status::Invalid(InvalidReason::Overflow));
+        view.update_batch(&[100.0; 20]);
+        assert_eq!(view.status(), Status::Invalid(InvalidReason::Overflow));
+        assert_eq!(view.last(), None);
+    }
+
+    #[test]
+    fn even_better_sinewave_extreme_values() {
+        // The wave doesn't depend on the scale of the values, even where their power overflows.
+        let vals = generators::sine(300, 20.0, 1.0, 0.0);
+        let large: Vec<f64> = vals.iter().map(|val| val * 1e300).collect();
+        let outs = EvenBetterSinewave::new(Echo::new()).update_batch(&vals);
+        let large_outs = EvenBetterSinewave::new(Echo::new()).update_batch(&large);
+        for (out, large_out) in outs.iter().zip(&large_outs).skip(11) {
+            assert!((out.unwrap() - large_out.unwrap()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn even_better_sinewave_high_pass_len() {
+        let len = |len| NonZeroUsize::new(len).unwrap();
+        assert_eq!(
+            EvenBetterSinewave::<f64, _>::try_with_lens(Echo::new(), len(4), len(10)).unwrap_err(),
+            Error::OnePoleHighPassLen(4)
+        );
+        let mut view = EvenBetterSinewave::<f64, _>::new(Echo::new());
+        assert_eq!(
+            view.set_param("high_pass_len", ParamValue::Int(4)),
+            Err(Error::OnePoleHighPassLen(4))
+        );
+        view.set_param("high_pass_len", ParamValue::Int(20))
+            .unwrap();
+        view.set_param("super_smoother_len", ParamValue::Int(8))
+            .unwrap();
+        let mut fresh = EvenBetterSinewave::<f64, _>::with_lens(Echo::new(), len(20), len(8));
+        assert_eq!(view.param_specs(), fresh.param_specs());
+        assert_eq!(
+            view.update_batch(&TEST_DATA),
+            fresh.update_batch(&TEST_DATA)
+        );
+    }
+
+    #[test]
+    fn even_better_sinewave_plot() {
+        let mut view = EvenBetterSinewave::new(Echo::new());
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/even_better_sinewave.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| EvenBetterSinewave::with_lens(
+        Echo::new(),
+        window_len.saturating_add(4),
+        window_len
+    ));
+
+    proptest! {
+        #[test]
+        fn even_better_sinewave_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            let view = EvenBetterSinewave::with_lens(Echo::new(), window_len.saturating_add(4), window_len);
+            // The root mean square bounds the mean, up to rounding.
+            check_bounded(view, &vals, -1.0 - 1e-12, 1.0 + 1e-12)?;
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Linear extrapolations of moving averages, e.g. the `2 * ema_1 - ema_2` of the DEMA,
+//! which overflow for values close to the largest float although each average is finite.
+//! The averages are scaled down by a power of two, such that no partial sum of the weighted averages can overflow,
+//! which is exact, so the extrapolation rounds the same as the unscaled one.
+//! Scaling the result back up reports `InvalidReason::Overflow` where the extrapolation itself exceeds the largest float.
+
+use num::Float;
+
+use crate::{
+    InvalidReason,
+    Status,
+};
+
+/// The largest power of two, at most one, by which the values of an extrapolation with `weights` are scaled,
+/// so that the absolute weights sum to at most its inverse.
+pub(super) fn scale<T: Float>(weights: &[T]) -> T {
+    let total = weights.iter().fold(T::zero(), |total, w| total + w.abs());
+    let half = T::from(0.5).expect("can convert");
+    let mut scale = T::one();
+    while total * scale > T::one() {
+        scale = scale * half;
+    }
+    scale
+}
+
+/// The weighted sum of `vals`, each scaled by `scale`, e.g. as the input of a linear filter.
+pub(super) fn scaled_sum<T: Float>(
+    weights: &[T],
+    vals: impl IntoIterator<Item = T>,
+    scale: T,
+) -> T {
+    weights
+        .iter()
+        .zip(vals)
+        .fold(T::zero(), |sum, (w, val)| sum + *w * (scale * val))
+}
+
+/// The scaled extrapolation `scaled` scaled back up, or `InvalidReason::Overflow` beyond the largest float.
+pub(super) fn unscaled<T: Float>(scaled: T, scale: T) -> Status<T> {
+    let out = scaled / scale;
+    if !out.is_finite() {
+        return Status::Invalid(InvalidReason::Overflow);
+    }
+    Status::Ready(out)
+}
+
+/// The weighted sum of `vals`, or `InvalidReason::Overflow` beyond the largest float.
+pub(super) fn extrapolate<T: Float>(weights: &[T], vals: impl IntoIterator<Item = T>) -> Status<T> {
+    let scale = scale(weights);
+    unscaled(scaled_sum(weights, vals, scale), scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extrapolate_extreme_values() {
+        // The doubled average overflows, while the extrapolation doesn't.
+        let val = 0.75 * f64::MAX;
+        assert_eq!(extrapolate(&[2.0, -1.0], [val, val]), Status::Ready(val));
+        assert_eq!(
+            extrapolate(&[2.0, -1.0], [val, -val]),
+            Status::Invalid(InvalidReason::Overflow)
+        );
+        assert_eq!(scale(&[3.0, -3.0, 1.0]), 0.125);
+    }
+}
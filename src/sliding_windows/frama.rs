@@ -0,0 +1,238 @@
+//! FRAMA - Fractal Adaptive Moving Average by John Ehlers
+//! from: <https://www.mesasoftware.com/papers/FRAMA.pdf>
+
+use std::num::NonZeroUsize;
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::{
+    Lag,
+    Max,
+    Min,
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// FRAMA - Fractal Adaptive Moving Average by John Ehlers
+/// from: <https://www.mesasoftware.com/papers/FRAMA.pdf>
+/// An EMA whose smoothing constant `exp(-4.6 * (D - 1))` follows the fractal dimension `D` of the window,
+/// estimated from the ranges of its older half, its newer half and the whole window.
+/// The window length must be even, so an odd one is rounded up. Ready once the window is full.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct Frama<T, V> {
+    view: V,
+    /// The even sliding window length.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    max_newer: Max<T, Echo<T>>,
+    min_newer: Min<T, Echo<T>>,
+    max_older: Max<T, Lag<T, Echo<T>>>,
+    min_older: Min<T, Lag<T, Echo<T>>>,
+    /// The last fractal dimension, kept while a half of the window is flat.
+    dimension: T,
+    frama: T,
+    n_observed_values: usize,
+}
+
+impl<T, V> Frama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Fractal Adaptive Moving Average with a chained View
+    /// and a given sliding window length, which is rounded up to an even length
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        let half = half_len(window_len);
+        Self {
+            view,
+            window_len: half.saturating_mul(NonZeroUsize::new(2).expect("is non-zero")),
+            max_newer: Max::new(Echo::new(), half),
+            min_newer: Min::new(Echo::new(), half),
+            max_older: Max::new(Lag::new(Echo::new(), lag_len(half)), half),
+            min_older: Min::new(Lag::new(Echo::new(), lag_len(half)), half),
+            dimension: T::one(),
+            frama: T::zero(),
+            n_observed_values: 0,
+        }
+    }
+
+    /// Set the window length, which is rounded up to an even length.
+    /// The values cannot be moved between the halves of differing lengths,
+    /// so the window refills before the outputs resume.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        let half = half_len(window_len);
+        self.window_len = half.saturating_mul(NonZeroUsize::new(2).expect("is non-zero"));
+        self.max_newer = Max::new(Echo::new(), half);
+        self.min_newer = Min::new(Echo::new(), half);
+        self.max_older = Max::new(Lag::new(Echo::new(), lag_len(half)), half);
+        self.min_older = Min::new(Lag::new(Echo::new(), lag_len(half)), half);
+        self.dimension = T::one();
+        self.n_observed_values = 0;
+    }
+}
+
+/// The length of each half of the window, at least 1.
+fn half_len(window_len: NonZeroUsize) -> NonZeroUsize {
+    NonZeroUsize::new(window_len.get().div_ceil(2)).expect("is non-zero")
+}
+
+/// The older half ends `half` values before the newest value, where a `Lag` of 1 is the newest value.
+fn lag_len(half: NonZeroUsize) -> NonZeroUsize {
+    half.saturating_add(1)
+}
+
+impl<T, V> View<T> for Frama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.n_observed_values += 1;
+        self.max_newer.update(val);
+        self.min_newer.update(val);
+        self.max_older.update(val);
+        self.min_older.update(val);
+
+        // Until the window is full, the FRAMA starts from the latest value.
+        if self.n_observed_values < self.window_len.get() {
+            self.frama = val;
+            return;
+        }
+
+        let (max_newer, min_newer, max_older, min_older) = (
+            self.max_newer.last().expect("is ready"),
+            self.min_newer.last().expect("is ready"),
+            self.max_older.last().expect("is ready"),
+            self.min_older.last().expect("is ready"),
+        );
+        let half = T::from(self.window_len.get() / 2).expect("can convert");
+        let n1 = (max_older - min_older) / half;
+        let n2 = (max_newer - min_newer) / half;
+        let n3 = (max_older.max(max_newer) - min_older.min(min_newer))
+            / T::from(self.window_len.get()).expect("can convert");
+        if n1 > T::zero() && n2 > T::zero() && n3 > T::zero() {
+            self.dimension = ((n1 + n2).ln() - n3.ln()) / T::from(2.0).expect("can convert").ln();
+        }
+
+        let alpha = (T::from(-4.6).expect("can convert") * (self.dimension - T::one()))
+            .exp()
+            .max(T::from(0.01).expect("can convert"))
+            .min(T::one());
+        self.frama = alpha * val + (T::one() - alpha) * self.frama;
+        debug_assert!(self.frama.is_finite(), "value must be finite");
+    }
+
+    fn last(&self) -> Option<T> {
+        if self.n_observed_values < self.window_len.get() {
+            return None;
+        }
+        Some(self.frama)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Frama<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Frama")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Frama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            2,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        plot::plot_values,
+        properties::view_properties,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn frama_dimension() {
+        // A straight trend has the dimension of a line, so the FRAMA follows it.
+        let window_len = NonZeroUsize::new(16).unwrap();
+        let trend: Vec<f64> = (0..40).map(|i| i as f64).collect();
+        let mut frama = Frama::new(Echo::new(), window_len);
+        let outs = frama.update_batch(&trend);
+        assert_eq!(outs.iter().position(Option::is_some), Some(15));
+        assert_eq!(outs[39], Some(39.0));
+
+        // A zig-zag fills the plane with a dimension of 2, so the FRAMA barely moves from the previous 0.
+        let zig_zag: Vec<f64> = (0..40).map(|i| (i % 2) as f64).collect();
+        let mut frama = Frama::new(Echo::new(), window_len);
+        let outs = frama.update_batch(&zig_zag);
+        assert!((outs[15].unwrap() - (-4.6_f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn frama_even_window_len() {
+        let frama = Frama::<f64, _>::new(Echo::new(), NonZeroUsize::new(5).unwrap());
+        assert_eq!(frama.window_len().get(), 6);
+        let frama = Frama::<f64, _>::new(Echo::new(), NonZeroUsize::new(1).unwrap());
+        assert_eq!(frama.window_len().get(), 2);
+    }
+
+    #[test]
+    fn frama_plot() {
+        let mut frama = Frama::new(Echo::new(), NonZeroUsize::new(16).unwrap());
+        let out: Vec<f64> = frama
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/frama.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| Frama::new(Echo::new(), window_len));
+}
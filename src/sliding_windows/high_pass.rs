@@ -0,0 +1,498 @@
+//! 1-pole and 2-pole high-pass filters by John Ehlers
+//! from: "Cycle Analytics for Traders" by John Ehlers, 2013
+
+use std::{
+    f64::consts::PI,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
+
+/// 1-pole high-pass filter by John Ehlers
+/// from: "Cycle Analytics for Traders" by John Ehlers, 2013
+/// Attenuates the cycles longer than `cutoff_period` by 6 dB per octave, which removes the trend,
+/// e.g. ahead of a `SuperSmoother` in the `EvenBetterSinewave`.
+/// Its complement, the values minus the high-pass, is the `Decycler`.
+/// Ready after `cutoff_period + 1` values, i.e. once it has warmed up over its cutoff period
+/// past the previous value it needs.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct OnePoleHighPass<T, V> {
+    view: V,
+    /// The cutoff period, beyond which cycles are attenuated.
+    #[getset(get_copy = "pub")]
+    cutoff_period: NonZeroUsize,
+    alpha: T,
+    n_observed: usize,
+    val_1: T,
+    hp: T,
+}
+
+impl<T, V> OnePoleHighPass<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new 1-pole high-pass filter with a chained View
+    pub fn new(view: V, cutoff_period: NonZeroUsize) -> Self {
+        Self {
+            view,
+            cutoff_period,
+            alpha: one_pole_alpha(T::from(cutoff_period.get()).expect("can convert")),
+            n_observed: 0,
+            val_1: T::zero(),
+            hp: T::zero(),
+        }
+    }
+
+    /// Create a new 1-pole high-pass filter with a chained View,
+    /// where the cutoff period must be at least 5 for the filter to be stable.
+    pub fn try_new(view: V, cutoff_period: NonZeroUsize) -> Result<Self, Error> {
+        check_one_pole_period(cutoff_period)?;
+        Ok(Self::new(view, cutoff_period))
+    }
+
+    /// Set the cutoff period, which must be at least 5, recomputing the filter coefficient.
+    /// The filter state is kept, while growing it waits for the additional values to be observed.
+    pub fn set_cutoff_period(&mut self, cutoff_period: NonZeroUsize) -> Result<(), Error> {
+        check_one_pole_period(cutoff_period)?;
+        self.cutoff_period = cutoff_period;
+        self.alpha = one_pole_alpha(T::from(cutoff_period.get()).expect("can convert"));
+        Ok(())
+    }
+
+    /// Create a new 1-pole high-pass filter with a fractional cutoff period, e.g. relative to a center period,
+    /// which is ready after the cutoff period rounded up.
+    pub(super) fn with_fractional_cutoff_period(view: V, cutoff_period: T) -> Self {
+        let mut high_pass = Self::new(view, NonZeroUsize::MIN);
+        high_pass.set_fractional_cutoff_period(cutoff_period);
+        high_pass
+    }
+
+    /// Set a fractional cutoff period, recomputing the filter coefficient.
+    /// The filter state is kept, as with `set_cutoff_period`.
+    pub(super) fn set_fractional_cutoff_period(&mut self, cutoff_period: T) {
+        self.cutoff_period = cutoff_period
+            .ceil()
+            .to_usize()
+            .and_then(NonZeroUsize::new)
+            .unwrap_or(NonZeroUsize::MAX);
+        self.alpha = one_pole_alpha(cutoff_period);
+    }
+
+    /// The latest filtered value, also while warming up, which is zero for the first value.
+    #[inline(always)]
+    pub(super) fn hp(&self) -> T {
+        self.hp
+    }
+}
+
+impl<T, V> View<T> for OnePoleHighPass<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        // The filter needs a previous value.
+        if self.n_observed > 0 {
+            self.hp =
+                T::from(0.5).expect("can convert") * (T::one() + self.alpha) * (val - self.val_1)
+                    + self.alpha * self.hp;
+        }
+        self.val_1 = val;
+        self.n_observed += 1;
+    }
+
+    #[inline]
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    /// The differences of values spanning more than the largest float overflow,
+    /// after which the status stays `InvalidReason::Overflow`.
+    fn status(&self) -> Status<T> {
+        if self.n_observed <= self.cutoff_period.get() {
+            return Status::WarmingUp.chained(self.view.status());
+        }
+        if !self.hp.is_finite() {
+            return Status::Invalid(InvalidReason::Overflow);
+        }
+        Status::Ready(self.hp)
+    }
+}
+
+impl<T, V> Introspect for OnePoleHighPass<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("OnePoleHighPass")
+            .param("cutoff_period", self.cutoff_period())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for OnePoleHighPass<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "cutoff_period",
+            self.cutoff_period.get(),
+            5,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "cutoff_period" => self.set_cutoff_period(window_len_param("cutoff_period", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+/// The cutoff period of a 1-pole high-pass filter, which must be at least 5,
+/// as the coefficient diverges at a period of 4 samples.
+fn check_one_pole_period(cutoff_period: NonZeroUsize) -> Result<(), Error> {
+    if cutoff_period.get() < 5 {
+        return Err(Error::OnePoleHighPassLen(cutoff_period.get()));
+    }
+    Ok(())
+}
+
+/// The coefficient of the 1-pole high-pass filter, also for fractional cutoff periods.
+pub(super) fn one_pole_alpha<T: Float>(cutoff_period: T) -> T {
+    let angle = T::from(2.0 * PI).expect("can convert") / cutoff_period;
+    (T::one() - angle.sin()) / angle.cos()
+}
+
+/// 2-pole high-pass filter by John Ehlers
+/// from: "Cycle Analytics for Traders" by John Ehlers, 2013
+/// Attenuates the cycles longer than `cutoff_period` by 12 dB per octave, which removes the trend more sharply
+/// than the `OnePoleHighPass`, e.g. ahead of the `SuperSmoother` in the `RoofingFilter`.
+/// Ready after `cutoff_period + 2` values, i.e. once it has warmed up over its cutoff period
+/// past the two previous values it needs.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct TwoPoleHighPass<T, V> {
+    view: V,
+    /// The cutoff period, beyond which cycles are attenuated.
+    #[getset(get_copy = "pub")]
+    cutoff_period: NonZeroUsize,
+    alpha: T,
+    n_observed: usize,
+    // previous value
+    val_1: T,
+    // value from 2 steps ago
+    val_2: T,
+    hp: T,
+    // high pass filter value of previous step
+    hp_1: T,
+}
+
+impl<T, V> TwoPoleHighPass<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new 2-pole high-pass filter with a chained View
+    pub fn new(view: V, cutoff_period: NonZeroUsize) -> Self {
+        Self {
+            view,
+            cutoff_period,
+            alpha: two_pole_alpha(cutoff_period),
+            n_observed: 0,
+            val_1: T::zero(),
+            val_2: T::zero(),
+            hp: T::zero(),
+            hp_1: T::zero(),
+        }
+    }
+
+    /// Create a new 2-pole high-pass filter with a chained View,
+    /// where the cutoff period must be at least 3 for the filter to be stable.
+    pub fn try_new(view: V, cutoff_period: NonZeroUsize) -> Result<Self, Error> {
+        check_two_pole_period(cutoff_period)?;
+        Ok(Self::new(view, cutoff_period))
+    }
+
+    /// Set the cutoff period, which must be at least 3, recomputing the filter coefficient.
+    /// The filter state is kept, while growing it waits for the additional values to be observed.
+    pub fn set_cutoff_period(&mut self, cutoff_period: NonZeroUsize) -> Result<(), Error> {
+        check_two_pole_period(cutoff_period)?;
+        self.cutoff_period = cutoff_period;
+        self.alpha = two_pole_alpha(cutoff_period);
+        Ok(())
+    }
+}
+
+impl<T, V> View<T> for TwoPoleHighPass<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        let two = T::from(2.0).expect("can convert");
+        // The filter needs two previous values.
+        let hp = if self.n_observed < 2 {
+            T::zero()
+        } else {
+            (T::one() - self.alpha / two).powi(2) * (val - two * self.val_1 + self.val_2)
+                + two * (T::one() - self.alpha) * self.hp
+                - (T::one() - self.alpha).powi(2) * self.hp_1
+        };
+        self.hp_1 = self.hp;
+        self.hp = hp;
+
+        self.val_2 = self.val_1;
+        self.val_1 = val;
+        self.n_observed += 1;
+    }
+
+    #[inline]
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    /// The second differences of values close to the largest float overflow,
+    /// after which the status stays `InvalidReason::Overflow`.
+    fn status(&self) -> Status<T> {
+        if self.n_observed <= self.cutoff_period.get() + 1 {
+            return Status::WarmingUp.chained(self.view.status());
+        }
+        if !self.hp.is_finite() {
+            return Status::Invalid(InvalidReason::Overflow);
+        }
+        Status::Ready(self.hp)
+    }
+}
+
+impl<T, V> Introspect for TwoPoleHighPass<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("TwoPoleHighPass")
+            .param("cutoff_period", self.cutoff_period())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for TwoPoleHighPass<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "cutoff_period",
+            self.cutoff_period.get(),
+            3,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "cutoff_period" => self.set_cutoff_period(window_len_param("cutoff_period", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+/// The cutoff period of a 2-pole high-pass filter, which must be at least 3 for the filter to be stable.
+fn check_two_pole_period(cutoff_period: NonZeroUsize) -> Result<(), Error> {
+    if cutoff_period.get() < 3 {
+        return Err(Error::TwoPoleHighPassLen(cutoff_period.get()));
+    }
+    Ok(())
+}
+
+/// The coefficient of the 2-pole high-pass filter.
+fn two_pole_alpha<T: Float>(cutoff_period: NonZeroUsize) -> T {
+    // NOTE: 0.707 * 2 * PI radians are the 0.707 * 360 degrees of the original formula.
+    let angle = T::from(0.707 * 2.0 * PI).expect("can convert")
+        / T::from(cutoff_period.get()).expect("can convert");
+    (angle.cos() + angle.sin() - T::one()) / angle.cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::view_properties,
+        pure_functions::Echo,
+        test_data::TEST_DATA,
+    };
+
+    fn len(len: usize) -> NonZeroUsize {
+        NonZeroUsize::new(len).unwrap()
+    }
+
+    /// The amplitude of the filtered sine of `period`, once the filter has settled.
+    fn amplitude<V: View<f64>>(mut view: V, period: f64) -> f64 {
+        let vals = generators::sine(600, period, 1.0, 100.0);
+        view.update_batch(&vals)[300..]
+            .iter()
+            .flatten()
+            .fold(0.0, |max, out| out.abs().max(max))
+    }
+
+    #[test]
+    fn high_pass_overflow() {
+        let vals = [f64::MAX, -f64::MAX, f64::MAX, -f64::MAX];
+        let mut one_pole = OnePoleHighPass::new(Echo::new(), len(1));
+        one_pole.update_batch(&vals);
+        assert_eq!(one_pole.status(), Status::Invalid(InvalidReason::Overflow));
+        let mut two_pole = TwoPoleHighPass::new(Echo::new(), len(1));
+        two_pole.update_batch(&vals);
+        assert_eq!(two_pole.status(), Status::Invalid(InvalidReason::Overflow));
+    }
+
+    #[test]
+    fn one_pole_high_pass_attenuation() {
+        // The trend is removed, up to the decaying response to its start.
+        let trend: Vec<f64> = (0..300).map(|i| 100.0 + 0.5 * i as f64).collect();
+        let outs = OnePoleHighPass::new(Echo::new(), len(20)).update_batch(&trend);
+        assert_eq!(outs.iter().position(Option::is_some), Some(20));
+        let out = outs.last().unwrap().unwrap();
+        // The ramp settles at its slope times the time constant of the filter.
+        let alpha = one_pole_alpha(20.0);
+        assert!(
+            (out - 0.5 * 0.5 * (1.0 + alpha) / (1.0 - alpha)).abs() < 1e-9,
+            "{out}"
+        );
+
+        // Cycles shorter than the cutoff pass, while longer ones are attenuated, by 6 dB per octave.
+        assert!(amplitude(OnePoleHighPass::new(Echo::new(), len(20)), 10.0) > 0.85);
+        let long = amplitude(OnePoleHighPass::new(Echo::new(), len(20)), 80.0);
+        assert!(long < 0.3, "{long}");
+    }
+
+    #[test]
+    fn two_pole_high_pass_attenuation() {
+        // The ramp is removed completely.
+        let trend: Vec<f64> = (0..300).map(|i| 100.0 + 0.5 * i as f64).collect();
+        let outs = TwoPoleHighPass::new(Echo::new(), len(20)).update_batch(&trend);
+        assert_eq!(outs.iter().position(Option::is_some), Some(21));
+        assert!(outs.last().unwrap().unwrap().abs() < 1e-6);
+
+        // Cycles longer than the cutoff are attenuated by 12 dB per octave.
+        assert!(amplitude(TwoPoleHighPass::new(Echo::new(), len(20)), 10.0) > 0.85);
+        let long = amplitude(TwoPoleHighPass::new(Echo::new(), len(20)), 80.0);
+        let one_pole = amplitude(OnePoleHighPass::new(Echo::new(), len(20)), 80.0);
+        assert!(long < 0.15 && long < 0.5 * one_pole, "{long} {one_pole}");
+    }
+
+    #[test]
+    fn high_pass_cutoff_period() {
+        assert_eq!(
+            OnePoleHighPass::<f64, _>::try_new(Echo::new(), len(4)).unwrap_err(),
+            Error::OnePoleHighPassLen(4)
+        );
+        assert_eq!(
+            TwoPoleHighPass::<f64, _>::try_new(Echo::new(), len(2)).unwrap_err(),
+            Error::TwoPoleHighPassLen(2)
+        );
+
+        let mut one_pole = OnePoleHighPass::<f64, _>::new(Echo::new(), len(10));
+        assert_eq!(
+            one_pole.set_param("cutoff_period", ParamValue::Int(4)),
+            Err(Error::OnePoleHighPassLen(4))
+        );
+        one_pole.update_batch(&TEST_DATA[..20]);
+        one_pole
+            .set_param("cutoff_period", ParamValue::Int(40))
+            .unwrap();
+        // Growing the cutoff period waits for the additional values.
+        assert_eq!(one_pole.status(), Status::WarmingUp);
+        one_pole.update_batch(&TEST_DATA[20..41]);
+        assert!(one_pole.last().is_some());
+        assert_eq!(
+            one_pole.alpha,
+            OnePoleHighPass::<f64, _>::new(Echo::new(), len(40)).alpha
+        );
+
+        let mut two_pole = TwoPoleHighPass::<f64, _>::new(Echo::new(), len(10));
+        assert_eq!(
+            two_pole.set_param("cutoff_period", ParamValue::Int(2)),
+            Err(Error::TwoPoleHighPassLen(2))
+        );
+        two_pole.update_batch(&TEST_DATA[..20]);
+        two_pole
+            .set_param("cutoff_period", ParamValue::Int(40))
+            .unwrap();
+        // Growing the cutoff period waits for the additional values.
+        assert_eq!(two_pole.status(), Status::WarmingUp);
+        two_pole.update_batch(&TEST_DATA[20..42]);
+        assert!(two_pole.last().is_some());
+        assert_eq!(
+            two_pole.alpha,
+            TwoPoleHighPass::<f64, _>::new(Echo::new(), len(40)).alpha
+        );
+    }
+
+    #[test]
+    fn high_pass_plot() {
+        let mut view = TwoPoleHighPass::new(Echo::new(), len(48));
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/high_pass.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    // The filters are only stable for cutoff periods of at least 5 and 3.
+    mod one_pole_high_pass {
+        use super::*;
+
+        view_properties!(|window_len| OnePoleHighPass::new(
+            Echo::new(),
+            window_len.saturating_add(4)
+        ));
+    }
+
+    mod two_pole_high_pass {
+        use super::*;
+
+        view_properties!(|window_len| TwoPoleHighPass::new(
+            Echo::new(),
+            window_len.saturating_add(2)
+        ));
+    }
+}
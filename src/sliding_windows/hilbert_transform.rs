@@ -0,0 +1,286 @@
+//! John Ehlers Hilbert Transform discriminator
+//! from: <https://www.mesasoftware.com/papers/MAMA.pdf>
+
+use num::Float;
+
+use crate::{
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+};
+
+/// The number of values after which the filter cascade has filled and the period has settled,
+/// as in the lookback of the Hilbert Transform indicators of TA-Lib.
+const WARM_UP_LEN: usize = 32;
+
+/// The longest period measured by the discriminator.
+pub(super) const MAX_PERIOD: usize = 50;
+
+/// John Ehlers Hilbert Transform discriminator
+/// from: <https://www.mesasoftware.com/papers/MAMA.pdf>
+/// Splits the detrended values into their in-phase and quadrature components,
+/// from which it measures the phase, the phase rate and, with a homodyne discriminator,
+/// the dominant cycle period within `[6, 50]` samples.
+/// The output is the smoothed period. Ready once 32 values have been observed.
+#[derive(Debug, Clone)]
+pub struct HilbertTransform<T, V> {
+    view: V,
+    n_observed: usize,
+    // The last ten values, newest first.
+    vals: [T; 10],
+    // The latest smoothed value.
+    smooth: T,
+    // The last ten detrended values, newest first, the oldest seven of which are in-phase components.
+    detrender: [T; 10],
+    // The last seven quadrature components, newest first.
+    quadrature: [T; 7],
+    // The smoothed phasor, advanced by a quarter cycle.
+    i2: T,
+    q2: T,
+    // The smoothed product of the phasor with its previous value.
+    re: T,
+    im: T,
+    period: T,
+    smooth_period: T,
+    phase: T,
+    delta_phase: T,
+}
+
+impl<T, V> HilbertTransform<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Hilbert Transform discriminator with a chained View
+    pub fn new(view: V) -> Self {
+        Self {
+            view,
+            n_observed: 0,
+            vals: [T::zero(); 10],
+            smooth: T::zero(),
+            detrender: [T::zero(); 10],
+            quadrature: [T::zero(); 7],
+            i2: T::zero(),
+            q2: T::zero(),
+            re: T::zero(),
+            im: T::zero(),
+            period: T::zero(),
+            smooth_period: T::zero(),
+            phase: T::zero(),
+            delta_phase: T::zero(),
+        }
+    }
+
+    /// The latest value smoothed by the weights `[4, 3, 2, 1] / 10` of the newest four values,
+    /// from which the components are computed, zero until four values have been observed.
+    #[inline(always)]
+    pub fn smooth(&self) -> T {
+        self.smooth
+    }
+
+    /// The latest in-phase component, zero until the cascade has filled.
+    #[inline(always)]
+    pub fn in_phase(&self) -> T {
+        self.detrender[3]
+    }
+
+    /// The latest quadrature component, zero until the cascade has filled.
+    #[inline(always)]
+    pub fn quadrature(&self) -> T {
+        self.quadrature[0]
+    }
+
+    /// The latest period measured by the homodyne discriminator, before the final smoothing.
+    #[inline(always)]
+    pub fn period(&self) -> T {
+        self.period
+    }
+
+    /// The latest phase of the in-phase and quadrature components in degrees.
+    #[inline(always)]
+    pub fn phase(&self) -> T {
+        self.phase
+    }
+
+    /// The latest phase rate in degrees per sample, which is at least 1.
+    #[inline(always)]
+    pub fn delta_phase(&self) -> T {
+        self.delta_phase
+    }
+}
+
+/// Shift a new value into a history, newest first.
+fn push<T: Copy, const N: usize>(history: &mut [T; N], val: T) {
+    history.rotate_right(1);
+    history[0] = val;
+}
+
+/// The Hilbert Transform FIR filter over the last seven values, newest first.
+fn hilbert<T: Float>(vals: &[T]) -> T {
+    let a = T::from(0.0962).expect("can convert");
+    let b = T::from(0.5769).expect("can convert");
+    a * vals[0] + b * vals[2] - b * vals[4] - a * vals[6]
+}
+
+impl<T, V> View<T> for HilbertTransform<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.n_observed += 1;
+        push(&mut self.vals, val);
+        // The cascade starts once the smoothing window has filled.
+        if self.n_observed < 4 {
+            return;
+        }
+
+        // The coefficients of the Hilbert Transform sum to zero, so detrending the smoothed deviations
+        // from the newest value is the same, without cancelling the level of the values in `f32`.
+        // The smoothed values before the cascade started are zero.
+        let two = T::from(2.0).expect("can convert");
+        let three = T::from(3.0).expect("can convert");
+        let four = T::from(4.0).expect("can convert");
+        let ten = T::from(10.0).expect("can convert");
+        // The weighted sum of the values divided by 16 can't overflow, and the division is exact,
+        // while rounding can push the smoothed value of values close to the largest float beyond them.
+        let sixteen = T::from(16.0).expect("can convert");
+        let [v0, v1, v2, v3] = [0, 1, 2, 3].map(|j| self.vals[j]);
+        self.smooth = ((four * (v0 / sixteen)
+            + three * (v1 / sixteen)
+            + two * (v2 / sixteen)
+            + v3 / sixteen)
+            / ten
+            * sixteen)
+            .max(v0.min(v1).min(v2).min(v3))
+            .min(v0.max(v1).max(v2).max(v3));
+        let n_smoothed = self.n_observed - 3;
+        let smooth: [T; 7] = std::array::from_fn(|i| {
+            if i >= n_smoothed {
+                return -val;
+            }
+            let [d0, d1, d2, d3] = [0, 1, 2, 3].map(|j| self.vals[i + j] - val);
+            (four * d0 + three * d1 + two * d2 + d3) / ten
+        });
+
+        // The gain of the filters is adjusted to the previous period.
+        let gain = T::from(0.075).expect("can convert") * self.period
+            + T::from(0.54).expect("can convert");
+        push(&mut self.detrender, hilbert(&smooth) * gain);
+        push(&mut self.quadrature, hilbert(&self.detrender) * gain);
+        let i1 = self.in_phase();
+        let q1 = self.quadrature();
+
+        // Advance the phase of the components by 90 degrees.
+        let j_i = hilbert(&self.detrender[3..]) * gain;
+        let j_q = hilbert(&self.quadrature) * gain;
+        let alpha = T::from(0.2).expect("can convert");
+        let i2 = alpha * (i1 - j_q) + (T::one() - alpha) * self.i2;
+        let q2 = alpha * (q1 + j_i) + (T::one() - alpha) * self.q2;
+
+        // The homodyne discriminator multiplies the phasor with the conjugate of its previous value,
+        // whose angle is the phase rate.
+        self.re = alpha * (i2 * self.i2 + q2 * self.q2) + (T::one() - alpha) * self.re;
+        self.im = alpha * (i2 * self.q2 - q2 * self.i2) + (T::one() - alpha) * self.im;
+        (self.i2, self.q2) = (i2, q2);
+
+        let prev_period = self.period;
+        let mut period = prev_period;
+        if self.im != T::zero() && self.re != T::zero() {
+            period = T::from(360.0).expect("can convert") / (self.im / self.re).atan().to_degrees();
+        }
+        let period = period
+            .min(T::from(1.5).expect("can convert") * prev_period)
+            .max(T::from(0.67).expect("can convert") * prev_period)
+            .max(T::from(6.0).expect("can convert"))
+            .min(T::from(MAX_PERIOD).expect("can convert"));
+        self.period = alpha * period + (T::one() - alpha) * prev_period;
+        self.smooth_period = T::from(0.33).expect("can convert") * self.period
+            + T::from(0.67).expect("can convert") * self.smooth_period;
+
+        let prev_phase = self.phase;
+        if i1 != T::zero() {
+            self.phase = (q1 / i1).atan().to_degrees();
+        }
+        self.delta_phase = (prev_phase - self.phase).max(T::one());
+        debug_assert!(self.smooth_period.is_finite(), "value must be finite");
+    }
+
+    fn last(&self) -> Option<T> {
+        if self.n_observed < WARM_UP_LEN {
+            return None;
+        }
+        Some(self.smooth_period)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for HilbertTransform<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("HilbertTransform").child(self.view.node())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::view_properties,
+        pure_functions::Echo,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn hilbert_transform_sine_period() {
+        for period in [10.0, 20.0, 30.0] {
+            let vals = generators::sine(500, period, 1.0, 100.0);
+            let mut ht = HilbertTransform::new(Echo::new());
+            let outs = ht.update_batch(&vals);
+            assert_eq!(outs.iter().position(Option::is_some), Some(WARM_UP_LEN - 1));
+            let measured = outs[499].unwrap();
+            assert!(
+                (measured - period).abs() < 0.05 * period,
+                "measured period {measured} of {period}"
+            );
+            // The phase advances by one cycle per period.
+            assert!((ht.delta_phase() - 360.0 / period).abs() < 0.05 * 360.0 / period);
+        }
+    }
+
+    #[test]
+    fn hilbert_transform_smooth() {
+        let mut ht = HilbertTransform::new(Echo::new());
+        ht.update_batch(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(ht.smooth(), 3.0);
+        // The smoothed value of values close to the largest float stays within them.
+        ht.update_batch(&[f64::MAX; 4]);
+        assert_eq!(ht.smooth(), f64::MAX);
+    }
+
+    #[test]
+    fn hilbert_transform_plot() {
+        let mut ht = HilbertTransform::new(Echo::new());
+        let out: Vec<f64> = ht.update_batch(&TEST_DATA).into_iter().flatten().collect();
+        let filename = "img/hilbert_transform.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|_window_len| HilbertTransform::new(Echo::new()));
+}
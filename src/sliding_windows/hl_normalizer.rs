@@ -8,7 +8,22 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// A sliding High - Low Normalizer
 #[derive(Clone, Debug, CopyGetters)]
@@ -42,14 +57,24 @@ where
             init: true,
         }
     }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
+        }
+        if let Some((min, max)) = extent_queue(&self.q_vals) {
+            (self.min, self.max) = (min, max);
+        }
+    }
 }
 
-fn extent_queue<T: Float>(q: &VecDeque<T>) -> (T, T) {
-    let mut min = *q.front().unwrap();
-    let mut max = *q.front().unwrap();
+fn extent_queue<T: Float>(q: &VecDeque<T>) -> Option<(T, T)> {
+    let mut min = *q.front()?;
+    let mut max = min;
 
-    for i in 0..q.len() {
-        let val = *q.get(i).unwrap();
+    for val in q.iter().copied() {
         if val > max {
             max = val;
         }
@@ -58,7 +83,7 @@ fn extent_queue<T: Float>(q: &VecDeque<T>) -> (T, T) {
         }
     }
 
-    (min, max)
+    Some((min, max))
 }
 
 impl<T, V> View<T> for HLNormalizer<T, V>
@@ -72,7 +97,7 @@ where
         let Some(view_last) = self.view.last() else {
             return;
         };
-        debug_assert!(val.is_finite(), "value must be finite");
+        debug_assert!(view_last.is_finite(), "value must be finite");
 
         if self.init {
             self.init = false;
@@ -81,13 +106,11 @@ where
             self.last = view_last;
         }
         if self.q_vals.len() >= self.window_len.get() {
-            let old = *self.q_vals.front().unwrap();
+            let old = self.q_vals.pop_front().unwrap();
             if old <= self.min || old >= self.max {
-                let (min, max) = extent_queue(&self.q_vals);
-                self.min = min;
-                self.max = max;
+                // re-compute the extent without the removed value.
+                (self.min, self.max) = extent_queue(&self.q_vals).unwrap_or((view_last, view_last));
             }
-            self.q_vals.pop_front();
         }
         self.q_vals.push_back(view_last);
         if view_last > self.max {
@@ -99,24 +122,83 @@ where
         self.last = view_last;
     }
 
+    /// A flat window outputs zero, while its status is `InvalidReason::FlatRange`.
+    /// Before the first value there is no window, so there is no output, unlike the zero of earlier versions.
     fn last(&self) -> Option<T> {
+        match self.status() {
+            Status::Invalid(InvalidReason::FlatRange) => Some(T::zero()),
+            status => status.value(),
+        }
+    }
+
+    fn status(&self) -> Status<T> {
+        if self.init {
+            return Status::WarmingUp.chained(self.view.status());
+        }
         if self.last == self.min && self.last == self.max {
-            Some(T::zero())
+            return Status::Invalid(InvalidReason::FlatRange);
+        }
+        let two = T::from(2.0).expect("can convert");
+        let range = self.max - self.min;
+        let out = if range.is_finite() {
+            -T::one() + two * ((self.last - self.min) / range)
         } else {
-            let out = -T::one()
-                + (((self.last - self.min) * T::from(2.0).expect("can convert"))
-                    / (self.max - self.min));
+            // The range of values beyond half the largest float overflows, unlike the range of their halves.
+            -T::one()
+                + two * ((self.last / two - self.min / two) / (self.max / two - self.min / two))
+        };
+
+        debug_assert!(out.is_finite(), "value must be finite");
+        Status::Ready(out)
+    }
+}
 
-            debug_assert!(out.is_finite(), "value must be finite");
-            Some(out)
+impl<T, V> Introspect for HLNormalizer<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("HLNormalizer")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for HLNormalizer<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
         }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
     use crate::{
+        properties::{
+            self,
+            check_bounded,
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
@@ -131,4 +213,45 @@ mod tests {
             assert!(last >= -1.0);
         }
     }
+
+    #[test]
+    fn normalizer_flat_range() {
+        let mut n = HLNormalizer::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        assert_eq!(n.status(), Status::WarmingUp);
+        n.update(1.0);
+        n.update(1.0);
+        assert_eq!(n.last(), Some(0.0));
+        assert_eq!(n.status(), Status::Invalid(InvalidReason::FlatRange));
+        n.update(2.0);
+        assert_eq!(n.status(), Status::Ready(1.0));
+    }
+
+    #[test]
+    fn normalizer_before_first_value() {
+        let n = HLNormalizer::<f64, _>::new(Echo::new(), NonZeroUsize::new(3).unwrap());
+        assert_eq!(n.last(), None);
+        assert_eq!(n.status(), Status::WarmingUp);
+    }
+
+    #[test]
+    fn normalizer_huge_range() {
+        // The range of the extremes overflows, unlike the normalized values.
+        let mut n = HLNormalizer::new(Echo::new(), NonZeroUsize::new(3).unwrap());
+        n.update_batch(&[f64::MAX, -f64::MAX, 0.0]);
+        assert_eq!(n.status(), Status::Ready(0.0));
+    }
+
+    view_properties!(|window_len| HLNormalizer::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| HLNormalizer::new(Echo::new(), window_len));
+
+    proptest! {
+        #[test]
+        fn hl_normalizer_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            check_bounded(HLNormalizer::new(Echo::new(), window_len), &vals, -1.0, 1.0)?;
+        }
+    }
 }
@@ -0,0 +1,234 @@
+//! Hull Moving Average by Alan Hull
+//! from: <https://alanhull.com/hull-moving-average>
+
+use std::num::NonZeroUsize;
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::{
+    Wma,
+    extrapolation::{
+        scale,
+        scaled_sum,
+        unscaled,
+    },
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// Hull Moving Average by Alan Hull
+/// from: <https://alanhull.com/hull-moving-average>
+/// Composed of WMAs as `WMA(2 * WMA(n / 2) - WMA(n), sqrt(n))`, which reduces the lag of a WMA of length `n`.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct HullMa<T, V> {
+    view: V,
+    /// The sliding window length.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    wma_half: Wma<T, Echo<T>>,
+    wma_full: Wma<T, Echo<T>>,
+    wma_sqrt: Wma<T, Echo<T>>,
+}
+
+impl<T, V> HullMa<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Hull Moving Average with a chained View
+    /// and a given sliding window length
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        let (half, sqrt) = sub_window_lens(window_len);
+        Self {
+            view,
+            window_len,
+            wma_half: Wma::new(Echo::new(), half),
+            wma_full: Wma::new(Echo::new(), window_len),
+            wma_sqrt: Wma::new(Echo::new(), sqrt),
+        }
+    }
+
+    /// Set the window length, where shrinking the WMAs evicts their oldest values.
+    /// The final WMA starts over, as its values were computed from WMAs of the previous lengths.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        if window_len == self.window_len {
+            return;
+        }
+        let (half, sqrt) = sub_window_lens(window_len);
+        self.window_len = window_len;
+        self.wma_half.set_window_len(half);
+        self.wma_full.set_window_len(window_len);
+        self.wma_sqrt = Wma::new(Echo::new(), sqrt);
+    }
+}
+
+/// The weights of `2 * WMA(n / 2) - WMA(n)`.
+fn weights<T: Float>() -> [T; 2] {
+    [T::from(2.0).expect("can convert"), -T::one()]
+}
+
+/// The window lengths `n / 2` and `sqrt(n)` of the inner WMAs, rounded down but at least 1.
+fn sub_window_lens(window_len: NonZeroUsize) -> (NonZeroUsize, NonZeroUsize) {
+    let half = NonZeroUsize::new(window_len.get() / 2).unwrap_or(NonZeroUsize::MIN);
+    let sqrt = NonZeroUsize::new(window_len.isqrt().get()).unwrap_or(NonZeroUsize::MIN);
+    (half, sqrt)
+}
+
+impl<T, V> View<T> for HullMa<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.wma_half.update(val);
+        self.wma_full.update(val);
+        if let (Some(half), Some(full)) = (self.wma_half.last(), self.wma_full.last()) {
+            let weights = weights();
+            self.wma_sqrt
+                .update(scaled_sum(&weights, [half, full], scale(&weights)));
+        }
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.wma_sqrt.last())
+            .and_then(|scaled| unscaled(scaled, scale(&weights())))
+            .chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for HullMa<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("HullMa")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for HullMa<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        InvalidReason,
+        plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn hull_ma_linear_trend() {
+        // A WMA of length `n` lags a linear trend by `(n - 1) / 3` values, which is 5 for the full WMA,
+        // but only `2 * 7 / 3 - 15 / 3 + 3 / 3 = 2 / 3` for the Hull MA composed of WMAs of length 8, 16 and 4.
+        let slope = 2.0;
+        let vals: Vec<f64> = (0..64).map(|i| slope * i as f64).collect();
+        let mut hull = HullMa::new(Echo::new(), NonZeroUsize::new(16).unwrap());
+        let outs = hull.update_batch(&vals);
+        assert_eq!(outs.iter().position(Option::is_some), Some(15 + 3));
+        for (val, out) in vals.iter().zip(outs).skip(18) {
+            assert!((out.unwrap() - (val - slope * 2.0 / 3.0)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn hull_ma_plot() {
+        let mut hull = HullMa::new(Echo::new(), NonZeroUsize::new(16).unwrap());
+        let out: Vec<f64> = hull
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/hull_ma.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    #[test]
+    fn hull_ma_overflow() {
+        let mut hull = HullMa::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        hull.update_batch(&[-f64::MAX, f64::MAX]);
+        assert_eq!(hull.status(), Status::Invalid(InvalidReason::Overflow));
+        assert_eq!(hull.last(), None);
+        hull.update(f64::MAX);
+        assert_eq!(hull.last(), Some(f64::MAX));
+    }
+
+    #[test]
+    fn hull_ma_set_window_len() {
+        // Growing the window from 4 to 16 values must not keep the differences of the WMAs of length 2 and 4.
+        let mut hull = HullMa::new(Echo::new(), NonZeroUsize::new(4).unwrap());
+        hull.update_batch(&TEST_DATA[..20]);
+        hull.set_param("window_len", ParamValue::Int(16)).unwrap();
+        let mut fresh = HullMa::new(Echo::new(), NonZeroUsize::new(16).unwrap());
+        fresh.update_batch(&TEST_DATA[..20]);
+        assert_eq!(hull.param_specs(), fresh.param_specs());
+        // Warmed up again once the full WMA has refilled and the final WMA has seen 4 differences.
+        let outs = hull.update_batch(&TEST_DATA[20..]);
+        assert_eq!(outs.iter().position(Option::is_some), Some(11 + 3));
+        for (out, expected) in outs
+            .into_iter()
+            .zip(fresh.update_batch(&TEST_DATA[20..]))
+            .skip(14)
+        {
+            assert!((out.unwrap() - expected.unwrap()).abs() < 1e-9);
+        }
+    }
+
+    view_properties!(|window_len| HullMa::new(Echo::new(), window_len));
+
+    reconfigure_properties!(
+        |window_len| HullMa::new(Echo::new(), window_len),
+        refill = |window_len| window_len.get() + window_len.isqrt().get()
+    );
+}
@@ -0,0 +1,223 @@
+//! Instantaneous Trendline by John Ehlers
+//! from: "Rocket Science for Traders" by John Ehlers, 2001
+
+use std::collections::VecDeque;
+
+use num::Float;
+
+use super::{
+    HilbertTransform,
+    hilbert_transform::MAX_PERIOD,
+};
+use crate::{
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+    pure_functions::Echo,
+};
+
+/// Instantaneous Trendline by John Ehlers
+/// from: "Rocket Science for Traders" by John Ehlers, 2001
+/// Averages the values over one dominant cycle period, measured by a `HilbertTransform`,
+/// which removes the cycle and leaves the trend, and smooths the averages with a 4-bar weighted moving average.
+/// Unlike the original average over the period rounded to whole samples,
+/// the last sample is weighted by the fraction of the period, so the trendline does not jump as the period changes.
+/// Ready once the values span the longest period of 50 samples and three averages have been smoothed,
+/// i.e. after 53 values.
+#[derive(Debug, Clone)]
+pub struct InstantaneousTrendline<T, V> {
+    view: V,
+    hilbert: HilbertTransform<T, Echo<T>>,
+    // The values, newest first.
+    q_vals: VecDeque<T>,
+    // The last four averages over the dominant cycle, newest first.
+    trends: [T; 4],
+    n_trends: usize,
+}
+
+impl<T, V> InstantaneousTrendline<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Instantaneous Trendline with a chained View
+    pub fn new(view: V) -> Self {
+        Self {
+            view,
+            hilbert: HilbertTransform::new(Echo::new()),
+            q_vals: VecDeque::with_capacity(MAX_PERIOD),
+            trends: [T::zero(); 4],
+            n_trends: 0,
+        }
+    }
+
+    /// The `HilbertTransform` measuring the dominant cycle period.
+    #[inline(always)]
+    pub fn hilbert(&self) -> &HilbertTransform<T, Echo<T>> {
+        &self.hilbert
+    }
+}
+
+impl<T, V> View<T> for InstantaneousTrendline<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.hilbert.update(val);
+        if self.q_vals.len() == MAX_PERIOD {
+            self.q_vals.pop_back();
+        }
+        self.q_vals.push_front(val);
+        if self.q_vals.len() < MAX_PERIOD {
+            return;
+        }
+        let period = self
+            .hilbert
+            .last()
+            .expect("is ready before the values span 50 samples")
+            .max(T::one());
+
+        // Average the values over one period, weighting the value beyond the last whole sample by the fraction.
+        let n_whole = period.floor();
+        let fraction = period - n_whole;
+        let n_whole = n_whole.to_usize().expect("can convert");
+        let weighted = self
+            .q_vals
+            .iter()
+            .take(n_whole + 1)
+            .enumerate()
+            .map(|(i, val)| (if i < n_whole { T::one() } else { fraction }, *val));
+        let sum = weighted
+            .clone()
+            .fold(T::zero(), |sum, (weight, val)| sum + weight * val);
+        let mut trend = sum / period;
+        if !trend.is_finite() {
+            // The mean of the largest floats is representable, unlike their sum,
+            // and clamping it to the values removes the rounding errors of the divided values.
+            let (mean, min, max) = weighted.fold(
+                (T::zero(), T::infinity(), T::neg_infinity()),
+                |(mean, min, max), (weight, val)| {
+                    (mean + weight * (val / period), min.min(val), max.max(val))
+                },
+            );
+            trend = mean.max(min).min(max);
+        }
+        let [t1, t2, t3, _] = self.trends;
+        self.trends = [trend, t1, t2, t3];
+        self.n_trends += 1;
+    }
+
+    fn last(&self) -> Option<T> {
+        if self.n_trends < self.trends.len() {
+            return None;
+        }
+        let [t0, t1, t2, t3] = self.trends;
+        let (two, three, four) = (
+            T::from(2.0).expect("can convert"),
+            T::from(3.0).expect("can convert"),
+            T::from(4.0).expect("can convert"),
+        );
+        let ten = T::from(10.0).expect("can convert");
+        let mut out = (four * t0 + three * t1 + two * t2 + t3) / ten;
+        if !out.is_finite() {
+            // Dividing the averages first keeps the weighted sum of the largest floats finite,
+            // and clamping it to the averages removes the rounding errors.
+            out = (four * (t0 / ten) + three * (t1 / ten) + two * (t2 / ten) + t3 / ten)
+                .max(t0.min(t1).min(t2).min(t3))
+                .min(t0.max(t1).max(t2).max(t3));
+        }
+        debug_assert!(out.is_finite(), "value must be finite");
+        Some(out)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for InstantaneousTrendline<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("InstantaneousTrendline").child(self.view.node())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::view_properties,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn instantaneous_trendline_cycle() {
+        for period in [15.0, 20.0, 30.0] {
+            let vals = generators::sine(500, period, 1.0, 100.0);
+            let outs = InstantaneousTrendline::new(Echo::new()).update_batch(&vals);
+            assert_eq!(outs.iter().position(Option::is_some), Some(MAX_PERIOD + 2));
+            // Averaging over the cycle leaves its offset.
+            for out in outs[200..].iter().flatten() {
+                assert!((out - 100.0).abs() < 0.05, "trendline {out} of {period}");
+            }
+        }
+    }
+
+    #[test]
+    fn instantaneous_trendline_trend() {
+        let vals: Vec<f64> = generators::sine(500, 20.0, 1.0, 100.0)
+            .into_iter()
+            .enumerate()
+            .map(|(i, val)| val + 0.05 * i as f64)
+            .collect();
+        let outs: Vec<f64> = InstantaneousTrendline::new(Echo::new())
+            .update_batch(&vals)
+            .into_iter()
+            .flatten()
+            .collect();
+        // The trendline rises with the slope of the trend,
+        // while the cycle, whose slope is up to 0.31 per sample, is mostly removed.
+        let slopes: Vec<f64> = outs[200..].windows(2).map(|w| w[1] - w[0]).collect();
+        for slope in &slopes {
+            assert!((slope - 0.05).abs() < 0.025, "slope {slope}");
+        }
+        let mean_slope = slopes.iter().sum::<f64>() / slopes.len() as f64;
+        assert!((mean_slope - 0.05).abs() < 0.002, "mean slope {mean_slope}");
+    }
+
+    #[test]
+    fn instantaneous_trendline_extreme_values() {
+        for val in [f64::MAX, -f64::MAX] {
+            let outs = InstantaneousTrendline::new(Echo::new()).update_batch(&[val; 100]);
+            assert!(outs[MAX_PERIOD + 2..].iter().all(|out| *out == Some(val)));
+        }
+    }
+
+    #[test]
+    fn instantaneous_trendline_plot() {
+        let mut view = InstantaneousTrendline::new(Echo::new());
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/instantaneous_trendline.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|_window_len| InstantaneousTrendline::new(Echo::new()));
+}
@@ -0,0 +1,317 @@
+//! John Ehlers Inverse Fisher Transform
+//! from: <https://www.mesasoftware.com/papers/TheInverseFisherTransform.pdf>
+
+use std::num::NonZeroUsize;
+
+use num::Float;
+
+use super::Wma;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamSpec,
+        Reconfigure,
+        float_param,
+    },
+    pure_functions::Echo,
+};
+
+/// John Ehlers Inverse Fisher Transform
+/// from: <https://www.mesasoftware.com/papers/TheInverseFisherTransform.pdf>
+/// Centers and scales the values by `scale * (val - center)` into about `[-5, 5]`,
+/// smooths them with a moving average and applies `(e^2x - 1) / (e^2x + 1)`,
+/// i.e. the `Tanh`, which saturates them into `(-1, 1)`,
+/// so the output switches quickly between the bounds, giving clear buy and sell signals at the crossings of +-0.5.
+/// E.g. Ehlers transforms the `Rsi`, within `[0, 100]`, by `0.1 * (rsi - 50)`, see `with_rsi_scaling`,
+/// and the `CyberCycle`, which is centered at zero, by a scale matching its amplitude.
+/// Ready once the moving average is.
+#[derive(Debug, Clone)]
+pub struct InverseFisherTransform<T, V, M> {
+    view: V,
+    moving_average: M,
+    center: T,
+    scale: T,
+}
+
+impl<T, V, M> InverseFisherTransform<T, V, M>
+where
+    V: View<T>,
+    M: View<T>,
+    T: Float,
+{
+    /// Create a new Inverse Fisher Transform with a chained View, the moving average smoothing the scaled values,
+    /// and the center and scale of the values.
+    pub fn new(view: V, moving_average: M, center: T, scale: T) -> Self {
+        debug_assert!(center.is_finite(), "value must be finite");
+        debug_assert!(scale.is_finite(), "value must be finite");
+        Self {
+            view,
+            moving_average,
+            center,
+            scale,
+        }
+    }
+
+    /// Create a new Inverse Fisher Transform with a chained View, the moving average smoothing the scaled values,
+    /// and the center and scale of the values, where the center must be finite and the scale positive and finite.
+    pub fn try_new(view: V, moving_average: M, center: T, scale: T) -> Result<Self, Error> {
+        check_center(center)?;
+        check_scale(scale)?;
+        Ok(Self::new(view, moving_average, center, scale))
+    }
+
+    /// The center of the values, which is subtracted before scaling.
+    #[inline(always)]
+    pub fn center(&self) -> T {
+        self.center
+    }
+
+    /// The scale of the centered values.
+    #[inline(always)]
+    pub fn scale(&self) -> T {
+        self.scale
+    }
+
+    /// Set the center of the values, which must be finite.
+    pub fn set_center(&mut self, center: T) -> Result<(), Error> {
+        check_center(center)?;
+        self.center = center;
+        Ok(())
+    }
+
+    /// Set the scale of the centered values, which must be positive and finite.
+    pub fn set_scale(&mut self, scale: T) -> Result<(), Error> {
+        check_scale(scale)?;
+        self.scale = scale;
+        Ok(())
+    }
+}
+
+impl<T, V> InverseFisherTransform<T, V, Wma<T, Echo<T>>>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Inverse Fisher Transform of an `Rsi` with Ehlers' conventions,
+    /// scaling the values by `0.1 * (rsi - 50)` and smoothing them with a `Wma` of 9 values.
+    pub fn with_rsi_scaling(view: V) -> Self {
+        Self::new(
+            view,
+            Wma::new(Echo::new(), NonZeroUsize::new(9).expect("is not zero")),
+            T::from(50.0).expect("can convert"),
+            T::from(0.1).expect("can convert"),
+        )
+    }
+}
+
+impl<T, V, M> View<T> for InverseFisherTransform<T, V, M>
+where
+    V: View<T>,
+    M: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        // The output saturates long before the scaled values overflow, so they are limited to the largest float.
+        let scaled = (self.scale * (val - self.center))
+            .max(T::min_value())
+            .min(T::max_value());
+        self.moving_average.update(scaled);
+    }
+
+    fn last(&self) -> Option<T> {
+        let smoothed = self.moving_average.last()?;
+        // `tanh(x) = (e^2x - 1) / (e^2x + 1)`, without overflowing for large values.
+        let out = smoothed.tanh();
+        debug_assert!(out.is_finite(), "value must be finite");
+        Some(out)
+    }
+
+    /// An overflowed moving average, e.g. of extrapolating weights, makes the output invalid.
+    fn status(&self) -> Status<T> {
+        match self.moving_average.status() {
+            Status::Invalid(reason) => Status::Invalid(reason),
+            _ => Status::from(self.last()).chained(self.view.status()),
+        }
+    }
+}
+
+impl<T, V, M> Introspect for InverseFisherTransform<T, V, M>
+where
+    V: Introspect,
+    M: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("InverseFisherTransform")
+            .param("center", self.center.to_f64().expect("can convert"))
+            .param("scale", self.scale.to_f64().expect("can convert"))
+            .child(self.view.node())
+            .child(self.moving_average.node())
+    }
+}
+
+impl<T, V, M> Reconfigure for InverseFisherTransform<T, V, M>
+where
+    V: View<T>,
+    M: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::float("center", self.center, Bound::Unbounded, Bound::Unbounded),
+            ParamSpec::float("scale", self.scale, Bound::Exclusive(0.0), Bound::Unbounded),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "center" => self.set_center(float_param("center", val)?),
+            "scale" => self.set_scale(float_param("scale", val)?),
+            _ => Err(Error::UnknownParameter),
+        }
+    }
+}
+
+fn check_center<T: Float>(center: T) -> Result<(), Error> {
+    if !center.is_finite() {
+        return Err(Error::InverseFisherTransformCenter(
+            center.to_f64().expect("can convert"),
+        ));
+    }
+    Ok(())
+}
+
+fn check_scale<T: Float>(scale: T) -> Result<(), Error> {
+    if !(scale > T::zero() && scale.is_finite()) {
+        return Err(Error::InverseFisherTransformScale(
+            scale.to_f64().expect("can convert"),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        plot::plot_values,
+        properties::{
+            self,
+            check_bounded,
+            view_properties,
+        },
+        sliding_windows::{
+            CyberCycle,
+            Rsi,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn inverse_fisher_transform_rsi_scaling() {
+        // The transform maps the center to zero and saturates the extremes of the RSI.
+        let mut view = InverseFisherTransform::with_rsi_scaling(Echo::new());
+        view.update_batch(&[50.0; 9]);
+        assert_eq!(view.last(), Some(0.0));
+        view.update_batch(&[100.0; 9]);
+        assert!((view.last().unwrap() - 5.0.tanh()).abs() < 1e-12);
+        view.update_batch(&[0.0; 9]);
+        assert!((view.last().unwrap() + 5.0.tanh()).abs() < 1e-12);
+        // An RSI of 70 still crosses the signal level.
+        view.update_batch(&[70.0; 9]);
+        assert!(view.last().unwrap() > 0.5);
+    }
+
+    #[test]
+    fn inverse_fisher_transform_set_param() {
+        assert_eq!(
+            InverseFisherTransform::try_new(Echo::new(), Echo::new(), 0.0, 0.0).unwrap_err(),
+            Error::InverseFisherTransformScale(0.0)
+        );
+        let mut view = InverseFisherTransform::<f64, _, _>::with_rsi_scaling(Echo::new());
+        assert_eq!(
+            view.set_param("center", ParamValue::Float(f64::INFINITY)),
+            Err(Error::InverseFisherTransformCenter(f64::INFINITY))
+        );
+        view.set_param("center", ParamValue::Float(0.0)).unwrap();
+        view.set_param("scale", ParamValue::Float(2.0)).unwrap();
+        let mut fresh = InverseFisherTransform::new(
+            Echo::new(),
+            Wma::new(Echo::new(), NonZeroUsize::new(9).unwrap()),
+            0.0,
+            2.0,
+        );
+        assert_eq!(view.param_specs(), fresh.param_specs());
+        assert_eq!(
+            view.update_batch(&TEST_DATA),
+            fresh.update_batch(&TEST_DATA)
+        );
+    }
+
+    #[test]
+    fn inverse_fisher_transform_extreme_values() {
+        let mut view = InverseFisherTransform::new(Echo::new(), Echo::new(), 0.0, 1e300);
+        assert_eq!(
+            view.update_batch(&[1e300, -f64::MAX]),
+            vec![Some(1.0), Some(-1.0)]
+        );
+    }
+
+    #[test]
+    fn inverse_fisher_transform_plot() {
+        let window_len = NonZeroUsize::new(5).unwrap();
+        let mut view = InverseFisherTransform::with_rsi_scaling(Rsi::new(Echo::new(), window_len));
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/inverse_fisher_transform.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(
+        |window_len| InverseFisherTransform::with_rsi_scaling(Rsi::new(Echo::new(), window_len))
+    );
+
+    proptest! {
+        #[test]
+        fn inverse_fisher_transform_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            check_bounded(
+                InverseFisherTransform::with_rsi_scaling(Rsi::new(Echo::new(), window_len)),
+                &vals,
+                -1.0,
+                1.0,
+            )?;
+            check_bounded(
+                InverseFisherTransform::new(
+                    CyberCycle::new(Echo::new(), window_len),
+                    Wma::new(Echo::new(), NonZeroUsize::new(4).unwrap()),
+                    0.0,
+                    1.0,
+                ),
+                &vals,
+                -1.0,
+                1.0,
+            )?;
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! KAMA - Kaufman Adaptive Moving Average
+//! from: "Smarter Trading" by Perry J. Kaufman, 1995
+
+use std::{
+    collections::VecDeque,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
+
+/// KAMA - Kaufman Adaptive Moving Average
+/// from: "Smarter Trading" by Perry J. Kaufman, 1995
+/// An EMA whose smoothing constant moves between those of the `fast_len` and the `slow_len` EMA,
+/// following the efficiency ratio of the net change over the window to the sum of its absolute changes.
+/// Ready once `window_len` changes have been observed.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct Kama<T, V> {
+    view: V,
+    /// The number of changes of the efficiency ratio.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    /// The window length of the EMA in a trending market.
+    #[getset(get_copy = "pub")]
+    fast_len: NonZeroUsize,
+    /// The window length of the EMA in a choppy market.
+    #[getset(get_copy = "pub")]
+    slow_len: NonZeroUsize,
+    q_vals: VecDeque<T>,
+    kama: T,
+    out: Option<T>,
+}
+
+impl<T, V> Kama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Kaufman Adaptive Moving Average with a chained View, a given window length
+    /// and Kaufman's default fast and slow EMA lengths of 2 and 30
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self::new_custom(
+            view,
+            window_len,
+            NonZeroUsize::new(2).expect("is non-zero"),
+            NonZeroUsize::new(30).expect("is non-zero"),
+        )
+    }
+
+    /// Create a new Kaufman Adaptive Moving Average with custom fast and slow EMA lengths
+    pub fn new_custom(
+        view: V,
+        window_len: NonZeroUsize,
+        fast_len: NonZeroUsize,
+        slow_len: NonZeroUsize,
+    ) -> Self {
+        Self {
+            view,
+            window_len,
+            fast_len,
+            slow_len,
+            q_vals: VecDeque::with_capacity(window_len.get() + 1),
+            kama: T::zero(),
+            out: None,
+        }
+    }
+
+    /// Set the window length, where shrinking evicts the oldest changes.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() + 1 {
+            self.q_vals.pop_front();
+        }
+    }
+
+    /// Set the window length of the EMA in a trending market.
+    pub fn set_fast_len(&mut self, fast_len: NonZeroUsize) {
+        self.fast_len = fast_len;
+    }
+
+    /// Set the window length of the EMA in a choppy market.
+    pub fn set_slow_len(&mut self, slow_len: NonZeroUsize) {
+        self.slow_len = slow_len;
+    }
+}
+
+/// The smoothing constant of an EMA of the given window length.
+fn ema_alpha<T: Float>(window_len: NonZeroUsize) -> T {
+    T::from(2.0).expect("can convert") / T::from(window_len.get() + 1).expect("can convert")
+}
+
+impl<T, V> View<T> for Kama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        if self.q_vals.len() > self.window_len.get() {
+            self.q_vals.pop_front();
+        }
+        self.q_vals.push_back(val);
+
+        // Until the efficiency ratio is defined, the KAMA starts from the latest value.
+        if self.q_vals.len() <= self.window_len.get() {
+            self.kama = val;
+            return;
+        }
+
+        // Summing the changes over the window, instead of keeping a running sum,
+        // prevents rounding errors from accumulating in flat markets.
+        // The changes are of the values divided by `scale`, which doesn't change their ratio.
+        let changes = |scale: T| {
+            let volatility = self
+                .q_vals
+                .iter()
+                .zip(self.q_vals.iter().skip(1))
+                .fold(T::zero(), |sum, (prev, val)| {
+                    sum + (*val / scale - *prev / scale).abs()
+                });
+            ((val / scale - self.q_vals[0] / scale).abs(), volatility)
+        };
+        let (mut change, mut volatility) = changes(T::one());
+        if !volatility.is_finite() {
+            // The changes of values close to the largest float overflow, as does their sum,
+            // unlike for the values divided by twice the number of changes.
+            let n = T::from(self.window_len.get()).expect("can convert");
+            (change, volatility) = changes(T::from(2.0).expect("can convert") * n);
+        }
+        let efficiency_ratio = if volatility > T::zero() {
+            change / volatility
+        } else {
+            T::zero()
+        };
+
+        let fast: T = ema_alpha(self.fast_len);
+        let slow: T = ema_alpha(self.slow_len);
+        let sc = (efficiency_ratio * (fast - slow) + slow).powi(2);
+        let step = val - self.kama;
+        self.kama = if step.is_finite() {
+            self.kama + sc * step
+        } else {
+            // The step between values of opposite signs close to the largest float overflows,
+            // unlike the weighted mean of the two.
+            (T::one() - sc) * self.kama + sc * val
+        };
+        debug_assert!(self.kama.is_finite(), "value must be finite");
+        self.out = Some(self.kama);
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<T> {
+        self.out
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Kama<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Kama")
+            .param("window_len", self.window_len())
+            .param("fast_len", self.fast_len())
+            .param("slow_len", self.slow_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Kama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::window_len("window_len", self.window_len.get(), 1),
+            ParamSpec::window_len("fast_len", self.fast_len.get(), 1),
+            ParamSpec::window_len("slow_len", self.slow_len.get(), 1),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            "fast_len" => self.set_fast_len(window_len_param("fast_len", val)?),
+            "slow_len" => self.set_slow_len(window_len_param("slow_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        plot::plot_values,
+        properties::view_properties,
+        pure_functions::Echo,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn kama_efficiency() {
+        let window_len = NonZeroUsize::new(10).unwrap();
+        // A straight trend is fully efficient, so the KAMA follows it with the fast EMA.
+        let trend: Vec<f64> = (0..12).map(|i| i as f64).collect();
+        let mut kama = Kama::new(Echo::new(), window_len);
+        let outs = kama.update_batch(&trend);
+        assert_eq!(outs.iter().position(Option::is_some), Some(10));
+        let fast = (2.0_f64 / 3.0).powi(2);
+        assert!((outs[10].unwrap() - (9.0 + fast)).abs() < 1e-12);
+
+        // A zig-zag without net change is inefficient, so the KAMA barely moves, with the slow EMA.
+        let zig_zag: Vec<f64> = (0..12).map(|i| (i % 2) as f64).collect();
+        let mut kama = Kama::new(Echo::new(), window_len);
+        let outs = kama.update_batch(&zig_zag);
+        let slow = (2.0_f64 / 31.0).powi(2);
+        assert!((outs[10].unwrap() - (1.0 - slow)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn kama_huge_values() {
+        // The efficient move moves the KAMA with the fast EMA, by 4 / 9 of the step of twice the largest float.
+        let mut kama = Kama::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        kama.update_batch(&[f64::MAX, f64::MAX, -f64::MAX]);
+        assert!((kama.last().unwrap() - f64::MAX / 9.0).abs() < 1e-12 * f64::MAX);
+    }
+
+    #[test]
+    fn kama_plot() {
+        let mut kama = Kama::new(Echo::new(), NonZeroUsize::new(10).unwrap());
+        let out: Vec<f64> = kama
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/kama.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| Kama::new(Echo::new(), window_len));
+}
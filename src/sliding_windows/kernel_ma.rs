@@ -0,0 +1,581 @@
+//! Moving averages with an arbitrary weight kernel,
+//! e.g. the triangular, sine- and pascal-weighted moving averages.
+
+use std::{
+    collections::VecDeque,
+    f64::consts::PI,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamSpec,
+        Reconfigure,
+        float_param,
+        window_len_param,
+    },
+};
+
+/// The weights of a `KernelMa` over its window.
+pub trait Kernel<T> {
+    /// The name of the moving average, as reported by `Introspect`.
+    const NAME: &'static str;
+
+    /// The weights from the oldest to the newest value of a window of `window_len` values.
+    /// They must be non-negative with a positive sum, and are normalized by the `KernelMa`.
+    fn weights(&self, window_len: NonZeroUsize) -> Vec<T>;
+
+    /// The parameters of the kernel besides the window length, as reported by `Introspect`.
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![]
+    }
+
+    /// The reconfigurable parameters of the kernel besides the window length.
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![]
+    }
+
+    /// Set a parameter of the kernel by name, after which the `KernelMa` recomputes its weights.
+    fn set_param(&mut self, _name: &str, _val: ParamValue) -> Result<(), Error> {
+        Err(Error::UnknownParameter)
+    }
+}
+
+/// A moving average weighting the values of its window by an arbitrary `Kernel`.
+/// Returns `None` until the window is full, as the weights are defined over the whole window.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct KernelMa<T, V, K> {
+    view: V,
+    /// The sliding window length.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    kernel: K,
+    /// The normalized weights, from the oldest to the newest value in the window.
+    weights: Vec<T>,
+    q_vals: VecDeque<T>,
+    out: Option<T>,
+}
+
+/// TRIMA - Triangular Moving Average, which equals an SMA of an SMA of half the window length.
+pub type Trima<T, V> = KernelMa<T, V, TriangularKernel>;
+
+/// Sine-weighted Moving Average, weighting the values by the first half period of a sine wave.
+pub type SineMa<T, V> = KernelMa<T, V, SineKernel>;
+
+/// Pascal-weighted Moving Average, weighting the values by the binomial coefficients of Pascal's triangle.
+pub type PascalMa<T, V> = KernelMa<T, V, PascalKernel>;
+
+impl<T, V, K> KernelMa<T, V, K>
+where
+    V: View<T>,
+    K: Kernel<T> + Default,
+    T: Float,
+{
+    /// Create a new moving average with the default kernel, a chained View
+    /// and a given sliding window length
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self::with_kernel(view, window_len, K::default())
+    }
+}
+
+impl<T, V, K> KernelMa<T, V, K>
+where
+    V: View<T>,
+    K: Kernel<T>,
+    T: Float,
+{
+    /// Create a new moving average with a custom kernel, a chained View
+    /// and a given sliding window length
+    pub fn with_kernel(view: V, window_len: NonZeroUsize, kernel: K) -> Self {
+        Self {
+            view,
+            window_len,
+            weights: normalized_weights(&kernel, window_len),
+            kernel,
+            q_vals: VecDeque::with_capacity(window_len.get()),
+            out: None,
+        }
+    }
+
+    /// The kernel of the weights.
+    #[inline(always)]
+    pub fn kernel(&self) -> &K {
+        &self.kernel
+    }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    /// The weights are recomputed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        self.weights = normalized_weights(&self.kernel, window_len);
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
+        }
+        self.out = self.weighted_mean();
+    }
+
+    fn weighted_mean(&self) -> Option<T> {
+        if self.q_vals.len() < self.window_len.get() {
+            return None;
+        }
+        Some(weighted_mean(&self.q_vals, &self.weights))
+    }
+}
+
+/// The kernel weights, scaled to sum up to one.
+pub(super) fn normalized_weights<T: Float, K: Kernel<T>>(
+    kernel: &K,
+    window_len: NonZeroUsize,
+) -> Vec<T> {
+    let weights = kernel.weights(window_len);
+    debug_assert_eq!(weights.len(), window_len.get(), "one weight per value");
+    let sum = weights.iter().fold(T::zero(), |sum, w| sum + *w);
+    debug_assert!(sum > T::zero(), "weights must have a positive sum");
+    weights.into_iter().map(|w| w / sum).collect()
+}
+
+/// The mean of the values of a window, weighted by the newest of the normalized `weights`,
+/// so a window which isn't full yet is weighted like the newest values of a full one.
+/// The rounding errors of the weights can push the mean of values close to the largest float beyond it,
+/// unlike the mean of their halves, which is doubled within the range of the values.
+pub(super) fn weighted_mean<T: Float>(vals: &VecDeque<T>, weights: &[T]) -> T {
+    let weights = &weights[weights.len() - vals.len()..];
+    let (sum, cum) = vals
+        .iter()
+        .zip(weights)
+        .fold((T::zero(), T::zero()), |(sum, cum), (v, w)| {
+            (sum + *w * *v, cum + *w)
+        });
+    if cum <= T::zero() {
+        // All the weights of the newest values underflow, so the oldest of them is nearest to the peak.
+        return vals[0];
+    }
+    let mean = sum / cum;
+    if mean.is_finite() {
+        return mean;
+    }
+    let two = T::from(2.0).expect("can convert");
+    let (half_mean, min, max) = vals.iter().zip(weights).fold(
+        (T::zero(), T::infinity(), T::neg_infinity()),
+        |(sum, min, max), (v, w)| (sum + (*w / cum) * (*v / two), min.min(*v), max.max(*v)),
+    );
+    (half_mean * two).max(min).min(max)
+}
+
+impl<T, V, K> View<T> for KernelMa<T, V, K>
+where
+    V: View<T>,
+    K: Kernel<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        if self.q_vals.len() >= self.window_len.get() {
+            self.q_vals.pop_front();
+        }
+        self.q_vals.push_back(val);
+        self.out = self.weighted_mean();
+        debug_assert!(
+            self.out.is_none_or(|out| out.is_finite()),
+            "value must be finite"
+        );
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<T> {
+        self.out
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V, K> Introspect for KernelMa<T, V, K>
+where
+    V: Introspect,
+    K: Kernel<T>,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        self.kernel
+            .params()
+            .into_iter()
+            .fold(
+                Node::new(K::NAME).param("window_len", self.window_len()),
+                |node, (name, val)| node.param(name, val),
+            )
+            .child(self.view.node())
+    }
+}
+
+impl<T, V, K> Reconfigure for KernelMa<T, V, K>
+where
+    V: View<T>,
+    K: Kernel<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        let mut specs = vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )];
+        specs.extend(self.kernel.param_specs());
+        specs
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => {
+                self.kernel.set_param(name, val)?;
+                self.weights = normalized_weights(&self.kernel, self.window_len);
+                self.out = self.weighted_mean();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Triangular weights rising linearly from the oldest value to the middle of the window and falling towards the newest.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TriangularKernel;
+
+impl<T: Float> Kernel<T> for TriangularKernel {
+    const NAME: &'static str = "Trima";
+
+    fn weights(&self, window_len: NonZeroUsize) -> Vec<T> {
+        let n = window_len.get();
+        (0..n)
+            .map(|k| T::from((k + 1).min(n - k)).expect("can convert"))
+            .collect()
+    }
+}
+
+/// Sine weights over the first half period of a sine wave, which vanish just outside the window.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SineKernel;
+
+impl<T: Float> Kernel<T> for SineKernel {
+    const NAME: &'static str = "SineMa";
+
+    fn weights(&self, window_len: NonZeroUsize) -> Vec<T> {
+        let period = T::from(window_len.get() + 1).expect("can convert");
+        (1..=window_len.get())
+            .map(|k| (T::from(k as f64 * PI).expect("can convert") / period).sin())
+            .collect()
+    }
+}
+
+/// Binomial weights from the row `window_len - 1` of Pascal's triangle, approaching a gaussian for long windows.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PascalKernel;
+
+impl<T: Float> Kernel<T> for PascalKernel {
+    const NAME: &'static str = "PascalMa";
+
+    fn weights(&self, window_len: NonZeroUsize) -> Vec<T> {
+        // Averaging the neighbours instead of adding them keeps the row summing up to one,
+        // so the binomial coefficients of long windows cannot overflow.
+        let half = T::from(0.5).expect("can convert");
+        let mut row = vec![T::one()];
+        for _ in 1..window_len.get() {
+            let mut next = Vec::with_capacity(row.len() + 1);
+            next.push(row[0] * half);
+            next.extend(row.windows(2).map(|pair| (pair[0] + pair[1]) * half));
+            next.push(row[row.len() - 1] * half);
+            row = next;
+        }
+        row
+    }
+}
+
+/// The gaussian weights of the `Alma`, peaking at `offset` of the window with a width of `window_len / sigma`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GaussianKernel<T> {
+    pub(super) sigma: T,
+    pub(super) offset: T,
+}
+
+impl<T: Float> GaussianKernel<T> {
+    /// Create a new gaussian kernel, where `sigma` must be positive and `offset` must be within `[0, 1]`.
+    pub fn new(sigma: T, offset: T) -> Result<Self, Error> {
+        check_sigma(sigma)?;
+        check_offset(offset)?;
+        Ok(Self::new_custom(sigma, offset))
+    }
+
+    /// Create a new gaussian kernel of the `Alma`, where an `offset` outside of `[0, 1]` moves the peak beyond the window.
+    pub(super) fn new_custom(sigma: T, offset: T) -> Self {
+        Self { sigma, offset }
+    }
+}
+
+pub(super) fn check_sigma<T: Float>(sigma: T) -> Result<(), Error> {
+    if !(sigma > T::zero() && sigma.is_finite()) {
+        return Err(Error::AlmaSigma(sigma.to_f64().expect("can convert")));
+    }
+    Ok(())
+}
+
+pub(super) fn check_offset<T: Float>(offset: T) -> Result<(), Error> {
+    if !(offset >= T::zero() && offset <= T::one()) {
+        return Err(Error::AlmaOffset(offset.to_f64().expect("can convert")));
+    }
+    Ok(())
+}
+
+impl<T: Float> Default for GaussianKernel<T> {
+    /// The defaults of the `Alma`.
+    fn default() -> Self {
+        Self {
+            sigma: T::from(6.0).expect("can convert"),
+            offset: T::from(0.85).expect("can convert"),
+        }
+    }
+}
+
+impl<T: Float> Kernel<T> for GaussianKernel<T> {
+    const NAME: &'static str = "GaussianMa";
+
+    fn weights(&self, window_len: NonZeroUsize) -> Vec<T> {
+        let wl = T::from(window_len.get()).expect("can convert");
+        let m = self.offset * (wl - T::one());
+        let inv_s = self.sigma / wl;
+        let two = T::from(2.0).expect("can convert");
+        // Measuring the distances relative to the value nearest to the peak gives it the weight one,
+        // so a large `sigma` cannot underflow all the weights, even where `inv_s^2` overflows.
+        // The peak of an `offset` outside of `[0, 1]`, which `Alma::new_custom` accepts, is beyond the window.
+        let nearest = (m.round().max(T::zero()).min(wl - T::one()) - m).powi(2);
+        (0..window_len.get())
+            .map(|k| {
+                let k = T::from(k).expect("can convert");
+                let excess = (k - m).powi(2) - nearest;
+                if excess <= T::zero() {
+                    return T::one();
+                }
+                (-(excess * inv_s.powi(2)) / two).exp()
+            })
+            .collect()
+    }
+
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![
+            ("sigma", self.sigma.to_f64().expect("can convert").into()),
+            ("offset", self.offset.to_f64().expect("can convert").into()),
+        ]
+    }
+
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::float("sigma", self.sigma, Bound::Exclusive(0.0), Bound::Unbounded),
+            ParamSpec::float(
+                "offset",
+                self.offset,
+                Bound::Inclusive(0.0),
+                Bound::Inclusive(1.0),
+            ),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        *self = match name {
+            "sigma" => Self::new(float_param("sigma", val)?, self.offset)?,
+            "offset" => Self::new(self.sigma, float_param("offset", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
+        pure_functions::Echo,
+        sliding_windows::{
+            Alma,
+            Sma,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn kernel_weights() {
+        let len = NonZeroUsize::new(5).unwrap();
+        let tri: Vec<f64> = TriangularKernel.weights(len);
+        assert_eq!(tri, vec![1.0, 2.0, 3.0, 2.0, 1.0]);
+        let pascal: Vec<f64> = PascalKernel.weights(len);
+        assert_eq!(pascal, [1.0, 4.0, 6.0, 4.0, 1.0].map(|w| w / 16.0).to_vec());
+        let sine: Vec<f64> = SineKernel.weights(len);
+        assert!((sine[2] - 1.0).abs() < 1e-12);
+        assert!((sine[0] - sine[4]).abs() < 1e-12);
+        assert!(GaussianKernel::new(0.0, 0.5).is_err());
+        assert!(GaussianKernel::new(6.0, 1.5).is_err());
+    }
+
+    #[test]
+    fn trima_double_sma() {
+        for n in 1..12 {
+            let mut trima = Trima::new(Echo::new(), NonZeroUsize::new(n).unwrap());
+            let mut double_sma = Sma::new(
+                Sma::new(Echo::new(), NonZeroUsize::new(n / 2 + 1).unwrap()),
+                NonZeroUsize::new(n.div_ceil(2)).unwrap(),
+            );
+            for (trima, sma) in trima
+                .update_batch(&TEST_DATA)
+                .into_iter()
+                .zip(double_sma.update_batch(&TEST_DATA))
+            {
+                match (trima, sma) {
+                    (Some(trima), Some(sma)) => assert!((trima - sma).abs() < 1e-9),
+                    (None, None) => {}
+                    _ => panic!("warm-up differs for window length {n}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gaussian_kernel_alma() {
+        let len = NonZeroUsize::new(16).unwrap();
+        let mut gaussian = KernelMa::<_, _, GaussianKernel<f64>>::new(Echo::new(), len);
+        let mut alma = Alma::new(Echo::new(), len);
+        for (i, val) in TEST_DATA.iter().enumerate() {
+            gaussian.update(*val);
+            alma.update(*val);
+            if i + 1 >= len.get() {
+                assert_eq!(gaussian.last(), alma.last());
+            }
+        }
+        assert_eq!(
+            gaussian.node().label(),
+            "GaussianMa(window_len=16, sigma=6, offset=0.85)"
+        );
+    }
+
+    #[test]
+    fn gaussian_kernel_alma_huge_values() {
+        // Both limit the mean of values close to the largest float to the window.
+        let len = NonZeroUsize::new(3).unwrap();
+        let mut gaussian = KernelMa::<_, _, GaussianKernel<f64>>::new(Echo::new(), len);
+        let mut alma = Alma::new(Echo::new(), len);
+        let vals = [f64::MAX, f64::MAX, 0.5 * f64::MAX, f64::MAX];
+        assert_eq!(
+            gaussian.update_batch(&vals)[2..],
+            alma.update_batch(&vals)[2..]
+        );
+    }
+
+    #[test]
+    fn gaussian_kernel_huge_sigma() {
+        // The peak at `0.85 * 3 = 2.55` is nearest to the newest value, which outweighs all others.
+        let kernel = GaussianKernel::new(1e300, 0.85).unwrap();
+        let mut view = KernelMa::with_kernel(Echo::new(), NonZeroUsize::new(4).unwrap(), kernel);
+        view.update_batch(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(view.last(), Some(4.0));
+    }
+
+    #[test]
+    fn kernel_ma_huge_values() {
+        let mut sine = SineMa::new(Echo::new(), NonZeroUsize::new(3).unwrap());
+        sine.update_batch(&[f64::MAX; 3]);
+        assert_eq!(sine.last(), Some(f64::MAX));
+        sine.update_batch(&[-f64::MAX; 3]);
+        assert_eq!(sine.last(), Some(-f64::MAX));
+    }
+
+    #[test]
+    fn gaussian_kernel_set_param() {
+        let len = NonZeroUsize::new(16).unwrap();
+        let mut view = KernelMa::<_, _, GaussianKernel<f64>>::new(Echo::new(), len);
+        assert_eq!(
+            view.set_param("sigma", ParamValue::Float(0.0)),
+            Err(Error::AlmaSigma(0.0))
+        );
+        assert_eq!(
+            view.set_param("offset", ParamValue::Float(1.5)),
+            Err(Error::AlmaOffset(1.5))
+        );
+        view.update_batch(&TEST_DATA[..20]);
+        view.set_param("sigma", ParamValue::Float(3.0)).unwrap();
+        view.set_param("offset", ParamValue::Float(0.5)).unwrap();
+        let mut fresh =
+            KernelMa::with_kernel(Echo::new(), len, GaussianKernel::new(3.0, 0.5).unwrap());
+        fresh.update_batch(&TEST_DATA[..20]);
+        assert_eq!(view.param_specs(), fresh.param_specs());
+        assert_eq!(view.last(), fresh.last());
+        assert_eq!(
+            view.update_batch(&TEST_DATA[20..]),
+            fresh.update_batch(&TEST_DATA[20..])
+        );
+    }
+
+    #[test]
+    fn kernel_ma_plot() {
+        let len = NonZeroUsize::new(16).unwrap();
+        let mut sine = SineMa::new(Echo::new(), len);
+        let out: Vec<f64> = sine
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        plot_values(out, "img/sine_ma.png").unwrap();
+        let mut pascal = PascalMa::new(Echo::new(), len);
+        let out: Vec<f64> = pascal
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        plot_values(out, "img/pascal_ma.png").unwrap();
+        let mut trima = Trima::new(Echo::new(), len);
+        let out: Vec<f64> = trima
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        plot_values(out, "img/trima.png").unwrap();
+    }
+
+    mod trima {
+        use super::*;
+
+        view_properties!(|window_len| Trima::new(Echo::new(), window_len));
+
+        reconfigure_properties!(|window_len| Trima::new(Echo::new(), window_len));
+    }
+
+    mod sine_ma {
+        use super::*;
+
+        view_properties!(|window_len| SineMa::new(Echo::new(), window_len));
+    }
+
+    mod pascal_ma {
+        use super::*;
+
+        view_properties!(|window_len| PascalMa::new(Echo::new(), window_len));
+    }
+}
@@ -6,7 +6,21 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// Lags a value such that it appears n ticks later.
 #[derive(Debug, Clone, CopyGetters)]
@@ -33,6 +47,18 @@ where
             out: None,
         }
     }
+
+    /// Set the window length, where shrinking outputs a more recent value,
+    /// while growing outputs `None` until the value of that lag has been observed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        if window_len > self.window_len {
+            self.out = None;
+        }
+        self.window_len = window_len;
+        while self.buffer.len() >= window_len.get() {
+            self.out = self.buffer.pop_front();
+        }
+    }
 }
 
 impl<T, V> View<T> for Lag<T, V>
@@ -55,12 +81,59 @@ where
     fn last(&self) -> Option<T> {
         self.out
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Lag<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Lag")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Lag<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use proptest::prelude::*;
+
     use super::*;
-    use crate::pure_functions::Echo;
+    use crate::{
+        properties::{
+            self,
+            reconfigure_properties,
+            view_properties,
+        },
+        pure_functions::Echo,
+    };
 
     #[test]
     fn lag() {
@@ -77,4 +150,24 @@ mod test {
         lag.update(5.0);
         assert_eq!(lag.last(), Some(3.0));
     }
+
+    view_properties!(|window_len| Lag::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Lag::new(Echo::new(), window_len));
+
+    proptest! {
+        #[test]
+        fn lag_shifts_input(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            let mut lag = Lag::new(Echo::new(), window_len);
+            let shift = window_len.get() - 1;
+            for (i, val) in vals.iter().enumerate() {
+                lag.update(*val);
+                let expected = i.checked_sub(shift).map(|j| vals[j]);
+                prop_assert_eq!(lag.last(), expected);
+            }
+        }
+    }
 }
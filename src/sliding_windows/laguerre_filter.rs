@@ -3,7 +3,23 @@
 
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamSpec,
+        Reconfigure,
+        float_param,
+    },
+};
 
 /// John Ehlers Laguerre Filter
 /// from: <http://mesasoftware.com/papers/TimeWarp.pdf>
@@ -41,6 +57,20 @@ where
             filts: Vec::new(),
         }
     }
+
+    /// Create a new LaguerreFilter with a chained View
+    /// and a gamma parameter, which must be within `[0, 1)`.
+    pub fn try_new(view: V, gamma: T) -> Result<Self, Error> {
+        check_gamma(gamma)?;
+        Ok(Self::new(view, gamma))
+    }
+
+    /// Set the gamma, which must be within `[0, 1)`.
+    pub fn set_gamma(&mut self, gamma: T) -> Result<(), Error> {
+        check_gamma(gamma)?;
+        self.gamma = gamma;
+        Ok(())
+    }
 }
 
 impl<T, V> View<T> for LaguerreFilter<T, V>
@@ -88,13 +118,65 @@ where
             + two * self.l2s[self.l2s.len() - 1]
             + self.l3s[self.l3s.len() - 1])
             / T::from(6.0).expect("can convert");
-        debug_assert!(out.is_finite(), "value must be finite");
         self.filts.push(out);
     }
 
     fn last(&self) -> Option<T> {
-        self.filts.last().copied()
+        self.status().value()
+    }
+
+    /// The filter overshoots its input, so its state overflows for values close to the largest float,
+    /// after which the status stays `InvalidReason::Overflow`.
+    fn status(&self) -> Status<T> {
+        match self.filts.last() {
+            None => Status::WarmingUp.chained(self.view.status()),
+            Some(out) if !out.is_finite() => Status::Invalid(InvalidReason::Overflow),
+            Some(out) => Status::Ready(*out),
+        }
+    }
+}
+
+impl<T, V> Introspect for LaguerreFilter<T, V>
+where
+    V: View<T> + Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("LaguerreFilter")
+            .param("gamma", self.gamma.to_f64().expect("can convert"))
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for LaguerreFilter<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::float(
+            "gamma",
+            self.gamma,
+            Bound::Inclusive(0.0),
+            Bound::Exclusive(1.0),
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "gamma" => self.set_gamma(float_param("gamma", val)?),
+            _ => Err(Error::UnknownParameter),
+        }
+    }
+}
+
+fn check_gamma<T: Float>(gamma: T) -> Result<(), Error> {
+    if !(gamma >= T::zero() && gamma < T::one()) {
+        return Err(Error::LaguerreFilterGamma(
+            gamma.to_f64().expect("can convert"),
+        ));
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -107,10 +189,20 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::view_properties,
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
 
+    #[test]
+    fn laguerre_filter_overflow() {
+        let mut laguerre = LaguerreFilter::new(Echo::new(), 0.8);
+        laguerre.update_batch(&[f64::MAX, -f64::MAX, f64::MAX]);
+        assert_eq!(laguerre.status(), Status::Invalid(InvalidReason::Overflow));
+        laguerre.update(0.0);
+        assert_eq!(laguerre.status(), Status::Invalid(InvalidReason::Overflow));
+    }
+
     #[test]
     fn laguerre_filter() {
         let mut laguerre = LaguerreFilter::new(Echo::new(), 0.8);
@@ -137,4 +229,29 @@ mod tests {
         let filename = "img/laguerre_filter.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|_window_len| LaguerreFilter::new(Echo::new(), 0.8));
+
+    #[test]
+    fn laguerre_filter_try_new() {
+        assert!(LaguerreFilter::try_new(Echo::new(), 0.0).is_ok());
+        assert!(LaguerreFilter::try_new(Echo::new(), 0.8).is_ok());
+        for gamma in [-0.1, 1.0, f64::NAN] {
+            assert!(matches!(
+                LaguerreFilter::try_new(Echo::new(), gamma),
+                Err(Error::LaguerreFilterGamma(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn laguerre_filter_set_param() {
+        let mut filter = LaguerreFilter::new(Echo::<f64>::new(), 0.8);
+        assert_eq!(
+            filter.set_param("gamma", ParamValue::Float(1.0)),
+            Err(Error::LaguerreFilterGamma(1.0))
+        );
+        filter.set_param("gamma", ParamValue::Float(0.5)).unwrap();
+        assert_eq!(filter.param_specs()[0].value, ParamValue::Float(0.5));
+    }
 }
@@ -1,27 +1,43 @@
 //! John Ehlers LaguerreRSI
 //! from: <http://mesasoftware.com/papers/TimeWarp.pdf>
 
-use std::{
-    collections::VecDeque,
-    num::NonZeroUsize,
-};
+use std::num::NonZeroUsize;
 
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// John Ehlers LaguerreRSI
 /// from: <http://mesasoftware.com/papers/TimeWarp.pdf>
 #[derive(Debug, Clone, CopyGetters)]
 pub struct LaguerreRSI<T, V> {
     view: V,
-    value: Option<T>,
+    value: Status<T>,
     gamma: T,
-    l0s: VecDeque<T>,
-    l1s: VecDeque<T>,
-    l2s: VecDeque<T>,
-    l3s: VecDeque<T>,
+    /// The filter elements are stored relative to the first value, so their small differences
+    /// don't cancel against the price level. As the elements have unit DC gain, this is exact.
+    shift: Option<T>,
+    // The Laguerre filter elements of the previous step.
+    l0: T,
+    l1: T,
+    l2: T,
+    l3: T,
     /// The sliding window length.
     #[getset(get_copy = "pub")]
     window_len: NonZeroUsize,
@@ -37,16 +53,22 @@ where
     pub fn new(view: V, window_len: NonZeroUsize) -> Self {
         LaguerreRSI {
             view,
-            value: None,
-            gamma: T::from(2.0).expect("can convert")
-                / (T::from(window_len.get()).expect("can convert") + T::one()),
-            l0s: VecDeque::with_capacity(window_len.get()),
-            l1s: VecDeque::with_capacity(window_len.get()),
-            l2s: VecDeque::with_capacity(window_len.get()),
-            l3s: VecDeque::with_capacity(window_len.get()),
+            value: Status::WarmingUp,
+            gamma: smoothing_factor(window_len),
+            shift: None,
+            l0: T::zero(),
+            l1: T::zero(),
+            l2: T::zero(),
+            l3: T::zero(),
             window_len,
         }
     }
+
+    /// Set the window length, recomputing the `gamma` of the Laguerre filter elements.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        self.gamma = smoothing_factor(window_len);
+    }
 }
 
 impl<T, V> View<T> for LaguerreRSI<T, V>
@@ -60,78 +82,107 @@ where
         let Some(val) = self.view.last() else { return };
         debug_assert!(val.is_finite(), "value must be finite");
 
-        if self.l0s.len() >= 3 {
-            self.l0s.pop_front();
-            self.l1s.pop_front();
-            self.l2s.pop_front();
-            self.l3s.pop_front();
-        }
+        let shift = *self.shift.get_or_insert_with(|| {
+            // The elements start at zero in absolute terms.
+            self.l0 = -val;
+            self.l1 = -val;
+            self.l2 = -val;
+            self.l3 = -val;
+            val
+        });
+        let val = val - shift;
 
-        if self.l0s.len() < 2 {
-            self.l0s.push_back(T::zero());
-            self.l1s.push_back(T::zero());
-            self.l2s.push_back(T::zero());
-            self.l3s.push_back(T::zero());
-            return;
-        } else {
-            let last = self.l0s.len() - 1;
-            self.l0s.push_back(
-                (T::one() - self.gamma) * val + self.gamma * *self.l0s.get(last - 1).unwrap(),
-            );
-            self.l1s.push_back(
-                -self.gamma * *self.l0s.get(last).unwrap()
-                    + *self.l0s.get(last - 1).unwrap()
-                    + self.gamma * *self.l1s.get(last - 1).unwrap(),
-            );
-            self.l2s.push_back(
-                -self.gamma * *self.l1s.get(last).unwrap()
-                    + *self.l1s.get(last - 1).unwrap()
-                    + self.gamma * *self.l2s.get(last - 1).unwrap(),
-            );
-            self.l3s.push_back(
-                -self.gamma * *self.l2s.get(last).unwrap()
-                    + *self.l2s.get(last - 1).unwrap()
-                    + self.gamma * *self.l3s.get(last - 1).unwrap(),
-            );
-        }
-        let last = self.l0s.len() - 1;
+        let (l0_1, l1_1, l2_1) = (self.l0, self.l1, self.l2);
+        self.l0 = (T::one() - self.gamma) * val + self.gamma * l0_1;
+        self.l1 = -self.gamma * self.l0 + l0_1 + self.gamma * self.l1;
+        self.l2 = -self.gamma * self.l1 + l1_1 + self.gamma * self.l2;
+        self.l3 = -self.gamma * self.l2 + l2_1 + self.gamma * self.l3;
 
         let mut cu = T::zero();
         let mut cd = T::zero();
-        if self.l0s.get(last) >= self.l1s.get(last) {
-            cu = *self.l0s.get(last).unwrap() - *self.l1s.get(last).unwrap();
-        } else {
-            cd = *self.l1s.get(last).unwrap() - *self.l0s.get(last).unwrap();
-        }
-        if self.l1s.get(last) >= self.l2s.get(last) {
-            cu = cu + (*self.l1s.get(last).unwrap() - *self.l2s.get(last).unwrap());
-        } else {
-            cd = cd + (*self.l2s.get(last).unwrap() - *self.l1s.get(last).unwrap());
-        }
-        if self.l2s.get(last) >= self.l3s.get(last) {
-            cu = cu + (*self.l2s.get(last).unwrap() - *self.l3s.get(last).unwrap());
-        } else {
-            cd = cd + (*self.l3s.get(last).unwrap() - *self.l2s.get(last).unwrap());
+        for (a, b) in [(self.l0, self.l1), (self.l1, self.l2), (self.l2, self.l3)] {
+            if a >= b {
+                cu = cu + (a - b);
+            } else {
+                cd = cd + (b - a);
+            }
         }
 
-        if cu + cd != T::zero() {
-            let value = cu / (cu + cd);
-            debug_assert!(value.is_finite(), "value must be finite");
-            self.value = Some(value);
+        if !(cu.is_finite() && cd.is_finite()) {
+            // The filter elements overflow for values spanning more than the largest float,
+            // after which they stay infinite.
+            self.value = Status::Invalid(InvalidReason::Overflow);
+        } else if (cu + cd).is_infinite() {
+            let two = T::from(2.0).expect("can convert");
+            self.value = Status::Ready((cu / two) / (cu / two + cd / two));
+        } else if cu + cd != T::zero() {
+            self.value = Status::Ready(cu / (cu + cd));
         }
     }
 
     #[inline(always)]
     fn last(&self) -> Option<T> {
-        self.value
+        self.value.value()
+    }
+
+    #[inline(always)]
+    fn status(&self) -> Status<T> {
+        self.value.chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for LaguerreRSI<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("LaguerreRSI")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
     }
 }
 
+impl<T, V> Reconfigure for LaguerreRSI<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+/// The smoothing factor `2 / (window_len + 1)`.
+fn smoothing_factor<T: Float>(window_len: NonZeroUsize) -> T {
+    T::from(2.0).expect("can convert")
+        / (T::from(window_len.get()).expect("can convert") + T::one())
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            self,
+            check_bounded,
+            view_properties,
+        },
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
@@ -148,6 +199,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn laguerre_rsi_overflow() {
+        let mut view = LaguerreRSI::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        view.update_batch(&[f64::MAX, -f64::MAX]);
+        assert_eq!(view.status(), Status::Invalid(InvalidReason::Overflow));
+    }
+
     #[test]
     fn laguerre_rsi_plot() {
         let mut lrsi = LaguerreRSI::new(Echo::new(), NonZeroUsize::new(16).unwrap());
@@ -162,4 +220,25 @@ mod tests {
         let filename = "img/laguerre_rsi.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| LaguerreRSI::new(Echo::new(), window_len));
+
+    proptest! {
+        #[test]
+        fn laguerre_rsi_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            check_bounded(LaguerreRSI::new(Echo::new(), window_len), &vals, 0.0, 1.0)?;
+        }
+    }
+
+    #[test]
+    fn laguerre_rsi_set_window_len() {
+        let mut view = LaguerreRSI::new(Echo::<f64>::new(), NonZeroUsize::new(16).unwrap());
+        view.set_param("window_len", ParamValue::Int(8)).unwrap();
+        let fresh = LaguerreRSI::new(Echo::<f64>::new(), NonZeroUsize::new(8).unwrap());
+        assert_eq!((view.gamma), (fresh.gamma));
+        assert_eq!(view.param_specs()[0].value, ParamValue::Int(8));
+    }
 }
@@ -0,0 +1,407 @@
+//! MAMA - MESA Adaptive Moving Average by John Ehlers
+//! from: <https://www.mesasoftware.com/papers/MAMA.pdf>
+
+use num::Float;
+
+use super::HilbertTransform;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamSpec,
+        Reconfigure,
+        float_param,
+    },
+    pure_functions::Echo,
+};
+
+/// MAMA - MESA Adaptive Moving Average by John Ehlers
+/// from: <https://www.mesasoftware.com/papers/MAMA.pdf>
+/// An EMA whose smoothing constant `fast_limit / delta_phase` follows the phase rate of a `HilbertTransform`,
+/// clamped to `[slow_limit, fast_limit]`.
+/// Alongside, it computes the Following Adaptive Moving Average (FAMA),
+/// an EMA of the MAMA with half the smoothing constant, which is also available as the `Fama` view.
+/// Ready once the `HilbertTransform` is.
+#[derive(Debug, Clone)]
+pub struct Mama<T, V> {
+    view: V,
+    hilbert: HilbertTransform<T, Echo<T>>,
+    fast_limit: T,
+    slow_limit: T,
+    mama: T,
+    fama: T,
+    n_observed: usize,
+}
+
+impl<T, V> Mama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new MESA Adaptive Moving Average with a chained View
+    /// and the default limits of 0.5 and 0.05
+    pub fn new(view: V) -> Self {
+        Self::with_limits(
+            view,
+            T::from(0.5).expect("can convert"),
+            T::from(0.05).expect("can convert"),
+        )
+    }
+
+    /// Create a new MESA Adaptive Moving Average with custom limits of the smoothing constant
+    pub fn with_limits(view: V, fast_limit: T, slow_limit: T) -> Self {
+        Self {
+            view,
+            hilbert: HilbertTransform::new(Echo::new()),
+            fast_limit,
+            slow_limit,
+            mama: T::zero(),
+            fama: T::zero(),
+            n_observed: 0,
+        }
+    }
+
+    /// Create a new MESA Adaptive Moving Average with custom limits of the smoothing constant,
+    /// where `0 < slow_limit <= fast_limit <= 1`.
+    pub fn try_with_limits(view: V, fast_limit: T, slow_limit: T) -> Result<Self, Error> {
+        check_limits(fast_limit, slow_limit)?;
+        Ok(Self::with_limits(view, fast_limit, slow_limit))
+    }
+
+    /// The upper limit of the smoothing constant.
+    #[inline(always)]
+    pub fn fast_limit(&self) -> T {
+        self.fast_limit
+    }
+
+    /// The lower limit of the smoothing constant.
+    #[inline(always)]
+    pub fn slow_limit(&self) -> T {
+        self.slow_limit
+    }
+
+    /// The Hilbert Transform discriminator measuring the phase rate.
+    #[inline(always)]
+    pub fn hilbert(&self) -> &HilbertTransform<T, Echo<T>> {
+        &self.hilbert
+    }
+
+    /// The Following Adaptive Moving Average, if ready.
+    pub fn fama(&self) -> Option<T> {
+        self.last()?;
+        Some(self.fama)
+    }
+
+    /// The smoothed dominant cycle period measured by the `HilbertTransform`, if ready.
+    pub fn period(&self) -> Option<T> {
+        self.last()?;
+        self.hilbert.last()
+    }
+
+    /// Set the upper limit of the smoothing constant, which must be within `[slow_limit, 1]`.
+    pub fn set_fast_limit(&mut self, fast_limit: T) -> Result<(), Error> {
+        check_limits(fast_limit, self.slow_limit)?;
+        self.fast_limit = fast_limit;
+        Ok(())
+    }
+
+    /// Set the lower limit of the smoothing constant, which must be within `(0, fast_limit]`.
+    pub fn set_slow_limit(&mut self, slow_limit: T) -> Result<(), Error> {
+        check_limits(self.fast_limit, slow_limit)?;
+        self.slow_limit = slow_limit;
+        Ok(())
+    }
+}
+
+fn check_limits<T: Float>(fast_limit: T, slow_limit: T) -> Result<(), Error> {
+    if !(fast_limit > T::zero() && fast_limit <= T::one()) {
+        return Err(Error::MamaFastLimit(
+            fast_limit.to_f64().expect("can convert"),
+        ));
+    }
+    if !(slow_limit > T::zero() && slow_limit <= fast_limit) {
+        return Err(Error::MamaSlowLimit(
+            slow_limit.to_f64().expect("can convert"),
+        ));
+    }
+    Ok(())
+}
+
+impl<T, V> View<T> for Mama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.n_observed += 1;
+        self.hilbert.update(val);
+        // Until the cascade of the Hilbert Transform starts, the averages start from the latest value.
+        if self.n_observed < 4 {
+            self.mama = val;
+            self.fama = val;
+            return;
+        }
+
+        let alpha = (self.fast_limit / self.hilbert.delta_phase())
+            .max(self.slow_limit)
+            .min(self.fast_limit);
+        self.mama = alpha * val + (T::one() - alpha) * self.mama;
+        let half_alpha = T::from(0.5).expect("can convert") * alpha;
+        self.fama = half_alpha * self.mama + (T::one() - half_alpha) * self.fama;
+        debug_assert!(self.mama.is_finite(), "value must be finite");
+        debug_assert!(self.fama.is_finite(), "value must be finite");
+    }
+
+    fn last(&self) -> Option<T> {
+        self.hilbert.last()?;
+        Some(self.mama)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Mama<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Mama")
+            .param("fast_limit", self.fast_limit.to_f64().expect("can convert"))
+            .param("slow_limit", self.slow_limit.to_f64().expect("can convert"))
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Mama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::float(
+                "fast_limit",
+                self.fast_limit,
+                Bound::Exclusive(0.0),
+                Bound::Inclusive(1.0),
+            ),
+            ParamSpec::float(
+                "slow_limit",
+                self.slow_limit,
+                Bound::Exclusive(0.0),
+                Bound::Inclusive(1.0),
+            ),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "fast_limit" => self.set_fast_limit(float_param("fast_limit", val)?)?,
+            "slow_limit" => self.set_slow_limit(float_param("slow_limit", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+/// FAMA - Following Adaptive Moving Average by John Ehlers
+/// from: <https://www.mesasoftware.com/papers/MAMA.pdf>
+/// The slower line of a `Mama`, as a view of its own, e.g. to compute the difference of both lines.
+/// Ready once the `Mama` is.
+#[derive(Debug, Clone)]
+pub struct Fama<T, V> {
+    mama: Mama<T, V>,
+}
+
+impl<T, V> Fama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Following Adaptive Moving Average with a chained View
+    /// and the default limits of 0.5 and 0.05
+    pub fn new(view: V) -> Self {
+        Self {
+            mama: Mama::new(view),
+        }
+    }
+
+    /// Create a new Following Adaptive Moving Average with custom limits of the smoothing constant
+    pub fn with_limits(view: V, fast_limit: T, slow_limit: T) -> Self {
+        Self {
+            mama: Mama::with_limits(view, fast_limit, slow_limit),
+        }
+    }
+
+    /// Create a new Following Adaptive Moving Average with custom limits of the smoothing constant,
+    /// where `0 < slow_limit <= fast_limit <= 1`.
+    pub fn try_with_limits(view: V, fast_limit: T, slow_limit: T) -> Result<Self, Error> {
+        Ok(Self {
+            mama: Mama::try_with_limits(view, fast_limit, slow_limit)?,
+        })
+    }
+
+    /// The `Mama` computing both lines.
+    #[inline(always)]
+    pub fn mama(&self) -> &Mama<T, V> {
+        &self.mama
+    }
+}
+
+impl<T, V> View<T> for Fama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        self.mama.update(val);
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<T> {
+        self.mama.fama()
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.mama.status())
+    }
+}
+
+impl<T, V> Introspect for Fama<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Fama")
+            .param(
+                "fast_limit",
+                self.mama.fast_limit.to_f64().expect("can convert"),
+            )
+            .param(
+                "slow_limit",
+                self.mama.slow_limit.to_f64().expect("can convert"),
+            )
+            .child(self.mama.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Fama<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        self.mama.param_specs()
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        self.mama.set_param(name, val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::view_properties,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn mama_constant() {
+        let mut mama = Mama::new(Echo::new());
+        let outs = mama.update_batch(&[100.0; 40]);
+        assert_eq!(outs.iter().position(Option::is_some), Some(31));
+        assert!((outs[39].unwrap() - 100.0).abs() < 1e-9);
+        assert!((mama.fama().unwrap() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn mama_fama_lag() {
+        // In a trend, the FAMA follows the MAMA, which follows the values.
+        let trend: Vec<f64> = (0..200).map(|i| i as f64).collect();
+        let mut mama = Mama::new(Echo::new());
+        mama.update_batch(&trend);
+        let (mama_val, fama_val) = (mama.last().unwrap(), mama.fama().unwrap());
+        assert!(fama_val < mama_val && mama_val < 199.0);
+        assert!((6.0..=50.0).contains(&mama.period().unwrap()));
+
+        let mut fama = Fama::new(Echo::new());
+        fama.update_batch(&trend);
+        assert_eq!(fama.last(), Some(fama_val));
+    }
+
+    #[test]
+    fn mama_limits() {
+        assert_eq!(
+            Mama::try_with_limits(Echo::<f64>::new(), 1.5, 0.05).unwrap_err(),
+            Error::MamaFastLimit(1.5)
+        );
+        assert_eq!(
+            Mama::try_with_limits(Echo::<f64>::new(), 0.5, 0.6).unwrap_err(),
+            Error::MamaSlowLimit(0.6)
+        );
+        let mut mama = Mama::new(Echo::<f64>::new());
+        assert!(
+            mama.set_param("fast_limit", ParamValue::Float(0.01))
+                .is_err()
+        );
+        mama.set_param("slow_limit", ParamValue::Float(0.1))
+            .unwrap();
+        assert_eq!(mama.slow_limit(), 0.1);
+    }
+
+    #[test]
+    fn mama_sine_period() {
+        let vals = generators::sine(300, 20.0, 1.0, 100.0);
+        let mut mama = Mama::new(Echo::new());
+        mama.update_batch(&vals);
+        let period = mama.period().unwrap();
+        assert!((period - 20.0).abs() < 1.0, "measured period {period}");
+        // The averages stay within the range of the cycle.
+        assert!((99.0..=101.0).contains(&mama.last().unwrap()));
+        assert!((99.0..=101.0).contains(&mama.fama().unwrap()));
+    }
+
+    #[test]
+    fn mama_plot() {
+        let mut mama = Mama::new(Echo::new());
+        let out: Vec<f64> = mama
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/mama.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    mod mama {
+        use super::*;
+
+        view_properties!(|_window_len| Mama::new(Echo::new()));
+    }
+
+    mod fama {
+        use super::*;
+
+        view_properties!(|_window_len| Fama::new(Echo::new()));
+    }
+}
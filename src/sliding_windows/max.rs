@@ -6,7 +6,21 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// Keep track of the maximum value observed over the sliding window.
 #[derive(Clone, Debug, CopyGetters)]
@@ -33,6 +47,15 @@ where
             window_len,
         }
     }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
+        }
+        self.opt_max = self.q_vals.iter().copied().reduce(T::max);
+    }
 }
 
 impl<T, V> View<T> for Max<T, V>
@@ -48,13 +71,9 @@ where
 
         if self.q_vals.len() >= self.window_len.get() {
             let popped = self.q_vals.pop_front().expect("There is a value");
-            if popped == self.opt_max.expect("Has a minimum value") {
+            if Some(popped) == self.opt_max {
                 // re-compute the max value.
-                self.opt_max = self
-                    .q_vals
-                    .iter()
-                    .copied()
-                    .max_by(|a, b| a.partial_cmp(b).expect("Can compare elements"));
+                self.opt_max = self.q_vals.iter().copied().reduce(T::max);
             }
         }
         self.q_vals.push_back(val);
@@ -71,12 +90,60 @@ where
     fn last(&self) -> Option<T> {
         self.opt_max
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Max<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Max")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Max<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use proptest::prelude::*;
+
     use super::*;
-    use crate::pure_functions::Echo;
+    use crate::{
+        properties::{
+            self,
+            reconfigure_properties,
+            view_properties,
+        },
+        pure_functions::Echo,
+        sliding_windows::Min,
+    };
 
     #[test]
     fn max() {
@@ -105,4 +172,26 @@ mod test {
         assert_eq!(v.last(), Some(1.4));
         assert_eq!(v.window_len(), NonZeroUsize::new(WINDOW_LEN).unwrap());
     }
+
+    view_properties!(|window_len| Max::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Max::new(Echo::new(), window_len));
+
+    proptest! {
+        #[test]
+        fn max_gte_min(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            let mut max = Max::new(Echo::new(), window_len);
+            let mut min = Min::new(Echo::new(), window_len);
+            for val in vals {
+                max.update(val);
+                min.update(val);
+                prop_assert!(max.last() >= min.last());
+                prop_assert!(max.last() >= Some(val));
+                prop_assert!(min.last() <= Some(val));
+            }
+        }
+    }
 }
@@ -0,0 +1,197 @@
+//! McGinley Dynamic by John R. McGinley
+//! from: "The McGinley Dynamic", Journal of Technical Analysis, 1997
+
+use std::num::NonZeroUsize;
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
+
+/// McGinley Dynamic by John R. McGinley
+/// from: "The McGinley Dynamic", Journal of Technical Analysis, 1997
+/// A moving average tracking the values with the step `(x - md) / (window_len * (x / md)^4)`,
+/// which speeds up in falling markets and slows down in rising ones.
+/// The step is capped at the full distance to the value, which the formula only exceeds
+/// when the value falls far below the average, so the average cannot overshoot.
+/// Ready once `window_len` values have been observed.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct McGinleyDynamic<T, V> {
+    view: V,
+    /// The window length, as of the comparable EMA.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    md: T,
+    n_observed_values: usize,
+}
+
+impl<T, V> McGinleyDynamic<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new McGinley Dynamic with a chained View
+    /// and a given window length
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self {
+            view,
+            window_len,
+            md: T::zero(),
+            n_observed_values: 0,
+        }
+    }
+
+    /// Set the window length, where growing it waits for the additional values to be observed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+    }
+}
+
+impl<T, V> View<T> for McGinleyDynamic<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.n_observed_values += 1;
+        // The ratio is undefined for an average of zero, from which it restarts at the value.
+        if self.n_observed_values == 1 || self.md == T::zero() {
+            self.md = val;
+            return;
+        }
+        let n = T::from(self.window_len.get()).expect("can convert");
+        let divisor = (n * (val / self.md).powi(4)).max(T::one());
+        let md = self.md + (val - self.md) / divisor;
+        // The difference of opposite extreme values overflows, while their weighted mean does not.
+        self.md = if md.is_finite() {
+            md
+        } else {
+            let weight = divisor.recip();
+            self.md * (T::one() - weight) + val * weight
+        };
+        debug_assert!(self.md.is_finite(), "value must be finite");
+    }
+
+    fn last(&self) -> Option<T> {
+        if self.n_observed_values < self.window_len.get() {
+            return None;
+        }
+        Some(self.md)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for McGinleyDynamic<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("McGinleyDynamic")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for McGinleyDynamic<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        plot::plot_values,
+        properties::view_properties,
+        pure_functions::Echo,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn mc_ginley_dynamic_asymmetry() {
+        // The average follows a fall faster than an equally large rise.
+        let window_len = NonZeroUsize::new(10).unwrap();
+        let mut rising = McGinleyDynamic::new(Echo::new(), window_len);
+        let mut falling = McGinleyDynamic::new(Echo::new(), window_len);
+        let base = [100.0; 10];
+        rising.update_batch(&base);
+        falling.update_batch(&base);
+        rising.update(110.0);
+        falling.update(90.0);
+        let rise = rising.last().unwrap() - 100.0;
+        let fall = 100.0 - falling.last().unwrap();
+        assert!((rise - 10.0 / (10.0 * 1.1_f64.powi(4))).abs() < 1e-12);
+        assert!(fall > rise);
+    }
+
+    #[test]
+    fn mc_ginley_dynamic_no_overshoot() {
+        let mut md = McGinleyDynamic::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        assert_eq!(md.update_batch(&[100.0, 10.0]), vec![None, Some(10.0)]);
+    }
+
+    #[test]
+    fn mc_ginley_dynamic_extreme_values() {
+        let mut md = McGinleyDynamic::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        assert_eq!(
+            md.update_batch(&[-f64::MAX, f64::MAX]),
+            vec![None, Some(0.0)]
+        );
+        // The divisor of a far larger value overflows, which holds the average.
+        let mut md = McGinleyDynamic::new(Echo::new(), NonZeroUsize::new(1).unwrap());
+        assert_eq!(
+            md.update_batch(&[-1e-300, f64::MAX]),
+            vec![Some(-1e-300), Some(-1e-300)]
+        );
+    }
+
+    #[test]
+    fn mc_ginley_dynamic_plot() {
+        let mut md = McGinleyDynamic::new(Echo::new(), NonZeroUsize::new(14).unwrap());
+        let out: Vec<f64> = md.update_batch(&TEST_DATA).into_iter().flatten().collect();
+        let filename = "img/mc_ginley_dynamic.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| McGinleyDynamic::new(Echo::new(), window_len));
+}
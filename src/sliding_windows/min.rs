@@ -6,7 +6,21 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// Keep track of the minimum value observed over the sliding window.
 #[derive(Clone, Debug, CopyGetters)]
@@ -33,6 +47,15 @@ where
             window_len,
         }
     }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
+        }
+        self.opt_min = self.q_vals.iter().copied().reduce(T::min);
+    }
 }
 
 impl<T, V> View<T> for Min<T, V>
@@ -48,13 +71,9 @@ where
 
         if self.q_vals.len() >= self.window_len.get() {
             let popped = self.q_vals.pop_front().expect("There is a value");
-            if popped == self.opt_min.expect("Has a minimum value") {
+            if Some(popped) == self.opt_min {
                 // re-compute the min value.
-                self.opt_min = self
-                    .q_vals
-                    .iter()
-                    .copied()
-                    .min_by(|a, b| a.partial_cmp(b).expect("Can compare elements"));
+                self.opt_min = self.q_vals.iter().copied().reduce(T::min);
             }
         }
         self.q_vals.push_back(val);
@@ -71,12 +90,56 @@ where
     fn last(&self) -> Option<T> {
         self.opt_min
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Min<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Min")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Min<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::pure_functions::Echo;
+    use crate::{
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
+        pure_functions::Echo,
+    };
 
     #[test]
     fn min() {
@@ -105,4 +168,8 @@ mod test {
         assert_eq!(v.last(), Some(1.2));
         assert_eq!(v.window_len(), WINDOW_LEN);
     }
+
+    view_properties!(|window_len| Min::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Min::new(Echo::new(), window_len));
 }
@@ -2,18 +2,37 @@
 //! Usually more memory intensive than the implementations in `rolling`.
 
 mod alma;
+mod autocorrelation_periodogram;
+mod automatic_gain_control;
+mod band_pass;
 mod binary_entropy;
 mod center_of_gravity;
+mod cmo;
 mod correlation_trend_indicator;
 mod cumulative;
 mod cyber_cycle;
+mod decycler;
+mod dema;
+mod dual_differentiator;
 mod ehlers_fisher_transform;
 mod ema;
+mod even_better_sinewave;
+mod extrapolation;
+mod frama;
+mod high_pass;
+mod hilbert_transform;
 mod hl_normalizer;
+mod hull_ma;
+mod instantaneous_trendline;
+mod inverse_fisher_transform;
+mod kama;
+mod kernel_ma;
 mod lag;
 mod laguerre_filter;
 mod laguerre_rsi;
+mod mama;
 mod max;
+mod mc_ginley_dynamic;
 mod min;
 mod my_rsi;
 mod noise_elimination_technology;
@@ -22,26 +41,74 @@ mod re_flex;
 mod roc;
 mod roofing_filter;
 mod rsi;
+mod sinewave;
 mod sma;
+mod std_dev_ratio;
 mod super_smoother;
+mod t3;
+mod tema;
 mod trend_flex;
+mod universal_oscillator;
 mod variance_stabilizing_transformation;
+mod vidya;
 mod vsct;
 mod welford_online;
+mod wma;
+mod zlema;
 
 pub use alma::Alma;
+pub use autocorrelation_periodogram::AutocorrelationPeriodogram;
+pub use automatic_gain_control::AutomaticGainControl;
+pub use band_pass::{
+    BandPass,
+    BandPassTrigger,
+};
 pub use binary_entropy::BinaryEntropy;
 pub use center_of_gravity::CenterOfGravity;
+pub use cmo::Cmo;
 pub use correlation_trend_indicator::CorrelationTrendIndicator;
 pub use cumulative::Cumulative;
 pub use cyber_cycle::CyberCycle;
+pub use decycler::{
+    Decycler,
+    DecyclerOscillator,
+};
+pub use dema::Dema;
+pub use dual_differentiator::DualDifferentiator;
 pub use ehlers_fisher_transform::EhlersFisherTransform;
 pub use ema::Ema;
+pub use even_better_sinewave::EvenBetterSinewave;
+pub use frama::Frama;
+pub use high_pass::{
+    OnePoleHighPass,
+    TwoPoleHighPass,
+};
+pub use hilbert_transform::HilbertTransform;
 pub use hl_normalizer::HLNormalizer;
+pub use hull_ma::HullMa;
+pub use instantaneous_trendline::InstantaneousTrendline;
+pub use inverse_fisher_transform::InverseFisherTransform;
+pub use kama::Kama;
+pub use kernel_ma::{
+    GaussianKernel,
+    Kernel,
+    KernelMa,
+    PascalKernel,
+    PascalMa,
+    SineKernel,
+    SineMa,
+    TriangularKernel,
+    Trima,
+};
 pub use lag::Lag;
 pub use laguerre_filter::LaguerreFilter;
 pub use laguerre_rsi::LaguerreRSI;
+pub use mama::{
+    Fama,
+    Mama,
+};
 pub use max::Max;
+pub use mc_ginley_dynamic::McGinleyDynamic;
 pub use min::Min;
 pub use my_rsi::MyRSI;
 pub use noise_elimination_technology::NoiseEliminationTechnology;
@@ -50,9 +117,20 @@ pub use re_flex::ReFlex;
 pub use roc::Roc;
 pub use roofing_filter::RoofingFilter;
 pub use rsi::Rsi;
+pub use sinewave::{
+    LeadSine,
+    Sinewave,
+};
 pub use sma::Sma;
+pub use std_dev_ratio::StdDevRatio;
 pub use super_smoother::SuperSmoother;
+pub use t3::T3;
+pub use tema::Tema;
 pub use trend_flex::TrendFlex;
+pub use universal_oscillator::UniversalOscillator;
 pub use variance_stabilizing_transformation::Vst;
+pub use vidya::Vidya;
 pub use vsct::Vsct;
 pub use welford_online::WelfordOnline;
+pub use wma::Wma;
+pub use zlema::Zlema;
@@ -9,7 +9,22 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// John Ehlers MyRSI
 /// from: <http://www.mesasoftware.com/papers/Noise%20Elimination%20Technology.pdf>
@@ -21,10 +36,8 @@ pub struct MyRSI<T, V> {
     window_len: NonZeroUsize,
     cu: T,
     cd: T,
-    out: T,
+    out: Status<T>,
     q_vals: VecDeque<T>,
-    last_val: T,
-    oldest_val: T,
 }
 
 impl<T, V> MyRSI<T, V>
@@ -39,11 +52,32 @@ where
             window_len,
             cu: T::zero(),
             cd: T::zero(),
-            out: T::zero(),
-            q_vals: VecDeque::with_capacity(window_len.get()),
-            last_val: T::zero(),
-            oldest_val: T::zero(),
+            out: Status::Ready(T::zero()),
+            q_vals: VecDeque::with_capacity(window_len.get() + 1),
+        }
+    }
+
+    /// Set the window length, where shrinking evicts the oldest changes.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() + 1 {
+            self.q_vals.pop_front();
         }
+        self.recompute_sums();
+    }
+
+    fn recompute_sums(&mut self) {
+        (self.cu, self.cd) = self.q_vals.iter().zip(self.q_vals.iter().skip(1)).fold(
+            (T::zero(), T::zero()),
+            |(cu, cd), (prev, val)| {
+                let change = *val - *prev;
+                if change > T::zero() {
+                    (cu + change, cd)
+                } else {
+                    (cu, cd - change)
+                }
+            },
+        );
     }
 }
 
@@ -58,49 +92,107 @@ where
         let Some(val) = self.view.last() else { return };
         debug_assert!(val.is_finite(), "value must be finite");
 
-        if self.q_vals.is_empty() {
-            self.oldest_val = val;
-            self.last_val = val;
-        }
-        if self.q_vals.len() >= self.window_len.get() {
+        if self.q_vals.len() > self.window_len.get() {
+            // remove the oldest change
             let old_val = self.q_vals.pop_front().unwrap();
-            if old_val > self.oldest_val {
-                self.cu = self.cu - (old_val - self.oldest_val);
+            let change = *self.q_vals.front().unwrap() - old_val;
+            if change > T::zero() {
+                self.cu = self.cu - change;
             } else {
-                self.cd = self.cd - (self.oldest_val - old_val);
+                self.cd = self.cd + change;
             }
-            self.oldest_val = old_val;
         }
-        self.q_vals.push_back(val);
-
         // accumulate 'closes up' and 'closes down'
-        if val > self.last_val {
-            self.cu = self.cu + val - self.last_val;
-        } else {
-            self.cd = self.cd + self.last_val - val;
+        if let Some(last_val) = self.q_vals.back() {
+            let change = val - *last_val;
+            if change > T::zero() {
+                self.cu = self.cu + change;
+            } else {
+                self.cd = self.cd - change;
+            }
+        }
+        self.q_vals.push_back(val);
+        // Overflowed sums can't subtract the evicted changes, so they're re-computed until they fit again,
+        // as are sums the rounding of evicting extreme changes left negative.
+        if !(self.cu.is_finite() && self.cd.is_finite())
+            || self.cu < T::zero()
+            || self.cd < T::zero()
+        {
+            self.recompute_sums();
         }
-        self.last_val = val;
 
-        if self.cu + self.cd != T::zero() {
-            self.out = (self.cu - self.cd) / (self.cu + self.cd);
+        if !(self.cu.is_finite() && self.cd.is_finite()) {
+            self.out = Status::Invalid(InvalidReason::Overflow);
+        } else if (self.cu + self.cd).is_infinite() {
+            let two = T::from(2.0).expect("can convert");
+            self.out =
+                Status::Ready((self.cu / two - self.cd / two) / (self.cu / two + self.cd / two));
+        } else if self.cu + self.cd != T::zero() {
+            self.out = Status::Ready((self.cu - self.cd) / (self.cu + self.cd));
         }
     }
 
     #[inline]
     fn last(&self) -> Option<T> {
-        if self.q_vals.len() < self.window_len.get() {
-            return None;
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        // `window_len` changes require one more value.
+        if self.q_vals.len() <= self.window_len.get() {
+            return Status::WarmingUp.chained(self.view.status());
         }
-        debug_assert!(self.out.is_finite(), "value must be finite");
-        Some(self.out)
+        self.out.chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for MyRSI<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("MyRSI")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for MyRSI<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            self,
+            check_bounded,
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
@@ -119,6 +211,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn my_rsi_overflow() {
+        let mut view = MyRSI::new(Echo::new(), NonZeroUsize::new(1).unwrap());
+        view.update_batch(&[f64::MAX, -f64::MAX]);
+        assert_eq!(view.status(), Status::Invalid(InvalidReason::Overflow));
+        // The sums recover, once the overflowing change is evicted.
+        view.update(0.0);
+        assert_eq!(view.status(), Status::Ready(1.0));
+    }
+
+    #[test]
+    fn my_rsi_extreme_values() {
+        // The small change rounds off the extreme one, so evicting both leaves a negative sum.
+        let mut view = MyRSI::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        view.update_batch(&[-1e300, 0.0, 1.0, 0.0, -0.5]);
+        assert_eq!(view.status(), Status::Ready(-1.0));
+    }
+
     #[test]
     fn my_rsi_plot() {
         let mut my_rsi = MyRSI::new(Echo::new(), NonZeroUsize::new(16).unwrap());
@@ -133,4 +243,18 @@ mod tests {
         let filename = "img/my_rsi.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| MyRSI::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| MyRSI::new(Echo::new(), window_len));
+
+    proptest! {
+        #[test]
+        fn my_rsi_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            check_bounded(MyRSI::new(Echo::new(), window_len), &vals, -1.0, 1.0)?;
+        }
+    }
 }
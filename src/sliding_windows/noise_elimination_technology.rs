@@ -9,7 +9,21 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// John Ehlers Noise elimination technology using kendall correlation
 /// from <http://www.mesasoftware.com/papers/Noise%20Elimination%20Technology.pdf>
@@ -37,6 +51,29 @@ where
             q_vals: VecDeque::with_capacity(window_len.get()),
         }
     }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
+        }
+        // A single value has no output.
+        if window_len.get() < 2 {
+            self.out = None;
+        }
+    }
+}
+
+/// The sign of `val`, which unlike `Float::signum` is zero for zero.
+fn sign<T: Float>(val: T) -> T {
+    if val > T::zero() {
+        T::one()
+    } else if val < T::zero() {
+        -T::one()
+    } else {
+        T::zero()
+    }
 }
 
 impl<T, V> View<T> for NoiseEliminationTechnology<T, V>
@@ -58,17 +95,13 @@ where
         if self.q_vals.len() < 2 {
             return;
         }
-        let mut x: Vec<T> = vec![T::zero(); self.q_vals.len()];
-        let mut y: Vec<T> = vec![T::zero(); self.q_vals.len()];
-        for count in 1..self.q_vals.len() {
-            x[count] = *self.q_vals.get(self.q_vals.len() - count).unwrap();
-            y[count] = -T::from(count).expect("can convert");
-        }
-
+        // `x[count]` is the value `count - 1` steps ago, for `count` in `1..=len`.
+        let len = self.q_vals.len();
+        let x = |count: usize| *self.q_vals.get(len - count).unwrap();
         let mut num = T::zero();
-        for count in 2..self.q_vals.len() {
-            for k in 1..count - 1 {
-                num = num - ((x[count] - x[k]).signum());
+        for count in 2..=len {
+            for k in 1..count {
+                num = num - sign(x(count) - x(k));
             }
         }
 
@@ -83,6 +116,44 @@ where
     fn last(&self) -> Option<T> {
         self.out
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for NoiseEliminationTechnology<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("NoiseEliminationTechnology")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for NoiseEliminationTechnology<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -90,6 +161,10 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
         sliding_windows::MyRSI,
         test_data::TEST_DATA,
@@ -113,4 +188,8 @@ mod tests {
         let filename = "img/net_my_rsi.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| NoiseEliminationTechnology::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| NoiseEliminationTechnology::new(Echo::new(), window_len));
 }
@@ -9,7 +9,21 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 #[derive(Debug, Clone, CopyGetters)]
 /// A PolarizedFractalEfficiency indicator with output range [-1.0 and 1.0] rather than [-100, 100]
@@ -37,10 +51,18 @@ where
             view,
             moving_average,
             window_len,
-            q_vals: VecDeque::with_capacity(window_len.get()),
+            q_vals: VecDeque::with_capacity(window_len.get() + 1),
             out: None,
         }
     }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() + 1 {
+            self.q_vals.pop_front();
+        }
+    }
 }
 
 impl<T, V, M> View<T> for PolarizedFractalEfficiency<T, V, M>
@@ -55,29 +77,43 @@ where
         let Some(val) = self.view.last() else { return };
         debug_assert!(val.is_finite(), "value must be finite");
 
-        if self.q_vals.len() >= self.window_len.get() {
+        if self.q_vals.len() > self.window_len.get() {
             self.q_vals.pop_front();
         }
         self.q_vals.push_back(val);
 
+        // The window spans `window_len` steps, so it requires one more value.
+        if self.q_vals.len() <= self.window_len.get() {
+            return;
+        }
         let window_len = T::from(self.window_len.get()).expect("can convert");
-        if self.q_vals.len() >= self.window_len.get() {
-            let mut s = T::zero();
-            let wl: usize = self.window_len.get() - 1;
-            for i in 0..self.window_len.get() - 2 {
-                let v_0 = *self.q_vals.get(wl - i).unwrap();
-                let v_1 = *self.q_vals.get(wl - i - 1).unwrap();
-                s = s + ((v_0 - v_1).powi(2) + T::one()).sqrt();
-            }
-            let mut p =
-                ((val - *self.q_vals.front().unwrap()).powi(2) + window_len.powi(2)).sqrt() / s;
-            if val < *self.q_vals.get(self.window_len.get() - 2).unwrap() {
-                p = -p;
-            }
-            // apply a moving average
-            self.moving_average.update(p);
-            self.out = self.moving_average.last();
+        let front = *self.q_vals.front().unwrap();
+        // The direct distance and the length of the path taken between the oldest and the newest value,
+        // of the values and of the time steps divided by `scale`, which doesn't change their ratio.
+        let distances = |scale: T| {
+            let s = self
+                .q_vals
+                .iter()
+                .zip(self.q_vals.iter().skip(1))
+                .fold(T::zero(), |s, (v_0, v_1)| {
+                    s + (*v_1 / scale - *v_0 / scale).hypot(T::one() / scale)
+                });
+            // `hypot` does not overflow for large differences.
+            ((val / scale - front / scale).hypot(window_len / scale), s)
+        };
+        let (mut direct, mut s) = distances(T::one());
+        if !s.is_finite() {
+            // The differences of values close to the largest float overflow, as does the length of their path,
+            // unlike for the values divided by twice the number of steps.
+            (direct, s) = distances(T::from(2.0).expect("can convert") * (window_len + T::one()));
+        }
+        let mut p = direct / s;
+        if val < self.q_vals[self.q_vals.len() - 2] {
+            p = -p;
         }
+        // apply a moving average
+        self.moving_average.update(p);
+        self.out = self.moving_average.last();
     }
 
     #[inline(always)]
@@ -86,6 +122,47 @@ where
             debug_assert!(v.is_finite(), "value must be finite");
         })
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V, M> Introspect for PolarizedFractalEfficiency<T, V, M>
+where
+    V: Introspect,
+    M: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("PolarizedFractalEfficiency")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+            .child(self.moving_average.node())
+    }
+}
+
+impl<T, V, M> Reconfigure for PolarizedFractalEfficiency<T, V, M>
+where
+    V: View<T>,
+    M: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -93,6 +170,7 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::view_properties,
         pure_functions::Echo,
         sliding_windows::Ema,
         test_data::TEST_DATA,
@@ -115,6 +193,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn polarized_fractal_efficiency_huge_values() {
+        let mut view = PolarizedFractalEfficiency::new(
+            Echo::new(),
+            Echo::new(),
+            NonZeroUsize::new(2).unwrap(),
+        );
+        let outs = view.update_batch(&[f64::MAX, -f64::MAX, f64::MAX]);
+        let out = outs[2].unwrap();
+        assert!(out > 0.0 && out < 0.01);
+    }
+
     #[test]
     fn polarized_fractal_efficiency_plot() {
         let mut pfe = PolarizedFractalEfficiency::new(
@@ -132,4 +222,10 @@ mod tests {
         let filename = "img/polarized_fractal_efficiency.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| PolarizedFractalEfficiency::new(
+        Echo::new(),
+        Ema::new(Echo::new(), window_len),
+        window_len
+    ));
 }
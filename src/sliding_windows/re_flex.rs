@@ -9,7 +9,22 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// John Ehlers ReFlex Indicator
 /// from: <https://financial-hacker.com/petra-on-programming-a-new-zero-lag-indicator/>
@@ -19,10 +34,13 @@ pub struct ReFlex<T, V> {
     /// The sliding window length
     #[getset(get_copy = "pub")]
     window_len: NonZeroUsize,
+    c1: T,
+    c2: T,
+    c3: T,
     last_val: T,
     last_m: T,
     q_vals: VecDeque<T>,
-    out: Option<T>,
+    out: Status<T>,
 }
 
 impl<T, V> ReFlex<T, V>
@@ -33,13 +51,27 @@ where
     /// Create a new ReFlex indicator with a chained View
     /// and a given sliding window length
     pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        let (c1, c2, c3) = coefficients(window_len);
         ReFlex {
             view,
             window_len,
+            c1,
+            c2,
+            c3,
             last_val: T::zero(),
             last_m: T::zero(),
-            q_vals: VecDeque::with_capacity(window_len.get()),
-            out: None,
+            q_vals: VecDeque::with_capacity(window_len.get() + 1),
+            out: Status::WarmingUp,
+        }
+    }
+
+    /// Set the window length, where shrinking evicts the oldest filter values.
+    /// The coefficients of the smoothing filter are recomputed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        (self.c1, self.c2, self.c3) = coefficients(window_len);
+        while self.q_vals.len() > window_len.get() + 1 {
+            self.q_vals.pop_front();
         }
     }
 }
@@ -58,40 +90,31 @@ where
         if self.q_vals.is_empty() {
             self.last_val = val;
         }
-        if self.q_vals.len() >= self.window_len.get() {
-            self.q_vals.pop_front();
-        }
-        let window_len = T::from(self.window_len.get()).expect("can convert");
-        let two = T::from(2.0).expect("can convert");
-        let a1 = T::from(8.88442402435).expect("can convert") / window_len;
-        let b1 = two * a1 * (T::from(4.44221201218).expect("can convert") / window_len).cos();
-        let c3 = -a1 * a1;
-        let c1 = T::one() - b1 - c3;
-
         let l = self.q_vals.len();
-        let mut filt = T::zero();
-        if l == 0 {
-            filt = c1 * (val + self.last_val) / two;
-        } else if l == 1 {
-            let filt1 = *self.q_vals.get(l - 1).unwrap();
-            filt = c1 * (val + self.last_val) / two + b1 * filt1;
-        } else if l > 1 {
-            let filt2 = *self.q_vals.get(l - 2).unwrap();
-            let filt1 = *self.q_vals.get(l - 1).unwrap();
-            filt = c1 * (val + self.last_val) / two + b1 * filt1 + c3 * filt2;
-        }
+        let filt_1 = self.q_vals.back().copied().unwrap_or(T::zero());
+        let filt_2 = if l > 1 { self.q_vals[l - 2] } else { T::zero() };
+        let filt = self.c1 * (val + self.last_val) / T::from(2.0).expect("can convert")
+            + self.c2 * filt_1
+            + self.c3 * filt_2;
         self.last_val = val;
+        if self.q_vals.len() > self.window_len.get() {
+            self.q_vals.pop_front();
+        }
         self.q_vals.push_back(filt);
+        // The oldest filter value must be `window_len` steps ago.
+        if self.q_vals.len() <= self.window_len.get() {
+            return;
+        }
 
+        let window_len = T::from(self.window_len.get()).expect("can convert");
         let slope = (*self.q_vals.front().unwrap() - filt) / window_len;
 
         // sum the differences
         let mut d_sum = T::zero();
-        for i in 0..self.q_vals.len() {
-            let index = self.q_vals.len() - 1 - i;
+        for count in 1..=self.window_len.get() {
+            let index = self.q_vals.len() - 1 - count;
             d_sum = d_sum
-                + ((filt + T::from(i).expect("can convert") * slope)
-                    - *self.q_vals.get(index).unwrap());
+                + ((filt + T::from(count).expect("can convert") * slope) - self.q_vals[index]);
         }
         d_sum = d_sum / window_len;
 
@@ -99,28 +122,91 @@ where
         let ms0 = T::from(0.04).expect("can convert") * d_sum.powi(2)
             + T::from(0.96).expect("can convert") * self.last_m;
         self.last_m = ms0;
-        if ms0 > T::zero() {
-            let out = d_sum / ms0.sqrt();
-            debug_assert!(out.is_finite(), "value must be finite");
-            self.out = Some(out);
-        }
+        // The squared differences overflow for values close to the largest float,
+        // after which the mean square stays infinite.
+        self.out = if !ms0.is_finite() {
+            Status::Invalid(InvalidReason::Overflow)
+        } else if ms0 > T::zero() {
+            Status::Ready(d_sum / ms0.sqrt())
+        } else {
+            Status::Ready(T::zero())
+        };
     }
 
     #[inline(always)]
     fn last(&self) -> Option<T> {
-        self.out
+        self.out.value()
+    }
+
+    #[inline(always)]
+    fn status(&self) -> Status<T> {
+        self.out.chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for ReFlex<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("ReFlex")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
     }
 }
 
+impl<T, V> Reconfigure for ReFlex<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+/// The coefficients `c1`, `c2` and `c3` of the SuperSmoother with half of `window_len` as its period.
+fn coefficients<T: Float>(window_len: NonZeroUsize) -> (T, T, T) {
+    let window_len_t = T::from(window_len.get()).expect("can convert");
+    // NOTE: 8.88442402435 radians are 1.414 * 180 degrees / (0.5 * window_len)
+    let angle = T::from(8.88442402435).expect("can convert") / window_len_t;
+    let a1 = (-angle).exp();
+    let c2 = T::from(2.0).expect("can convert") * a1 * angle.cos();
+    let c3 = -a1 * a1;
+    (T::one() - c2 - c3, c2, c3)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
+        generators,
         plot::plot_values,
+        properties::view_properties,
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
 
+    #[test]
+    fn re_flex_overflow() {
+        let mut view = ReFlex::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        view.update_batch(&[f64::MAX, -f64::MAX, f64::MAX, -f64::MAX]);
+        assert_eq!(view.status(), Status::Invalid(InvalidReason::Overflow));
+    }
+
     #[test]
     fn re_flex_plot() {
         let mut rf = ReFlex::new(Echo::new(), NonZeroUsize::new(16).unwrap());
@@ -134,4 +220,63 @@ mod tests {
         let filename = "img/re_flex.png";
         plot_values(out, filename).unwrap();
     }
+
+    #[test]
+    fn re_flex_window_len_1() {
+        let mut rf = ReFlex::new(Echo::new(), NonZeroUsize::new(1).unwrap());
+        for v in &TEST_DATA {
+            rf.update(*v);
+            if let Some(val) = rf.last() {
+                assert!(val.is_finite());
+            }
+        }
+        assert!(rf.last().is_some());
+    }
+
+    #[test]
+    fn re_flex_response() {
+        let window_len = NonZeroUsize::new(16).unwrap();
+        // Normalized by its mean square, so a sine has an amplitude of `sqrt(2)` at every period.
+        for period in [10.0, 20.0, 40.0, 80.0] {
+            let outs = ReFlex::new(Echo::new(), window_len)
+                .update_batch(&generators::sine(1000, period, 1.0, 100.0));
+            assert_eq!(
+                outs.iter().position(Option::is_some),
+                Some(window_len.get())
+            );
+            let amplitude = outs[500..]
+                .iter()
+                .flatten()
+                .fold(0.0_f64, |max, out| out.abs().max(max));
+            assert!(
+                (amplitude - 2.0_f64.sqrt()).abs() < 0.03,
+                "amplitude {amplitude} at period {period}"
+            );
+        }
+        // Removes a linear trend, leaving the cycle centered at zero.
+        let trending: Vec<f64> = generators::sine(1000, 20.0, 1.0, 100.0)
+            .iter()
+            .enumerate()
+            .map(|(i, val)| val + 0.05 * i as f64)
+            .collect();
+        let outs: Vec<f64> = ReFlex::new(Echo::new(), window_len)
+            .update_batch(&trending)
+            .into_iter()
+            .skip(500)
+            .flatten()
+            .collect();
+        let mean = outs.iter().sum::<f64>() / outs.len() as f64;
+        assert!(mean.abs() < 1e-6, "mean {mean}");
+    }
+
+    view_properties!(|window_len| ReFlex::new(Echo::new(), window_len));
+
+    #[test]
+    fn re_flex_set_window_len() {
+        let mut view = ReFlex::new(Echo::<f64>::new(), NonZeroUsize::new(16).unwrap());
+        view.set_param("window_len", ParamValue::Int(8)).unwrap();
+        let fresh = ReFlex::new(Echo::<f64>::new(), NonZeroUsize::new(8).unwrap());
+        assert_eq!((view.c1, view.c2, view.c3), (fresh.c1, fresh.c2, fresh.c3));
+        assert_eq!(view.param_specs()[0].value, ParamValue::Int(8));
+    }
 }
@@ -8,7 +8,22 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// Rate of Change Indicator
 #[derive(Debug, Clone, CopyGetters)]
@@ -19,7 +34,7 @@ pub struct Roc<T, V> {
     window_len: NonZeroUsize,
     oldest: Option<T>,
     q_vals: VecDeque<T>,
-    out: Option<T>,
+    out: Status<T>,
 }
 
 impl<T, V> Roc<T, V>
@@ -35,7 +50,16 @@ where
             window_len,
             oldest: None,
             q_vals: VecDeque::with_capacity(window_len.get()),
-            out: None,
+            out: Status::WarmingUp,
+        }
+    }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            // The oldest value is the one just before the window.
+            self.oldest = self.q_vals.pop_front();
         }
     }
 }
@@ -62,17 +86,61 @@ where
         self.q_vals.push_back(val);
 
         let Some(oldest) = self.oldest else { return };
+        // The rate of change relative to zero is undefined, and it overflows for tiny values.
         if oldest == T::zero() {
+            self.out = Status::Invalid(InvalidReason::ZeroDenominator);
             return;
         }
         let roc = ((val - oldest) / oldest) * T::from(100.0).expect("can convert");
-        debug_assert!(roc.is_finite(), "`roc` must be finite");
-        self.out = Some(roc);
+        self.out = if roc.is_finite() {
+            Status::Ready(roc)
+        } else {
+            Status::Invalid(InvalidReason::Overflow)
+        };
     }
 
     #[inline(always)]
     fn last(&self) -> Option<T> {
-        self.out
+        self.out.value()
+    }
+
+    #[inline(always)]
+    fn status(&self) -> Status<T> {
+        self.out.chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Roc<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Roc")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Roc<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
     }
 }
 
@@ -81,7 +149,12 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
+        sliding_windows::Ema,
         test_data::TEST_DATA,
     };
 
@@ -98,4 +171,38 @@ mod tests {
         let filename = "img/roc.png";
         plot_values(out, filename).unwrap();
     }
+
+    #[test]
+    fn roc_zero_oldest() {
+        let mut roc = Roc::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        assert_eq!(roc.status(), Status::WarmingUp);
+        roc.update(0.0);
+        roc.update(1.0);
+        assert_eq!(roc.last(), None);
+        assert_eq!(
+            roc.status(),
+            Status::Invalid(InvalidReason::ZeroDenominator)
+        );
+        roc.update(2.0);
+        roc.update(3.0);
+        assert_eq!(roc.status(), Status::Ready(200.0));
+    }
+
+    #[test]
+    fn roc_invalid_forwarded() {
+        // A view chained on an invalid `Roc` reports it, rather than warming up.
+        let len = NonZeroUsize::new(2).unwrap();
+        let mut ema = Ema::new(Roc::new(Echo::new(), len), len);
+        ema.update_batch(&[0.0, 1.0]);
+        assert_eq!(
+            ema.status(),
+            Status::Invalid(InvalidReason::ZeroDenominator)
+        );
+        ema.update_batch(&[2.0, 3.0, 4.0]);
+        assert!(ema.status().is_ready());
+    }
+
+    view_properties!(|window_len| Roc::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Roc::new(Echo::new(), window_len));
 }
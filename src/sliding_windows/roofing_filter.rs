@@ -1,33 +1,43 @@
-use std::num::NonZeroUsize;
+use std::{
+    marker::PhantomData,
+    num::NonZeroUsize,
+};
 
-use getset::CopyGetters;
 use num::Float;
 
-use super::SuperSmoother;
+use super::{
+    SuperSmoother,
+    TwoPoleHighPass,
+};
 use crate::{
+    Error,
+    Status,
     View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
     pure_functions::Echo,
 };
 
 /// Roofing Filter by John Ehlers
 /// From paper: <http://www.stockspotter.com/files/PredictiveIndicators.pdf>
-#[derive(Debug, Clone, CopyGetters)]
-pub struct RoofingFilter<T, V> {
+/// Passes the values through a high-pass filter, which removes the trend,
+/// and smooths the high-pass filtered values, which removes the noise, once the high-pass filter is ready.
+/// By default, these are a `TwoPoleHighPass` and a `SuperSmoother`,
+/// while `with_filters` takes any high-pass filter and smoother, both chained on an `Echo`.
+#[derive(Debug, Clone)]
+pub struct RoofingFilter<T, V, H = TwoPoleHighPass<T, Echo<T>>, S = SuperSmoother<T, Echo<T>>> {
     view: V,
-    super_smoother: SuperSmoother<T, Echo<T>>,
-    /// The sliding window length.
-    #[getset(get_copy = "pub")]
-    window_len: NonZeroUsize,
-    i: usize,
-    alpha_1: T,
-    // previous value
-    val_1: T,
-    // value from 2 steps ago
-    val_2: T,
-    // high pass filter value of previous step
-    hp_1: T,
-    // high pass filter value from two steps ago
-    hp_2: T,
+    high_pass: H,
+    smoother: S,
+    _marker: PhantomData<T>,
 }
 
 impl<T, V> RoofingFilter<T, V>
@@ -41,28 +51,88 @@ where
         window_len_low_pass: NonZeroUsize,
         super_smoother_len_high_pass: NonZeroUsize,
     ) -> Self {
-        // NOTE: 4.4422 radians from  0.707 * 360 degrees
-        let wl = T::from(window_len_low_pass.get()).expect("can convert");
-        let f = T::from(4.4422).expect("Can convert");
-        let alpha_1 = ((f / wl).cos() + (f / wl).sin() - T::one()) / (f / wl).cos();
+        Self::with_filters(
+            view,
+            TwoPoleHighPass::new(Echo::new(), window_len_low_pass),
+            SuperSmoother::new(Echo::new(), super_smoother_len_high_pass),
+        )
+    }
 
-        RoofingFilter {
+    /// Create a Roofing Filter with a chained view,
+    /// where the low-pass window length must be at least 3 for the filter to be stable.
+    pub fn try_new(
+        view: V,
+        window_len_low_pass: NonZeroUsize,
+        super_smoother_len_high_pass: NonZeroUsize,
+    ) -> Result<Self, Error> {
+        check_window_len_low_pass(window_len_low_pass)?;
+        Ok(Self::new(
             view,
-            super_smoother: SuperSmoother::new(Echo::new(), super_smoother_len_high_pass),
-            window_len: window_len_low_pass,
-            i: 0,
-            alpha_1,
-            val_1: T::zero(),
-            val_2: T::zero(),
-            hp_1: T::zero(),
-            hp_2: T::zero(),
+            window_len_low_pass,
+            super_smoother_len_high_pass,
+        ))
+    }
+
+    /// The low-pass window length, i.e. the cutoff period of the high-pass filter.
+    #[inline(always)]
+    pub fn window_len(&self) -> NonZeroUsize {
+        self.high_pass.cutoff_period()
+    }
+
+    /// Set the low-pass window length, which must be at least 3, recomputing the high-pass filter coefficient.
+    pub fn set_window_len_low_pass(
+        &mut self,
+        window_len_low_pass: NonZeroUsize,
+    ) -> Result<(), Error> {
+        check_window_len_low_pass(window_len_low_pass)?;
+        self.high_pass
+            .set_cutoff_period(window_len_low_pass)
+            .expect("is at least 3");
+        Ok(())
+    }
+
+    /// Set the window length of the high-pass SuperSmoother, recomputing its coefficients.
+    pub fn set_super_smoother_len_high_pass(&mut self, super_smoother_len_high_pass: NonZeroUsize) {
+        self.smoother.set_window_len(super_smoother_len_high_pass);
+    }
+}
+
+impl<T, V, H, S> RoofingFilter<T, V, H, S>
+where
+    V: View<T>,
+    H: View<T>,
+    S: View<T>,
+    T: Float,
+{
+    /// Create a Roofing Filter with a chained view from any high-pass filter and smoother,
+    /// which are both expected to be chained on an `Echo`, as they observe the values passed to them.
+    pub fn with_filters(view: V, high_pass: H, smoother: S) -> Self {
+        Self {
+            view,
+            high_pass,
+            smoother,
+            _marker: PhantomData,
         }
     }
+
+    /// The high-pass filter, which removes the trend.
+    #[inline(always)]
+    pub fn high_pass(&self) -> &H {
+        &self.high_pass
+    }
+
+    /// The smoother of the high-pass filtered values, which removes the noise.
+    #[inline(always)]
+    pub fn smoother(&self) -> &S {
+        &self.smoother
+    }
 }
 
-impl<T, V> View<T> for RoofingFilter<T, V>
+impl<T, V, H, S> View<T> for RoofingFilter<T, V, H, S>
 where
     V: View<T>,
+    H: View<T>,
+    S: View<T>,
     T: Float,
 {
     fn update(&mut self, val: T) {
@@ -71,39 +141,105 @@ where
         let Some(val) = self.view.last() else { return };
         debug_assert!(val.is_finite(), "value must be finite");
 
-        let two = T::from(2.0).expect("can convert");
-        let hp = (T::one() - self.alpha_1 / two).powi(2) * (val - two * self.val_1 + self.val_2)
-            + two * (T::one() - self.alpha_1) * self.hp_1
-            - (T::one() - self.alpha_1).powi(2) * self.hp_2;
-        self.hp_2 = self.hp_1;
-        self.hp_1 = hp;
-
-        self.val_2 = self.val_1;
-        self.val_1 = val;
-
-        if self.i > self.window_len.get() {
-            // to avoid weird output, only update, once warmup stage is done
-            self.super_smoother.update(hp);
+        self.high_pass.update(val);
+        // to avoid weird output, only smooth, once the high-pass filter is warmed up
+        if let Some(hp) = self.high_pass.last() {
+            self.smoother.update(hp);
         }
-        self.i += 1;
     }
 
     #[inline(always)]
     fn last(&self) -> Option<T> {
-        self.super_smoother.last().inspect(|v| {
-            debug_assert!(v.is_finite(), "value must be finite");
-        })
+        self.status().value()
+    }
+
+    /// An overflowed high-pass filter no longer updates the smoother, so its status takes precedence.
+    fn status(&self) -> Status<T> {
+        match self.high_pass.status() {
+            Status::Invalid(reason) => Status::Invalid(reason),
+            _ => self.smoother.status().chained(self.view.status()),
+        }
+    }
+}
+
+impl<T, V, H, S> Introspect for RoofingFilter<T, V, H, S>
+where
+    V: Introspect,
+    H: Introspect,
+    S: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("RoofingFilter")
+            .child(self.view.node())
+            .child(self.high_pass.node())
+            .child(self.smoother.node())
     }
 }
 
+impl<T, V> Reconfigure for RoofingFilter<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::window_len("window_len_low_pass", self.window_len().get(), 3),
+            ParamSpec::window_len(
+                "super_smoother_len_high_pass",
+                self.smoother.window_len().get(),
+                1,
+            ),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len_low_pass" => {
+                self.set_window_len_low_pass(window_len_param("window_len_low_pass", val)?)?
+            }
+            "super_smoother_len_high_pass" => self.set_super_smoother_len_high_pass(
+                window_len_param("super_smoother_len_high_pass", val)?,
+            ),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+/// The low-pass window length, which must be at least 3 for the high-pass filter to be stable.
+fn check_window_len_low_pass(window_len_low_pass: NonZeroUsize) -> Result<(), Error> {
+    if window_len_low_pass.get() < 3 {
+        return Err(Error::RoofingFilterLowPassLen(window_len_low_pass.get()));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
+        InvalidReason,
         plot::plot_values,
+        properties::view_properties,
+        sliding_windows::{
+            Ema,
+            OnePoleHighPass,
+        },
         test_data::TEST_DATA,
     };
 
+    #[test]
+    fn roofing_filter_overflow() {
+        let mut view = RoofingFilter::new(
+            Echo::new(),
+            NonZeroUsize::new(1).unwrap(),
+            NonZeroUsize::new(2).unwrap(),
+        );
+        view.update_batch(&[f64::MAX, -f64::MAX, f64::MAX, -f64::MAX, f64::MAX]);
+        assert_eq!(view.status(), Status::Invalid(InvalidReason::Overflow));
+    }
+
     #[test]
     fn roofing_filter_plot() {
         let mut rf = RoofingFilter::new(
@@ -121,4 +257,89 @@ mod tests {
         let filename = "img/roofing_filter.png";
         plot_values(out, filename).unwrap();
     }
+
+    // The low-pass filter is only stable for window lengths of at least 3.
+    view_properties!(|window_len| RoofingFilter::new(
+        Echo::new(),
+        window_len.saturating_add(2),
+        window_len
+    ));
+
+    #[test]
+    fn roofing_filter_try_new() {
+        let len = |len| NonZeroUsize::new(len).unwrap();
+        assert!(RoofingFilter::<f64, _>::try_new(Echo::new(), len(3), len(10)).is_ok());
+        assert_eq!(
+            RoofingFilter::<f64, _>::try_new(Echo::new(), len(2), len(10)).unwrap_err(),
+            Error::RoofingFilterLowPassLen(2)
+        );
+    }
+
+    #[test]
+    fn roofing_filter_set_param() {
+        let len = NonZeroUsize::new(16).unwrap();
+        let mut view = RoofingFilter::new(Echo::<f64>::new(), len, len);
+        assert_eq!(
+            view.set_param("window_len_low_pass", ParamValue::Int(2)),
+            Err(Error::RoofingFilterLowPassLen(2))
+        );
+        view.set_param("window_len_low_pass", ParamValue::Int(48))
+            .unwrap();
+        view.set_param("super_smoother_len_high_pass", ParamValue::Int(10))
+            .unwrap();
+        let mut fresh = RoofingFilter::new(
+            Echo::<f64>::new(),
+            NonZeroUsize::new(48).unwrap(),
+            NonZeroUsize::new(10).unwrap(),
+        );
+        assert_eq!(view.param_specs(), fresh.param_specs());
+        // Without any values observed, the reconfigured filter computes the same coefficients.
+        assert_eq!(
+            view.update_batch(&TEST_DATA),
+            fresh.update_batch(&TEST_DATA)
+        );
+    }
+
+    #[test]
+    fn roofing_filter_with_filters() {
+        let len = |len| NonZeroUsize::new(len).unwrap();
+        let mut default = RoofingFilter::new(Echo::<f64>::new(), len(48), len(10));
+        let mut composed = RoofingFilter::with_filters(
+            Echo::new(),
+            TwoPoleHighPass::new(Echo::new(), len(48)),
+            SuperSmoother::new(Echo::new(), len(10)),
+        );
+        assert_eq!(
+            default.update_batch(&TEST_DATA),
+            composed.update_batch(&TEST_DATA)
+        );
+
+        // Any high-pass filter and smoother, which start smoothing once the high-pass filter is ready.
+        let mut view = RoofingFilter::with_filters(
+            Echo::<f64>::new(),
+            OnePoleHighPass::new(Echo::new(), len(20)),
+            Ema::new(Echo::new(), len(5)),
+        );
+        let outs = view.update_batch(&TEST_DATA);
+        let mut high_pass = OnePoleHighPass::new(Echo::new(), len(20));
+        let mut ema = Ema::new(Echo::new(), len(5));
+        let expected: Vec<Option<f64>> = TEST_DATA
+            .iter()
+            .map(|val| {
+                high_pass.update(*val);
+                high_pass.last().and_then(|hp| {
+                    ema.update(hp);
+                    ema.last()
+                })
+            })
+            .collect();
+        assert_eq!(outs, expected);
+        assert_eq!(
+            view.node(),
+            Node::new("RoofingFilter")
+                .child(Echo::<f64>::new().node())
+                .child(OnePoleHighPass::<f64, _>::new(Echo::new(), len(20)).node())
+                .child(Ema::<f64, _>::new(Echo::new(), len(5)).node())
+        );
+    }
 }
@@ -8,7 +8,22 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// Relative Strength Index Indicator
 #[derive(Debug, Clone, CopyGetters)]
@@ -17,12 +32,16 @@ pub struct Rsi<T, V> {
     /// The sliding window length.
     #[getset(get_copy = "pub")]
     window_len: NonZeroUsize,
-    avg_gain: T,
-    avg_loss: T,
-    old_ref: T,
-    last_val: T,
     q_vals: VecDeque<T>,
-    out: Option<T>,
+    /// The running sums of the gains and losses over the window.
+    gain: T,
+    loss: T,
+    /// The number of gains and losses within the window, so the sums are exactly zero without any.
+    n_gains: usize,
+    n_losses: usize,
+    /// The number of updates until the sums are re-computed from the window.
+    n_until_recompute: usize,
+    out: Status<T>,
 }
 
 impl<T, V> Rsi<T, V>
@@ -37,14 +56,71 @@ where
         Rsi {
             view,
             window_len,
-            avg_gain: T::zero(),
-            avg_loss: T::zero(),
-            old_ref: T::zero(),
-            last_val: T::zero(),
-            q_vals: VecDeque::with_capacity(window_len.get()),
-            out: None,
+            q_vals: VecDeque::with_capacity(window_len.get() + 1),
+            gain: T::zero(),
+            loss: T::zero(),
+            n_gains: 0,
+            n_losses: 0,
+            n_until_recompute: window_len.get(),
+            out: Status::WarmingUp,
         }
     }
+
+    #[inline]
+    fn add_change(&mut self, change: T) {
+        if change > T::zero() {
+            self.gain = self.gain + change;
+            self.n_gains += 1;
+        } else if change < T::zero() {
+            self.loss = self.loss - change;
+            self.n_losses += 1;
+        }
+    }
+
+    /// Returns whether the sum cancelled to a fraction of the removed change,
+    /// where the rounding errors of the removed change dominate it,
+    /// or whether an overflowed change left it undefined.
+    #[inline]
+    fn remove_change(&mut self, change: T) -> bool {
+        let cancellation = T::epsilon().sqrt();
+        if change > T::zero() {
+            self.gain = self.gain - change;
+            self.n_gains -= 1;
+            self.gain < change * cancellation || self.gain.is_nan()
+        } else if change < T::zero() {
+            self.loss = self.loss + change;
+            self.n_losses -= 1;
+            self.loss < -change * cancellation || self.loss.is_nan()
+        } else {
+            false
+        }
+    }
+
+    /// Removing changes accumulates rounding errors, so re-compute the sums from the window
+    /// once every `window_len` updates, which keeps the cost amortized constant.
+    fn recompute_sums(&mut self) {
+        self.gain = T::zero();
+        self.loss = T::zero();
+        self.n_gains = 0;
+        self.n_losses = 0;
+        for i in 1..self.q_vals.len() {
+            self.add_change(self.q_vals[i] - self.q_vals[i - 1]);
+        }
+    }
+
+    /// Set the window length, where shrinking evicts the oldest changes
+    /// and growing it waits for the additional values to be observed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() + 1 {
+            self.q_vals.pop_front();
+        }
+        if self.q_vals.len() <= window_len.get() {
+            self.out = Status::WarmingUp;
+        }
+        self.n_until_recompute = window_len.get();
+        self.recompute_sums();
+    }
 }
 
 impl<T, V> View<T> for Rsi<T, V>
@@ -58,64 +134,115 @@ where
         let Some(val) = self.view.last() else { return };
         debug_assert!(val.is_finite(), "value from `View` must be finite");
 
-        if self.q_vals.is_empty() {
-            self.old_ref = val;
-            self.last_val = val;
-        }
-        let window_len = T::from(self.window_len.get()).expect("can convert");
-        if self.q_vals.len() >= self.window_len.get() {
-            // remove old
-            let old_val = *self.q_vals.front().unwrap();
-            let change = old_val - self.old_ref;
-            debug_assert!(change.is_finite(), "`change` must be finite");
-            self.old_ref = old_val;
-            self.q_vals.pop_front();
-            if change > T::zero() {
-                self.avg_gain = self.avg_gain - change / window_len;
-            } else {
-                self.avg_loss = self.avg_loss - change.abs() / window_len;
-            }
+        let mut cancelled = false;
+        if self.q_vals.len() > self.window_len.get() {
+            let old_val = self.q_vals.pop_front().expect("is not empty");
+            let next_val = *self.q_vals.front().expect("is not empty");
+            cancelled = self.remove_change(next_val - old_val);
+        }
+        if let Some(last_val) = self.q_vals.back() {
+            self.add_change(val - *last_val);
         }
         self.q_vals.push_back(val);
 
-        let change = val - self.last_val;
-        debug_assert!(change.is_finite(), "`change` must be finite");
-        self.last_val = val;
-        if change > T::zero() {
-            self.avg_gain = self.avg_gain + change / window_len;
-        } else {
-            self.avg_loss = self.avg_loss + change.abs() / window_len;
+        self.n_until_recompute -= 1;
+        if self.n_until_recompute == 0 || cancelled {
+            self.n_until_recompute = self.window_len.get();
+            self.recompute_sums();
         }
-        debug_assert!(self.avg_gain.is_finite(), "`avg_gain` must be finite");
-        debug_assert!(self.avg_loss.is_finite(), "`avg_loss` must be finite");
 
-        if self.q_vals.len() < self.window_len.get() {
+        // `window_len` changes require one more value.
+        if self.q_vals.len() <= self.window_len.get() {
             return;
         }
 
+        // Without any gains or losses in the window, the running sums are exactly zero,
+        // so rounding errors don't accumulate in flat markets.
+        let gain = if self.n_gains == 0 {
+            T::zero()
+        } else {
+            self.gain.max(T::zero())
+        };
+        let loss = if self.n_losses == 0 {
+            T::zero()
+        } else {
+            self.loss.max(T::zero())
+        };
+
+        // The changes and their sums overflow for values beyond about half the largest float.
+        if !(gain + loss).is_finite() {
+            self.out = Status::Invalid(InvalidReason::Overflow);
+            return;
+        }
         let hundred = T::from(100.0).expect("can convert");
-        if self.avg_loss == T::zero() {
-            self.out = Some(hundred);
+        if loss == T::zero() {
+            self.out = Status::Ready(hundred);
         } else {
-            let rs = self.avg_gain / self.avg_loss;
-            debug_assert!(rs.is_finite(), "`rs` must be finite");
-            let rsi = hundred - hundred / (T::one() + rs);
+            // Equal to `100 - 100 / (1 + gain / loss)`, without overflowing for tiny losses.
+            let rsi = hundred * (gain / (gain + loss));
             debug_assert!(rsi.is_finite(), "value must be finite");
-            self.out = Some(rsi);
+            self.out = Status::Ready(rsi);
         }
     }
 
     #[inline(always)]
     fn last(&self) -> Option<T> {
-        self.out
+        self.out.value()
+    }
+
+    #[inline(always)]
+    fn status(&self) -> Status<T> {
+        self.out.chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Rsi<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Rsi")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Rsi<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            self,
+            check_bounded,
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
@@ -145,4 +272,68 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn rsi_grow_window() {
+        let len = |len| NonZeroUsize::new(len).unwrap();
+        let mut rsi = Rsi::new(Echo::new(), len(4));
+        rsi.update_batch(&TEST_DATA[..10]);
+        rsi.set_window_len(len(8));
+        // Growing the window waits for the additional values.
+        assert_eq!(rsi.status(), Status::WarmingUp);
+        rsi.update_batch(&TEST_DATA[10..13]);
+        assert_eq!(rsi.last(), None);
+        rsi.update(TEST_DATA[13]);
+        let mut fresh = Rsi::new(Echo::new(), len(8));
+        let expected = fresh.update_batch(&TEST_DATA[5..14]);
+        assert_eq!(rsi.last(), expected[8]);
+    }
+
+    #[test]
+    fn rsi_running_sums() {
+        // The running sums agree with the changes in the window, long after warming up.
+        let len = NonZeroUsize::new(16).unwrap();
+        let vals: Vec<f64> = TEST_DATA
+            .iter()
+            .cycle()
+            .take(20 * TEST_DATA.len())
+            .copied()
+            .collect();
+        let mut rsi = Rsi::new(Echo::new(), len);
+        for (i, val) in vals.iter().enumerate().skip(len.get()) {
+            rsi.update(*val);
+            if i % 97 == 0 {
+                let expected = Rsi::new(Echo::new(), len).update_batch(&vals[i - len.get()..=i]);
+                let expected = expected[len.get()].unwrap();
+                assert!((rsi.last().unwrap() - expected).abs() < 1e-9);
+            }
+        }
+        // A flat market after the swings has neither gains nor losses.
+        rsi.update_batch(&[100.0; 17]);
+        assert_eq!(rsi.last(), Some(100.0));
+    }
+
+    #[test]
+    fn rsi_overflow() {
+        let mut rsi = Rsi::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        rsi.update_batch(&[f64::MAX, -f64::MAX, 0.0]);
+        assert_eq!(rsi.status(), Status::Invalid(InvalidReason::Overflow));
+        // The sums recover once the overflowing change has left the window.
+        rsi.update_batch(&[1.0, 0.0]);
+        assert_eq!(rsi.status(), Status::Ready(50.0));
+    }
+
+    view_properties!(|window_len| Rsi::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Rsi::new(Echo::new(), window_len));
+
+    proptest! {
+        #[test]
+        fn rsi_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            check_bounded(Rsi::new(Echo::new(), window_len), &vals, 0.0, 100.0)?;
+        }
+    }
 }
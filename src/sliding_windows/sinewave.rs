@@ -0,0 +1,371 @@
+//! Sinewave Indicator by John Ehlers
+//! from: "Rocket Science for Traders" by John Ehlers, 2001
+
+use std::{
+    collections::VecDeque,
+    f64::consts::PI,
+};
+
+use num::Float;
+
+use super::{
+    HilbertTransform,
+    hilbert_transform::MAX_PERIOD,
+};
+use crate::{
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+    },
+    pure_functions::Echo,
+};
+
+/// Sinewave Indicator by John Ehlers
+/// from: "Rocket Science for Traders" by John Ehlers, 2001
+/// Measures the phase of the dominant cycle, whose period is measured by a `HilbertTransform`,
+/// by correlating the smoothed values of the `HilbertTransform` over one period with a sine and a cosine wave.
+/// Unlike the original correlation over the period rounded to whole samples,
+/// the last sample is weighted by the fraction of the period, and the values deviate from their mean over it,
+/// which is the same correlation for whole periods,
+/// and the phase is continuous where the imaginary part vanishes, rather than jumping by 90 degrees within 0.001 of it,
+/// so the lines do not jump as the period changes.
+/// The output is the sine of the phase, alongside the lead sine, advanced by 45 degrees,
+/// which is also available as the `LeadSine` view.
+/// In cycle mode, both lines cross ahead of the turning points of the cycle,
+/// while in trend mode, the phase stalls and the lines flatten out without crossing.
+/// Ready once the smoothed values span the longest period of 50 samples, i.e. after 53 values.
+#[derive(Debug, Clone)]
+pub struct Sinewave<T, V> {
+    view: V,
+    hilbert: HilbertTransform<T, Echo<T>>,
+    n_observed: usize,
+    // The smoothed values of the `HilbertTransform`, newest first.
+    smooth: VecDeque<T>,
+    phase: T,
+}
+
+impl<T, V> Sinewave<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Sinewave Indicator with a chained View
+    pub fn new(view: V) -> Self {
+        Self {
+            view,
+            hilbert: HilbertTransform::new(Echo::new()),
+            n_observed: 0,
+            smooth: VecDeque::with_capacity(MAX_PERIOD),
+            phase: T::zero(),
+        }
+    }
+
+    /// The `HilbertTransform` measuring the dominant cycle period.
+    #[inline(always)]
+    pub fn hilbert(&self) -> &HilbertTransform<T, Echo<T>> {
+        &self.hilbert
+    }
+
+    /// The latest phase of the dominant cycle in degrees, within `(-45, 315]`.
+    #[inline(always)]
+    pub fn phase(&self) -> T {
+        self.phase
+    }
+
+    /// The latest lead sine, the sine of the phase advanced by 45 degrees, once ready.
+    pub fn lead_sine(&self) -> Option<T> {
+        self.is_ready().then(|| {
+            (self.phase + T::from(45.0).expect("can convert"))
+                .to_radians()
+                .sin()
+        })
+    }
+
+    fn is_ready(&self) -> bool {
+        self.smooth.len() == MAX_PERIOD
+    }
+}
+
+impl<T, V> View<T> for Sinewave<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.hilbert.update(val);
+        self.n_observed += 1;
+        if self.n_observed < 4 {
+            return;
+        }
+        if self.smooth.len() == MAX_PERIOD {
+            self.smooth.pop_back();
+        }
+        self.smooth.push_front(self.hilbert.smooth());
+        if !self.is_ready() {
+            return;
+        }
+        let period = self
+            .hilbert
+            .last()
+            .expect("is ready before the smoothed values span 50 samples")
+            .max(T::one());
+
+        // Correlate the deviations of the smoothed values from their mean over one period with a sine and a cosine wave,
+        // weighting the value beyond the last whole sample by the fraction.
+        // Over whole periods, the waves sum to zero, so the deviations correlate as the smoothed values themselves,
+        // while over a fractional period, the mean keeps the level of the values out of the phase.
+        let n_whole = period.floor();
+        let fraction = period - n_whole;
+        let n_whole = n_whole.to_usize().expect("can convert");
+        let weighted = self
+            .smooth
+            .iter()
+            .take(n_whole + 1)
+            .enumerate()
+            .map(|(i, smooth)| (i, if i < n_whole { T::one() } else { fraction }, *smooth));
+        // The correlations are of the smoothed values divided by `scale`, which doesn't change the phase.
+        let correlations = |scale: T| {
+            let mean = weighted
+                .clone()
+                .fold(T::zero(), |sum, (_, weight, smooth)| {
+                    sum + weight * (smooth / scale)
+                })
+                / period;
+            weighted.clone().fold(
+                (T::zero(), T::zero()),
+                |(real, imag), (i, weight, smooth)| {
+                    let angle = T::from(2.0 * PI * i as f64).expect("can convert") / period;
+                    let deviation = weight * (smooth / scale - mean);
+                    (
+                        real + angle.sin() * deviation,
+                        imag + angle.cos() * deviation,
+                    )
+                },
+            )
+        };
+        let (mut real, mut imag) = correlations(T::one());
+        if !(real.is_finite() && imag.is_finite()) {
+            // The sums of values close to the largest float overflow,
+            // unlike for the values divided by twice the number of values.
+            (real, imag) = correlations(T::from(2 * (n_whole + 1)).expect("can convert"));
+        }
+        let ninety = T::from(90.0).expect("can convert");
+        let mut phase = self.phase;
+        if imag.abs() > T::zero() {
+            phase = (real / imag).atan().to_degrees();
+        } else if real != T::zero() {
+            phase = ninety * real.signum();
+        }
+        // Compensate the lag of the smoothing by one sample.
+        phase = phase + ninety + T::from(360.0).expect("can convert") / period;
+        if imag < T::zero() {
+            phase = phase + T::from(180.0).expect("can convert");
+        }
+        if phase > T::from(315.0).expect("can convert") {
+            phase = phase - T::from(360.0).expect("can convert");
+        }
+        debug_assert!(phase.is_finite(), "value must be finite");
+        self.phase = phase;
+    }
+
+    fn last(&self) -> Option<T> {
+        self.is_ready().then(|| self.phase.to_radians().sin())
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Sinewave<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Sinewave").child(self.view.node())
+    }
+}
+
+/// Lead Sine of the Sinewave Indicator by John Ehlers
+/// from: "Rocket Science for Traders" by John Ehlers, 2001
+/// The sine of the phase of the dominant cycle advanced by 45 degrees, as a view of its own,
+/// e.g. to compute its difference to the `Sinewave`, which crosses zero ahead of the turning points of the cycle.
+/// Ready once the `Sinewave` is.
+#[derive(Debug, Clone)]
+pub struct LeadSine<T, V> {
+    sinewave: Sinewave<T, V>,
+}
+
+impl<T, V> LeadSine<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Lead Sine with a chained View
+    pub fn new(view: V) -> Self {
+        Self {
+            sinewave: Sinewave::new(view),
+        }
+    }
+
+    /// The `Sinewave` computing both lines.
+    #[inline(always)]
+    pub fn sinewave(&self) -> &Sinewave<T, V> {
+        &self.sinewave
+    }
+}
+
+impl<T, V> View<T> for LeadSine<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        self.sinewave.update(val);
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<T> {
+        self.sinewave.lead_sine()
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.sinewave.status())
+    }
+}
+
+impl<T, V> Introspect for LeadSine<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("LeadSine").child(self.sinewave.view.node())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::{
+            view_properties,
+            zero_crossing_period,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn sinewave_cycle_mode() {
+        for period in [15.0, 20.0, 30.0] {
+            let vals = generators::sine(500, period, 1.0, 100.0);
+            let mut sinewave = Sinewave::new(Echo::new());
+            let outs = sinewave.update_batch(&vals);
+            assert_eq!(outs.iter().position(Option::is_some), Some(MAX_PERIOD + 2));
+            let outs: Vec<f64> = outs[200..].iter().flatten().copied().collect();
+            let measured = zero_crossing_period(&outs).unwrap();
+            assert!(
+                (measured - period).abs() < 0.05 * period,
+                "measured period {measured} of {period}"
+            );
+            assert!(outs.iter().any(|out| *out > 0.95) && outs.iter().any(|out| *out < -0.95));
+        }
+    }
+
+    #[test]
+    fn sinewave_lead_sine() {
+        let period = 20.0;
+        let vals = generators::sine(500, period, 1.0, 100.0);
+        let mut sinewave = Sinewave::new(Echo::new());
+        let mut lead_sine = LeadSine::new(Echo::new());
+        let (mut sines, mut lead_sines) = (Vec::new(), Vec::new());
+        for val in &vals {
+            sinewave.update(*val);
+            lead_sine.update(*val);
+            assert_eq!(lead_sine.last(), sinewave.lead_sine());
+            if let (Some(sine), Some(lead)) = (sinewave.last(), sinewave.lead_sine()) {
+                sines.push(sine);
+                lead_sines.push(lead);
+            }
+        }
+        // The lead sine is an eighth of a cycle, i.e. 2.5 samples, ahead of the sine.
+        let distance = |shift: usize| {
+            sines[200..]
+                .iter()
+                .zip(&lead_sines[200 - shift..])
+                .map(|(sine, lead)| (sine - lead).abs())
+                .sum::<f64>()
+        };
+        let closest = (0..=5)
+            .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+            .unwrap();
+        assert!(
+            (2..=3).contains(&closest),
+            "closest at a shift of {closest}"
+        );
+    }
+
+    #[test]
+    fn sinewave_trend_mode() {
+        let trend: Vec<f64> = (0..500).map(|i| 100.0 + 0.5 * i as f64).collect();
+        let mut sinewave = Sinewave::new(Echo::new());
+        let outs: Vec<f64> = sinewave
+            .update_batch(&trend)
+            .into_iter()
+            .flatten()
+            .collect();
+        // The phase stalls, so the sine flattens out without crossing zero.
+        let outs = &outs[100..];
+        let (min, max) = outs.iter().fold((f64::MAX, f64::MIN), |(min, max), out| {
+            (min.min(*out), max.max(*out))
+        });
+        assert!(max - min < 0.1, "{min} {max}");
+        assert!(zero_crossing_period(outs).is_none());
+    }
+
+    #[test]
+    fn sinewave_extreme_values() {
+        let vals: Vec<f64> = generators::sine(200, 20.0, 1.0, 0.0)
+            .into_iter()
+            .map(|val| val * f64::MAX)
+            .collect();
+        let mut sinewave = Sinewave::new(Echo::new());
+        let outs = sinewave.update_batch(&vals);
+        assert!(outs[100..].iter().all(|out| out.unwrap().is_finite()));
+        assert!(sinewave.lead_sine().unwrap().is_finite());
+    }
+
+    #[test]
+    fn sinewave_plot() {
+        let mut sinewave = Sinewave::new(Echo::new());
+        let out: Vec<f64> = sinewave
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/sinewave.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    mod sinewave {
+        use super::*;
+
+        view_properties!(|_window_len| Sinewave::new(Echo::new()));
+    }
+
+    mod lead_sine {
+        use super::*;
+
+        view_properties!(|_window_len| LeadSine::new(Echo::new()));
+    }
+}
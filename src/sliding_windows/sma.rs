@@ -8,7 +8,21 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 #[derive(Debug, Clone, CopyGetters)]
 /// SMA - Simple Moving Average
@@ -19,6 +33,8 @@ pub struct Sma<T, V> {
     window_len: NonZeroUsize,
     q_vals: VecDeque<T>,
     sum: T,
+    /// The number of updates until the sum is re-computed from the window.
+    n_until_recompute: usize,
 }
 
 impl<T, V> Sma<T, V>
@@ -35,7 +51,24 @@ where
             window_len,
             q_vals: VecDeque::new(),
             sum: T::zero(),
+            n_until_recompute: window_len.get(),
+        }
+    }
+
+    /// Removing values accumulates rounding errors, which are as large as the largest value in the window,
+    /// so re-compute the sum from the window once every `window_len` updates, keeping the cost amortized constant.
+    fn recompute_sum(&mut self) {
+        self.sum = self.q_vals.iter().fold(T::zero(), |sum, val| sum + *val);
+    }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
         }
+        self.n_until_recompute = window_len.get();
+        self.recompute_sum();
     }
 }
 
@@ -50,23 +83,79 @@ where
         let Some(val) = self.view.last() else { return };
         debug_assert!(val.is_finite(), "value must be finite");
 
-        if self.q_vals.len() > self.window_len.get() {
+        if self.q_vals.len() >= self.window_len.get() {
             let old_val = self.q_vals.pop_front().unwrap();
             self.sum = self.sum - old_val;
         }
         self.q_vals.push_back(val);
 
         self.sum = self.sum + val;
+
+        // An overflowed sum can't subtract the evicted values, so it's re-computed until it fits again.
+        let overflowed = !self.sum.is_finite();
+        self.n_until_recompute -= 1;
+        if self.n_until_recompute == 0 || overflowed {
+            self.n_until_recompute = self.window_len.get();
+            self.recompute_sum();
+        }
     }
 
     fn last(&self) -> Option<T> {
         if self.q_vals.len() < self.window_len.get() {
             return None;
         }
-        let sma = self.sum / T::from(self.q_vals.len()).expect("can convert");
+        let n = T::from(self.q_vals.len()).expect("can convert");
+        if !self.sum.is_finite() {
+            // The mean of the largest floats is representable, unlike their sum,
+            // and clamping it to the window removes the rounding errors of the divided values.
+            let (sma, min, max) = self.q_vals.iter().fold(
+                (T::zero(), T::infinity(), T::neg_infinity()),
+                |(sma, min, max), val| (sma + *val / n, min.min(*val), max.max(*val)),
+            );
+            return Some(sma.max(min).min(max));
+        }
+        let sma = self.sum / n;
         debug_assert!(sma.is_finite(), "value must be finite");
         Some(sma)
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Sma<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Sma")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Sma<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -79,6 +168,10 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
@@ -98,6 +191,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sma_huge_values() {
+        let mut sma = Sma::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        sma.update_batch(&[f64::MAX, f64::MAX]);
+        assert_eq!(sma.last(), Some(f64::MAX));
+        // The rounding error of evicting the largest float is gone after the next re-computation.
+        sma.update_batch(&[1.0, 2.0, 4.0]);
+        assert_eq!(sma.last(), Some(3.0));
+        let window_len = NonZeroUsize::new(223).unwrap();
+        let mut sma = Sma::new(Echo::new(), window_len);
+        sma.update_batch(&vec![-f64::MAX; window_len.get()]);
+        assert_eq!(sma.last(), Some(-f64::MAX));
+    }
+
     #[test]
     fn sma_plot() {
         let mut sma = Sma::new(Echo::new(), NonZeroUsize::new(16).unwrap());
@@ -111,4 +218,8 @@ mod tests {
         let filename = "img/sma.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| Sma::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Sma::new(Echo::new(), window_len));
 }
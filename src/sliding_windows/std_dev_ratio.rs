@@ -0,0 +1,165 @@
+//! The ratio of a short-term to a long-term standard deviation,
+//! used by Chande as the volatility index of the `Vidya`.
+
+use std::num::NonZeroUsize;
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::WelfordOnline;
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// The ratio of the standard deviation over the last `short_len` values
+/// to the standard deviation over the last `long_len` values,
+/// which rises above 1 when the volatility expands. Ready once the long window is full.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct StdDevRatio<T: Float, V> {
+    view: V,
+    /// The window length of the short-term standard deviation.
+    #[getset(get_copy = "pub")]
+    short_len: NonZeroUsize,
+    /// The window length of the long-term standard deviation.
+    #[getset(get_copy = "pub")]
+    long_len: NonZeroUsize,
+    short: WelfordOnline<T, Echo<T>>,
+    long: WelfordOnline<T, Echo<T>>,
+}
+
+impl<T, V> StdDevRatio<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new standard deviation ratio with a chained View
+    /// and the given short and long window lengths
+    pub fn new(view: V, short_len: NonZeroUsize, long_len: NonZeroUsize) -> Self {
+        Self {
+            view,
+            short_len,
+            long_len,
+            short: WelfordOnline::new(Echo::new(), short_len),
+            long: WelfordOnline::new(Echo::new(), long_len),
+        }
+    }
+
+    /// Set the window length of the short-term standard deviation, where shrinking evicts the oldest values.
+    pub fn set_short_len(&mut self, short_len: NonZeroUsize) {
+        self.short_len = short_len;
+        self.short.set_window_len(short_len);
+    }
+
+    /// Set the window length of the long-term standard deviation, where shrinking evicts the oldest values.
+    pub fn set_long_len(&mut self, long_len: NonZeroUsize) {
+        self.long_len = long_len;
+        self.long.set_window_len(long_len);
+    }
+}
+
+impl<T, V> View<T> for StdDevRatio<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.short.update(val);
+        self.long.update(val);
+    }
+
+    fn last(&self) -> Option<T> {
+        let long = self.long.last()?;
+        let short = self.short.last()?;
+        // Without any long-term volatility, there is no volatility to compare against.
+        if long <= T::zero() {
+            return Some(T::zero());
+        }
+        let ratio = short / long;
+        debug_assert!(ratio.is_finite(), "value must be finite");
+        Some(ratio)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for StdDevRatio<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("StdDevRatio")
+            .param("short_len", self.short_len())
+            .param("long_len", self.long_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for StdDevRatio<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::window_len("short_len", self.short_len.get(), 1),
+            ParamSpec::window_len("long_len", self.long_len.get(), 1),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "short_len" => self.set_short_len(window_len_param("short_len", val)?),
+            "long_len" => self.set_long_len(window_len_param("long_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::properties::view_properties;
+
+    #[test]
+    fn std_dev_ratio_expansion() {
+        let short = NonZeroUsize::new(5).unwrap();
+        let long = NonZeroUsize::new(20).unwrap();
+        let mut ratio = StdDevRatio::new(Echo::new(), short, long);
+        let mut vals: Vec<f64> = (0..20).map(|i| 100.0 + (i % 2) as f64).collect();
+        let outs = ratio.update_batch(&vals);
+        assert_eq!(outs.iter().position(Option::is_some), Some(19));
+
+        // Larger swings in the short window raise its deviation above the long-term one.
+        vals = (0..5).map(|i| 100.0 + 10.0 * (i % 2) as f64).collect();
+        let outs = ratio.update_batch(&vals);
+        assert!(outs[4].unwrap() > 1.0);
+    }
+
+    view_properties!(|window_len| StdDevRatio::new(
+        Echo::new(),
+        NonZeroUsize::new(window_len.get().div_ceil(4)).unwrap(),
+        window_len
+    ));
+}
@@ -6,7 +6,22 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// John Ehlers SuperSmoother filter
 /// from <https://www.mesasoftware.com/papers/PredictiveIndicatorsForEffectiveTrading%20Strategies.pdf>
@@ -36,21 +51,12 @@ where
 {
     /// Create a new instance of the SuperSmoother with a chained View
     pub fn new(view: V, window_len: NonZeroUsize) -> Self {
-        let wl = T::from(window_len.get()).expect("can convert");
-        let a1 =
-            (-T::from(1.414).expect("can convert") * T::from(PI).expect("can convert") / wl).exp();
-        // NOTE: 4.4422 is radians of 1.414 * 180 degrees
-        let b1 = T::from(2.0).expect("can convert")
-            * a1
-            * (T::from(4.4422).expect("can convert") / wl).cos();
-        let c2 = b1;
-        let c3 = -a1 * a1;
-
+        let (c1, c2, c3) = coefficients(window_len);
         Self {
             view,
             window_len,
             i: 0,
-            c1: T::one() - c2 - c3,
+            c1,
             c2,
             c3,
             filt: T::zero(),
@@ -59,6 +65,13 @@ where
             last_val: T::zero(),
         }
     }
+
+    /// Set the window length, recomputing the filter coefficients.
+    /// The filter state is kept, while growing it waits for the additional values to be observed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        (self.c1, self.c2, self.c3) = coefficients(window_len);
+    }
 }
 
 impl<T, V> View<T> for SuperSmoother<T, V>
@@ -72,9 +85,16 @@ where
         let Some(val) = self.view.last() else { return };
         debug_assert!(val.is_finite(), "value must be finite");
 
-        self.filt = self.c1 * (val + self.last_val) / T::from(2.0).expect("can convert")
-            + (self.c2 * self.filt_1)
-            + (self.c3 * self.filt_2);
+        if self.i < 2 {
+            // Seed the filter with the first values, so it does not start from zero.
+            self.filt = val;
+        } else {
+            // Halving before adding doesn't overflow, while it rounds the same.
+            let two = T::from(2.0).expect("can convert");
+            self.filt = self.c1 * (val / two + self.last_val / two)
+                + (self.c2 * self.filt_1)
+                + (self.c3 * self.filt_2);
+        }
         self.filt_2 = self.filt_1;
         self.filt_1 = self.filt;
         self.last_val = val;
@@ -83,22 +103,75 @@ where
 
     #[inline]
     fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    /// The filter overshoots its input, so its state overflows for values close to the largest float,
+    /// after which the status stays `InvalidReason::Overflow`.
+    fn status(&self) -> Status<T> {
         // NOTE: filter only kicks in after warmup steps are done
         if self.i < self.window_len.get() {
-            None
-        } else {
-            let out = self.filt;
-            debug_assert!(out.is_finite(), "value must be finite");
-            Some(self.filt)
+            return Status::WarmingUp.chained(self.view.status());
         }
+        if !self.filt.is_finite() {
+            return Status::Invalid(InvalidReason::Overflow);
+        }
+        Status::Ready(self.filt)
+    }
+}
+
+impl<T, V> Introspect for SuperSmoother<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("SuperSmoother")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
     }
 }
 
+impl<T, V> Reconfigure for SuperSmoother<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+/// The filter coefficients `c1`, `c2` and `c3`.
+fn coefficients<T: Float>(window_len: NonZeroUsize) -> (T, T, T) {
+    let wl = T::from(window_len.get()).expect("can convert");
+    // NOTE: 1.414 * PI radians are the 1.414 * 180 degrees of the original formula.
+    let angle = T::from(1.414 * PI).expect("can convert") / wl;
+    let a1 = (-angle).exp();
+    let b1 = T::from(2.0).expect("can convert") * a1 * angle.cos();
+    let c2 = b1;
+    let c3 = -a1 * a1;
+    (T::one() - c2 - c3, c2, c3)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::view_properties,
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
@@ -116,4 +189,23 @@ mod tests {
         let filename = "img/super_smoother.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| SuperSmoother::new(Echo::new(), window_len));
+
+    #[test]
+    fn super_smoother_set_window_len() {
+        let mut view = SuperSmoother::new(Echo::<f64>::new(), NonZeroUsize::new(16).unwrap());
+        view.set_param("window_len", ParamValue::Int(8)).unwrap();
+        let fresh = SuperSmoother::new(Echo::<f64>::new(), NonZeroUsize::new(8).unwrap());
+        assert_eq!((view.c1, view.c2, view.c3), (fresh.c1, fresh.c2, fresh.c3));
+        assert_eq!(view.param_specs()[0].value, ParamValue::Int(8));
+        view.update_batch(&TEST_DATA[..8]);
+        assert!(view.last().is_some());
+
+        // Growing the window waits for the additional values.
+        view.set_param("window_len", ParamValue::Int(32)).unwrap();
+        assert_eq!(view.status(), Status::WarmingUp);
+        view.update_batch(&TEST_DATA[8..32]);
+        assert!(view.last().is_some());
+    }
 }
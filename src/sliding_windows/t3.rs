@@ -0,0 +1,283 @@
+//! T3 - Tillson's Triple Exponential Moving Average
+//! from: "Smoothing Techniques For More Accurate Signals" by Tim Tillson, Technical Analysis of Stocks & Commodities, 1998
+
+use std::num::NonZeroUsize;
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::{
+    Ema,
+    ema::update_cascade,
+    extrapolation::extrapolate,
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        Bound,
+        ParamSpec,
+        Reconfigure,
+        float_param,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// T3 - Tillson's Triple Exponential Moving Average
+/// from: "Smoothing Techniques For More Accurate Signals" by Tim Tillson, Technical Analysis of Stocks & Commodities, 1998
+/// Applies the generalized DEMA `(1 + v) * EMA - v * EMA(EMA)` three times,
+/// expanded into a weighted sum of a cascade of six EMAs, where `v` is the volume factor.
+/// Ready once the innermost EMA is, i.e. after `6 * window_len - 5` values.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct T3<T, V> {
+    view: V,
+    /// The window length of each EMA.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    /// The volume factor, trading off smoothness against lag.
+    volume_factor: T,
+    /// The weights of the third to the sixth EMA.
+    weights: [T; 4],
+    emas: [Ema<T, Echo<T>>; 6],
+}
+
+impl<T, V> T3<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new T3 with a chained View, a given window length
+    /// and the default volume factor of 0.7
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self::with_volume_factor(view, window_len, T::from(0.7).expect("can convert"))
+    }
+
+    /// Create a new T3 with a custom volume factor as well
+    pub fn with_volume_factor(view: V, window_len: NonZeroUsize, volume_factor: T) -> Self {
+        Self {
+            view,
+            window_len,
+            volume_factor,
+            weights: weights(volume_factor),
+            emas: std::array::from_fn(|_| Ema::new(Echo::new(), window_len)),
+        }
+    }
+
+    /// Create a new T3 with a custom volume factor as well, which must be within `[0, 1]`.
+    pub fn try_with_volume_factor(
+        view: V,
+        window_len: NonZeroUsize,
+        volume_factor: T,
+    ) -> Result<Self, Error> {
+        check_volume_factor(volume_factor)?;
+        Ok(Self::with_volume_factor(view, window_len, volume_factor))
+    }
+
+    /// The volume factor, trading off smoothness against lag.
+    #[inline(always)]
+    pub fn volume_factor(&self) -> T {
+        self.volume_factor
+    }
+
+    /// Set the window length of each EMA, where growing it waits for the additional values to be observed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        for ema in &mut self.emas {
+            ema.set_window_len(window_len);
+        }
+    }
+
+    /// Set the volume factor, which must be within `[0, 1]`.
+    pub fn set_volume_factor(&mut self, volume_factor: T) -> Result<(), Error> {
+        check_volume_factor(volume_factor)?;
+        self.volume_factor = volume_factor;
+        self.weights = weights(volume_factor);
+        Ok(())
+    }
+}
+
+/// The weights of the third to the sixth EMA, which sum up to one.
+fn weights<T: Float>(v: T) -> [T; 4] {
+    let three = T::from(3.0).expect("can convert");
+    let v2 = v * v;
+    let v3 = v2 * v;
+    [
+        T::one() + three * v + three * v2 + v3,
+        -T::from(6.0).expect("can convert") * v2 - three * v - three * v3,
+        three * v2 + three * v3,
+        -v3,
+    ]
+}
+
+fn check_volume_factor<T: Float>(volume_factor: T) -> Result<(), Error> {
+    if !(volume_factor >= T::zero() && volume_factor <= T::one()) {
+        return Err(Error::T3VolumeFactor(
+            volume_factor.to_f64().expect("can convert"),
+        ));
+    }
+    Ok(())
+}
+
+impl<T, V> View<T> for T3<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        update_cascade(&mut self.emas, val);
+    }
+
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.emas[5].last())
+            .and_then(|_| {
+                extrapolate(
+                    &self.weights,
+                    self.emas[2..]
+                        .iter()
+                        .map(|ema| ema.last().expect("is ready")),
+                )
+            })
+            .chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for T3<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("T3")
+            .param("window_len", self.window_len())
+            .param(
+                "volume_factor",
+                self.volume_factor.to_f64().expect("can convert"),
+            )
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for T3<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::window_len("window_len", self.window_len.get(), 1),
+            ParamSpec::float(
+                "volume_factor",
+                self.volume_factor,
+                Bound::Inclusive(0.0),
+                Bound::Inclusive(1.0),
+            ),
+        ]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            "volume_factor" => self.set_volume_factor(float_param("volume_factor", val)?)?,
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        InvalidReason,
+        plot::plot_values,
+        properties::view_properties,
+        sliding_windows::Tema,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn t3_weights() {
+        for v in [0.0, 0.3, 0.7, 1.0] {
+            let sum: f64 = weights(v).iter().sum();
+            assert!((sum - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn t3_volume_factor() {
+        let window_len = NonZeroUsize::new(4).unwrap();
+        for volume_factor in [-0.1, 1.5, f64::NAN] {
+            assert!(matches!(
+                T3::try_with_volume_factor(Echo::new(), window_len, volume_factor),
+                Err(Error::T3VolumeFactor(_))
+            ));
+        }
+        let mut t3 = T3::new(Echo::<f64>::new(), window_len);
+        assert_eq!(
+            t3.set_param("volume_factor", ParamValue::Float(2.0)),
+            Err(Error::T3VolumeFactor(2.0))
+        );
+        t3.set_param("volume_factor", ParamValue::Float(0.5))
+            .unwrap();
+        assert_eq!(t3.volume_factor(), 0.5);
+    }
+
+    #[test]
+    fn t3_warm_up() {
+        let mut t3 = T3::new(Echo::new(), NonZeroUsize::new(5).unwrap());
+        let outs = t3.update_batch(&TEST_DATA);
+        assert_eq!(outs.iter().position(Option::is_some), Some(6 * 5 - 6));
+    }
+
+    #[test]
+    fn t3_linear_trend() {
+        // With a volume factor of zero the T3 is the EMA of the EMA of the EMA, which lags three times as much as the EMA,
+        // while the lags of the EMAs of the TEMA cancel out.
+        let window_len = NonZeroUsize::new(5).unwrap();
+        let vals: Vec<f64> = (0..400).map(|i| i as f64).collect();
+        let mut t3 = T3::with_volume_factor(Echo::new(), window_len, 0.0);
+        let mut tema = Tema::new(Echo::new(), window_len);
+        let mut ema = Ema::new(Echo::new(), window_len);
+        let ema_lag = vals[399] - ema.update_batch(&vals)[399].unwrap();
+        let t3 = t3.update_batch(&vals)[399].unwrap();
+        assert!((vals[399] - t3 - 3.0 * ema_lag).abs() < 1e-9);
+        assert!((vals[399] - tema.update_batch(&vals)[399].unwrap()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn t3_overflow() {
+        // The T3 overshoots a step, beyond the largest float.
+        let mut t3 = T3::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        t3.update_batch(&[0.0; 7]);
+        assert_eq!(t3.last(), Some(0.0));
+        t3.update_batch(&[f64::MAX; 3]);
+        assert_eq!(t3.status(), Status::Invalid(InvalidReason::Overflow));
+        assert_eq!(t3.last(), None);
+    }
+
+    #[test]
+    fn t3_plot() {
+        let mut t3 = T3::new(Echo::new(), NonZeroUsize::new(16).unwrap());
+        let out: Vec<f64> = t3.update_batch(&TEST_DATA).into_iter().flatten().collect();
+        let filename = "img/t3.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| T3::new(Echo::new(), window_len));
+}
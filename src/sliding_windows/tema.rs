@@ -0,0 +1,190 @@
+//! TEMA - Triple Exponential Moving Average by Patrick Mulloy
+
+use std::num::NonZeroUsize;
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::{
+    Ema,
+    ema::update_cascade,
+    extrapolation::extrapolate,
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// TEMA - Triple Exponential Moving Average by Patrick Mulloy,
+/// which reduces the lag of an EMA further than the `Dema` as `3 * EMA - 3 * EMA(EMA) + EMA(EMA(EMA))`.
+/// Ready once the innermost EMA is, i.e. after `3 * window_len - 2` values.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct Tema<T, V> {
+    view: V,
+    /// The window length of each EMA.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    emas: [Ema<T, Echo<T>>; 3],
+}
+
+impl<T, V> Tema<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Triple Exponential Moving Average with a chained View
+    /// and a given window length
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self {
+            view,
+            window_len,
+            emas: std::array::from_fn(|_| Ema::new(Echo::new(), window_len)),
+        }
+    }
+
+    /// Set the window length of each EMA, where growing it waits for the additional values to be observed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        for ema in &mut self.emas {
+            ema.set_window_len(window_len);
+        }
+    }
+}
+
+impl<T, V> View<T> for Tema<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        update_cascade(&mut self.emas, val);
+    }
+
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.emas[2].last())
+            .and_then(|ema_3| {
+                let ema_2 = self.emas[1].last().expect("is ready");
+                let ema_1 = self.emas[0].last().expect("is ready");
+                let three = T::from(3.0).expect("can convert");
+                extrapolate(&[three, -three, T::one()], [ema_1, ema_2, ema_3])
+            })
+            .chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Tema<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Tema")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Tema<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        InvalidReason,
+        plot::plot_values,
+        properties::view_properties,
+        pure_functions::{
+            Add,
+            Constant,
+            Multiply,
+            Subtract,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn tema_hand_wired() {
+        let window_len = NonZeroUsize::new(8).unwrap();
+        let mut tema = Tema::new(Echo::new(), window_len);
+        let ema = || Ema::new(Echo::new(), window_len);
+        let mut wired = Add::new(
+            Multiply::new(
+                Subtract::new(ema(), Ema::new(ema(), window_len)),
+                Constant::new(3.0),
+            ),
+            Ema::new(Ema::new(ema(), window_len), window_len),
+        );
+        let outs = tema.update_batch(&TEST_DATA);
+        assert_eq!(outs.iter().position(Option::is_some), Some(21));
+        for (tema, wired) in outs.into_iter().zip(wired.update_batch(&TEST_DATA)) {
+            match (tema, wired) {
+                (Some(tema), Some(wired)) => assert!((tema - wired).abs() < 1e-9),
+                (tema, wired) => assert_eq!(tema, wired),
+            }
+        }
+    }
+
+    #[test]
+    fn tema_overflow() {
+        let mut tema = Tema::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        // The step response overshoots beyond the largest float.
+        tema.update_batch(&[-f64::MAX, -f64::MAX, -f64::MAX, f64::MAX, f64::MAX]);
+        assert_eq!(tema.status(), Status::Invalid(InvalidReason::Overflow));
+        assert_eq!(tema.last(), None);
+    }
+
+    #[test]
+    fn tema_plot() {
+        let mut tema = Tema::new(Echo::new(), NonZeroUsize::new(16).unwrap());
+        let out: Vec<f64> = tema
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/tema.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| Tema::new(Echo::new(), window_len));
+}
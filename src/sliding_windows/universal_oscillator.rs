@@ -0,0 +1,240 @@
+//! Universal Oscillator by John Ehlers
+//! from: "Whiter Is Brighter" by John Ehlers, Stocks & Commodities, 2015
+
+use std::num::NonZeroUsize;
+
+use num::Float;
+
+use super::{
+    AutomaticGainControl,
+    SuperSmoother,
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// Universal Oscillator by John Ehlers
+/// from: "Whiter Is Brighter" by John Ehlers, Stocks & Commodities, 2015
+/// Whitens the values by their half difference over two samples, which flattens their spectrum,
+/// smooths the whitened values with a `SuperSmoother` of `band_edge`
+/// and normalizes them with an `AutomaticGainControl`, so the output swings within `[-1, 1]`.
+/// Ready once the `SuperSmoother` is, i.e. after `band_edge + 2` values.
+#[derive(Debug, Clone)]
+pub struct UniversalOscillator<T, V> {
+    view: V,
+    n_observed: usize,
+    // The last two values, newest first.
+    vals: [T; 2],
+    super_smoother: SuperSmoother<T, Echo<T>>,
+    agc: AutomaticGainControl<T, Echo<T>>,
+}
+
+impl<T, V> UniversalOscillator<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Universal Oscillator with a chained View and the window length of its `SuperSmoother`
+    pub fn new(view: V, band_edge: NonZeroUsize) -> Self {
+        Self {
+            view,
+            n_observed: 0,
+            vals: [T::zero(); 2],
+            super_smoother: SuperSmoother::new(Echo::new(), band_edge),
+            agc: AutomaticGainControl::new(Echo::new()),
+        }
+    }
+
+    /// The window length of the `SuperSmoother`.
+    #[inline(always)]
+    pub fn band_edge(&self) -> NonZeroUsize {
+        self.super_smoother.window_len()
+    }
+
+    /// Set the window length of the `SuperSmoother`, recomputing its coefficients.
+    pub fn set_band_edge(&mut self, band_edge: NonZeroUsize) {
+        self.super_smoother.set_window_len(band_edge);
+    }
+}
+
+impl<T, V> View<T> for UniversalOscillator<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        let [val_1, val_2] = self.vals;
+        self.vals = [val, val_1];
+        self.n_observed += 1;
+        // The whitening needs two previous values.
+        if self.n_observed < 3 {
+            return;
+        }
+        // Halving before subtracting doesn't overflow, while it rounds the same.
+        let two = T::from(2.0).expect("can convert");
+        let white_noise = val / two - val_2 / two;
+        self.super_smoother.update(white_noise);
+        if let Some(filt) = self.super_smoother.last() {
+            self.agc.update(filt);
+        }
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    /// The overshoot of values close to the largest float overflows the `SuperSmoother`,
+    /// after which the status stays `InvalidReason::Overflow`.
+    fn status(&self) -> Status<T> {
+        if let Status::Invalid(reason) = self.super_smoother.status() {
+            return Status::Invalid(reason);
+        }
+        Status::from(self.agc.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for UniversalOscillator<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("UniversalOscillator")
+            .param("band_edge", self.super_smoother.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for UniversalOscillator<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "band_edge",
+            self.band_edge().get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "band_edge" => self.set_band_edge(window_len_param("band_edge", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        generators,
+        plot::plot_values,
+        properties::{
+            self,
+            check_bounded,
+            view_properties,
+            zero_crossing_period,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn universal_oscillator_cycle() {
+        let band_edge = NonZeroUsize::new(20).unwrap();
+        for period in [20.0, 40.0] {
+            let vals = generators::sine(500, period, 3.0, 100.0);
+            let outs = UniversalOscillator::new(Echo::new(), band_edge).update_batch(&vals);
+            assert_eq!(outs.iter().position(Option::is_some), Some(21));
+            // The oscillator follows the cycle, normalized to its bounds.
+            let outs: Vec<f64> = outs[200..].iter().flatten().copied().collect();
+            assert!(outs.iter().any(|out| *out > 0.99) && outs.iter().any(|out| *out < -0.99));
+            let measured = zero_crossing_period(&outs).unwrap();
+            assert!(
+                (measured - period).abs() < 0.05 * period,
+                "measured period {measured} of {period}"
+            );
+        }
+    }
+
+    #[test]
+    fn universal_oscillator_trend() {
+        // A linear trend whitens to a constant, so the oscillator sticks to the upper bound.
+        let trend: Vec<f64> = (0..300).map(|i| 100.0 + 0.5 * i as f64).collect();
+        let outs = UniversalOscillator::new(Echo::new(), NonZeroUsize::new(20).unwrap())
+            .update_batch(&trend);
+        for out in outs[100..].iter().flatten() {
+            assert!(*out > 0.99, "{out}");
+        }
+    }
+
+    #[test]
+    fn universal_oscillator_set_param() {
+        let mut view =
+            UniversalOscillator::<f64, _>::new(Echo::new(), NonZeroUsize::new(10).unwrap());
+        view.set_param("band_edge", ParamValue::Int(20)).unwrap();
+        let mut fresh =
+            UniversalOscillator::<f64, _>::new(Echo::new(), NonZeroUsize::new(20).unwrap());
+        assert_eq!(view.param_specs(), fresh.param_specs());
+        assert_eq!(
+            view.update_batch(&TEST_DATA),
+            fresh.update_batch(&TEST_DATA)
+        );
+    }
+
+    #[test]
+    fn universal_oscillator_extreme_values() {
+        let mut view = UniversalOscillator::new(Echo::new(), NonZeroUsize::new(6).unwrap());
+        let outs = view.update_batch(&[f64::MAX, f64::MAX, -f64::MAX, -f64::MAX].repeat(5));
+        assert!(outs.iter().flatten().all(|out| out.abs() <= 1.0));
+        assert!(view.last().is_some());
+    }
+
+    #[test]
+    fn universal_oscillator_plot() {
+        let mut view = UniversalOscillator::new(Echo::new(), NonZeroUsize::new(20).unwrap());
+        let out: Vec<f64> = view
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/universal_oscillator.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| UniversalOscillator::new(Echo::new(), window_len));
+
+    proptest! {
+        #[test]
+        fn universal_oscillator_bounded(
+            vals in properties::series(),
+            window_len in properties::window_len(),
+        ) {
+            check_bounded(UniversalOscillator::new(Echo::new(), window_len), &vals, -1.0, 1.0)?;
+        }
+    }
+}
@@ -6,7 +6,20 @@ use num::Float;
 
 use super::WelfordOnline;
 use crate::{
+    Error,
+    InvalidReason,
+    Status,
     View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
     pure_functions::Echo,
 };
 
@@ -38,6 +51,11 @@ where
     pub fn window_len(&self) -> NonZeroUsize {
         self.welford_online.window_len()
     }
+
+    /// Set the window length of the standard deviation, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.welford_online.set_window_len(window_len);
+    }
 }
 
 impl<T, V> View<T> for Vst<T, V>
@@ -55,14 +73,58 @@ where
         self.last = val;
     }
 
+    /// A flat window outputs the untransformed value, while its status is `InvalidReason::FlatRange`.
     fn last(&self) -> Option<T> {
-        let std_dev = self.welford_online.last()?;
+        match self.status() {
+            Status::Invalid(InvalidReason::FlatRange) => Some(self.last),
+            status => status.value(),
+        }
+    }
+
+    fn status(&self) -> Status<T> {
+        let Some(std_dev) = self.welford_online.last() else {
+            return Status::WarmingUp.chained(self.view.status());
+        };
         if std_dev == T::zero() {
-            return Some(self.last);
+            return Status::Invalid(InvalidReason::FlatRange);
         }
         let out = self.last / std_dev;
         debug_assert!(out.is_finite(), "value must be finite");
-        Some(out)
+        Status::Ready(out)
+    }
+}
+
+impl<T, V> Introspect for Vst<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Vst")
+            .param("window_len", self.welford_online.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Vst<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.welford_online.window_len().get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
     }
 }
 
@@ -71,6 +133,10 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
         test_data::TEST_DATA,
     };
 
@@ -87,4 +153,8 @@ mod tests {
         let filename = "img/trend_flex.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| Vst::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Vst::new(Echo::new(), window_len));
 }
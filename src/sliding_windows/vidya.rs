@@ -0,0 +1,248 @@
+//! VIDYA - Variable Index Dynamic Average by Tushar Chande
+//! from: "Adapting Moving Averages to Market Volatility", Technical Analysis of Stocks & Commodities, 1992
+
+use std::num::NonZeroUsize;
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::{
+    Cmo,
+    StdDevRatio,
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// VIDYA - Variable Index Dynamic Average by Tushar Chande
+/// from: "Adapting Moving Averages to Market Volatility", Technical Analysis of Stocks & Commodities, 1992
+/// An EMA whose smoothing constant `2 / (window_len + 1)` is scaled by the absolute value `k`
+/// of a volatility index, which observes the same values, e.g. a `Cmo` or a `StdDevRatio`.
+/// The scaled smoothing constant is capped at 1, so a volatility index above 1 cannot overshoot.
+/// Ready once the volatility index is ready and `window_len` values have been observed.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct Vidya<T, V, K> {
+    view: V,
+    volatility_index: K,
+    /// The window length of the underlying EMA.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    vidya: T,
+    n_observed_values: usize,
+}
+
+impl<T, V> Vidya<T, V, Cmo<T, Echo<T>>>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new VIDYA with a chained View, a given window length
+    /// and the absolute CMO over `cmo_len` changes as volatility index
+    pub fn with_cmo(view: V, window_len: NonZeroUsize, cmo_len: NonZeroUsize) -> Self {
+        Self::new(view, window_len, Cmo::new(Echo::new(), cmo_len))
+    }
+}
+
+impl<T, V> Vidya<T, V, StdDevRatio<T, Echo<T>>>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new VIDYA with a chained View, a given window length
+    /// and the ratio of the short-term to the long-term standard deviation as volatility index
+    pub fn with_std_dev_ratio(
+        view: V,
+        window_len: NonZeroUsize,
+        short_len: NonZeroUsize,
+        long_len: NonZeroUsize,
+    ) -> Self {
+        Self::new(
+            view,
+            window_len,
+            StdDevRatio::new(Echo::new(), short_len, long_len),
+        )
+    }
+}
+
+impl<T, V, K> Vidya<T, V, K>
+where
+    V: View<T>,
+    K: View<T>,
+    T: Float,
+{
+    /// Create a new VIDYA with a chained View, a given window length
+    /// and a custom volatility index, which must be chained to an `Echo`
+    pub fn new(view: V, window_len: NonZeroUsize, volatility_index: K) -> Self {
+        Self {
+            view,
+            volatility_index,
+            window_len,
+            vidya: T::zero(),
+            n_observed_values: 0,
+        }
+    }
+
+    /// Set the window length of the underlying EMA, where growing it waits for the additional values to be observed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+    }
+}
+
+impl<T, V, K> View<T> for Vidya<T, V, K>
+where
+    V: View<T>,
+    K: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        self.n_observed_values += 1;
+        self.volatility_index.update(val);
+        // Until the volatility index is ready, the VIDYA starts from the latest value.
+        match self.volatility_index.last() {
+            Some(k) if self.n_observed_values > 1 => {
+                let alpha = T::from(2.0).expect("can convert")
+                    / T::from(self.window_len.get() + 1).expect("can convert");
+                let alpha = (alpha * k.abs()).min(T::one());
+                self.vidya = alpha * val + (T::one() - alpha) * self.vidya;
+                debug_assert!(self.vidya.is_finite(), "value must be finite");
+            }
+            _ => self.vidya = val,
+        }
+    }
+
+    fn last(&self) -> Option<T> {
+        if self.n_observed_values < self.window_len.get() {
+            return None;
+        }
+        self.volatility_index.last()?;
+        Some(self.vidya)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V, K> Introspect for Vidya<T, V, K>
+where
+    V: Introspect,
+    K: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Vidya")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+            .child(self.volatility_index.node())
+    }
+}
+
+impl<T, V, K> Reconfigure for Vidya<T, V, K>
+where
+    V: View<T>,
+    K: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        plot::plot_values,
+        properties::view_properties,
+        pure_functions::Constant,
+        sliding_windows::Ema,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn vidya_constant_index() {
+        // With a volatility index of 1, the VIDYA is an EMA.
+        let window_len = NonZeroUsize::new(9).unwrap();
+        let mut vidya = Vidya::new(Echo::new(), window_len, Constant::new(1.0));
+        let mut ema = Ema::new(Echo::new(), window_len);
+        for (vidya, ema) in vidya
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .zip(ema.update_batch(&TEST_DATA))
+        {
+            match (vidya, ema) {
+                (Some(vidya), Some(ema)) => assert!((vidya - ema).abs() < 1e-9),
+                (vidya, ema) => assert_eq!(vidya, ema),
+            }
+        }
+    }
+
+    #[test]
+    fn vidya_flat_index() {
+        // Without volatility, the VIDYA holds its value.
+        let window_len = NonZeroUsize::new(4).unwrap();
+        let mut vidya = Vidya::new(Echo::new(), window_len, Constant::new(0.0));
+        let outs = vidya.update_batch(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(outs, vec![None, None, None, Some(1.0), Some(1.0)]);
+    }
+
+    #[test]
+    fn vidya_plot() {
+        let window_len = NonZeroUsize::new(14).unwrap();
+        let mut vidya = Vidya::with_cmo(Echo::new(), window_len, NonZeroUsize::new(9).unwrap());
+        let out: Vec<f64> = vidya
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/vidya.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    mod cmo {
+        use super::*;
+
+        view_properties!(|window_len| Vidya::with_cmo(Echo::new(), window_len, window_len));
+    }
+
+    mod std_dev_ratio {
+        use super::*;
+
+        view_properties!(|window_len| Vidya::with_std_dev_ratio(
+            Echo::new(),
+            window_len,
+            NonZeroUsize::new(window_len.get().div_ceil(4)).unwrap(),
+            window_len
+        ));
+    }
+}
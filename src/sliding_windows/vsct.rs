@@ -6,7 +6,19 @@ use num::Float;
 
 use super::WelfordOnline;
 use crate::{
+    Error,
+    Status,
     View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
     pure_functions::Echo,
 };
 
@@ -39,6 +51,11 @@ where
     pub fn window_len(&self) -> NonZeroUsize {
         self.welford_online.window_len()
     }
+
+    /// Set the window length of the standard deviation, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.welford_online.set_window_len(window_len);
+    }
 }
 
 impl<T, V> View<T> for Vsct<T, V>
@@ -61,11 +78,48 @@ where
         if std_dev == T::zero() {
             return Some(T::zero());
         }
-        let mean = self.welford_online.mean();
-        let out = (self.last - mean) / std_dev;
+        let out = self.welford_online.deviation(self.last) / std_dev;
         debug_assert!(out.is_finite(), "value must be finite");
         Some(out)
     }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Vsct<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Vsct")
+            .param("window_len", self.welford_online.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Vsct<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.welford_online.window_len().get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -73,6 +127,10 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
         test_data::TEST_DATA,
     };
 
@@ -89,4 +147,8 @@ mod tests {
         let filename = "img/vsct.png";
         plot_values(out, filename).unwrap();
     }
+
+    view_properties!(|window_len| Vsct::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Vsct::new(Echo::new(), window_len));
 }
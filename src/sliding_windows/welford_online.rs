@@ -9,7 +9,22 @@ use std::{
 use getset::CopyGetters;
 use num::Float;
 
-use crate::View;
+use crate::{
+    Error,
+    InvalidReason,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
 
 /// Welford online algorithm for computing mean and variance on-the-fly
 /// over a sliding window
@@ -20,11 +35,18 @@ pub struct WelfordOnline<T: Float, V> {
     #[getset(get_copy = "pub")]
     window_len: NonZeroUsize,
     q_vals: VecDeque<T>,
-    /// The mean of the observed samples
-    #[getset(get_copy = "pub")]
+    /// The statistics are computed relative to a value of the window,
+    /// which avoids cancellation when the variance is small compared to the mean.
+    shift: T,
     mean: T,
     m2: T,
+    /// The largest `m2` since the statistics were last re-computed from the window.
+    m2_peak: T,
     count: usize,
+    /// The number of most recent values that are equal to the newest one.
+    n_equal: usize,
+    /// The number of updates until the statistics are re-computed from the window.
+    n_until_recompute: usize,
 }
 
 impl<T, V> WelfordOnline<T, V>
@@ -38,37 +60,91 @@ where
             view,
             window_len,
             q_vals: VecDeque::with_capacity(window_len.get()),
+            shift: T::zero(),
             mean: T::zero(),
             m2: T::zero(),
+            m2_peak: T::zero(),
             count: 0,
+            n_equal: 0,
+            n_until_recompute: window_len.get(),
         }
     }
 
     #[inline]
     fn update_stats_add(&mut self, x: T) {
+        if self.count == 0 {
+            self.shift = x;
+        }
+        let x = x - self.shift;
         let delta = x - self.mean;
         self.mean = self.mean + (delta / T::from(self.count + 1).unwrap());
         self.m2 = self.m2 + (delta * (x - self.mean));
+        self.m2_peak = self.m2_peak.max(self.m2);
         self.count += 1;
     }
 
     #[inline]
     fn update_stats_remove(&mut self, old_value: T) {
+        self.count -= 1;
+        if self.count == 0 {
+            self.mean = T::zero();
+            self.m2 = T::zero();
+            return;
+        }
+        let old_value = old_value - self.shift;
         let delta = old_value - self.mean;
         self.mean = self.mean - (delta / T::from(self.count).unwrap());
         self.m2 = self.m2 - (delta * (old_value - self.mean));
-        self.count -= 1;
+    }
+
+    /// Removing values accumulates rounding errors, so re-compute the statistics from the window
+    /// once every `window_len` updates, which keeps the cost amortized constant.
+    fn recompute_stats(&mut self) {
+        self.count = 0;
+        self.mean = T::zero();
+        self.m2 = T::zero();
+        self.m2_peak = T::zero();
+        for i in 0..self.q_vals.len() {
+            self.update_stats_add(self.q_vals[i]);
+        }
+    }
+
+    /// The mean of the observed samples
+    #[inline]
+    pub fn mean(&self) -> T {
+        self.shift + self.mean
+    }
+
+    /// The deviation of `val` from the mean, which is more precise than subtracting `mean()`.
+    #[inline]
+    pub(crate) fn deviation(&self, val: T) -> T {
+        (val - self.shift) - self.mean
     }
 
     /// Return the variance of the sliding window
     #[inline]
     pub fn variance(&self) -> T {
+        if self.n_equal >= self.count {
+            // A flat window has no variance, regardless of any rounding errors.
+            return T::zero();
+        }
         if self.count > 1 {
             self.m2 / T::from(self.count - 1).expect("can convert")
         } else {
             T::zero()
         }
     }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
+        }
+        self.n_equal = self.n_equal.min(self.q_vals.len());
+        self.n_until_recompute = window_len.get();
+        self.recompute_stats();
+    }
 }
 
 impl<T, V> View<T> for WelfordOnline<T, V>
@@ -82,6 +158,11 @@ where
         let Some(val) = self.view.last() else { return };
         debug_assert!(val.is_finite(), "value must be finite");
 
+        if self.q_vals.back() == Some(&val) {
+            self.n_equal += 1;
+        } else {
+            self.n_equal = 1;
+        }
         self.q_vals.push_back(val);
 
         if self.q_vals.len() > self.window_len.get() {
@@ -89,22 +170,73 @@ where
             self.update_stats_remove(old_val);
         }
         self.update_stats_add(val);
+
+        self.n_until_recompute -= 1;
+        // When a large deviation leaves the window, `m2` cancels to a fraction of its peak
+        // and the absolute rounding errors of the peak dominate it.
+        let cancelled = self.m2 < self.m2_peak * T::epsilon().sqrt();
+        // Once overflowed values have left the window, the statistics are finite again.
+        let overflowed = !self.m2.is_finite();
+        if self.n_until_recompute == 0 || cancelled || overflowed {
+            self.n_until_recompute = self.window_len.get();
+            self.recompute_stats();
+        }
     }
 
     #[inline]
     fn last(&self) -> Option<T> {
-        if self.count < self.window_len.get() - 1 {
+        self.status().value()
+    }
+
+    /// The squared deviations of values beyond about `1e154` overflow,
+    /// so the status is `InvalidReason::Overflow` until they have left the window.
+    fn status(&self) -> Status<T> {
+        if self.count < self.window_len.get() {
             // To ensure we don't return anything when there are not enough samples.
-            return None;
+            return Status::WarmingUp.chained(self.view.status());
         }
         let var = self.variance();
+        if !var.is_finite() {
+            return Status::Invalid(InvalidReason::Overflow);
+        }
         if var <= T::zero() {
-            return Some(T::zero());
+            return Status::Ready(T::zero());
+        }
+        Status::Ready(var.sqrt())
+    }
+}
+
+impl<T, V> Introspect for WelfordOnline<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("WelfordOnline")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for WelfordOnline<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
         }
-        debug_assert!(var >= T::zero(), "Variance must be positive");
-        let out = var.sqrt();
-        debug_assert!(out.is_finite(), "value must be finite");
-        Some(out)
+        Ok(())
     }
 }
 
@@ -115,6 +247,10 @@ mod tests {
     use super::*;
     use crate::{
         plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
         pure_functions::Echo,
         test_data::TEST_DATA,
     };
@@ -152,4 +288,23 @@ mod tests {
         let filename = "img/welford_online_sliding.png";
         plot_values(out, filename).unwrap();
     }
+
+    #[test]
+    fn welford_online_overflow() {
+        let mut wo = WelfordOnline::new(Echo::new(), NonZeroUsize::new(4).unwrap());
+        wo.update_batch(&[1e200, -1e200, 1.0, 2.0]);
+        assert_eq!(wo.status(), Status::Invalid(InvalidReason::Overflow));
+        assert_eq!(wo.last(), None);
+        // The statistics recover once the huge values have left the window.
+        wo.update_batch(&[3.0, 4.0]);
+        let std_dev = wo.last().unwrap();
+        assert!(
+            (std_dev - (5.0_f64 / 3.0).sqrt()).abs() < 1e-12,
+            "{std_dev}"
+        );
+    }
+
+    view_properties!(|window_len| WelfordOnline::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| WelfordOnline::new(Echo::new(), window_len));
 }
@@ -0,0 +1,228 @@
+//! WMA - Linearly Weighted Moving Average
+
+use std::{
+    collections::VecDeque,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+};
+
+/// WMA - Linearly Weighted Moving Average, where the newest value has the weight `window_len`
+/// and the oldest the weight 1. Updates in O(1) by maintaining the plain and the weighted sum.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct Wma<T, V> {
+    view: V,
+    /// The sliding window length.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    q_vals: VecDeque<T>,
+    sum: T,
+    weighted_sum: T,
+    /// The number of updates until the sums are re-computed from the window.
+    n_until_recompute: usize,
+}
+
+impl<T, V> Wma<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new linearly weighted moving average with a chained View
+    /// and a given sliding window length
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self {
+            view,
+            window_len,
+            q_vals: VecDeque::with_capacity(window_len.get()),
+            sum: T::zero(),
+            weighted_sum: T::zero(),
+            n_until_recompute: window_len.get(),
+        }
+    }
+
+    /// Removing values accumulates rounding errors, which are as large as the largest value in the window,
+    /// so re-compute the sums from the window once every `window_len` updates, keeping the cost amortized constant.
+    fn recompute_sums(&mut self) {
+        (self.sum, self.weighted_sum) = self.q_vals.iter().enumerate().fold(
+            (T::zero(), T::zero()),
+            |(sum, weighted_sum), (i, val)| {
+                let weight = T::from(i + 1).expect("can convert");
+                (sum + *val, weighted_sum + weight * *val)
+            },
+        );
+    }
+
+    /// Set the window length, where shrinking evicts the oldest values.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        while self.q_vals.len() > window_len.get() {
+            self.q_vals.pop_front();
+        }
+        self.n_until_recompute = window_len.get();
+        self.recompute_sums();
+    }
+}
+
+impl<T, V> View<T> for Wma<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        // Every value in the window moves down by one weight,
+        // which drops the oldest value from the weighted sum.
+        if self.q_vals.len() >= self.window_len.get() {
+            self.weighted_sum = self.weighted_sum - self.sum;
+            self.sum = self.sum - self.q_vals.pop_front().expect("is not empty");
+        }
+        self.q_vals.push_back(val);
+        self.sum = self.sum + val;
+        self.weighted_sum =
+            self.weighted_sum + T::from(self.q_vals.len()).expect("can convert") * val;
+
+        // Overflowed sums can't subtract the evicted values, so they're re-computed until they fit again.
+        let overflowed = !(self.sum.is_finite() && self.weighted_sum.is_finite());
+        self.n_until_recompute -= 1;
+        if self.n_until_recompute == 0 || overflowed {
+            self.n_until_recompute = self.window_len.get();
+            self.recompute_sums();
+        }
+    }
+
+    fn last(&self) -> Option<T> {
+        if self.q_vals.len() < self.window_len.get() {
+            return None;
+        }
+        let n = T::from(self.window_len.get()).expect("can convert");
+        let total_weight = n * (n + T::one()) / T::from(2.0).expect("can convert");
+        if !self.weighted_sum.is_finite() {
+            // The weighted mean of the largest floats is representable, unlike their weighted sum,
+            // and clamping it to the window removes the rounding errors of the weights.
+            let (wma, min, max) = self.q_vals.iter().enumerate().fold(
+                (T::zero(), T::infinity(), T::neg_infinity()),
+                |(wma, min, max), (i, val)| {
+                    let weight = T::from(i + 1).expect("can convert") / total_weight;
+                    (wma + weight * *val, min.min(*val), max.max(*val))
+                },
+            );
+            return Some(wma.max(min).min(max));
+        }
+        let wma = self.weighted_sum / total_weight;
+        debug_assert!(wma.is_finite(), "value must be finite");
+        Some(wma)
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.last()).chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Wma<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Wma")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Wma<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        plot::plot_values,
+        properties::{
+            reconfigure_properties,
+            view_properties,
+        },
+        pure_functions::Echo,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn wma() {
+        let mut wma = Wma::new(Echo::new(), NonZeroUsize::new(3).unwrap());
+        let outs = wma.update_batch(&[1.0, 2.0, 3.0, 4.0, 8.0]);
+        assert_eq!(
+            outs,
+            vec![
+                None,
+                None,
+                Some((1.0 + 4.0 + 9.0) / 6.0),
+                Some((2.0 + 6.0 + 12.0) / 6.0),
+                Some((3.0 + 8.0 + 24.0) / 6.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn wma_huge_values() {
+        let mut wma = Wma::new(Echo::new(), NonZeroUsize::new(2).unwrap());
+        wma.update_batch(&[f64::MAX, f64::MAX]);
+        assert_eq!(wma.last(), Some(f64::MAX));
+        wma.update(-f64::MAX);
+        assert!((wma.last().unwrap() + f64::MAX / 3.0).abs() <= 1e-15 * f64::MAX);
+        // The rounding error of evicting the largest float is gone after the next re-computation.
+        wma.update_batch(&[1.0, 2.0, 4.0]);
+        assert_eq!(wma.last(), Some((2.0 + 2.0 * 4.0) / 3.0));
+    }
+
+    #[test]
+    fn wma_plot() {
+        let mut wma = Wma::new(Echo::new(), NonZeroUsize::new(16).unwrap());
+        let out: Vec<f64> = wma.update_batch(&TEST_DATA).into_iter().flatten().collect();
+        let filename = "img/wma.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| Wma::new(Echo::new(), window_len));
+
+    reconfigure_properties!(|window_len| Wma::new(Echo::new(), window_len));
+}
@@ -0,0 +1,200 @@
+//! ZLEMA - Zero Lag Exponential Moving Average by John Ehlers and Ric Way
+//! from: "Zero Lag (Well, Almost)", Technical Analysis of Stocks & Commodities, 2010
+
+use std::{
+    collections::VecDeque,
+    num::NonZeroUsize,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use super::{
+    Ema,
+    extrapolation::{
+        scale,
+        scaled_sum,
+        unscaled,
+    },
+};
+use crate::{
+    Error,
+    Status,
+    View,
+    introspect::{
+        Introspect,
+        Node,
+        ParamValue,
+    },
+    params::{
+        ParamSpec,
+        Reconfigure,
+        window_len_param,
+    },
+    pure_functions::Echo,
+};
+
+/// ZLEMA - Zero Lag Exponential Moving Average by John Ehlers and Ric Way
+/// from: "Zero Lag (Well, Almost)", Technical Analysis of Stocks & Commodities, 2010
+/// An EMA of the de-lagged values `2 * x - x[lag]`, where `lag = (window_len - 1) / 2` is the lag of the EMA.
+/// Ready after `lag + window_len` values.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct Zlema<T, V> {
+    view: V,
+    /// The window length of the EMA.
+    #[getset(get_copy = "pub")]
+    window_len: NonZeroUsize,
+    /// The last `lag + 1` values.
+    q_vals: VecDeque<T>,
+    ema: Ema<T, Echo<T>>,
+}
+
+impl<T, V> Zlema<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    /// Create a new Zero Lag Exponential Moving Average with a chained View
+    /// and a given window length
+    pub fn new(view: V, window_len: NonZeroUsize) -> Self {
+        Self {
+            view,
+            window_len,
+            q_vals: VecDeque::with_capacity(lag(window_len) + 1),
+            ema: Ema::new(Echo::new(), window_len),
+        }
+    }
+
+    /// Set the window length, where growing it waits for the additional values to be observed.
+    pub fn set_window_len(&mut self, window_len: NonZeroUsize) {
+        self.window_len = window_len;
+        self.ema.set_window_len(window_len);
+        while self.q_vals.len() > lag(window_len) + 1 {
+            self.q_vals.pop_front();
+        }
+    }
+}
+
+/// The weights of the de-lagged values `2 * x - x[lag]`.
+fn weights<T: Float>() -> [T; 2] {
+    [T::from(2.0).expect("can convert"), -T::one()]
+}
+
+/// The lag of an EMA of the given window length.
+fn lag(window_len: NonZeroUsize) -> usize {
+    (window_len.get() - 1) / 2
+}
+
+impl<T, V> View<T> for Zlema<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.view.update(val);
+        let Some(val) = self.view.last() else { return };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        if self.q_vals.len() > lag(self.window_len) {
+            self.q_vals.pop_front();
+        }
+        self.q_vals.push_back(val);
+        if self.q_vals.len() > lag(self.window_len) {
+            let lagged = self.q_vals[0];
+            let weights = weights();
+            self.ema
+                .update(scaled_sum(&weights, [val, lagged], scale(&weights)));
+        }
+    }
+
+    #[inline(always)]
+    fn last(&self) -> Option<T> {
+        self.status().value()
+    }
+
+    fn status(&self) -> Status<T> {
+        Status::from(self.ema.last())
+            .and_then(|scaled| unscaled(scaled, scale(&weights())))
+            .chained(self.view.status())
+    }
+}
+
+impl<T, V> Introspect for Zlema<T, V>
+where
+    V: Introspect,
+    T: Float,
+{
+    fn node(&self) -> Node {
+        Node::new("Zlema")
+            .param("window_len", self.window_len())
+            .child(self.view.node())
+    }
+}
+
+impl<T, V> Reconfigure for Zlema<T, V>
+where
+    V: View<T>,
+    T: Float,
+{
+    fn param_specs(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::window_len(
+            "window_len",
+            self.window_len.get(),
+            1,
+        )]
+    }
+
+    fn set_param(&mut self, name: &str, val: ParamValue) -> Result<(), Error> {
+        match name {
+            "window_len" => self.set_window_len(window_len_param("window_len", val)?),
+            _ => return Err(Error::UnknownParameter),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        InvalidReason,
+        plot::plot_values,
+        properties::view_properties,
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn zlema_linear_trend() {
+        // The de-lagging compensates the lag of the EMA on a linear trend.
+        let vals: Vec<f64> = (0..200).map(|i| 0.5 * i as f64).collect();
+        let mut zlema = Zlema::new(Echo::new(), NonZeroUsize::new(9).unwrap());
+        let outs = zlema.update_batch(&vals);
+        assert_eq!(outs.iter().position(Option::is_some), Some(4 + 8));
+        assert!((outs[199].unwrap() - vals[199]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zlema_overflow() {
+        let mut zlema = Zlema::new(Echo::new(), NonZeroUsize::new(3).unwrap());
+        zlema.update_batch(&[f64::MAX, -f64::MAX, f64::MAX, -f64::MAX]);
+        assert_eq!(zlema.status(), Status::Invalid(InvalidReason::Overflow));
+        assert_eq!(zlema.last(), None);
+        zlema.update_batch(&[f64::MAX; 8]);
+        assert!(zlema.status().is_ready());
+    }
+
+    #[test]
+    fn zlema_plot() {
+        let mut zlema = Zlema::new(Echo::new(), NonZeroUsize::new(16).unwrap());
+        let out: Vec<f64> = zlema
+            .update_batch(&TEST_DATA)
+            .into_iter()
+            .flatten()
+            .collect();
+        let filename = "img/zlema.png";
+        plot_values(out, filename).unwrap();
+    }
+
+    view_properties!(|window_len| Zlema::new(Echo::new(), window_len));
+}
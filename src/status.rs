@@ -0,0 +1,138 @@
+//! The readiness of a `View` output, distinguishing a view that is still warming up
+//! from one whose output is undefined for the current window.
+
+/// The reason why the output of a `View` is undefined for the current window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidReason {
+    /// The denominator is zero, e.g. the divisor of a `Divide` or the oldest value of a `Roc`.
+    ZeroDenominator,
+    /// The ratio of consecutive values is not positive, so the `LnReturn` is undefined.
+    NonPositiveRatio,
+    /// The result overflows, e.g. when dividing by a tiny value.
+    Overflow,
+    /// All values of the window are equal, so there is no range or deviation to normalize by.
+    FlatRange,
+    /// The input is NaN, infinite or missing, see `MissingDataPolicy::EmitNone`.
+    MissingInput,
+}
+
+/// The status of a `View` output.
+/// In contrast to `View::last`, it tells apart why no output is available.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status<T> {
+    /// The view has not observed enough values yet.
+    WarmingUp,
+    /// The output is ready.
+    Ready(T),
+    /// The output is undefined for the current window.
+    Invalid(InvalidReason),
+}
+
+impl<T> Status<T> {
+    /// The ready output, if any.
+    #[inline]
+    pub fn value(self) -> Option<T> {
+        match self {
+            Status::Ready(val) => Some(val),
+            Status::WarmingUp | Status::Invalid(_) => None,
+        }
+    }
+
+    /// Whether the output is ready.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Status::Ready(_))
+    }
+
+    /// Map the ready output, keeping the status otherwise.
+    #[inline]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Status<U> {
+        self.and_then(|val| Status::Ready(f(val)))
+    }
+
+    /// Map the ready output to a new status, keeping the status otherwise.
+    #[inline]
+    pub fn and_then<U>(self, f: impl FnOnce(T) -> Status<U>) -> Status<U> {
+        match self {
+            Status::Ready(val) => f(val),
+            Status::WarmingUp => Status::WarmingUp,
+            Status::Invalid(reason) => Status::Invalid(reason),
+        }
+    }
+
+    /// The status of a view, given the status of the `upstream` view it is chained on.
+    /// Views skip the invalid outputs of their upstream view, so while a view has no output of its own,
+    /// an invalid upstream output is the reason, rather than its warm-up.
+    #[inline]
+    pub fn chained<U>(self, upstream: Status<U>) -> Status<T> {
+        match (self, upstream) {
+            (Status::WarmingUp, Status::Invalid(reason)) => Status::Invalid(reason),
+            (status, _) => status,
+        }
+    }
+
+    /// Combine the status of two views, as done by the binary combinators.
+    /// An invalid input makes the output invalid, even if the other input is still warming up.
+    #[inline]
+    pub fn zip_with<U, R>(self, other: Status<U>, f: impl FnOnce(T, U) -> Status<R>) -> Status<R> {
+        match (self, other) {
+            (Status::Ready(a), Status::Ready(b)) => f(a, b),
+            (Status::Invalid(reason), _) | (_, Status::Invalid(reason)) => Status::Invalid(reason),
+            (Status::WarmingUp, _) | (_, Status::WarmingUp) => Status::WarmingUp,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Status<T> {
+    /// Without further information, a missing output is assumed to be warming up.
+    #[inline]
+    fn from(val: Option<T>) -> Self {
+        match val {
+            Some(val) => Status::Ready(val),
+            None => Status::WarmingUp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_zip_with() {
+        let add = |a: f64, b: f64| Status::Ready(a + b);
+        assert_eq!(
+            Status::Ready(1.0).zip_with(Status::Ready(2.0), add),
+            Status::Ready(3.0)
+        );
+        assert_eq!(
+            Status::WarmingUp.zip_with(Status::Ready(2.0), add),
+            Status::WarmingUp
+        );
+        assert_eq!(
+            Status::WarmingUp.zip_with(Status::Invalid(InvalidReason::FlatRange), add),
+            Status::Invalid(InvalidReason::FlatRange)
+        );
+    }
+
+    #[test]
+    fn status_chained() {
+        let invalid = Status::<f64>::Invalid(InvalidReason::ZeroDenominator);
+        assert_eq!(Status::<f64>::WarmingUp.chained(invalid), invalid);
+        assert_eq!(Status::Ready(1.0).chained(invalid), Status::Ready(1.0));
+        assert_eq!(
+            Status::<f64>::WarmingUp.chained(Status::Ready(1.0)),
+            Status::WarmingUp
+        );
+    }
+
+    #[test]
+    fn status_value() {
+        assert_eq!(Status::Ready(1.0).value(), Some(1.0));
+        assert_eq!(Status::<f64>::WarmingUp.value(), None);
+        assert_eq!(
+            Status::<f64>::Invalid(InvalidReason::Overflow).value(),
+            None
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Parameter sweeps for hyperparameter search, e.g. an `Rsi` for every window length in `5..200`.
+//! A `Sweep` instantiates one variant per point of a grid of parameter values and updates them all
+//! in a single pass over the data. The variants observe a shared upstream chain, e.g. an `Ema` of the price,
+//! which is updated only once per value instead of once per variant.
+
+use std::{
+    fmt::{
+        Display,
+        Formatter,
+    },
+    marker::PhantomData,
+    num::NonZeroUsize,
+};
+
+use num::Float;
+
+use crate::{
+    View,
+    introspect::ParamValue,
+};
+
+/// A point of the parameter grid, labelling a variant of a `Sweep`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params(Vec<(&'static str, ParamValue)>);
+
+impl Params {
+    /// The value of the parameter `name`, if it is swept.
+    pub fn get(&self, name: &str) -> Option<ParamValue> {
+        self.0
+            .iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, val)| *val)
+    }
+
+    /// The value of the window length parameter `name`.
+    ///
+    /// # Panics
+    /// If `name` is not swept or not a positive integer.
+    pub fn window_len(&self, name: &str) -> NonZeroUsize {
+        match self.get(name) {
+            Some(ParamValue::Int(len)) => {
+                NonZeroUsize::new(len).expect("window length is positive")
+            }
+            _ => panic!("parameter {name} is not a swept integer"),
+        }
+    }
+
+    /// The value of the floating point parameter `name`, which may also be swept as integers.
+    ///
+    /// # Panics
+    /// If `name` is not swept or not a number.
+    pub fn float<T: Float>(&self, name: &str) -> T {
+        match self.get(name) {
+            Some(ParamValue::Float(val)) => T::from(val).expect("can convert"),
+            Some(ParamValue::Int(val)) => T::from(val).expect("can convert"),
+            _ => panic!("parameter {name} is not a swept number"),
+        }
+    }
+
+    /// The parameters in the order of the axes.
+    pub fn iter(&self) -> impl Iterator<Item = &(&'static str, ParamValue)> {
+        self.0.iter()
+    }
+}
+
+impl Display for Params {
+    /// The parameters as `name=value`, e.g. `sigma=6, offset=0.85`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, (name, val)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{name}={val}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds a `Sweep` from the shared upstream chain and the axes of the parameter grid.
+#[derive(Debug, Clone)]
+pub struct SweepBuilder<T, U> {
+    upstream: U,
+    axes: Vec<(&'static str, Vec<ParamValue>)>,
+    _marker: PhantomData<T>,
+}
+
+impl<T, U> SweepBuilder<T, U>
+where
+    U: View<T>,
+    T: Float,
+{
+    /// Create a new builder, whose variants observe the outputs of `upstream`.
+    /// Use an `Echo` as upstream if the variants share nothing.
+    pub fn new(upstream: U) -> Self {
+        Self {
+            upstream,
+            axes: vec![],
+            _marker: PhantomData,
+        }
+    }
+
+    /// Add an axis to the grid, sweeping the parameter `name` over `vals`.
+    pub fn axis<I>(mut self, name: &'static str, vals: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ParamValue>,
+    {
+        self.axes
+            .push((name, vals.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// The cartesian product of the axes, where the last axis varies fastest.
+    pub fn grid(&self) -> Vec<Params> {
+        self.axes
+            .iter()
+            .fold(vec![Params::default()], |grid, (name, vals)| {
+                grid.iter()
+                    .flat_map(|params| {
+                        vals.iter().map(|val| {
+                            let mut params = params.clone();
+                            params.0.push((name, *val));
+                            params
+                        })
+                    })
+                    .collect()
+            })
+    }
+
+    /// Build the sweep, constructing each variant with `new_variant` from its point of the grid.
+    /// The variants must observe an `Echo`, which is fed the outputs of the upstream chain.
+    pub fn build<V, F>(self, mut new_variant: F) -> Sweep<T, U, V>
+    where
+        V: View<T>,
+        F: FnMut(&Params) -> V,
+    {
+        let params = self.grid();
+        Sweep {
+            upstream: self.upstream,
+            variants: params.iter().map(&mut new_variant).collect(),
+            params,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A family of variants of a chain, one per point of a parameter grid, observing a shared upstream chain.
+/// Each variant produces the same outputs as if it observed its own copy of the upstream chain.
+#[derive(Debug, Clone)]
+pub struct Sweep<T, U, V> {
+    upstream: U,
+    params: Vec<Params>,
+    variants: Vec<V>,
+    _marker: PhantomData<T>,
+}
+
+impl<T, U, V> Sweep<T, U, V>
+where
+    U: View<T>,
+    V: View<T>,
+    T: Float,
+{
+    /// The parameters of each variant, in the order of the outputs.
+    #[inline(always)]
+    pub fn params(&self) -> &[Params] {
+        &self.params
+    }
+
+    /// The variants, in the order of the outputs.
+    #[inline(always)]
+    pub fn variants(&self) -> &[V] {
+        &self.variants
+    }
+
+    /// The number of variants, i.e. the number of points of the grid.
+    #[inline(always)]
+    pub fn n_variants(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Update the upstream chain with a new value and all variants with its output.
+    pub fn update(&mut self, val: T) {
+        debug_assert!(val.is_finite(), "value must be finite");
+        self.upstream.update(val);
+        let Some(val) = self.upstream.last() else {
+            return;
+        };
+        debug_assert!(val.is_finite(), "value must be finite");
+
+        for variant in &mut self.variants {
+            variant.update(val);
+        }
+    }
+
+    /// The last value of each variant, in the order of `params`.
+    pub fn last(&self) -> Vec<Option<T>> {
+        self.variants.iter().map(|variant| variant.last()).collect()
+    }
+
+    /// Update with a batch of values, returning the outputs of each variant observed after each value,
+    /// labelled by its parameters.
+    pub fn update_batch(&mut self, vals: &[T]) -> Vec<(Params, Vec<Option<T>>)> {
+        let mut outs = vec![Vec::with_capacity(vals.len()); self.variants.len()];
+        for val in vals {
+            self.update(*val);
+            for (out, variant) in outs.iter_mut().zip(&self.variants) {
+                out.push(variant.last());
+            }
+        }
+        self.params.iter().cloned().zip(outs).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pure_functions::Echo,
+        sliding_windows::{
+            Alma,
+            Ema,
+            Rsi,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn sweep_grid() {
+        let builder = SweepBuilder::<f64, _>::new(Echo::new())
+            .axis("sigma", [3.0, 6.0])
+            .axis("offset", [0.5, 0.85, 1.0]);
+        let grid = builder.grid();
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid[0].to_string(), "sigma=3, offset=0.5");
+        assert_eq!(grid[5].to_string(), "sigma=6, offset=1");
+        assert_eq!(grid[4].get("offset"), Some(ParamValue::Float(0.85)));
+        assert_eq!(grid[4].get("window_len"), None);
+        assert_eq!(
+            SweepBuilder::<f64, _>::new(Echo::new())
+                .axis("window_len", 0..0_usize)
+                .grid(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn sweep_shared_upstream() {
+        let ema_len = NonZeroUsize::new(8).unwrap();
+        let mut sweep = SweepBuilder::new(Ema::new(Echo::new(), ema_len))
+            .axis("window_len", (5..40).step_by(5))
+            .build(|params| Rsi::new(Echo::new(), params.window_len("window_len")));
+        assert_eq!(sweep.n_variants(), 7);
+        let outs = sweep.update_batch(&TEST_DATA);
+        for (params, outs) in outs {
+            let window_len = params.window_len("window_len");
+            let mut chain = Rsi::new(Ema::new(Echo::new(), ema_len), window_len);
+            assert_eq!(outs, chain.update_batch(&TEST_DATA), "{params}");
+        }
+    }
+
+    #[test]
+    fn sweep_alma_grid() {
+        let window_len = NonZeroUsize::new(16).unwrap();
+        let mut sweep = SweepBuilder::new(Echo::new())
+            .axis("sigma", [2.0, 4.0, 6.0])
+            .axis("offset", [0.5, 0.85])
+            .build(|params| {
+                Alma::new_custom(
+                    Echo::new(),
+                    window_len,
+                    params.float("sigma"),
+                    params.float("offset"),
+                )
+            });
+        for val in TEST_DATA {
+            sweep.update(val);
+        }
+        let expected: Vec<Option<f64>> = sweep
+            .params()
+            .iter()
+            .map(|params| {
+                let mut alma = Alma::new_custom(
+                    Echo::new(),
+                    window_len,
+                    params.float("sigma"),
+                    params.float("offset"),
+                );
+                alma.update_batch(&TEST_DATA).pop().unwrap()
+            })
+            .collect();
+        assert_eq!(sweep.last(), expected);
+    }
+}
@@ -0,0 +1,166 @@
+//! Evaluate one chain of `View`s per symbol across a whole universe of instruments in parallel.
+
+use std::{
+    marker::PhantomData,
+    num::NonZeroUsize,
+    thread,
+};
+
+use getset::CopyGetters;
+use num::Float;
+
+use crate::View;
+
+/// Owns one cloned chain per symbol and updates them in parallel threads.
+/// Outputs are always ordered by symbol index, regardless of the number of threads.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct Universe<T, V> {
+    views: Vec<V>,
+    /// The number of threads used to update the chains.
+    #[getset(get_copy = "pub")]
+    n_threads: NonZeroUsize,
+    _marker: PhantomData<T>,
+}
+
+impl<T, V> Universe<T, V>
+where
+    V: View<T> + Clone + Send,
+    T: Float + Send + Sync,
+{
+    /// Create a new instance with `n_symbols` clones of the `prototype` chain,
+    /// using all available threads.
+    pub fn new(prototype: V, n_symbols: usize) -> Self {
+        let n_threads = thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+        Self::with_threads(prototype, n_symbols, n_threads)
+    }
+
+    /// Create a new instance with `n_symbols` clones of the `prototype` chain,
+    /// using at most `n_threads` threads.
+    pub fn with_threads(prototype: V, n_symbols: usize, n_threads: NonZeroUsize) -> Self {
+        Self {
+            views: vec![prototype; n_symbols],
+            n_threads,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The chains of all symbols, ordered by symbol index.
+    #[inline(always)]
+    pub fn views(&self) -> &[V] {
+        &self.views
+    }
+
+    /// The number of symbols in the universe.
+    #[inline(always)]
+    pub fn n_symbols(&self) -> usize {
+        self.views.len()
+    }
+
+    /// Update each symbol with its new value, where `vals[i]` belongs to symbol `i`.
+    /// Spawns threads on every call, so prefer `update_batch` for many ticks at once.
+    pub fn update(&mut self, vals: &[T]) {
+        assert_eq!(vals.len(), self.views.len(), "need one value per symbol");
+        let chunk_len = self.chunk_len();
+        thread::scope(|s| {
+            for (views, vals) in self.views.chunks_mut(chunk_len).zip(vals.chunks(chunk_len)) {
+                s.spawn(move || {
+                    for (view, val) in views.iter_mut().zip(vals) {
+                        view.update(*val);
+                    }
+                });
+            }
+        });
+    }
+
+    /// The last value of each symbol, ordered by symbol index.
+    pub fn last(&self) -> Vec<Option<T>> {
+        self.views.iter().map(|view| view.last()).collect()
+    }
+
+    /// Update each symbol with a batch of its values, where `batches[i]` belongs to symbol `i`.
+    /// Returns the outputs observed after each value, ordered by symbol index.
+    pub fn update_batch<B>(&mut self, batches: &[B]) -> Vec<Vec<Option<T>>>
+    where
+        B: AsRef<[T]> + Sync,
+    {
+        assert_eq!(batches.len(), self.views.len(), "need one batch per symbol");
+        let mut outs = vec![Vec::new(); self.views.len()];
+        let chunk_len = self.chunk_len();
+        thread::scope(|s| {
+            for ((views, batches), outs) in self
+                .views
+                .chunks_mut(chunk_len)
+                .zip(batches.chunks(chunk_len))
+                .zip(outs.chunks_mut(chunk_len))
+            {
+                s.spawn(move || {
+                    for ((view, batch), out) in views.iter_mut().zip(batches).zip(outs) {
+                        *out = view.update_batch(batch.as_ref());
+                    }
+                });
+            }
+        });
+        outs
+    }
+
+    #[inline]
+    fn chunk_len(&self) -> usize {
+        self.views.len().div_ceil(self.n_threads.get()).max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pure_functions::Echo,
+        sliding_windows::{
+            Rsi,
+            Sma,
+        },
+        test_data::TEST_DATA,
+    };
+
+    #[test]
+    fn universe_update() {
+        const N_SYMBOLS: usize = 13;
+        let prototype = Rsi::new(Echo::new(), NonZeroUsize::new(16).unwrap());
+        let mut universe =
+            Universe::with_threads(prototype.clone(), N_SYMBOLS, NonZeroUsize::new(4).unwrap());
+        let mut scalars = vec![prototype; N_SYMBOLS];
+        for i in 0..TEST_DATA.len() {
+            let vals = Vec::from_iter((0..N_SYMBOLS).map(|s| TEST_DATA[(i + s) % TEST_DATA.len()]));
+            universe.update(&vals);
+            for (scalar, val) in scalars.iter_mut().zip(&vals) {
+                scalar.update(*val);
+            }
+            assert_eq!(
+                universe.last(),
+                Vec::from_iter(scalars.iter().map(|s| s.last()))
+            );
+        }
+    }
+
+    #[test]
+    fn universe_update_batch() {
+        const N_SYMBOLS: usize = 7;
+        let prototype = Sma::new(Echo::new(), NonZeroUsize::new(8).unwrap());
+        let batches = Vec::from_iter(
+            (0..N_SYMBOLS).map(|s| Vec::from_iter(TEST_DATA.iter().map(|v| *v + s as f64))),
+        );
+        let expected = Vec::from_iter(
+            batches
+                .iter()
+                .map(|batch| prototype.clone().update_batch(batch)),
+        );
+        for n_threads in [1, 2, 3, 16] {
+            let mut universe = Universe::with_threads(
+                prototype.clone(),
+                N_SYMBOLS,
+                NonZeroUsize::new(n_threads).unwrap(),
+            );
+            assert_eq!(universe.update_batch(&batches), expected);
+            assert_eq!(universe.n_symbols(), N_SYMBOLS);
+        }
+    }
+}